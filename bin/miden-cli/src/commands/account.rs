@@ -1,18 +1,25 @@
+use std::{fs, path::PathBuf};
+
 use clap::Parser;
 use comfy_table::{Cell, ContentArrangement, presets};
 use miden_client::{
     Client, ZERO,
-    account::{Account, AccountId, AccountType, StorageSlot},
+    account::{
+        Account, AccountId, AccountType, StorageSlot, component::COMPONENT_TEMPLATE_EXTENSION,
+    },
     asset::Asset,
     rpc::{NodeRpcClient, TonicRpcClient},
 };
 use miden_objects::PrettyPrint;
+use serde_json::json;
 
 use crate::{
     CLIENT_BINARY_NAME,
+    commands::new_account::load_component_templates,
     config::CliConfig,
     create_dynamic_table,
     errors::CliError,
+    print_json,
     utils::{load_config_file, load_faucet_details_map, parse_account_id, update_config},
 };
 
@@ -39,10 +46,14 @@ pub struct AccountCmd {
     /// account to the provided ID.
     #[arg(short, long, group = "action", value_name = "ID")]
     default: Option<Option<String>>,
+    /// List the account component templates available in the templates directory, which can be
+    /// used with `new-account --component-templates`.
+    #[arg(long, group = "action")]
+    templates: bool,
 }
 
 impl AccountCmd {
-    pub async fn execute(&self, client: Client) -> Result<(), CliError> {
+    pub async fn execute(&self, client: Client, json: bool) -> Result<(), CliError> {
         let (cli_config, _) = load_config_file()?;
         match self {
             AccountCmd {
@@ -52,7 +63,7 @@ impl AccountCmd {
                 ..
             } => {
                 let account_id = parse_account_id(&client, id).await?;
-                show_account(client, account_id, &cli_config, self.with_code).await?;
+                show_account(client, account_id, &cli_config, self.with_code, json).await?;
             },
             AccountCmd {
                 list: false,
@@ -62,7 +73,7 @@ impl AccountCmd {
             } => {
                 match id {
                     None => {
-                        display_default_account_id()?;
+                        display_default_account_id(json)?;
                     },
                     Some(id) => {
                         let default_account = if id == "none" {
@@ -78,7 +89,11 @@ impl AccountCmd {
 
                         set_default_account(default_account)?;
 
-                        if let Some(id) = default_account {
+                        if json {
+                            print_json(&json!({
+                                "default_account_id": default_account.map(AccountId::to_hex),
+                            }))?;
+                        } else if let Some(id) = default_account {
                             let id = id.to_hex();
                             println!("Setting default account to {id}...");
                         } else {
@@ -87,8 +102,11 @@ impl AccountCmd {
                     },
                 }
             },
+            AccountCmd { templates: true, .. } => {
+                list_component_templates(&cli_config, json)?;
+            },
             _ => {
-                list_accounts(client, &cli_config).await?;
+                list_accounts(client, &cli_config, json).await?;
             },
         }
         Ok(())
@@ -98,11 +116,12 @@ impl AccountCmd {
 // LIST ACCOUNTS
 // ================================================================================================
 
-async fn list_accounts(client: Client, cli_config: &CliConfig) -> Result<(), CliError> {
+async fn list_accounts(client: Client, cli_config: &CliConfig, json: bool) -> Result<(), CliError> {
     let accounts = client.get_account_headers().await?;
 
     let mut table =
         create_dynamic_table(&["Address", "Account ID", "Type", "Storage Mode", "Nonce", "Status"]);
+    let mut json_accounts = vec![];
     for (acc, _acc_seed) in &accounts {
         let status = client
             .get_account(acc.id())
@@ -111,14 +130,92 @@ async fn list_accounts(client: Client, cli_config: &CliConfig) -> Result<(), Cli
             .status()
             .to_string();
 
-        table.add_row(vec![
-            acc.id().to_bech32(cli_config.rpc.endpoint.0.to_network_id()?),
-            acc.id().to_hex(),
-            account_type_display_name(&acc.id())?,
-            acc.id().storage_mode().to_string(),
-            acc.nonce().as_int().to_string(),
-            status,
-        ]);
+        let address = acc.id().to_bech32(cli_config.rpc.endpoint.0.to_network_id()?);
+        let account_type = account_type_display_name(&acc.id())?;
+        if json {
+            json_accounts.push(json!({
+                "address": address,
+                "id": acc.id().to_hex(),
+                "type": account_type,
+                "storage_mode": acc.id().storage_mode().to_string(),
+                "nonce": acc.nonce().as_int(),
+                "status": status,
+            }));
+        } else {
+            table.add_row(vec![
+                address,
+                acc.id().to_hex(),
+                account_type,
+                acc.id().storage_mode().to_string(),
+                acc.nonce().as_int().to_string(),
+                status,
+            ]);
+        }
+    }
+
+    if json {
+        return print_json(&json_accounts);
+    }
+
+    println!("{table}");
+    Ok(())
+}
+
+// LIST COMPONENT TEMPLATES
+// ================================================================================================
+
+/// Lists the account component templates in the configured templates directory, along with the
+/// placeholders that have to be provided to instantiate each of them.
+fn list_component_templates(cli_config: &CliConfig, json: bool) -> Result<(), CliError> {
+    let mut template_files: Vec<PathBuf> = fs::read_dir(&cli_config.component_template_directory)?
+        .filter_map(|entry| entry.ok().map(|entry| PathBuf::from(entry.file_name())))
+        .filter(|path| {
+            path.extension()
+                .is_some_and(|extension| extension == COMPONENT_TEMPLATE_EXTENSION)
+        })
+        .collect();
+    template_files.sort();
+
+    let templates = load_component_templates(&template_files)?;
+
+    let mut table = create_dynamic_table(&["File", "Name", "Description", "Placeholders"]);
+    let mut json_templates = vec![];
+    for (file, template) in template_files.iter().zip(&templates) {
+        let metadata = template.metadata();
+        let file = file.display().to_string();
+        let placeholders: Vec<(String, String)> = metadata
+            .get_placeholder_requirements()
+            .into_iter()
+            .map(|(key, requirement)| (key.to_string(), requirement.r#type.to_string()))
+            .collect();
+
+        if json {
+            json_templates.push(json!({
+                "file": file,
+                "name": metadata.name(),
+                "description": metadata.description(),
+                "placeholders": placeholders
+                    .iter()
+                    .map(|(key, value_type)| json!({ "name": key, "type": value_type }))
+                    .collect::<Vec<_>>(),
+            }));
+        } else {
+            let placeholders = placeholders
+                .iter()
+                .map(|(key, value_type)| format!("{key} ({value_type})"))
+                .collect::<Vec<_>>()
+                .join("\n");
+            table.add_row(vec![
+                file,
+                metadata.name().to_string(),
+                metadata.description().to_string(),
+                placeholders,
+            ]);
+        }
+    }
+
+    if json {
+        return print_json(&json_templates);
     }
 
     println!("{table}");
@@ -133,12 +230,17 @@ pub async fn show_account(
     account_id: AccountId,
     cli_config: &CliConfig,
     with_code: bool,
+    json: bool,
 ) -> Result<(), CliError> {
     let account = if let Some(account) = client.get_account(account_id).await? {
         account.into()
     } else {
         let bech32_id = account_id.to_bech32(cli_config.rpc.endpoint.0.to_network_id()?);
-        println!("Account {bech32_id} is not tracked by the client. Fetching from the network...",);
+        if !json {
+            println!(
+                "Account {bech32_id} is not tracked by the client. Fetching from the network...",
+            );
+        }
 
         let rpc_client =
             TonicRpcClient::new(&cli_config.rpc.endpoint.clone().into(), cli_config.rpc.timeout_ms);
@@ -156,32 +258,62 @@ pub async fn show_account(
         )))?
     };
 
+    let faucet_details_map = load_faucet_details_map()?;
+    let mut assets = vec![];
+    for asset in account.vault().assets() {
+        let (asset_type, faucet, amount) = match asset {
+            Asset::Fungible(fungible_asset) => {
+                let (faucet, amount) = faucet_details_map
+                    .format_fungible_asset_with_metadata(&client, &fungible_asset)
+                    .await?;
+                ("Fungible Asset", faucet, amount)
+            },
+            Asset::NonFungible(non_fungible_asset) => {
+                // TODO: Display non-fungible assets more clearly.
+                (
+                    "Non Fungible Asset",
+                    non_fungible_asset.faucet_id_prefix().to_hex(),
+                    1.0.to_string(),
+                )
+            },
+        };
+        assets.push((asset_type, faucet, amount));
+    }
+
+    let account_storage = account.storage();
+    let mut storage_items = vec![];
+    for (idx, entry) in account_storage.slots().iter().enumerate() {
+        let item = account_storage
+            .get_item(u8::try_from(idx).expect("there are no more than 256 slots"))
+            .map_err(|err| CliError::Account(err, "Index out of bounds".to_string()))?;
+
+        // Last entry is reserved so I don't think the user cares about it. Also, to keep the
+        // output smaller, if the [StorageSlot] is a value and it's 0 we assume it's not
+        // initialized and skip it
+        if matches!(entry, StorageSlot::Value { .. }) && item == [ZERO; 4].into() {
+            continue;
+        }
+
+        let slot_type = match entry {
+            StorageSlot::Value(..) => "Value",
+            StorageSlot::Map(..) => "Map",
+        };
+        storage_items.push((idx, slot_type, item.to_hex()));
+    }
+
+    if json {
+        return print_account_json(&account, cli_config, &assets, &storage_items, with_code);
+    }
+
     print_summary_table(&account, cli_config)?;
 
     // Vault Table
     {
-        let assets = account.vault().assets();
-        let faucet_details_map = load_faucet_details_map()?;
         println!("Assets: ");
 
         let mut table = create_dynamic_table(&["Asset Type", "Faucet", "Amount"]);
-        for asset in assets {
-            let (asset_type, faucet, amount) = match asset {
-                Asset::Fungible(fungible_asset) => {
-                    let (faucet, amount) =
-                        faucet_details_map.format_fungible_asset(&fungible_asset)?;
-                    ("Fungible Asset", faucet, amount)
-                },
-                Asset::NonFungible(non_fungible_asset) => {
-                    // TODO: Display non-fungible assets more clearly.
-                    (
-                        "Non Fungible Asset",
-                        non_fungible_asset.faucet_id_prefix().to_hex(),
-                        1.0.to_string(),
-                    )
-                },
-            };
-            table.add_row(vec![asset_type, &faucet, &amount.to_string()]);
+        for (asset_type, faucet, amount) in &assets {
+            table.add_row(vec![*asset_type, faucet.as_str(), amount.as_str()]);
         }
 
         println!("{table}\n");
@@ -189,31 +321,14 @@ pub async fn show_account(
 
     // Storage Table
     {
-        let account_storage = account.storage();
-
         println!("Storage: \n");
 
         let mut table =
             create_dynamic_table(&["Item Slot Index", "Item Slot Type", "Value/Commitment"]);
-
-        for (idx, entry) in account_storage.slots().iter().enumerate() {
-            let item = account_storage
-                .get_item(u8::try_from(idx).expect("there are no more than 256 slots"))
-                .map_err(|err| CliError::Account(err, "Index out of bounds".to_string()))?;
-
-            // Last entry is reserved so I don't think the user cares about it. Also, to keep the
-            // output smaller, if the [StorageSlot] is a value and it's 0 we assume it's not
-            // initialized and skip it
-            if matches!(entry, StorageSlot::Value { .. }) && item == [ZERO; 4].into() {
-                continue;
-            }
-
-            let slot_type = match entry {
-                StorageSlot::Value(..) => "Value",
-                StorageSlot::Map(..) => "Map",
-            };
-            table.add_row(vec![&idx.to_string(), slot_type, &item.to_hex()]);
+        for (idx, slot_type, value) in &storage_items {
+            table.add_row(vec![idx.to_string(), (*slot_type).to_string(), value.clone()]);
         }
+
         println!("{table}\n");
     }
 
@@ -271,6 +386,44 @@ fn print_summary_table(account: &Account, cli_config: &CliConfig) -> Result<(),
     Ok(())
 }
 
+/// Prints the account information, along with its assets and storage items, as JSON.
+fn print_account_json(
+    account: &Account,
+    cli_config: &CliConfig,
+    assets: &[(&str, String, String)],
+    storage_items: &[(usize, &str, String)],
+    with_code: bool,
+) -> Result<(), CliError> {
+    let mut account_json = json!({
+        "address": account.id().to_bech32(cli_config.rpc.endpoint.0.to_network_id()?),
+        "id": account.id().to_hex(),
+        "commitment": account.commitment().to_hex(),
+        "type": account_type_display_name(&account.id())?,
+        "storage_mode": account.id().storage_mode().to_string(),
+        "code_commitment": account.code().commitment().to_hex(),
+        "vault_root": account.vault().asset_tree().root().to_hex(),
+        "storage_root": account.storage().commitment().to_hex(),
+        "nonce": account.nonce().as_int(),
+        "assets": assets
+            .iter()
+            .map(|(asset_type, faucet, amount)| {
+                json!({ "type": asset_type, "faucet": faucet, "amount": amount })
+            })
+            .collect::<Vec<_>>(),
+        "storage": storage_items
+            .iter()
+            .map(|(idx, slot_type, value)| {
+                json!({ "index": idx, "type": slot_type, "value": value })
+            })
+            .collect::<Vec<_>>(),
+    });
+    if with_code {
+        account_json["code"] = account.code().to_pretty_string().into();
+    }
+
+    print_json(&account_json)
+}
+
 /// Returns a display name for the account type.
 fn account_type_display_name(account_id: &AccountId) -> Result<String, CliError> {
     Ok(match account_id.account_type() {
@@ -287,13 +440,17 @@ fn account_type_display_name(account_id: &AccountId) -> Result<String, CliError>
 }
 
 /// Loads config file and displays current default account ID.
-fn display_default_account_id() -> Result<(), CliError> {
+fn display_default_account_id(json: bool) -> Result<(), CliError> {
     let (cli_config, _) = load_config_file()?;
 
     let default_account = cli_config.default_account_id.ok_or(CliError::Config(
         "Default account".to_string().into(),
         "No default account found in the configuration file".to_string(),
     ))?;
+    if json {
+        return print_json(&json!({ "default_account_id": default_account }));
+    }
+
     println!("Current default account ID: {default_account}");
     Ok(())
 }
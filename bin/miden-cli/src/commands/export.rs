@@ -94,9 +94,8 @@ async fn export_account(
     for pub_key in get_public_keys_from_account(&account) {
         key_pairs.push(
             keystore
-                .get_key(pub_key)
-                .map_err(CliError::KeyStore)?
-                .ok_or(CliError::Export("Auth not found for account".to_string()))?,
+                .get_key(pub_key)?
+                .ok_or(CliError::KeyStoreKeyNotFound(account_id.to_string()))?,
         );
     }
 
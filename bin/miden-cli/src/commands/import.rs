@@ -78,7 +78,7 @@ async fn import_account(
     let account_id = account_data.account.id();
 
     for key in account_data.auth_secret_keys {
-        keystore.add_key(&key).map_err(CliError::KeyStore)?;
+        keystore.add_key(&key)?;
     }
 
     client
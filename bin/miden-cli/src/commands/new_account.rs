@@ -26,8 +26,10 @@ use rand::RngCore;
 use tracing::debug;
 
 use crate::{
-    CLIENT_BINARY_NAME, CliKeyStore, commands::account::maybe_set_default_account,
-    errors::CliError, utils::load_config_file,
+    CLIENT_BINARY_NAME, CliKeyStore,
+    commands::account::maybe_set_default_account,
+    errors::CliError,
+    utils::{load_config_file, record_account_network_id},
 };
 
 // CLI TYPES
@@ -94,6 +96,14 @@ pub struct NewWalletCmd {
     /// present in the init storage data file.
     #[arg(short, long)]
     pub init_storage_data_path: Option<PathBuf>,
+    /// Value for one of the placeholders of the component templates, as `KEY=VALUE`. Can be
+    /// repeated, and takes precedence over the values in the init storage data file.
+    #[arg(long = "storage-value", value_name = "KEY=VALUE", value_parser = parse_storage_value)]
+    pub storage_values: Vec<(StorageValueName, String)>,
+    /// If set, fail instead of prompting for placeholder values that weren't provided through
+    /// `--storage-value` or the init storage data file.
+    #[arg(long, default_value_t = false)]
+    pub non_interactive: bool,
     /// If set, the newly created wallet will be deployed to the network by submitting an
     /// authentication transaction.
     #[arg(long, default_value_t = false)]
@@ -118,7 +128,8 @@ impl NewWalletCmd {
             account_type,
             self.storage_mode.into(),
             &component_template_paths,
-            self.init_storage_data_path.clone(),
+            &load_init_storage_data(self.init_storage_data_path.clone(), &self.storage_values)?,
+            self.non_interactive,
             self.deploy,
         )
         .await?;
@@ -163,6 +174,14 @@ pub struct NewAccountCmd {
     /// present in the init storage data file.
     #[arg(short, long)]
     pub init_storage_data_path: Option<PathBuf>,
+    /// Value for one of the placeholders of the component templates, as `KEY=VALUE`. Can be
+    /// repeated, and takes precedence over the values in the init storage data file.
+    #[arg(long = "storage-value", value_name = "KEY=VALUE", value_parser = parse_storage_value)]
+    pub storage_values: Vec<(StorageValueName, String)>,
+    /// If set, fail instead of prompting for placeholder values that weren't provided through
+    /// `--storage-value` or the init storage data file.
+    #[arg(long, default_value_t = false)]
+    pub non_interactive: bool,
     /// If set, the newly created account will be deployed to the network by submitting an
     /// authentication transaction.
     #[arg(long, default_value_t = false)]
@@ -177,7 +196,8 @@ impl NewAccountCmd {
             self.account_type.into(),
             self.storage_mode.into(),
             &self.component_templates,
-            self.init_storage_data_path.clone(),
+            &load_init_storage_data(self.init_storage_data_path.clone(), &self.storage_values)?,
+            self.non_interactive,
             self.deploy,
         )
         .await?;
@@ -200,7 +220,9 @@ impl NewAccountCmd {
 
 /// Reads component templates from the given file paths.
 // TODO: IO errors should have more context
-fn load_component_templates(paths: &[PathBuf]) -> Result<Vec<AccountComponentTemplate>, CliError> {
+pub(crate) fn load_component_templates(
+    paths: &[PathBuf],
+) -> Result<Vec<AccountComponentTemplate>, CliError> {
     let (cli_config, _) = load_config_file()?;
     let components_base_dir = &cli_config.component_template_directory;
     let mut templates = Vec::new();
@@ -223,16 +245,36 @@ fn load_component_templates(paths: &[PathBuf]) -> Result<Vec<AccountComponentTem
     Ok(templates)
 }
 
-/// Loads the initialization storage data from an optional TOML file.
-/// If None is passed, an empty object is returned.
-fn load_init_storage_data(path: Option<PathBuf>) -> Result<InitStorageData, CliError> {
-    if let Some(path) = path {
+/// Loads the initialization storage data from an optional TOML file, and adds the given storage
+/// values to it, overriding the values from the file for the same keys.
+/// If None is passed and there are no storage values, an empty object is returned.
+fn load_init_storage_data(
+    path: Option<PathBuf>,
+    storage_values: &[(StorageValueName, String)],
+) -> Result<InitStorageData, CliError> {
+    let file_init_storage_data = if let Some(path) = path {
         let mut contents = String::new();
         File::open(path).and_then(|mut f| f.read_to_string(&mut contents))?;
-        InitStorageData::from_toml(&contents).map_err(|err| CliError::Internal(Box::new(err)))
+        InitStorageData::from_toml(&contents).map_err(|err| CliError::Internal(Box::new(err)))?
     } else {
-        Ok(InitStorageData::default())
-    }
+        InitStorageData::default()
+    };
+
+    let mut placeholders = file_init_storage_data.placeholders().clone();
+    placeholders.extend(storage_values.iter().cloned());
+
+    Ok(InitStorageData::new(placeholders))
+}
+
+/// Parses a `KEY=VALUE` storage value passed through the `--storage-value` flag.
+fn parse_storage_value(storage_value: &str) -> Result<(StorageValueName, String), String> {
+    let (key, value) = storage_value
+        .split_once('=')
+        .ok_or_else(|| format!("invalid storage value `{storage_value}`, expected KEY=VALUE"))?;
+    let key = StorageValueName::new(key.trim())
+        .map_err(|err| format!("invalid storage value name `{key}`: {err}"))?;
+
+    Ok((key, value.trim().to_string()))
 }
 
 /// Helper function to create the seed, initialize the account builder, add the given components,
@@ -246,7 +288,8 @@ async fn create_client_account(
     account_type: AccountType,
     storage_mode: AccountStorageMode,
     component_template_paths: &[PathBuf],
-    init_storage_data_path: Option<PathBuf>,
+    init_storage_data: &InitStorageData,
+    non_interactive: bool,
     deploy: bool,
 ) -> Result<Account, CliError> {
     if component_template_paths.is_empty() {
@@ -259,9 +302,6 @@ async fn create_client_account(
     debug!("Loading component templates...");
     let component_templates = load_component_templates(component_template_paths)?;
     debug!("Loaded {} component templates", component_templates.len());
-    debug!("Loading initialization storage data...");
-    let init_storage_data = load_init_storage_data(init_storage_data_path)?;
-    debug!("Loaded initialization storage data");
 
     let mut init_seed = [0u8; 32];
     client.rng().fill_bytes(&mut init_seed);
@@ -274,7 +314,8 @@ async fn create_client_account(
         .with_auth_component(RpoFalcon512::new(key_pair.public_key()));
 
     // Process component templates and add them to the account builder.
-    let account_components = process_component_templates(&component_templates, &init_storage_data)?;
+    let account_components =
+        process_component_templates(&component_templates, init_storage_data, non_interactive)?;
     for component in account_components {
         builder = builder.with_component(component);
     }
@@ -283,11 +324,10 @@ async fn create_client_account(
         .build()
         .map_err(|err| CliError::Account(err, "failed to build account".into()))?;
 
-    keystore
-        .add_key(&AuthSecretKey::RpoFalcon512(key_pair))
-        .map_err(CliError::KeyStore)?;
+    keystore.add_key(&AuthSecretKey::RpoFalcon512(key_pair))?;
 
     client.add_account(&account, Some(seed), false).await?;
+    record_account_network_id(account.id())?;
 
     if deploy {
         deploy_account(client, &account).await?;
@@ -330,23 +370,32 @@ async fn deploy_account(client: &mut Client, account: &Account) -> Result<(), Cl
 }
 
 /// Helper function to process extra component templates.
-/// It reads user input for each placeholder in a component template.
+/// It reads user input for each placeholder in a component template that doesn't have a value in
+/// `init_storage_data`, or fails if `non_interactive` is set.
 fn process_component_templates(
     extra_components: &[AccountComponentTemplate],
-    file_init_storage_data: &InitStorageData,
+    init_storage_data: &InitStorageData,
+    non_interactive: bool,
 ) -> Result<Vec<AccountComponent>, CliError> {
     let mut account_components = vec![];
     for component_template in extra_components {
-        let mut init_storage_data: BTreeMap<StorageValueName, String> =
-            file_init_storage_data.placeholders().clone();
+        let mut component_storage_data: BTreeMap<StorageValueName, String> =
+            init_storage_data.placeholders().clone();
         for (placeholder_key, placeholder_type) in
             component_template.metadata().get_placeholder_requirements()
         {
-            if init_storage_data.contains_key(&placeholder_key) {
-                // The use provided it through the TOML file, so we can skip it
+            if component_storage_data.contains_key(&placeholder_key) {
+                // The user provided it through a flag or the TOML file, so we can skip it
                 continue;
             }
 
+            if non_interactive {
+                return Err(CliError::Input(format!(
+                    "missing value for placeholder '{placeholder_key}', provide it with \
+                     `--storage-value {placeholder_key}=<VALUE>` or in the init storage data file"
+                )));
+            }
+
             let description = placeholder_type.description.unwrap_or("[No description]".into());
             print!(
                 "Enter value for '{placeholder_key}' - {description} (type: {}): ",
@@ -357,12 +406,12 @@ fn process_component_templates(
             let mut input_value = String::new();
             std::io::stdin().read_line(&mut input_value)?;
             let input_value = input_value.trim();
-            init_storage_data.insert(placeholder_key, input_value.to_string());
+            component_storage_data.insert(placeholder_key, input_value.to_string());
         }
 
         let component = AccountComponent::from_template(
             component_template,
-            &InitStorageData::new(init_storage_data),
+            &InitStorageData::new(component_storage_data),
         )
         .map_err(|e| CliError::Account(e, "error instantiating component from template".into()))?;
 
@@ -4,12 +4,12 @@ use clap::{Parser, ValueEnum};
 use miden_client::{
     Client, RemoteTransactionProver,
     account::AccountId,
-    asset::{FungibleAsset, NonFungibleDeltaAction},
+    asset::{Asset, FungibleAsset, NonFungibleDeltaAction},
     crypto::Digest,
     note::{BlockNumber, NoteType as MidenNoteType, build_swap_tag, get_input_note_with_id_prefix},
     store::NoteRecordError,
     transaction::{
-        InputNote, OutputNote, PaymentNoteDescription, SwapTransactionData, TransactionRequest,
+        InputNote, OutputNote, PaymentNoteDescription, TransactionId, TransactionRequest,
         TransactionRequestBuilder, TransactionResult,
     },
 };
@@ -88,7 +88,9 @@ impl MintCmd {
             force,
             self.delegate_proving,
         )
-        .await
+        .await?;
+
+        Ok(())
     }
 }
 
@@ -171,7 +173,9 @@ impl SendCmd {
             force,
             self.delegate_proving,
         )
-        .await
+        .await?;
+
+        Ok(())
     }
 }
 
@@ -180,15 +184,15 @@ impl SendCmd {
 pub struct SwapCmd {
     /// Sender account ID or its hex prefix. If none is provided, the default account's ID is used
     /// instead.
-    #[arg(short = 's', long = "source")]
+    #[arg(short = 's', long = "source", visible_alias = "account")]
     sender_account_id: Option<String>,
 
     /// Asset offered.
-    #[arg(long = "offered-asset", help=format!("Asset offered.\n{SHARED_TOKEN_DOCUMENTATION}"))]
+    #[arg(long = "offered-asset", visible_alias = "offer", help=format!("Asset offered.\n{SHARED_TOKEN_DOCUMENTATION}"))]
     offered_asset: String,
 
     /// Asset requested.
-    #[arg(short, long, help=format!("Asset requested.\n{SHARED_TOKEN_DOCUMENTATION}"))]
+    #[arg(short, long, visible_alias = "request", help=format!("Asset requested.\n{SHARED_TOKEN_DOCUMENTATION}"))]
     requested_asset: String,
 
     #[arg(short, long, value_enum)]
@@ -217,19 +221,41 @@ impl SwapCmd {
         let sender_account_id =
             get_input_acc_id_by_prefix_or_default(&client, self.sender_account_id.clone()).await?;
 
-        let swap_transaction = SwapTransactionData::new(
-            sender_account_id,
-            offered_fungible_asset.into(),
-            requested_fungible_asset.into(),
-        );
+        // check the offered amount up front, as the transaction would otherwise fail with a
+        // less descriptive execution error
+        let sender_account = client
+            .get_account(sender_account_id)
+            .await?
+            .ok_or(CliError::Input(format!("Account {sender_account_id} is not tracked")))?;
+        let offered_balance = sender_account
+            .account()
+            .vault()
+            .get_balance(offered_fungible_asset.faucet_id())
+            .map_err(|err| CliError::Internal(Box::new(err)))?;
+        if offered_balance < offered_fungible_asset.amount() {
+            return Err(CliError::Input(format!(
+                "Account {sender_account_id} doesn't have enough of the offered asset: it holds {offered_balance} but {} were offered",
+                offered_fungible_asset.amount()
+            )));
+        }
 
-        let transaction_request = TransactionRequestBuilder::new()
-            .build_swap(&swap_transaction, (&self.note_type).into(), client.rng())
-            .map_err(|err| {
-                CliError::Transaction(err.into(), "Failed to build swap transaction".to_string())
-            })?;
+        let offered_asset: Asset = offered_fungible_asset.into();
+        let requested_asset: Asset = requested_fungible_asset.into();
 
-        execute_transaction(
+        let transaction_request = client
+            .build_swap_offer(
+                sender_account_id,
+                offered_asset,
+                requested_asset,
+                (&self.note_type).into(),
+            )
+            .await?;
+        let swap_note = transaction_request
+            .expected_output_own_notes()
+            .pop()
+            .expect("SWAP transaction request should create the SWAP note");
+
+        let transaction_id = execute_transaction(
             &mut client,
             sender_account_id,
             transaction_request,
@@ -237,14 +263,19 @@ impl SwapCmd {
             self.delegate_proving,
         )
         .await?;
+        if transaction_id.is_none() {
+            return Ok(());
+        }
 
-        let payback_note_tag: u32 = build_swap_tag(
-            (&self.note_type).into(),
-            &swap_transaction.offered_asset(),
-            &swap_transaction.requested_asset(),
-        )
-        .map_err(|err| CliError::Transaction(err.into(), "Failed to build swap tag".to_string()))?
-        .into();
+        println!("SWAP note ID: {}", swap_note.id());
+        println!("SWAP note tag: {}", swap_note.metadata().tag());
+
+        let payback_note_tag: u32 =
+            build_swap_tag((&self.note_type).into(), &offered_asset, &requested_asset)
+                .map_err(|err| {
+                    CliError::Transaction(err.into(), "Failed to build swap tag".to_string())
+                })?
+                .into();
         println!(
             "To receive updates about the payback Swap Note run `miden tags add {payback_note_tag}`",
         );
@@ -335,20 +366,24 @@ impl ConsumeNotesCmd {
             force,
             self.delegate_proving,
         )
-        .await
+        .await?;
+
+        Ok(())
     }
 }
 
 // EXECUTE TRANSACTION
 // ================================================================================================
 
+/// Executes the transaction and, once confirmed, proves and submits it. Returns the ID of the
+/// submitted transaction, or `None` if the user cancelled it.
 async fn execute_transaction(
     client: &mut Client,
     account_id: AccountId,
     transaction_request: TransactionRequest,
     force: bool,
     delegated_proving: bool,
-) -> Result<(), CliError> {
+) -> Result<Option<TransactionId>, CliError> {
     println!("Executing transaction...");
     let transaction_execution_result =
         client.new_transaction(account_id, transaction_request).await?;
@@ -364,7 +399,7 @@ async fn execute_transaction(
 
         if proceed_str.trim().to_lowercase() != "y" {
             println!("Transaction was cancelled.");
-            return Ok(());
+            return Ok(None);
         }
     }
 
@@ -406,7 +441,7 @@ async fn execute_transaction(
         }
     }
 
-    Ok(())
+    Ok(Some(transaction_id))
 }
 
 fn print_transaction_details(transaction_result: &TransactionResult) -> Result<(), CliError> {
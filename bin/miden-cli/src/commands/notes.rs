@@ -1,20 +1,21 @@
 use clap::ValueEnum;
 use comfy_table::{Attribute, Cell, ContentArrangement, Table, presets};
 use miden_client::{
-    Client, ClientError, IdPrefixFetchError,
+    Client, ClientError, Felt, IdPrefixFetchError,
     asset::Asset,
     crypto::Digest,
-    note::{
-        NoteConsumability, NoteInputs, NoteMetadata, WellKnownNote, get_input_note_with_id_prefix,
-    },
+    note::{NoteInputs, NoteMetadata, NoteRelevance, WellKnownNote, get_input_note_with_id_prefix},
     store::{InputNoteRecord, NoteFilter as ClientNoteFilter, OutputNoteRecord},
 };
 use miden_objects::PrettyPrint;
+use serde::Serialize;
+use serde_json::json;
 
 use crate::{
     Parser, create_dynamic_table,
     errors::CliError,
-    get_output_note_with_id_prefix,
+    faucet_details_map::FaucetDetailsMap,
+    get_output_note_with_id_prefix, print_json,
     utils::{load_faucet_details_map, parse_account_id},
 };
 
@@ -57,34 +58,40 @@ pub struct NotesCmd {
     with_code: bool,
     /// (only has effect on `--list consumable`) Account ID used to filter list. Only notes
     /// consumable by this account will be shown.
-    #[arg(short, long, value_name = "account_id")]
+    #[arg(short, long, visible_alias = "account", value_name = "account_id")]
     account_id: Option<String>,
+    /// (only has effect on `--list consumable`) Only show notes that can be consumed right now.
+    #[arg(long)]
+    now_only: bool,
 }
 
 impl NotesCmd {
-    pub async fn execute(&self, client: Client) -> Result<(), CliError> {
+    pub async fn execute(&self, client: Client, json: bool) -> Result<(), CliError> {
         match self {
             NotesCmd { list: Some(NoteFilter::Consumable), .. } => {
-                list_consumable_notes(client, None).await?;
+                list_consumable_notes(client, self.account_id.as_ref(), self.now_only, json)
+                    .await?;
             },
             NotesCmd { list: Some(filter), .. } => {
                 list_notes(
                     client,
                     filter.clone().try_into().expect("Filter shouldn't be consumable"),
+                    json,
                 )
                 .await?;
             },
             NotesCmd { show: Some(id), .. } => {
-                show_note(client, id.to_owned(), self.with_code).await?;
+                show_note(client, id.to_owned(), self.with_code, json).await?;
             },
             _ => {
-                list_notes(client, ClientNoteFilter::All).await?;
+                list_notes(client, ClientNoteFilter::All, json).await?;
             },
         }
         Ok(())
     }
 }
 
+#[derive(Serialize)]
 struct CliNoteSummary {
     id: String,
     script_root: String,
@@ -100,7 +107,7 @@ struct CliNoteSummary {
 
 // LIST NOTES
 // ================================================================================================
-async fn list_notes(client: Client, filter: ClientNoteFilter) -> Result<(), CliError> {
+async fn list_notes(client: Client, filter: ClientNoteFilter, json: bool) -> Result<(), CliError> {
     let input_notes = client
         .get_input_notes(filter.clone())
         .await?
@@ -114,6 +121,10 @@ async fn list_notes(client: Client, filter: ClientNoteFilter) -> Result<(), CliE
         .map(|output_note_record| note_summary(None, Some(&output_note_record)))
         .collect::<Vec<CliNoteSummary>>();
 
+    if json {
+        return print_json(&json!({ "input_notes": input_notes, "output_notes": output_notes }));
+    }
+
     print_notes_summary(input_notes, "Input Notes");
     print_notes_summary(output_notes, "Output Notes");
 
@@ -123,7 +134,12 @@ async fn list_notes(client: Client, filter: ClientNoteFilter) -> Result<(), CliE
 // SHOW NOTE
 // ================================================================================================
 #[allow(clippy::too_many_lines)]
-async fn show_note(client: Client, note_id: String, with_code: bool) -> Result<(), CliError> {
+async fn show_note(
+    client: Client,
+    note_id: String,
+    with_code: bool,
+    json: bool,
+) -> Result<(), CliError> {
     let input_note_record = get_input_note_with_id_prefix(&client, &note_id).await;
     let output_note_record = get_output_note_with_id_prefix(&client, &note_id).await;
 
@@ -158,50 +174,20 @@ async fn show_note(client: Client, note_id: String, with_code: bool) -> Result<(
         _ => {},
     }
 
-    let mut table = create_dynamic_table(&["Note Information"]);
-    table
-        .load_preset(presets::UTF8_HORIZONTAL_ONLY)
-        .set_content_arrangement(ContentArrangement::DynamicFullWidth);
-
-    let CliNoteSummary {
-        id,
-        mut script_root,
-        assets_commitment,
-        inputs_commitment,
-        serial_num,
-        note_type,
-        state,
-        tag,
-        sender,
-        exportable,
-    } = note_summary(input_note_record.as_ref(), output_note_record.as_ref());
-    table.add_row(vec![Cell::new("ID"), Cell::new(id)]);
-
-    match script_root {
+    let mut summary = note_summary(input_note_record.as_ref(), output_note_record.as_ref());
+    match summary.script_root {
         ref p2id_root if p2id_root == &WellKnownNote::P2ID.script_root().to_string() => {
-            script_root += " (P2ID)";
+            summary.script_root += " (P2ID)";
         },
         ref p2ide_root if p2ide_root == &WellKnownNote::P2IDE.script_root().to_string() => {
-            script_root += " (P2IDE)";
+            summary.script_root += " (P2IDE)";
         },
         ref swap_root if swap_root == &WellKnownNote::SWAP.script_root().to_string() => {
-            script_root += " (SWAP)";
+            summary.script_root += " (SWAP)";
         },
         _ => {},
     }
 
-    table.add_row(vec![Cell::new("Script Root"), Cell::new(script_root)]);
-    table.add_row(vec![Cell::new("Assets Commitment"), Cell::new(assets_commitment)]);
-    table.add_row(vec![Cell::new("Inputs Commitment"), Cell::new(inputs_commitment)]);
-    table.add_row(vec![Cell::new("Serial Number"), Cell::new(serial_num)]);
-    table.add_row(vec![Cell::new("Type"), Cell::new(note_type)]);
-    table.add_row(vec![Cell::new("State"), Cell::new(state)]);
-    table.add_row(vec![Cell::new("Tag"), Cell::new(tag)]);
-    table.add_row(vec![Cell::new("Sender"), Cell::new(sender)]);
-    table.add_row(vec![Cell::new("Exportable"), Cell::new(if exportable { "✔" } else { "✘" })]);
-
-    println!("{table}");
-
     let inputs = match (&input_note_record, &output_note_record) {
         (Some(record), _) => {
             let details = record.details();
@@ -214,28 +200,19 @@ async fn show_note(client: Client, note_id: String, with_code: bool) -> Result<(
             panic!("One of the two records should be Some")
         },
     };
+    let inputs = inputs
+        .map(|inputs| NoteInputs::new(inputs).map_err(ClientError::NoteError))
+        .transpose()?;
 
-    let assets = input_note_record
+    let note_assets = input_note_record
         .clone()
         .map(|record| record.assets().clone())
         .or(output_note_record.clone().map(|record| record.assets().clone()))
         .expect("One of the two records should be Some");
 
-    // print note vault
-    let mut table = create_dynamic_table(&["Note Assets"]);
-    table
-        .load_preset(presets::UTF8_HORIZONTAL_ONLY)
-        .set_content_arrangement(ContentArrangement::DynamicFullWidth);
-
-    table.add_row(vec![
-        Cell::new("Type").add_attribute(Attribute::Bold),
-        Cell::new("Faucet ID").add_attribute(Attribute::Bold),
-        Cell::new("Amount").add_attribute(Attribute::Bold),
-    ]);
     let faucet_details_map = load_faucet_details_map()?;
-    let assets = assets.iter();
-
-    for asset in assets {
+    let mut assets = vec![];
+    for asset in note_assets.iter() {
         let (asset_type, faucet, amount) = match asset {
             Asset::Fungible(fungible_asset) => {
                 let (faucet, amount) = faucet_details_map.format_fungible_asset(fungible_asset)?;
@@ -247,12 +224,93 @@ async fn show_note(client: Client, note_id: String, with_code: bool) -> Result<(
                 1.0.to_string(),
             ),
         };
-        table.add_row(vec![asset_type, &faucet, &amount.to_string()]);
+        assets.push((asset_type, faucet, amount));
+    }
+
+    let code = with_code.then(|| match (&input_note_record, &output_note_record) {
+        (Some(record), _) => record.details().script().to_pretty_string(),
+        (_, Some(record)) => {
+            record.state().recipient().map_or("Code unavailable".to_string(), |recipient| {
+                recipient.script().to_pretty_string()
+            })
+        },
+        (None, None) => {
+            panic!("One of the two records should be Some")
+        },
+    });
+
+    if json {
+        let mut note_json =
+            serde_json::to_value(&summary).map_err(|err| CliError::Internal(err.into()))?;
+        note_json["assets"] = assets
+            .iter()
+            .map(|(asset_type, faucet, amount)| {
+                json!({ "type": asset_type, "faucet": faucet, "amount": amount })
+            })
+            .collect::<Vec<_>>()
+            .into();
+        note_json["inputs"] = inputs
+            .as_ref()
+            .map(|inputs| {
+                serde_json::Value::from(
+                    inputs.values().iter().map(Felt::as_int).collect::<Vec<_>>(),
+                )
+            })
+            .unwrap_or_default();
+        if let Some(code) = code {
+            note_json["code"] = code.into();
+        }
+
+        return print_json(&note_json);
+    }
+
+    let mut table = create_dynamic_table(&["Note Information"]);
+    table
+        .load_preset(presets::UTF8_HORIZONTAL_ONLY)
+        .set_content_arrangement(ContentArrangement::DynamicFullWidth);
+
+    let CliNoteSummary {
+        id,
+        script_root,
+        assets_commitment,
+        inputs_commitment,
+        serial_num,
+        note_type,
+        state,
+        tag,
+        sender,
+        exportable,
+    } = summary;
+    table.add_row(vec![Cell::new("ID"), Cell::new(id)]);
+    table.add_row(vec![Cell::new("Script Root"), Cell::new(script_root)]);
+    table.add_row(vec![Cell::new("Assets Commitment"), Cell::new(assets_commitment)]);
+    table.add_row(vec![Cell::new("Inputs Commitment"), Cell::new(inputs_commitment)]);
+    table.add_row(vec![Cell::new("Serial Number"), Cell::new(serial_num)]);
+    table.add_row(vec![Cell::new("Type"), Cell::new(note_type)]);
+    table.add_row(vec![Cell::new("State"), Cell::new(state)]);
+    table.add_row(vec![Cell::new("Tag"), Cell::new(tag)]);
+    table.add_row(vec![Cell::new("Sender"), Cell::new(sender)]);
+    table.add_row(vec![Cell::new("Exportable"), Cell::new(if exportable { "✔" } else { "✘" })]);
+
+    println!("{table}");
+
+    // print note vault
+    let mut table = create_dynamic_table(&["Note Assets"]);
+    table
+        .load_preset(presets::UTF8_HORIZONTAL_ONLY)
+        .set_content_arrangement(ContentArrangement::DynamicFullWidth);
+
+    table.add_row(vec![
+        Cell::new("Type").add_attribute(Attribute::Bold),
+        Cell::new("Faucet ID").add_attribute(Attribute::Bold),
+        Cell::new("Amount").add_attribute(Attribute::Bold),
+    ]);
+    for (asset_type, faucet, amount) in &assets {
+        table.add_row(vec![*asset_type, faucet.as_str(), amount.as_str()]);
     }
     println!("{table}");
 
     if let Some(inputs) = inputs {
-        let inputs = NoteInputs::new(inputs.clone()).map_err(ClientError::NoteError)?;
         let mut table = create_dynamic_table(&["Note Inputs"]);
         table
             .load_preset(presets::UTF8_HORIZONTAL_ONLY)
@@ -268,19 +326,8 @@ async fn show_note(client: Client, note_id: String, with_code: bool) -> Result<(
         println!("{table}");
     }
 
-    if with_code {
+    if let Some(code) = code {
         let mut table = create_dynamic_table(&["Note Code"]);
-        let code = match (&input_note_record, &output_note_record) {
-            (Some(record), _) => record.details().script().to_pretty_string(),
-            (_, Some(record)) => {
-                record.state().recipient().map_or("Code unavailable".to_string(), |recipient| {
-                    recipient.script().to_pretty_string()
-                })
-            },
-            (None, None) => {
-                panic!("One of the two records should be Some")
-            },
-        };
         table.add_row(vec![Cell::new(code)]);
         println!("{table}");
     }
@@ -293,16 +340,105 @@ async fn show_note(client: Client, note_id: String, with_code: bool) -> Result<(
 async fn list_consumable_notes(
     client: Client,
     account_id: Option<&String>,
+    now_only: bool,
+    json: bool,
 ) -> Result<(), CliError> {
     let account_id = match account_id {
         Some(id) => Some(parse_account_id(&client, id).await?),
         None => None,
     };
-    let notes = client.get_consumable_notes(account_id).await?;
-    print_consumable_notes_summary(&notes);
+    let faucet_details_map = load_faucet_details_map()?;
+
+    let mut consumable_notes = vec![];
+    for (note, relevances) in client.get_consumable_notes(account_id).await? {
+        let (faucets, amounts) = format_note_assets(note.assets().iter(), &faucet_details_map)?;
+        for (account_id, relevance) in relevances {
+            consumable_notes.push(CliConsumableNote {
+                note_id: note.id().to_hex(),
+                account_id: account_id.to_string(),
+                faucet: faucets.clone(),
+                amount: amounts.clone(),
+                relevance,
+            });
+        }
+    }
+
+    if json {
+        let notes = consumable_notes
+            .iter()
+            .filter(|note| !now_only || note.relevance == NoteRelevance::Now)
+            .map(|note| {
+                json!({
+                    "note_id": note.note_id,
+                    "account_id": note.account_id,
+                    "faucet": note.faucet,
+                    "amount": note.amount,
+                    "relevance": note.relevance.to_string(),
+                })
+            })
+            .collect::<Vec<_>>();
+        return print_json(&notes);
+    }
+
+    println!("{}", consumable_notes_table(&consumable_notes, now_only));
     Ok(())
 }
 
+/// A single row of the consumable notes table, describing when an account can consume a note.
+struct CliConsumableNote {
+    note_id: String,
+    account_id: String,
+    faucet: String,
+    amount: String,
+    relevance: NoteRelevance,
+}
+
+/// Builds the table listing the consumable notes. If `now_only` is set, notes that can't be
+/// consumed in the current block are left out.
+fn consumable_notes_table(notes: &[CliConsumableNote], now_only: bool) -> Table {
+    let mut table =
+        create_dynamic_table(&["Note ID", "Account ID", "Faucet", "Amount", "Relevance"]);
+
+    for note in notes {
+        if now_only && note.relevance != NoteRelevance::Now {
+            continue;
+        }
+
+        table.add_row(vec![
+            note.note_id.clone(),
+            note.account_id.clone(),
+            note.faucet.clone(),
+            note.amount.clone(),
+            note.relevance.to_string(),
+        ]);
+    }
+
+    table
+}
+
+/// Formats the note assets into a faucet column and an amount column, with one line per asset.
+fn format_note_assets<'a>(
+    assets: impl Iterator<Item = &'a Asset>,
+    faucet_details_map: &FaucetDetailsMap,
+) -> Result<(String, String), CliError> {
+    let mut faucets = vec![];
+    let mut amounts = vec![];
+    for asset in assets {
+        let (faucet, amount) = match asset {
+            Asset::Fungible(fungible_asset) => {
+                faucet_details_map.format_fungible_asset(fungible_asset)?
+            },
+            Asset::NonFungible(non_fungible_asset) => {
+                (non_fungible_asset.faucet_id_prefix().to_hex(), 1.to_string())
+            },
+        };
+        faucets.push(faucet);
+        amounts.push(amount);
+    }
+
+    Ok((faucets.join("\n"), amounts.join("\n")))
+}
+
 // HELPERS
 // ================================================================================================
 fn print_notes_summary<I>(notes: I, header: &str)
@@ -320,25 +456,6 @@ where
     }
 }
 
-fn print_consumable_notes_summary<'a, I>(notes: I)
-where
-    I: IntoIterator<Item = &'a (InputNoteRecord, Vec<NoteConsumability>)>,
-{
-    let mut table = create_dynamic_table(&["Note ID", "Account ID", "Relevance"]);
-
-    for (note, relevances) in notes {
-        for relevance in relevances {
-            table.add_row(vec![
-                note.id().to_hex(),
-                relevance.0.to_string(),
-                relevance.1.to_string(),
-            ]);
-        }
-    }
-
-    println!("{table}");
-}
-
 fn note_record_type(note_record_metadata: Option<&NoteMetadata>) -> String {
     match note_record_metadata {
         Some(metadata) => match metadata.note_type() {
@@ -422,3 +539,56 @@ fn note_summary(
         exportable: output_note_record.is_some(),
     }
 }
+
+// HELPER TESTS
+// ================================================================================================
+
+#[test]
+fn consumable_notes_table_output() {
+    let notes = [
+        CliConsumableNote {
+            note_id: "0x01".to_string(),
+            account_id: "0xaa".to_string(),
+            faucet: "TST".to_string(),
+            amount: "1.50".to_string(),
+            relevance: NoteRelevance::Now,
+        },
+        CliConsumableNote {
+            note_id: "0x02".to_string(),
+            account_id: "0xbb".to_string(),
+            faucet: "0xcc".to_string(),
+            amount: "100".to_string(),
+            relevance: NoteRelevance::After(42),
+        },
+    ];
+
+    let mut table = consumable_notes_table(&notes, false);
+    table.force_no_tty();
+    assert_eq!(
+        table.to_string(),
+        [
+            "┌─────────┬────────────┬────────┬────────┬────────────────┐",
+            "│ Note ID ┆ Account ID ┆ Faucet ┆ Amount ┆ Relevance      │",
+            "╞═════════╪════════════╪════════╪════════╪════════════════╡",
+            "│ 0x01    ┆ 0xaa       ┆ TST    ┆ 1.50   ┆ Now            │",
+            "├╌╌╌╌╌╌╌╌╌┼╌╌╌╌╌╌╌╌╌╌╌╌┼╌╌╌╌╌╌╌╌┼╌╌╌╌╌╌╌╌┼╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌┤",
+            "│ 0x02    ┆ 0xbb       ┆ 0xcc   ┆ 100    ┆ After block 42 │",
+            "└─────────┴────────────┴────────┴────────┴────────────────┘",
+        ]
+        .join("\n")
+    );
+
+    let mut table = consumable_notes_table(&notes, true);
+    table.force_no_tty();
+    assert_eq!(
+        table.to_string(),
+        [
+            "┌─────────┬────────────┬────────┬────────┬───────────┐",
+            "│ Note ID ┆ Account ID ┆ Faucet ┆ Amount ┆ Relevance │",
+            "╞═════════╪════════════╪════════╪════════╪═══════════╡",
+            "│ 0x01    ┆ 0xaa       ┆ TST    ┆ 1.50   ┆ Now       │",
+            "└─────────┴────────────┴────────┴────────┴───────────┘",
+        ]
+        .join("\n")
+    );
+}
@@ -1,6 +1,7 @@
 use miden_client::{Client, store::TransactionFilter, transaction::TransactionRecord};
+use serde_json::json;
 
-use crate::{Parser, create_dynamic_table, errors::CliError};
+use crate::{Parser, create_dynamic_table, errors::CliError, print_json};
 
 #[derive(Default, Debug, Parser, Clone)]
 #[command(about = "Manage and view transactions. Defaults to `list` command")]
@@ -11,16 +12,33 @@ pub struct TransactionCmd {
 }
 
 impl TransactionCmd {
-    pub async fn execute(&self, client: Client) -> Result<(), CliError> {
-        list_transactions(client).await?;
+    pub async fn execute(&self, client: Client, json: bool) -> Result<(), CliError> {
+        list_transactions(client, json).await?;
         Ok(())
     }
 }
 
 // LIST TRANSACTIONS
 // ================================================================================================
-async fn list_transactions(client: Client) -> Result<(), CliError> {
+async fn list_transactions(client: Client, json: bool) -> Result<(), CliError> {
     let transactions = client.get_transactions(TransactionFilter::All).await?;
+    if json {
+        let transactions = transactions
+            .iter()
+            .map(|tx| {
+                json!({
+                    "id": tx.id.to_string(),
+                    "status": tx.status.to_string(),
+                    "account_id": tx.details.account_id.to_string(),
+                    "script_root": tx.script.as_ref().map(|script| script.root().to_string()),
+                    "input_notes_count": tx.details.input_note_nullifiers.len(),
+                    "output_notes_count": tx.details.output_notes.num_notes(),
+                })
+            })
+            .collect::<Vec<_>>();
+        return print_json(&transactions);
+    }
+
     print_transactions_summary(&transactions);
     Ok(())
 }
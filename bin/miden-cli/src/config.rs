@@ -1,5 +1,6 @@
 use core::fmt::Debug;
 use std::{
+    collections::BTreeMap,
     fmt::Display,
     path::{Path, PathBuf},
     str::FromStr,
@@ -39,6 +40,11 @@ pub struct CliConfig {
     /// Maximum number of blocks the client can be behind the network for transactions and account
     /// proofs to be considered valid.
     pub max_block_number_delta: Option<u32>,
+    /// Network IDs of the endpoints the accounts were created with, keyed by account ID. Used to
+    /// warn when the configured endpoint belongs to a different network, as the accounts'
+    /// addresses differ between networks.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub account_network_ids: BTreeMap<String, String>,
 }
 
 // Make `ClientConfig` a provider itself for composability.
@@ -74,6 +80,7 @@ impl Default for CliConfig {
             remote_prover_endpoint: None,
             component_template_directory: Path::new(DEFAULT_COMPONENT_TEMPLATE_DIR).to_path_buf(),
             max_block_number_delta: None,
+            account_network_ids: BTreeMap::new(),
         }
     }
 }
@@ -58,6 +58,30 @@ pub enum CliError {
     #[error("keystore error")]
     #[diagnostic(code(cli::keystore_error))]
     KeyStore(#[source] KeyStoreError),
+    #[error("duplicate key: {0}")]
+    #[diagnostic(
+        code(cli::keystore_duplicate_key),
+        help(
+            "A different key is stored in the keystore for the same public key. Check the contents of the `secret_keys_directory` in the configuration file."
+        )
+    )]
+    KeyStoreDuplicateKey(String),
+    #[error("key not found for account {0}")]
+    #[diagnostic(
+        code(cli::keystore_key_not_found),
+        help(
+            "Check that the `secret_keys_directory` in the configuration file contains the account's keys, or import them with `{CLIENT_BINARY_NAME} import`."
+        )
+    )]
+    KeyStoreKeyNotFound(String),
+    #[error("keystore directory not writable: {0}")]
+    #[diagnostic(
+        code(cli::keystore_not_writable),
+        help(
+            "Check that the `secret_keys_directory` in the configuration file points to a directory the client can write to."
+        )
+    )]
+    KeyStoreNotWritable(String),
     #[error("missing flag: {0}")]
     #[diagnostic(code(cli::config_error), help("Check the configuration file format."))]
     MissingFlag(String),
@@ -75,3 +99,17 @@ pub enum CliError {
     #[diagnostic(code(cli::transaction_error))]
     Transaction(#[source] SourceError, String),
 }
+
+impl From<KeyStoreError> for CliError {
+    fn from(err: KeyStoreError) -> Self {
+        match err {
+            KeyStoreError::DuplicateKey(pub_key) => {
+                CliError::KeyStoreDuplicateKey(format!("public key {pub_key}"))
+            },
+            KeyStoreError::DirectoryNotWritable(directory, reason) => {
+                CliError::KeyStoreNotWritable(format!("{directory} ({reason})"))
+            },
+            err => CliError::KeyStore(err),
+        }
+    }
+}
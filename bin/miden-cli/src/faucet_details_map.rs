@@ -3,12 +3,20 @@ use std::{
     path::PathBuf,
 };
 
-use miden_client::{Client, account::AccountId, asset::FungibleAsset};
-use miden_lib::account::faucets::BasicFungibleFaucet;
+use miden_client::{
+    Client,
+    account::AccountId,
+    amount::{format_amount, parse_amount},
+    asset::FungibleAsset,
+};
 use serde::{Deserialize, Serialize};
 
 use crate::{errors::CliError, load_config_file, utils::parse_account_id};
 
+/// Suffix of amounts that are given in whole tokens along with a faucet ID, such as
+/// `1.25tokens::<FAUCET_ID>`.
+const TOKEN_AMOUNT_SUFFIX: &str = "tokens";
+
 /// Stores the detail information of a faucet to be stored in the token symbol map file.
 #[derive(Debug, Serialize, Deserialize)]
 pub struct FaucetDetails {
@@ -72,25 +80,28 @@ impl FaucetDetailsMap {
         self.get_token_symbol(faucet_id).unwrap_or("Unknown".to_string())
     }
 
-    /// Parses a string representing a [`FungibleAsset`]. There are two accepted formats for the
-    /// string:
-    /// - `<AMOUNT>::<FAUCET_ID>` where `<AMOUNT>` is in the faucet base units and `<FAUCET_ID>` is
-    ///   the faucet's account iD.
-    /// - `<AMOUNT>::<FAUCET_ADDRESS>` where `<AMOUNT>` is in the faucet base units and
-    ///   `<FAUCET_ADDRESS>` is the faucet address.
+    /// Parses a string representing a [`FungibleAsset`]. The accepted formats for the string are:
+    /// - `<AMOUNT>::<FAUCET_ID>` where `<AMOUNT>` is an integer in the faucet base units and
+    ///   `<FAUCET_ID>` is the faucet's account ID or address.
+    /// - `<AMOUNT>tokens::<FAUCET_ID>` where `<AMOUNT>` is a quantity of whole tokens, optionally
+    ///   with a decimal point (such as `1.25`). The amount is converted to base units using the
+    ///   decimals from the faucet's token metadata.
     /// - `<AMOUNT>::<TOKEN_SYMBOL>` where `<AMOUNT>` is a decimal number representing the quantity
     ///   of the token (specified to the precision allowed by the token's decimals), and
     ///   `<TOKEN_SYMBOL>` is a symbol tracked in the token symbol map file.
     ///
     /// Some examples of valid `arg` values are `100::mlcl1qru2e5yvx40ndgqqqzusrryr0ucyd0uj`,
-    /// `100::0xabcdef0123456789` and `1.23::TST`.
+    /// `100::0xabcdef0123456789`, `1.25tokens::0xabcdef0123456789` and `1.23::TST`.
     ///
     /// # Errors
     ///
     /// Will return an error if:
     /// - The provided `arg` doesn't match one of the expected formats.
-    /// - A faucet ID was provided but the amount isn't in base units.
+    /// - A faucet ID was provided but the amount is neither an integer number of base units nor
+    ///   has the `tokens` suffix.
     /// - The amount has more than the allowed number of decimals.
+    /// - An amount in tokens was provided with a faucet ID but the faucet's token metadata
+    ///   couldn't be retrieved.
     /// - The token symbol isn't present in the token symbol map file.
     pub async fn parse_fungible_asset(
         &self,
@@ -102,9 +113,24 @@ impl FaucetDetailsMap {
             "Failed to parse amount and asset".to_string(),
         ))?;
         let (faucet_id, amount) = if let Ok(id) = parse_account_id(client, asset).await {
-            let amount = amount
-                .parse::<u64>()
-                .map_err(|err| CliError::Parse(err.into(), "Failed to parse u64".to_string()))?;
+            // Amounts are only converted from whole tokens when the unit is explicit, so that
+            // `10::<FAUCET_ID>` and `10.0::<FAUCET_ID>` can't mean different quantities
+            let amount = if let Some(token_amount) = amount.strip_suffix(TOKEN_AMOUNT_SUFFIX) {
+                let decimals = client.get_token_metadata(id).await?.decimals();
+                parse_amount(decimals, token_amount).map_err(|err| {
+                    CliError::Parse(err.into(), "Failed to parse amount".to_string())
+                })?
+            } else {
+                amount.parse::<u64>().map_err(|err| {
+                    CliError::Parse(
+                        err.into(),
+                        format!(
+                            "Failed to parse amount in base units (amounts in whole tokens need \
+                             the `{TOKEN_AMOUNT_SUFFIX}` suffix, such as `1.25{TOKEN_AMOUNT_SUFFIX}`)"
+                        ),
+                    )
+                })?
+            };
             (id, amount)
         } else {
             let FaucetDetails { id, decimals: faucet_decimals } =
@@ -114,7 +140,8 @@ impl FaucetDetailsMap {
                 ))?;
 
             // Convert from decimal to integer.
-            let amount = parse_number_as_base_units(amount, *faucet_decimals)?;
+            let amount = parse_amount(*faucet_decimals, amount)
+                .map_err(|err| CliError::Parse(err.into(), "Failed to parse amount".to_string()))?;
 
             (parse_account_id(client, id).await?, amount)
         };
@@ -141,7 +168,7 @@ impl FaucetDetailsMap {
                     token_symbol.clone(),
                 ))?
                 .decimals;
-            let amount = format_amount_from_faucet_units(asset.amount(), decimals);
+            let amount = format_amount(decimals, asset.amount());
 
             Ok((token_symbol, amount))
         } else {
@@ -153,113 +180,26 @@ impl FaucetDetailsMap {
             ))
         }
     }
-}
-
-/// Converts an amount in the faucet base units to the token's decimals.
-fn format_amount_from_faucet_units(units: u64, decimals: u8) -> String {
-    let units_str = units.to_string();
-    let len = units_str.len();
-
-    if decimals as usize >= len {
-        // Handle cases where the number of decimals is greater than the length of units
-        "0.".to_owned() + &"0".repeat(decimals as usize - len) + &units_str
-    } else {
-        // Insert the decimal point at the correct position
-        let integer_part = &units_str[..len - decimals as usize];
-        let fractional_part = &units_str[len - decimals as usize..];
-        format!("{integer_part}.{fractional_part}")
-    }
-}
-
-/// Converts a decimal number, represented as a string, into an integer by shifting
-/// the decimal point to the right by a specified number of decimal places.
-fn parse_number_as_base_units(decimal_str: &str, n_decimals: u8) -> Result<u64, CliError> {
-    if n_decimals > BasicFungibleFaucet::MAX_DECIMALS {
-        return Err(CliError::Parse(
-            format!(
-                "Number of decimals must be less than or equal to {}",
-                BasicFungibleFaucet::MAX_DECIMALS
-            )
-            .into(),
-            "Faucet maximum decimals".to_string(),
-        ));
-    }
-
-    // Split the string on the decimal point
-    let parts: Vec<&str> = decimal_str.split('.').collect();
-
-    if parts.len() > 2 {
-        return Err(CliError::Parse(
-            "More than one decimal point".into(),
-            "Decimals format".to_string(),
-        ));
-    }
-
-    // Validate that the parts are valid numbers
-    for part in &parts {
-        part.parse::<u64>()
-            .map_err(|err| CliError::Parse(err.into(), "Failed to parse u64".to_string()))?;
-    }
 
-    // Get the integer part
-    let integer_part = parts[0];
-
-    // Get the fractional part; remove trailing zeros
-    let mut fractional_part = if parts.len() > 1 {
-        parts[1].trim_end_matches('0').to_string()
-    } else {
-        String::new()
-    };
-
-    // Check if the fractional part has more than N decimals
-    if fractional_part.len() > n_decimals.into() {
-        return Err(CliError::Parse(
-            format!("Amount has more than {n_decimals} decimal places").into(),
-            "Failed to parse fractional part".to_string(),
-        ));
-    }
+    /// Formats a [`FungibleAsset`] like [`Self::format_fungible_asset`], but falls back to the
+    /// faucet's token metadata for faucets that aren't tracked by the token symbol map file. In
+    /// that case, the token symbol is returned along with the amount in the token's decimals.
+    ///
+    /// If the token metadata can't be retrieved (for example, because the faucet is private and
+    /// not tracked by the client), the faucet ID is returned along with the amount in base units.
+    pub async fn format_fungible_asset_with_metadata(
+        &self,
+        client: &Client,
+        asset: &FungibleAsset,
+    ) -> Result<(String, String), CliError> {
+        if self.get_token_symbol(&asset.faucet_id()).is_none()
+            && let Ok(metadata) = client.get_token_metadata(asset.faucet_id()).await
+        {
+            let token_symbol =
+                metadata.symbol().to_string().map_err(|err| CliError::Internal(Box::new(err)))?;
+            return Ok((token_symbol, format_amount(metadata.decimals(), asset.amount())));
+        }
 
-    // Add extra zeros if the fractional part is shorter than N decimals
-    while fractional_part.len() < n_decimals.into() {
-        fractional_part.push('0');
+        self.format_fungible_asset(asset)
     }
-
-    // Combine the integer and padded fractional part
-    let combined = format!("{}{}", integer_part, &fractional_part[0..n_decimals.into()]);
-
-    // Convert the combined string to an integer
-    combined
-        .parse::<u64>()
-        .map_err(|err| CliError::Parse(err.into(), "Failed to parse u64".to_string()))
-}
-
-// HELPER TESTS
-// ================================================================================================
-
-#[test]
-fn parsing_number_as_base_units() {
-    assert_eq!(parse_number_as_base_units("18446744.073709551615", 12).unwrap(), u64::MAX);
-    assert_eq!(parse_number_as_base_units("7531.2468", 8).unwrap(), 753_124_680_000);
-    assert_eq!(parse_number_as_base_units("7531.2468", 4).unwrap(), 75_312_468);
-    assert_eq!(parse_number_as_base_units("0", 3).unwrap(), 0);
-    assert_eq!(parse_number_as_base_units("0", 3).unwrap(), 0);
-    assert_eq!(parse_number_as_base_units("0", 3).unwrap(), 0);
-    assert_eq!(parse_number_as_base_units("1234", 8).unwrap(), 123_400_000_000);
-    assert_eq!(parse_number_as_base_units("1", 0).unwrap(), 1);
-    assert!(matches!(parse_number_as_base_units("1.1", 0), Err(CliError::Parse(_, _))),);
-    assert!(matches!(
-        parse_number_as_base_units("18446744.073709551615", 11),
-        Err(CliError::Parse(_, _))
-    ),);
-    assert!(matches!(parse_number_as_base_units("123u3.23", 4), Err(CliError::Parse(_, _))),);
-    assert!(matches!(parse_number_as_base_units("2.k3", 4), Err(CliError::Parse(_, _))),);
-    assert_eq!(parse_number_as_base_units("12.345000", 4).unwrap(), 123_450);
-    assert!(parse_number_as_base_units("0.0001.00000001", 12).is_err());
-}
-
-#[test]
-fn formatting_amount_from_faucet_units() {
-    assert_eq!(format_amount_from_faucet_units(u64::MAX, 12), "18446744.073709551615");
-    assert_eq!(format_amount_from_faucet_units(753_124_680_000, 8), "7531.24680000");
-    assert_eq!(format_amount_from_faucet_units(75_312_468, 4), "7531.2468");
 }
@@ -1,4 +1,4 @@
-use std::{env, sync::Arc};
+use std::{env, fmt::Write, sync::Arc};
 
 use clap::Parser;
 use comfy_table::{Attribute, Cell, ContentArrangement, Table, presets};
@@ -11,6 +11,7 @@ use miden_client::{
     store::{NoteFilter as ClientNoteFilter, OutputNoteRecord},
 };
 use rand::rngs::StdRng;
+use serde::Serialize;
 mod commands;
 use commands::{
     account::AccountCmd,
@@ -26,7 +27,7 @@ use commands::{
     transactions::TransactionCmd,
 };
 
-use self::utils::load_config_file;
+use self::utils::{load_config_file, warn_on_account_network_mismatch};
 
 pub type CliKeyStore = FilesystemKeyStore<StdRng>;
 
@@ -62,6 +63,11 @@ pub struct Cli {
     /// that were compiled and executed with this mode.
     #[arg(short, long, default_value_t = false)]
     debug: bool,
+
+    /// Prints the output of the `account`, `notes` and `tx` commands as JSON instead of tables.
+    /// Errors are printed to stderr as JSON as well. Other commands reject this flag.
+    #[arg(long, global = true, default_value_t = false)]
+    json: bool,
 }
 
 /// CLI actions.
@@ -87,9 +93,27 @@ pub enum Command {
     Exec(ExecCmd),
 }
 
+impl Command {
+    /// Returns whether the command can print its output as JSON.
+    fn supports_json_output(&self) -> bool {
+        matches!(self, Command::Account(_) | Command::Notes(_) | Command::Transaction(_))
+    }
+}
+
 /// CLI entry point.
 impl Cli {
+    /// Returns whether the output should be printed as JSON.
+    pub fn json_output(&self) -> bool {
+        self.json
+    }
+
     pub async fn execute(&self) -> Result<(), CliError> {
+        if self.json && !self.action.supports_json_output() {
+            return Err(CliError::InvalidArgument(
+                "--json is only supported by the `account`, `notes` and `tx` commands".to_string(),
+            ));
+        }
+
         let mut current_dir = std::env::current_dir()?;
         current_dir.push(CLIENT_CONFIG_FILE_NAME);
 
@@ -110,9 +134,9 @@ impl Cli {
 
         // Create the client
         let (cli_config, _config_path) = load_config_file()?;
+        warn_on_account_network_mismatch(&cli_config)?;
 
-        let keystore = CliKeyStore::new(cli_config.secret_keys_directory.clone())
-            .map_err(CliError::KeyStore)?;
+        let keystore = CliKeyStore::new(cli_config.secret_keys_directory.clone())?;
 
         let mut builder = ClientBuilder::new()
             .sqlite_store(cli_config.store_filepath.to_str().expect("Store path should be valid"))
@@ -134,16 +158,16 @@ impl Cli {
 
         // Execute CLI command
         match &self.action {
-            Command::Account(account) => account.execute(client).await,
+            Command::Account(account) => account.execute(client, self.json).await,
             Command::NewWallet(new_wallet) => new_wallet.execute(client, keystore).await,
             Command::NewAccount(new_account) => new_account.execute(client, keystore).await,
             Command::Import(import) => import.execute(client, keystore).await,
             Command::Init(_) => Ok(()),
             Command::Info => info::print_client_info(&client).await,
-            Command::Notes(notes) => notes.execute(client).await,
+            Command::Notes(notes) => notes.execute(client, self.json).await,
             Command::Sync(sync) => sync.execute(client).await,
             Command::Tags(tags) => tags.execute(client).await,
-            Command::Transaction(transaction) => transaction.execute(client).await,
+            Command::Transaction(transaction) => transaction.execute(client, self.json).await,
             Command::Exec(execute_program) => execute_program.execute(client).await,
             Command::Export(cmd) => cmd.execute(client, keystore).await,
             Command::Mint(mint) => mint.execute(client).await,
@@ -154,6 +178,25 @@ impl Cli {
     }
 }
 
+/// Prints the provided value to stdout as pretty-printed JSON.
+pub(crate) fn print_json(value: &impl Serialize) -> Result<(), CliError> {
+    let json = serde_json::to_string_pretty(value).map_err(|err| CliError::Internal(err.into()))?;
+    println!("{json}");
+    Ok(())
+}
+
+/// Prints the error, along with its sources, to stderr as a JSON object with an `error` field.
+pub fn print_json_error(err: &CliError) {
+    let mut message = err.to_string();
+    let mut source = std::error::Error::source(err);
+    while let Some(err) = source {
+        write!(message, ": {err}").expect("writing to a string should not fail");
+        source = err.source();
+    }
+
+    eprintln!("{}", serde_json::json!({ "error": message }));
+}
+
 pub fn create_dynamic_table(headers: &[&str]) -> Table {
     let header_cells = headers
         .iter()
@@ -11,5 +11,12 @@ async fn main() -> miette::Result<()> {
     let cli = Cli::parse();
 
     // execute cli action
-    Ok(cli.execute().await?)
+    match cli.execute().await {
+        Ok(()) => Ok(()),
+        Err(err) if cli.json_output() => {
+            miden_client_cli::print_json_error(&err);
+            std::process::exit(1);
+        },
+        Err(err) => Err(err.into()),
+    }
 }
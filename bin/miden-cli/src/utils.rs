@@ -14,13 +14,16 @@ use tracing::info;
 use super::{CLIENT_CONFIG_FILE_NAME, config::CliConfig, get_account_with_id_prefix};
 use crate::{errors::CliError, faucet_details_map::FaucetDetailsMap};
 
-pub(crate) const SHARED_TOKEN_DOCUMENTATION: &str = "There are two accepted formats for the asset:
-- `<AMOUNT>::<FAUCET_ID>` where `<AMOUNT>` is in the faucet base units.
+pub(crate) const SHARED_TOKEN_DOCUMENTATION: &str =
+    "There are three accepted formats for the asset:
+- `<AMOUNT>::<FAUCET_ID>` where `<AMOUNT>` is an integer in the faucet base units.
+- `<AMOUNT>tokens::<FAUCET_ID>` where `<AMOUNT>` is a quantity of whole tokens (such as `1.25`),
+converted to base units using the faucet's token decimals.
 - `<AMOUNT>::<TOKEN_SYMBOL>` where `<AMOUNT>` is a decimal number representing the quantity of
 the token (specified to the precision allowed by the token's decimals), and `<TOKEN_SYMBOL>`
 is a symbol tracked in the token symbol map file.
 
-For example, `100::0xabcdef0123456789` or `1.23::TST`";
+For example, `100::0xabcdef0123456789`, `1.25tokens::0xabcdef0123456789` or `1.23::TST`";
 
 /// Returns a tracked Account ID matching a hex string or the default one defined in the Client
 /// config.
@@ -91,6 +94,35 @@ pub(crate) fn update_config(config_path: &Path, client_config: &CliConfig) -> Re
     Ok(())
 }
 
+/// Records the network ID of the configured endpoint as the one the account was created with.
+pub(crate) fn record_account_network_id(account_id: AccountId) -> Result<(), CliError> {
+    let (mut cli_config, config_path) = load_config_file()?;
+    let network_id = cli_config.rpc.endpoint.0.to_network_id()?;
+
+    cli_config
+        .account_network_ids
+        .insert(account_id.to_hex(), network_id.as_str().to_string());
+
+    update_config(&config_path, &cli_config)
+}
+
+/// Prints a warning for each account that was created with an endpoint from a different network
+/// than the configured one.
+pub(crate) fn warn_on_account_network_mismatch(cli_config: &CliConfig) -> Result<(), CliError> {
+    let network_id = cli_config.rpc.endpoint.0.to_network_id()?;
+
+    for (account_id, account_network_id) in &cli_config.account_network_ids {
+        if account_network_id != network_id.as_str() {
+            eprintln!(
+                "Warning: account {account_id} was created on network `{account_network_id}`, but the configured endpoint belongs to network `{}`. Its address will differ from the one shown when it was created.",
+                network_id.as_str()
+            );
+        }
+    }
+
+    Ok(())
+}
+
 /// Loads config file from current directory and default filename and returns it alongside its path.
 ///
 /// This function will look for the configuration file at the provided path. If the path is
@@ -11,13 +11,14 @@ use assert_cmd::Command;
 use miden_client::{
     self, Client, ExecutionOptions, Felt,
     account::{AccountId, AccountStorageMode},
+    asset::{FungibleAsset, TokenSymbol},
     crypto::{FeltRng, RpoRandomCoin},
     note::{
         Note, NoteAssets, NoteExecutionHint, NoteFile, NoteId, NoteInputs, NoteMetadata,
         NoteRecipient, NoteTag, NoteType,
     },
     rpc::{Endpoint, TonicRpcClient},
-    store::sqlite_store::SqliteStore,
+    store::{NoteFilter, sqlite_store::SqliteStore},
     testing::{
         account_id::ACCOUNT_ID_PRIVATE_SENDER,
         common::{
@@ -29,7 +30,7 @@ use miden_client::{
 };
 use miden_client_cli::CliKeyStore;
 use miden_objects::{MAX_TX_EXECUTION_CYCLES, MIN_TX_EXECUTION_CYCLES};
-use predicates::str::contains;
+use predicates::{prelude::PredicateBooleanExt, str::contains};
 use rand::Rng;
 use toml::Table;
 use uuid::Uuid;
@@ -160,6 +161,125 @@ async fn token_symbol_mapping() {
     assert_eq!(note.assets().iter().next().unwrap().unwrap_fungible().amount(), 100_000);
 }
 
+/// This test sends an amount in whole tokens using the faucet ID, which is converted to base units
+/// using the faucet's token metadata.
+#[tokio::test]
+async fn send_decimal_amount() {
+    let (store_path, temp_dir) = init_cli();
+
+    let wallet_id = new_wallet_cli(&temp_dir, AccountStorageMode::Private);
+    let faucet_id = new_faucet_with_decimals_cli(&temp_dir, AccountStorageMode::Private, 2);
+
+    sync_cli(&temp_dir);
+
+    // Fund the wallet with 1000 base units (10.00 tokens)
+    let mut mint_cmd = Command::cargo_bin("miden-client").unwrap();
+    mint_cmd.args([
+        "mint",
+        "--target",
+        &wallet_id,
+        "--asset",
+        &format!("10.00tokens::{faucet_id}"),
+        "-n",
+        "private",
+        "--force",
+    ]);
+    let output = mint_cmd.current_dir(&temp_dir).output().unwrap();
+    assert!(output.status.success());
+    let minted_note_id = String::from_utf8(output.stdout)
+        .unwrap()
+        .split_whitespace()
+        .skip_while(|&word| word != "Output")
+        .find(|word| word.starts_with("0x"))
+        .unwrap()
+        .to_string();
+
+    sync_until_committed_note(&temp_dir);
+    consume_note_cli(&temp_dir, &wallet_id, &[&minted_note_id]);
+
+    let target_account_id = AccountId::try_from(ACCOUNT_ID_REGULAR).unwrap().to_hex();
+
+    // Amounts with more precision than the faucet allows are rejected, as are amounts with a
+    // decimal point but without the `tokens` suffix
+    let mut send_cmd = Command::cargo_bin("miden-client").unwrap();
+    send_cmd.args([
+        "send",
+        "--sender",
+        &wallet_id,
+        "--target",
+        &target_account_id,
+        "--asset",
+        &format!("1.255tokens::{faucet_id}"),
+        "-n",
+        "private",
+        "--force",
+    ]);
+    send_cmd.current_dir(&temp_dir).assert().failure();
+
+    let mut send_cmd = Command::cargo_bin("miden-client").unwrap();
+    send_cmd.args([
+        "send",
+        "--sender",
+        &wallet_id,
+        "--target",
+        &target_account_id,
+        "--asset",
+        &format!("1.25::{faucet_id}"),
+        "-n",
+        "private",
+        "--force",
+    ]);
+    send_cmd.current_dir(&temp_dir).assert().failure();
+
+    let mut send_cmd = Command::cargo_bin("miden-client").unwrap();
+    send_cmd.args([
+        "send",
+        "--sender",
+        &wallet_id,
+        "--target",
+        &target_account_id,
+        "--asset",
+        &format!("1.25tokens::{faucet_id}"),
+        "-n",
+        "private",
+        "--force",
+    ]);
+    let output = send_cmd.current_dir(&temp_dir).output().unwrap();
+    assert!(output.status.success());
+
+    let note_id = String::from_utf8(output.stdout)
+        .unwrap()
+        .split_whitespace()
+        .skip_while(|&word| word != "Output")
+        .find(|word| word.starts_with("0x"))
+        .unwrap()
+        .to_string();
+
+    let (client, _) = create_rust_client_with_store_path(&store_path).await;
+    let note = client
+        .get_output_note(NoteId::try_from_hex(&note_id).unwrap())
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(note.assets().num_assets(), 1);
+    assert_eq!(note.assets().iter().next().unwrap().unwrap_fungible().amount(), 125);
+
+    let wallet = client
+        .get_account(AccountId::from_bech32(&wallet_id).unwrap().1)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        wallet
+            .account()
+            .vault()
+            .get_balance(AccountId::from_bech32(&faucet_id).unwrap().1)
+            .unwrap(),
+        875
+    );
+}
+
 // IMPORT TESTS
 // ================================================================================================
 
@@ -364,6 +484,331 @@ async fn cli_export_import_account() {
     consume_note_cli(&temp_dir_2, &wallet_id, &[&note_id]);
 }
 
+#[tokio::test]
+async fn cli_keystore_errors() {
+    const ACCOUNT_FILENAME: &str = "test_wallet.mac";
+
+    let temp_dir = init_cli().1;
+    let account_id = new_wallet_cli(&temp_dir, AccountStorageMode::Private);
+
+    let mut export_cmd = Command::cargo_bin("miden-client").unwrap();
+    export_cmd.args(["export", &account_id, "--account", "--filename", ACCOUNT_FILENAME]);
+    export_cmd.current_dir(&temp_dir).assert().success();
+
+    // Re-importing the account stores its keys again, which is a no-op
+    let mut import_cmd = Command::cargo_bin("miden-client").unwrap();
+    import_cmd.args(["import", ACCOUNT_FILENAME, "--overwrite"]);
+    import_cmd.current_dir(&temp_dir).assert().success();
+
+    // Accounts can't be exported without their keys
+    let keystore_dir = temp_dir.join("keystore");
+    fs::remove_dir_all(&keystore_dir).unwrap();
+    fs::create_dir(&keystore_dir).unwrap();
+    let mut export_cmd = Command::cargo_bin("miden-client").unwrap();
+    export_cmd.args(["export", &account_id, "--account", "--filename", ACCOUNT_FILENAME]);
+    export_cmd
+        .current_dir(&temp_dir)
+        .assert()
+        .failure()
+        .stderr(contains("key not found for account"));
+
+    // The keystore directory can't be created under a file
+    let blocking_file = temp_dir.join("blocking_file");
+    fs::write(&blocking_file, "").unwrap();
+    let config_path = temp_dir.join("miden-client.toml");
+    let mut config = fs::read_to_string(&config_path).unwrap().parse::<Table>().unwrap();
+    config["secret_keys_directory"] = blocking_file.join("keystore").to_str().unwrap().into();
+    fs::write(&config_path, toml::to_string(&config).unwrap()).unwrap();
+
+    let mut list_cmd = Command::cargo_bin("miden-client").unwrap();
+    list_cmd.args(["account", "-l"]);
+    list_cmd
+        .current_dir(&temp_dir)
+        .assert()
+        .failure()
+        .stderr(contains("keystore directory not writable"));
+}
+
+#[tokio::test]
+async fn cli_account_json_output() {
+    let temp_dir = init_cli().1;
+
+    let account_id = new_wallet_cli(&temp_dir, AccountStorageMode::Private);
+
+    let mut show_cmd = Command::cargo_bin("miden-client").unwrap();
+    show_cmd.args(["account", "-s", &account_id, "--json"]);
+    let output = show_cmd.current_dir(&temp_dir).output().unwrap();
+    assert!(output.status.success());
+
+    let account: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(account["address"], account_id.as_str());
+    assert!(account["id"].as_str().unwrap().starts_with("0x"));
+    assert_eq!(account["storage_mode"], "private");
+    assert_eq!(account["nonce"], 0);
+    assert!(account["commitment"].is_string());
+    assert!(account["assets"].as_array().unwrap().is_empty());
+    assert!(!account["storage"].as_array().unwrap().is_empty());
+
+    // Errors are printed to stderr as JSON
+    let mut show_cmd = Command::cargo_bin("miden-client").unwrap();
+    show_cmd.args(["account", "-s", "0x1234", "--json"]);
+    let output = show_cmd.current_dir(&temp_dir).output().unwrap();
+    assert!(!output.status.success());
+
+    let error: serde_json::Value = serde_json::from_slice(&output.stderr).unwrap();
+    assert!(error["error"].is_string());
+
+    // Commands without JSON output reject the flag
+    let mut info_cmd = Command::cargo_bin("miden-client").unwrap();
+    info_cmd.args(["info", "--json"]);
+    let output = info_cmd.current_dir(&temp_dir).output().unwrap();
+    assert!(!output.status.success());
+
+    let error: serde_json::Value = serde_json::from_slice(&output.stderr).unwrap();
+    assert!(error["error"].as_str().unwrap().contains("--json is only supported"));
+}
+
+#[test]
+fn cli_list_component_templates() {
+    let temp_dir = init_cli().1;
+
+    // Keep only two of the templates written by `init`
+    fs::remove_file(temp_dir.join("templates").join("basic-wallet.mct")).unwrap();
+
+    let mut templates_cmd = Command::cargo_bin("miden-client").unwrap();
+    templates_cmd.args(["account", "--templates", "--json"]);
+    let output = templates_cmd.current_dir(&temp_dir).output().unwrap();
+    assert!(output.status.success());
+
+    let templates: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let templates = templates.as_array().unwrap();
+    assert_eq!(templates.len(), 2);
+
+    let placeholder_names = |template: &serde_json::Value| -> Vec<String> {
+        template["placeholders"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|placeholder| placeholder["name"].as_str().unwrap().to_string())
+            .collect()
+    };
+
+    assert_eq!(templates[0]["file"], "basic-auth.mct");
+    assert_eq!(templates[0]["name"], "basic_auth");
+    assert_eq!(placeholder_names(&templates[0]), ["falcon_pubkey"]);
+
+    assert_eq!(templates[1]["file"], "basic-fungible-faucet.mct");
+    assert_eq!(templates[1]["name"], "basic_fungible_faucet");
+    let mut faucet_placeholders = placeholder_names(&templates[1]);
+    faucet_placeholders.sort();
+    assert_eq!(
+        faucet_placeholders,
+        ["token_metadata.decimals", "token_metadata.max_supply", "token_metadata.ticker"]
+    );
+
+    // The table output lists the same templates
+    let mut templates_cmd = Command::cargo_bin("miden-client").unwrap();
+    templates_cmd.args(["account", "--templates"]);
+    templates_cmd
+        .current_dir(&temp_dir)
+        .assert()
+        .success()
+        .stdout(contains("basic_auth").and(contains("basic_fungible_faucet")))
+        .stdout(contains("basic_wallet").not());
+}
+
+#[tokio::test]
+async fn new_account_with_storage_value_flags() {
+    let (store_path, temp_dir) = init_cli();
+
+    let new_faucet_args = [
+        "new-account",
+        "--account-type",
+        "fungible-faucet",
+        "-c",
+        "basic-fungible-faucet",
+        "--storage-value",
+        "token_metadata.max_supply=10000000",
+        "--storage-value",
+        "token_metadata.decimals=6",
+        "--non-interactive",
+    ];
+
+    // A placeholder without a value fails instead of prompting for it
+    let mut create_faucet_cmd = Command::cargo_bin("miden-client").unwrap();
+    create_faucet_cmd.args(new_faucet_args);
+    create_faucet_cmd
+        .current_dir(&temp_dir)
+        .assert()
+        .failure()
+        .stderr(contains("token_metadata.ticker"));
+
+    let mut create_faucet_cmd = Command::cargo_bin("miden-client").unwrap();
+    create_faucet_cmd
+        .args(new_faucet_args)
+        .args(["--storage-value", "token_metadata.ticker=BTC"]);
+    let output = create_faucet_cmd.current_dir(&temp_dir).output().unwrap();
+    assert!(output.status.success());
+
+    let faucet_id = std::str::from_utf8(&output.stdout)
+        .unwrap()
+        .split_whitespace()
+        .skip_while(|&word| word != "-s")
+        .nth(1)
+        .unwrap()
+        .to_string();
+
+    let client = create_rust_client_with_store_path(&store_path).await.0;
+    let metadata = client
+        .get_token_metadata(AccountId::from_bech32(&faucet_id).unwrap().1)
+        .await
+        .unwrap();
+    assert_eq!(metadata.symbol(), TokenSymbol::new("BTC").unwrap());
+    assert_eq!(metadata.decimals(), 6);
+}
+
+#[tokio::test]
+async fn cli_account_network_mismatch() {
+    const MISMATCH_WARNING: &str = "was created on network";
+
+    let temp_dir = init_cli().1;
+    let account_id = new_wallet_cli(&temp_dir, AccountStorageMode::Private);
+
+    let mut list_cmd = Command::cargo_bin("miden-client").unwrap();
+    list_cmd.args(["account", "-l"]);
+    let output = list_cmd.current_dir(&temp_dir).output().unwrap();
+    assert!(output.status.success());
+    assert!(!String::from_utf8(output.stderr).unwrap().contains(MISMATCH_WARNING));
+
+    // Point the client to an endpoint from a different network
+    let other_endpoint = if get_network_from_rpc_config() == "testnet" {
+        Endpoint::devnet()
+    } else {
+        Endpoint::testnet()
+    };
+    let config_path = temp_dir.join("miden-client.toml");
+    let mut config = fs::read_to_string(&config_path).unwrap().parse::<Table>().unwrap();
+    config["rpc"].as_table_mut().unwrap()["endpoint"] = other_endpoint.to_string().into();
+    fs::write(&config_path, toml::to_string(&config).unwrap()).unwrap();
+
+    let mut list_cmd = Command::cargo_bin("miden-client").unwrap();
+    list_cmd.args(["account", "-l"]);
+    let output = list_cmd.current_dir(&temp_dir).output().unwrap();
+    assert!(output.status.success());
+
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    let (_, account_id) = AccountId::from_bech32(&account_id).unwrap();
+    assert!(stderr.contains(MISMATCH_WARNING));
+    assert!(stderr.contains(&account_id.to_hex()));
+}
+
+#[tokio::test]
+async fn cli_swap() {
+    let (store_path, temp_dir) = init_cli();
+
+    let offering_account_id = new_wallet_cli(&temp_dir, AccountStorageMode::Private);
+    let requesting_account_id = new_wallet_cli(&temp_dir, AccountStorageMode::Private);
+    let offered_faucet_id = new_faucet_cli(&temp_dir, AccountStorageMode::Private);
+    let requested_faucet_id = new_faucet_cli(&temp_dir, AccountStorageMode::Private);
+
+    sync_cli(&temp_dir);
+
+    // Fund both wallets
+    let offered_note_id = mint_cli(&temp_dir, &offering_account_id, &offered_faucet_id);
+    let requested_note_id = mint_cli(&temp_dir, &requesting_account_id, &requested_faucet_id);
+    sync_until_committed_note(&temp_dir);
+    consume_note_cli(&temp_dir, &offering_account_id, &[&offered_note_id]);
+    consume_note_cli(&temp_dir, &requesting_account_id, &[&requested_note_id]);
+
+    // Offering more than the account holds fails with a handled error
+    let mut swap_cmd = Command::cargo_bin("miden-client").unwrap();
+    swap_cmd.args([
+        "swap",
+        "--account",
+        &offering_account_id,
+        "--offer",
+        &format!("1000::{offered_faucet_id}"),
+        "--request",
+        &format!("20::{requested_faucet_id}"),
+        "-n",
+        "public",
+        "--force",
+    ]);
+    swap_cmd.current_dir(&temp_dir).assert().failure().code(1);
+
+    // Create the SWAP note
+    let mut swap_cmd = Command::cargo_bin("miden-client").unwrap();
+    swap_cmd.args([
+        "swap",
+        "--account",
+        &offering_account_id,
+        "--offer",
+        &format!("10::{offered_faucet_id}"),
+        "--request",
+        &format!("20::{requested_faucet_id}"),
+        "-n",
+        "public",
+        "--force",
+    ]);
+    let output = swap_cmd.current_dir(&temp_dir).output().unwrap();
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let swap_note_id = stdout
+        .lines()
+        .find_map(|line| line.strip_prefix("SWAP note ID: "))
+        .unwrap()
+        .trim()
+        .to_string();
+    assert!(stdout.lines().any(|line| line.starts_with("SWAP note tag: ")));
+
+    // Consume the SWAP note with the requesting account, which creates the payback note
+    sync_until_committed_note(&temp_dir);
+    consume_note_cli(&temp_dir, &requesting_account_id, &[&swap_note_id]);
+
+    // The offering account can then consume the payback note
+    sync_until_committed_note(&temp_dir);
+    consume_note_cli(&temp_dir, &offering_account_id, &[]);
+
+    let (client, _) = create_rust_client_with_store_path(&store_path).await;
+    let offered_faucet_id = AccountId::from_bech32(&offered_faucet_id).unwrap().1;
+    let requested_faucet_id = AccountId::from_bech32(&requested_faucet_id).unwrap().1;
+
+    // The offered and requested assets changed hands
+    let offering_account_id = AccountId::from_bech32(&offering_account_id).unwrap().1;
+    let requesting_account_id = AccountId::from_bech32(&requesting_account_id).unwrap().1;
+    assert_eq!(get_account_balance(&client, offering_account_id, offered_faucet_id).await, 90);
+    assert_eq!(get_account_balance(&client, offering_account_id, requested_faucet_id).await, 20);
+    assert_eq!(
+        get_account_balance(&client, requesting_account_id, requested_faucet_id).await,
+        80
+    );
+    assert_eq!(get_account_balance(&client, requesting_account_id, offered_faucet_id).await, 10);
+
+    // The SWAP note holding the offered asset was created and consumed
+    let swap_note_id = NoteId::try_from_hex(&swap_note_id).unwrap();
+    let swap_note = client.get_input_note(swap_note_id).await.unwrap().unwrap();
+    assert!(swap_note.is_consumed());
+    assert_eq!(
+        swap_note.assets().iter().collect::<Vec<_>>(),
+        [&FungibleAsset::new(offered_faucet_id, 10).unwrap().into()]
+    );
+    assert!(client.get_output_note(swap_note_id).await.unwrap().is_some());
+
+    // The payback note holding the requested asset was created when consuming the SWAP note and
+    // then consumed by the offering account
+    let payback_asset = FungibleAsset::new(requested_faucet_id, 20).unwrap().into();
+    let payback_notes: Vec<_> = client
+        .get_input_notes(NoteFilter::Consumed)
+        .await
+        .unwrap()
+        .into_iter()
+        .filter(|note| note.assets().iter().collect::<Vec<_>>() == [&payback_asset])
+        .collect();
+    assert_eq!(payback_notes.len(), 1);
+    assert!(client.get_output_note(payback_notes[0].id()).await.unwrap().is_some());
+}
+
 #[test]
 fn cli_empty_commands() {
     let temp_dir = init_cli().1;
@@ -664,6 +1109,17 @@ fn sync_until_committed_note(cli_path: &Path) {
     }
 }
 
+/// Returns the balance of the `faucet_id` token in the vault of the account, as stored by the
+/// client.
+async fn get_account_balance(
+    client: &TestClient,
+    account_id: AccountId,
+    faucet_id: AccountId,
+) -> u64 {
+    let account = client.get_account(account_id).await.unwrap().unwrap();
+    account.account().vault().get_balance(faucet_id).unwrap()
+}
+
 /// Consumes a series of notes with a given account using the CLI given by `cli_path`.
 fn consume_note_cli(cli_path: &Path, account_id: &str, note_ids: &[&str]) {
     let mut consume_note_cmd = Command::cargo_bin("miden-client").unwrap();
@@ -675,15 +1131,27 @@ fn consume_note_cli(cli_path: &Path, account_id: &str, note_ids: &[&str]) {
 
 /// Creates a new faucet account using the CLI given by `cli_path`.
 fn new_faucet_cli(cli_path: &Path, storage_mode: AccountStorageMode) -> String {
+    new_faucet_with_decimals_cli(cli_path, storage_mode, 10)
+}
+
+/// Creates a new faucet account whose token has `decimals` decimals using the CLI given by
+/// `cli_path`.
+fn new_faucet_with_decimals_cli(
+    cli_path: &Path,
+    storage_mode: AccountStorageMode,
+    decimals: u8,
+) -> String {
     const INIT_DATA_FILENAME: &str = "init_data.toml";
     let mut create_faucet_cmd = Command::cargo_bin("miden-client").unwrap();
 
     // Create a TOML file with the InitStorageData
-    let init_storage_data_toml = r#"
-        token_metadata.decimals=10
+    let init_storage_data_toml = format!(
+        r#"
+        token_metadata.decimals={decimals}
         token_metadata.max_supply=10000000
         token_metadata.ticker="BTC"
-        "#;
+        "#
+    );
     let file_path = cli_path.join(INIT_DATA_FILENAME);
     fs::write(&file_path, init_storage_data_toml).unwrap();
 
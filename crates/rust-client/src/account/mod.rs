@@ -35,27 +35,40 @@
 //!
 //! For more details on accounts, refer to the [Account] documentation.
 
-use alloc::vec::Vec;
+use alloc::{
+    collections::{BTreeMap, BTreeSet},
+    vec::Vec,
+};
 
-use miden_lib::account::{auth::RpoFalcon512, wallets::BasicWallet};
-use miden_objects::{Word, crypto::dsa::rpo_falcon512::PublicKey};
+use miden_lib::account::{
+    auth::RpoFalcon512,
+    interface::{AccountComponentInterface, AccountInterface},
+    wallets::BasicWallet,
+};
+use miden_objects::{
+    Word,
+    account::{AccountComponent, AccountComponentTemplate, InitStorageData, StorageValueName},
+    asset::{Asset, TokenSymbol},
+    crypto::dsa::rpo_falcon512::PublicKey,
+};
 
 use super::Client;
 use crate::{
     errors::ClientError,
     rpc::domain::account::FetchedAccount,
-    store::{AccountRecord, AccountStatus},
+    store::{AccountRecord, AccountStatus, NoteFilter},
 };
 
 pub mod procedure_roots;
 
+mod token_metadata;
 // RE-EXPORTS
 // ================================================================================================
-
 pub use miden_objects::account::{
     Account, AccountBuilder, AccountCode, AccountDelta, AccountFile, AccountHeader, AccountId,
     AccountStorage, AccountStorageMode, AccountType, StorageMap, StorageSlot,
 };
+pub use token_metadata::TokenMetadata;
 
 pub mod component {
     pub const COMPONENT_TEMPLATE_EXTENSION: &str = "mct";
@@ -88,6 +101,68 @@ impl Client {
     // ACCOUNT CREATION
     // --------------------------------------------------------------------------------------------
 
+    /// Instantiates an [`AccountComponent`] from the provided `template`, using `init_data` to
+    /// provide the values of the template's placeholders.
+    ///
+    /// This allows checking that a component can be built from a template, and previewing it,
+    /// before creating an account with it. The client's state isn't modified.
+    ///
+    /// # Errors
+    ///
+    /// - Returns [`ClientError::MissingComponentPlaceholders`] listing every placeholder of the
+    ///   template that doesn't have a value in `init_data`.
+    /// - Returns [`ClientError::AccountError`] if the provided values aren't valid for their
+    ///   placeholders' types or the component can't be built from them.
+    pub fn validate_component_template(
+        &self,
+        template: &AccountComponentTemplate,
+        init_data: &InitStorageData,
+    ) -> Result<AccountComponent, ClientError> {
+        let missing_placeholders: Vec<StorageValueName> = template
+            .metadata()
+            .get_placeholder_requirements()
+            .into_keys()
+            .filter(|placeholder| !init_data.placeholders().contains_key(placeholder))
+            .collect();
+        if !missing_placeholders.is_empty() {
+            return Err(ClientError::MissingComponentPlaceholders(missing_placeholders));
+        }
+
+        Ok(AccountComponent::from_template(template, init_data)?)
+    }
+
+    /// Returns the [`AccountId`] of the account that [`AccountBuilder`] builds from the provided
+    /// parameters, without building it in the client or adding it to the store.
+    ///
+    /// The ID only depends on these parameters, so this allows showing the ID of an account
+    /// before creating it, as long as it's then built with the same `init_seed`, account type,
+    /// storage mode and components.
+    ///
+    /// # Errors
+    ///
+    /// - Returns [`ClientError::AccountError`] if the account can't be built from the provided
+    ///   components.
+    pub fn preview_account_id(
+        &self,
+        init_seed: [u8; 32],
+        account_type: AccountType,
+        storage_mode: AccountStorageMode,
+        auth_component: AccountComponent,
+        components: Vec<AccountComponent>,
+    ) -> Result<AccountId, ClientError> {
+        let mut builder = AccountBuilder::new(init_seed)
+            .account_type(account_type)
+            .storage_mode(storage_mode)
+            .with_auth_component(auth_component);
+        for component in components {
+            builder = builder.with_component(component);
+        }
+
+        let (account, _) = builder.build()?;
+
+        Ok(account.id())
+    }
+
     /// Adds the provided [Account] in the store so it can start being tracked by the client.
     ///
     /// If the account is already being tracked and `overwrite` is set to `true`, the account will
@@ -251,6 +326,150 @@ impl Client {
             .await?
             .ok_or(ClientError::AccountDataNotFound(account_id))
     }
+
+    /// Returns the entries of the storage map at `slot_index` of the specified account, as of the
+    /// last sync.
+    ///
+    /// The keys are returned as they're stored in the map, which is how they're committed to in
+    /// the account's storage. Entries are returned as `(key, value)` pairs in the map's iteration
+    /// order.
+    ///
+    /// # Errors
+    ///
+    /// - Returns [`ClientError::AccountDataNotFound`] if the account isn't tracked by the client.
+    /// - Returns [`ClientError::AccountStorageMapNotFound`] if the account has no storage slot at
+    ///   `slot_index` or the slot isn't a map.
+    pub async fn get_account_storage_map(
+        &self,
+        account_id: AccountId,
+        slot_index: u8,
+    ) -> Result<Vec<(Word, Word)>, ClientError> {
+        let account: Account = self.try_get_account(account_id).await?.into();
+
+        match account.storage().slots().get(usize::from(slot_index)) {
+            Some(StorageSlot::Map(map)) => {
+                Ok(map.entries().map(|(key, value)| (Word::from(*key), *value)).collect())
+            },
+            _ => Err(ClientError::AccountStorageMapNotFound(account_id, slot_index)),
+        }
+    }
+
+    /// Returns the [`AccountHeader`] of every tracked account along with its fungible asset
+    /// balances, keyed by the ID of the faucet that issued them.
+    ///
+    /// Balances are read from each account's vault as of the last sync. Non-fungible assets are
+    /// not included, and a faucet's own token (which is tracked as issuance rather than as a
+    /// vault balance) is never reported for the faucet itself.
+    pub async fn get_accounts_with_balances(
+        &self,
+    ) -> Result<Vec<(AccountHeader, BTreeMap<AccountId, u64>)>, ClientError> {
+        let mut accounts = Vec::new();
+        for (header, _) in self.store.get_account_headers().await? {
+            let account: Account = self.try_get_account(header.id()).await?.into();
+
+            let balances = account
+                .vault()
+                .assets()
+                .filter_map(|asset| match asset {
+                    Asset::Fungible(fungible_asset)
+                        if fungible_asset.faucet_id() != account.id() =>
+                    {
+                        Some((fungible_asset.faucet_id(), fungible_asset.amount()))
+                    },
+                    _ => None,
+                })
+                .collect();
+
+            accounts.push((header, balances));
+        }
+
+        Ok(accounts)
+    }
+
+    /// Returns the IDs of every faucet the client has interacted with, sorted and without
+    /// duplicates.
+    ///
+    /// Faucets are derived from the fungible assets held in the vaults of tracked accounts and
+    /// carried by tracked input and output notes. Non-fungible assets are not included, as they
+    /// only reference the prefix of their faucet's ID.
+    pub async fn known_faucets(&self) -> Result<Vec<AccountId>, ClientError> {
+        let mut assets = Vec::new();
+
+        for (header, _) in self.store.get_account_headers().await? {
+            let account: Account = self.try_get_account(header.id()).await?.into();
+            assets.extend(account.vault().assets());
+        }
+
+        for note in self.store.get_input_notes(NoteFilter::All).await? {
+            assets.extend(note.assets().iter().copied());
+        }
+
+        for note in self.store.get_output_notes(NoteFilter::All).await? {
+            assets.extend(note.assets().iter().copied());
+        }
+
+        let faucets: BTreeSet<AccountId> = assets
+            .into_iter()
+            .filter_map(|asset| match asset {
+                Asset::Fungible(fungible_asset) => Some(fungible_asset.faucet_id()),
+                Asset::NonFungible(_) => None,
+            })
+            .collect();
+
+        Ok(faucets.into_iter().collect())
+    }
+
+    /// Returns the [`TokenMetadata`] of the token issued by the specified fungible faucet.
+    ///
+    /// The metadata is read from the faucet's storage, using the tracked account if available or
+    /// fetching its public state from the network otherwise. As the metadata of a basic fungible
+    /// faucet can't change, it's cached in the store after the first successful lookup.
+    ///
+    /// # Errors
+    ///
+    /// - Returns [`ClientError::AccountIsPrivate`] if the faucet isn't tracked and it's private.
+    /// - Returns [`ClientError::AccountNotFungibleFaucet`] if the account isn't a basic fungible
+    ///   faucet.
+    pub async fn get_token_metadata(
+        &self,
+        faucet_id: AccountId,
+    ) -> Result<TokenMetadata, ClientError> {
+        if let Some(metadata) = self.store.get_token_metadata(faucet_id).await? {
+            return Ok(metadata);
+        }
+
+        let faucet: Account = match self.store.get_account(faucet_id).await? {
+            Some(account_record) => account_record.into(),
+            None => match self.rpc_api.get_account_details(faucet_id).await? {
+                FetchedAccount::Private(..) => {
+                    return Err(ClientError::AccountIsPrivate(faucet_id));
+                },
+                FetchedAccount::Public(account, ..) => account,
+            },
+        };
+
+        // The basic fungible faucet component stores its metadata as
+        // `[max_supply, decimals, token_symbol, 0]`
+        let metadata_slot = AccountInterface::from(&faucet)
+            .components()
+            .iter()
+            .find_map(|component| match component {
+                AccountComponentInterface::BasicFungibleFaucet(offset) => Some(*offset),
+                _ => None,
+            })
+            .ok_or(ClientError::AccountNotFungibleFaucet(faucet_id))?;
+        let metadata_word = faucet.storage().get_item(metadata_slot)?;
+
+        let decimals = u8::try_from(metadata_word[1].as_int())
+            .map_err(|_| ClientError::AccountNotFungibleFaucet(faucet_id))?;
+        let symbol = TokenSymbol::try_from(metadata_word[2])
+            .map_err(|_| ClientError::AccountNotFungibleFaucet(faucet_id))?;
+        let metadata = TokenMetadata::new(symbol, decimals);
+
+        self.store.upsert_token_metadata(faucet_id, metadata).await?;
+
+        Ok(metadata)
+    }
 }
 
 // UTILITY FUNCTIONS
@@ -299,19 +518,27 @@ pub fn build_wallet_id(
 
 #[cfg(test)]
 pub mod tests {
-    use alloc::vec::Vec;
+    use alloc::{string::ToString, vec::Vec};
 
-    use miden_lib::{account::auth::RpoFalcon512, transaction::TransactionKernel};
+    use miden_lib::{
+        account::{auth::RpoFalcon512, components::basic_wallet_library, wallets::BasicWallet},
+        transaction::TransactionKernel,
+    };
     use miden_objects::{
-        EMPTY_WORD, Felt, Word,
-        account::{Account, AccountFile, AuthSecretKey},
+        EMPTY_WORD, Felt, Word, ZERO,
+        account::{
+            Account, AccountBuilder, AccountComponent, AccountComponentMetadata,
+            AccountComponentTemplate, AccountFile, AccountStorageMode, AccountType, AuthSecretKey,
+            InitStorageData, StorageMap, StorageSlot, StorageValueName,
+        },
         crypto::dsa::rpo_falcon512::{PublicKey, SecretKey},
-        testing::account_id::{
-            ACCOUNT_ID_PRIVATE_FUNGIBLE_FAUCET, ACCOUNT_ID_PUBLIC_FUNGIBLE_FAUCET,
+        testing::{
+            account_component::BASIC_WALLET_CODE,
+            account_id::{ACCOUNT_ID_PRIVATE_FUNGIBLE_FAUCET, ACCOUNT_ID_PUBLIC_FUNGIBLE_FAUCET},
         },
     };
 
-    use crate::tests::create_test_client;
+    use crate::{ClientError, tests::create_test_client};
 
     fn create_account_data(account_id: u128) -> AccountFile {
         let account = Account::mock(
@@ -380,4 +607,135 @@ pub mod tests {
             assert_eq!(client_acc.0.commitment(), expected_acc.commitment());
         }
     }
+
+    #[tokio::test]
+    async fn validate_component_template() {
+        let (client, ..) = create_test_client().await;
+
+        let metadata = AccountComponentMetadata::from_toml(
+            r#"
+            name = "test_component"
+            description = "Component with two placeholders"
+            version = "0.1.0"
+            supported-types = ["RegularAccountImmutableCode"]
+
+            [[storage]]
+            name = "config"
+            slot = 0
+            value = [
+                { name = "first", type = "felt" },
+                { name = "second", type = "felt" },
+                { value = "0" },
+                { value = "0" },
+            ]
+            "#,
+        )
+        .unwrap();
+        let template = AccountComponentTemplate::new(metadata, basic_wallet_library());
+
+        let first = StorageValueName::new("config.first").unwrap();
+        let second = StorageValueName::new("config.second").unwrap();
+
+        // The error names the placeholder without a value
+        let init_data = InitStorageData::new([(first.clone(), "1".to_string())]);
+        let result = client.validate_component_template(&template, &init_data);
+        assert!(matches!(
+            &result,
+            Err(ClientError::MissingComponentPlaceholders(missing)) if missing == core::slice::from_ref(&second)
+        ));
+        assert!(result.unwrap_err().to_string().contains("config.second"));
+
+        // With every placeholder provided, the component is built
+        let init_data = InitStorageData::new([(first, "1".to_string()), (second, "2".to_string())]);
+        let component = client.validate_component_template(&template, &init_data).unwrap();
+        assert_eq!(
+            component.storage_slots(),
+            &[StorageSlot::Value([Felt::new(1), Felt::new(2), ZERO, ZERO])]
+        );
+    }
+
+    #[tokio::test]
+    async fn preview_account_id() {
+        let (mut client, ..) = create_test_client().await;
+
+        let init_seed = [7u8; 32];
+        let public_key = SecretKey::new().public_key();
+
+        let preview = client
+            .preview_account_id(
+                init_seed,
+                AccountType::RegularAccountUpdatableCode,
+                AccountStorageMode::Public,
+                RpoFalcon512::new(public_key).into(),
+                vec![BasicWallet.into()],
+            )
+            .unwrap();
+
+        // The preview doesn't track the account
+        assert!(client.get_account_headers().await.unwrap().is_empty());
+
+        let (account, seed) = AccountBuilder::new(init_seed)
+            .account_type(AccountType::RegularAccountUpdatableCode)
+            .storage_mode(AccountStorageMode::Public)
+            .with_auth_component(RpoFalcon512::new(public_key))
+            .with_component(BasicWallet)
+            .build()
+            .unwrap();
+        client.add_account(&account, Some(seed), false).await.unwrap();
+
+        assert_eq!(preview, account.id());
+        assert!(client.get_account(preview).await.unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn get_account_storage_map() {
+        let (mut client, ..) = create_test_client().await;
+
+        let mut storage_map = StorageMap::new();
+        storage_map.insert([Felt::new(1); 4].into(), [Felt::new(10); 4]);
+        storage_map.insert([Felt::new(2); 4].into(), [Felt::new(20); 4]);
+
+        let component = AccountComponent::compile(
+            BASIC_WALLET_CODE,
+            TransactionKernel::assembler(),
+            vec![StorageSlot::Value(Word::default()), StorageSlot::Map(storage_map.clone())],
+        )
+        .unwrap()
+        .with_supports_all_types();
+
+        let account = AccountBuilder::new(Default::default())
+            .with_auth_component(RpoFalcon512::new(PublicKey::new(EMPTY_WORD)))
+            .with_component(component)
+            .build_existing()
+            .unwrap();
+        client.add_account(&account, None, false).await.unwrap();
+
+        let slot_index = |is_map: bool| {
+            let index = account
+                .storage()
+                .slots()
+                .iter()
+                .position(|slot| matches!(slot, StorageSlot::Map(_)) == is_map)
+                .unwrap();
+            u8::try_from(index).unwrap()
+        };
+
+        let entries = client.get_account_storage_map(account.id(), slot_index(true)).await.unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(
+            entries,
+            storage_map
+                .entries()
+                .map(|(key, value)| (Word::from(*key), *value))
+                .collect::<Vec<_>>()
+        );
+
+        // Value slots and missing slots aren't maps
+        for index in [slot_index(false), u8::MAX] {
+            assert!(matches!(
+                client.get_account_storage_map(account.id(), index).await.unwrap_err(),
+                ClientError::AccountStorageMapNotFound(id, slot) if id == account.id() && slot == index
+            ));
+        }
+    }
 }
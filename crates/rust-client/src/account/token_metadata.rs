@@ -0,0 +1,56 @@
+use alloc::string::ToString;
+
+use miden_objects::{
+    Felt,
+    asset::TokenSymbol,
+    utils::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable},
+};
+
+// TOKEN METADATA
+// ================================================================================================
+
+/// Metadata of the token issued by a fungible faucet.
+///
+/// Amounts of a fungible asset are expressed in the faucet's base units, so an amount of
+/// `1_000_000` for a token with `6` decimals represents `1.000000` tokens.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TokenMetadata {
+    /// Symbol of the token.
+    symbol: TokenSymbol,
+    /// Number of decimals used to represent the token's amounts.
+    decimals: u8,
+}
+
+impl TokenMetadata {
+    /// Creates a new [`TokenMetadata`] with the provided symbol and decimals.
+    pub fn new(symbol: TokenSymbol, decimals: u8) -> Self {
+        Self { symbol, decimals }
+    }
+
+    /// Returns the symbol of the token.
+    pub fn symbol(&self) -> TokenSymbol {
+        self.symbol
+    }
+
+    /// Returns the number of decimals used to represent the token's amounts.
+    pub fn decimals(&self) -> u8 {
+        self.decimals
+    }
+}
+
+impl Serializable for TokenMetadata {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        Felt::from(self.symbol).write_into(target);
+        self.decimals.write_into(target);
+    }
+}
+
+impl Deserializable for TokenMetadata {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let symbol = TokenSymbol::try_from(Felt::read_from(source)?)
+            .map_err(|err| DeserializationError::InvalidValue(err.to_string()))?;
+        let decimals = u8::read_from(source)?;
+
+        Ok(Self { symbol, decimals })
+    }
+}
@@ -0,0 +1,177 @@
+//! Provides helpers to convert fungible asset amounts between the faucet's base units and their
+//! human-readable decimal representation.
+//!
+//! Fungible assets are always expressed in base units, and the number of decimals of the token
+//! (see [`crate::account::TokenMetadata`]) defines where the decimal point is placed. For a token
+//! with `6` decimals, an amount of `1_250_000` base units is represented as `1.250000`.
+//!
+//! ```rust
+//! # use miden_client::amount::{format_amount, parse_amount};
+//! assert_eq!(parse_amount(6, "1.25").unwrap(), 1_250_000);
+//! assert_eq!(format_amount(6, 1_250_000), "1.250000");
+//! ```
+
+use alloc::{
+    format,
+    string::{String, ToString},
+};
+
+use miden_lib::account::faucets::BasicFungibleFaucet;
+use thiserror::Error;
+
+// AMOUNT ERROR
+// ================================================================================================
+
+/// Errors that can occur when parsing a decimal amount into base units.
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum AmountError {
+    #[error("amount `{0}` is not a valid decimal number")]
+    InvalidFormat(String),
+    #[error("amount `{0}` doesn't fit in a u64 when expressed in base units")]
+    Overflow(String),
+    #[error("amount `{amount}` has more than {decimals} decimal places")]
+    TooManyDecimalPlaces { amount: String, decimals: u8 },
+    #[error("number of decimals {0} is greater than the maximum of {max}", max = BasicFungibleFaucet::MAX_DECIMALS)]
+    TooManyDecimals(u8),
+}
+
+// AMOUNT HELPERS
+// ================================================================================================
+
+/// Formats an amount expressed in base units as a decimal number with exactly `decimals`
+/// fractional digits.
+///
+/// For example, an amount of `1_250_000` base units with `6` decimals is formatted as
+/// `1.250000`. If `decimals` is `0`, the amount is formatted as an integer.
+pub fn format_amount(decimals: u8, amount: u64) -> String {
+    let units = amount.to_string();
+    let decimals = usize::from(decimals);
+
+    if decimals == 0 {
+        units
+    } else if decimals >= units.len() {
+        format!("0.{}{units}", "0".repeat(decimals - units.len()))
+    } else {
+        let (integer_part, fractional_part) = units.split_at(units.len() - decimals);
+        format!("{integer_part}.{fractional_part}")
+    }
+}
+
+/// Parses a decimal number (such as `1.25`) into an amount expressed in base units, shifting the
+/// decimal point to the right by `decimals` places.
+///
+/// Trailing zeros in the fractional part are ignored, so `1.2500` can be parsed with `2`
+/// decimals. Amounts are never rounded: if the fractional part has more significant digits than
+/// `decimals`, an error is returned.
+///
+/// # Errors
+///
+/// - Returns [`AmountError::TooManyDecimals`] if `decimals` is greater than
+///   [`BasicFungibleFaucet::MAX_DECIMALS`].
+/// - Returns [`AmountError::InvalidFormat`] if the amount isn't a non-negative decimal number with
+///   at most one decimal point.
+/// - Returns [`AmountError::TooManyDecimalPlaces`] if the amount can't be represented with
+///   `decimals` places without rounding.
+/// - Returns [`AmountError::Overflow`] if the amount in base units doesn't fit in a `u64`.
+pub fn parse_amount(decimals: u8, amount: &str) -> Result<u64, AmountError> {
+    if decimals > BasicFungibleFaucet::MAX_DECIMALS {
+        return Err(AmountError::TooManyDecimals(decimals));
+    }
+
+    let (integer_part, fractional_part) = match amount.split_once('.') {
+        Some((integer_part, fractional_part)) => (integer_part, fractional_part),
+        None => (amount, ""),
+    };
+
+    let is_digits = |part: &str| part.chars().all(|c| c.is_ascii_digit());
+    if integer_part.is_empty() || !is_digits(integer_part) || !is_digits(fractional_part) {
+        return Err(AmountError::InvalidFormat(amount.to_string()));
+    }
+
+    let fractional_part = fractional_part.trim_end_matches('0');
+    if fractional_part.len() > usize::from(decimals) {
+        return Err(AmountError::TooManyDecimalPlaces { amount: amount.to_string(), decimals });
+    }
+
+    let base_units =
+        format!("{integer_part}{fractional_part:0<width$}", width = usize::from(decimals));
+
+    base_units.parse::<u64>().map_err(|_| AmountError::Overflow(amount.to_string()))
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::{AmountError, format_amount, parse_amount};
+
+    #[test]
+    fn parsing_amounts() {
+        assert_eq!(parse_amount(12, "18446744.073709551615").unwrap(), u64::MAX);
+        assert_eq!(parse_amount(8, "7531.2468").unwrap(), 753_124_680_000);
+        assert_eq!(parse_amount(4, "7531.2468").unwrap(), 75_312_468);
+        assert_eq!(parse_amount(3, "0").unwrap(), 0);
+        assert_eq!(parse_amount(8, "1234").unwrap(), 123_400_000_000);
+        assert_eq!(parse_amount(0, "1").unwrap(), 1);
+        assert_eq!(parse_amount(2, "1.").unwrap(), 100);
+        assert_eq!(parse_amount(2, "007.5").unwrap(), 750);
+    }
+
+    #[test]
+    fn parsing_amounts_with_trailing_zeros() {
+        assert_eq!(parse_amount(4, "12.345000").unwrap(), 123_450);
+        assert_eq!(parse_amount(2, "1.2500").unwrap(), 125);
+        assert_eq!(parse_amount(0, "3.000").unwrap(), 3);
+    }
+
+    #[test]
+    fn parsing_amounts_with_too_many_decimal_places() {
+        assert!(matches!(parse_amount(0, "1.1"), Err(AmountError::TooManyDecimalPlaces { .. })));
+        assert!(matches!(
+            parse_amount(11, "18446744.073709551615"),
+            Err(AmountError::TooManyDecimalPlaces { .. })
+        ));
+        assert!(matches!(
+            parse_amount(2, "0.001"),
+            Err(AmountError::TooManyDecimalPlaces { .. })
+        ));
+    }
+
+    #[test]
+    fn parsing_invalid_amounts() {
+        for amount in ["123u3.23", "2.k3", "0.0001.00000001", "", ".5", "-1", "+1", "1 000"] {
+            assert_eq!(parse_amount(4, amount), Err(AmountError::InvalidFormat(amount.into())));
+        }
+
+        assert_eq!(parse_amount(13, "1"), Err(AmountError::TooManyDecimals(13)));
+    }
+
+    #[test]
+    fn parsing_overflowing_amounts() {
+        assert!(matches!(
+            parse_amount(12, "18446744.073709551616"),
+            Err(AmountError::Overflow(_))
+        ));
+        assert!(matches!(parse_amount(0, "18446744073709551616"), Err(AmountError::Overflow(_))));
+    }
+
+    #[test]
+    fn formatting_amounts() {
+        assert_eq!(format_amount(12, u64::MAX), "18446744.073709551615");
+        assert_eq!(format_amount(8, 753_124_680_000), "7531.24680000");
+        assert_eq!(format_amount(4, 75_312_468), "7531.2468");
+        assert_eq!(format_amount(6, 1_250_000), "1.250000");
+        assert_eq!(format_amount(6, 42), "0.000042");
+        assert_eq!(format_amount(2, 100), "1.00");
+        assert_eq!(format_amount(0, 1234), "1234");
+        assert_eq!(format_amount(3, 0), "0.000");
+    }
+
+    #[test]
+    fn formatting_and_parsing_round_trip() {
+        for (decimals, amount) in [(0, 7), (2, 12_345), (8, 1), (12, u64::MAX)] {
+            assert_eq!(parse_amount(decimals, &format_amount(decimals, amount)).unwrap(), amount);
+        }
+    }
+}
@@ -1,21 +1,33 @@
 use alloc::{
+    collections::BTreeMap,
     string::{String, ToString},
     sync::Arc,
 };
+use core::num::NonZeroU32;
 use std::boxed::Box;
 
 use miden_objects::{
-    Felt, MAX_TX_EXECUTION_CYCLES, MIN_TX_EXECUTION_CYCLES,
+    Digest, Felt, MAX_TX_EXECUTION_CYCLES, MIN_TX_EXECUTION_CYCLES,
+    account::Account,
     crypto::rand::{FeltRng, RpoRandomCoin},
+    note::Note,
 };
 use miden_tx::{ExecutionOptions, auth::TransactionAuthenticator};
-use rand::Rng;
+use rand::{Rng, SeedableRng, rngs::StdRng};
 
 #[cfg(feature = "tonic")]
 use crate::rpc::{Endpoint, TonicRpcClient};
 #[cfg(feature = "sqlite")]
 use crate::store::sqlite_store::SqliteStore;
-use crate::{Client, ClientError, keystore::FilesystemKeyStore, rpc::NodeRpcClient, store::Store};
+use crate::{
+    Client, ClientError,
+    clock::Clock,
+    keystore::{FilesystemKeyStore, KeyStore, KeyStoreAuthenticator},
+    note::{KnownScriptHandler, NoteRelevance},
+    rpc::NodeRpcClient,
+    store::Store,
+    sync::SyncMode,
+};
 
 // CONSTANTS
 // ================================================================================================
@@ -66,6 +78,20 @@ pub struct ClientBuilder {
     /// Maximum number of blocks the client can be behind the network for transactions and account
     /// proofs to be considered valid.
     max_block_number_delta: Option<u32>,
+    /// Maximum number of sync steps whose updates are applied to the store at once.
+    sync_batch_size: Option<u32>,
+    /// Whether received notes are strictly filtered by relevance during sync.
+    strict_note_tag_matching: bool,
+    /// Maximum serialized size in bytes of imported notes.
+    max_note_bytes: Option<usize>,
+    /// Whether transaction proofs are verified locally before being submitted.
+    verify_before_submit: bool,
+    /// An optional clock provided by the user.
+    clock: Option<Arc<dyn Clock>>,
+    /// Whether MMR nodes are skipped when applying sync updates.
+    sync_mode: SyncMode,
+    /// Handlers registered for custom well-known note scripts, by script root.
+    known_scripts: BTreeMap<Digest, KnownScriptHandler>,
 }
 
 impl Default for ClientBuilder {
@@ -80,6 +106,13 @@ impl Default for ClientBuilder {
             in_debug_mode: false,
             tx_graceful_blocks: Some(TX_GRACEFUL_BLOCKS),
             max_block_number_delta: None,
+            sync_batch_size: None,
+            strict_note_tag_matching: false,
+            max_note_bytes: None,
+            verify_before_submit: false,
+            clock: None,
+            sync_mode: SyncMode::Full,
+            known_scripts: BTreeMap::new(),
         }
     }
 }
@@ -150,6 +183,83 @@ impl ClientBuilder {
         self
     }
 
+    /// Optionally set the maximum number of sync steps (requests to the node) whose updates are
+    /// accumulated in memory before being applied to the store. Smaller batches use less memory
+    /// at the cost of more store writes and round trips. By default, the whole sync is applied at
+    /// once.
+    ///
+    /// The batch size must be at least 1, otherwise [`ClientBuilder::build`] will fail.
+    #[must_use]
+    pub fn with_sync_batch_size(mut self, sync_batch_size: u32) -> Self {
+        self.sync_batch_size = Some(sync_batch_size);
+        self
+    }
+
+    /// Optionally discard notes received during sync that aren't relevant to any tracked account,
+    /// even if their tag is tracked by the client. Tags are matched fuzzily by the node, so this
+    /// avoids storing unrelated notes and marking their blocks as having client notes. Notes that
+    /// only match a tag added with [`Client::add_note_tag`] are discarded too. By default, every
+    /// note with a tracked tag is kept.
+    #[must_use]
+    pub fn with_strict_note_tag_matching(mut self, strict: bool) -> Self {
+        self.strict_note_tag_matching = strict;
+        self
+    }
+
+    /// Optionally reject imported notes whose serialized size exceeds `max_note_bytes`, so that
+    /// malformed notes with huge inputs or assets aren't loaded into the store. By default, there
+    /// is no limit.
+    #[must_use]
+    pub fn with_max_note_bytes(mut self, max_note_bytes: usize) -> Self {
+        self.max_note_bytes = Some(max_note_bytes);
+        self
+    }
+
+    /// Optionally verify the proof of each transaction locally before submitting it to the
+    /// network, so that invalid proofs produced by a faulty prover are caught before reaching the
+    /// node. Verification adds some overhead to every submission, so it's disabled by default.
+    #[must_use]
+    pub fn with_verify_before_submit(mut self, verify: bool) -> Self {
+        self.verify_before_submit = verify;
+        self
+    }
+
+    /// Optionally provide a custom [`Clock`] as the source of the client's timestamps. This allows
+    /// time-dependent behavior to be tested deterministically. By default, the system clock is
+    /// used.
+    #[must_use]
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = Some(clock);
+        self
+    }
+
+    /// Optionally run the client in light mode, where the MMR nodes of the partial blockchain
+    /// aren't stored when syncing. Notes, nullifiers and accounts are still tracked, but the client
+    /// can't build inclusion proofs for its notes, so transactions that consume authenticated
+    /// notes won't be possible. By default, light mode is disabled.
+    #[must_use]
+    pub fn light_mode(mut self, light_mode: bool) -> Self {
+        self.sync_mode = if light_mode { SyncMode::Light } else { SyncMode::Full };
+        self
+    }
+
+    /// Registers a handler that computes the relevance of notes with the provided script root.
+    ///
+    /// Notes with a registered script aren't executed when the client checks their relevance
+    /// (during sync and when listing consumable notes): the handler is called for each tracked
+    /// account instead, and the note is relevant to the account if it returns `Some`. This extends
+    /// the fast path used for P2ID, P2IDE and SWAP notes to custom protocols. Registering a
+    /// handler for a script root replaces any previous one.
+    #[must_use]
+    pub fn register_known_script(
+        mut self,
+        script_root: Digest,
+        handler: impl Fn(&Note, &Account) -> Option<NoteRelevance> + Send + Sync + 'static,
+    ) -> Self {
+        self.known_scripts.insert(script_root, Box::new(handler));
+        self
+    }
+
     /// Optionally set a maximum number of blocks to wait for a transaction to be confirmed. If
     /// `None`, there is no limit and transactions will be kept indefinitely.
     /// By default, the maximum is set to `TX_GRACEFUL_BLOCKS`.
@@ -169,6 +279,18 @@ impl ClientBuilder {
         self
     }
 
+    /// Provide a custom [`KeyStore`] holding the keys used to sign transactions.
+    ///
+    /// This can be used instead of [`ClientBuilder::filesystem_keystore`] to plug other key
+    /// storages, such as OS keychains or hardware security modules. The keystore is wrapped in a
+    /// [`KeyStoreAuthenticator`].
+    #[must_use]
+    pub fn keystore(mut self, keystore: impl KeyStore + Send + Sync + 'static) -> Self {
+        let authenticator = KeyStoreAuthenticator::new(Box::new(keystore), StdRng::from_os_rng());
+        self.keystore = Some(AuthenticatorConfig::Instance(Arc::new(authenticator)));
+        self
+    }
+
     /// Build and return the `Client`.
     ///
     /// # Errors
@@ -176,6 +298,7 @@ impl ClientBuilder {
     /// - Returns an error if no RPC client or endpoint was provided.
     /// - Returns an error if the store cannot be instantiated.
     /// - Returns an error if the keystore is not specified or fails to initialize.
+    /// - Returns an error if the sync batch size is set to 0.
     #[allow(clippy::unused_async, unused_mut)]
     pub async fn build(mut self) -> Result<Client, ClientError> {
         // Determine the RPC client to use.
@@ -231,7 +354,16 @@ impl ClientBuilder {
             }
         };
 
-        Ok(Client::new(
+        let sync_batch_size = self
+            .sync_batch_size
+            .map(|sync_batch_size| {
+                NonZeroU32::new(sync_batch_size).ok_or(ClientError::ClientInitializationError(
+                    "Sync batch size must be at least 1".into(),
+                ))
+            })
+            .transpose()?;
+
+        let mut client = Client::new(
             rpc_api,
             rng,
             arc_store,
@@ -245,6 +377,17 @@ impl ClientBuilder {
             .expect("Default executor's options should always be valid"),
             self.tx_graceful_blocks,
             self.max_block_number_delta,
-        ))
+        );
+        client.sync_batch_size = sync_batch_size;
+        client.strict_note_tag_matching = self.strict_note_tag_matching;
+        client.max_note_bytes = self.max_note_bytes;
+        client.verify_before_submit = self.verify_before_submit;
+        if let Some(clock) = self.clock {
+            client.clock = clock;
+        }
+        client.sync_mode = self.sync_mode;
+        client.known_scripts = Arc::new(self.known_scripts);
+
+        Ok(client)
     }
 }
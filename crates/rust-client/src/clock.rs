@@ -0,0 +1,23 @@
+//! Provides the [`Clock`] trait, which abstracts the source of the timestamps used by the client,
+//! and [`SystemClock`], its default implementation.
+//!
+//! A custom clock can be set with [`crate::builder::ClientBuilder::with_clock`], for example to
+//! make time-dependent logic deterministic in tests.
+
+/// A source of timestamps for the client.
+pub trait Clock: Send + Sync {
+    /// Returns the current timestamp, measured in non-leap seconds since Unix epoch. If the clock
+    /// is incapable of tracking time, it should return `None`.
+    fn current_timestamp(&self) -> Option<u64>;
+}
+
+/// A [`Clock`] that returns the current system time.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn current_timestamp(&self) -> Option<u64> {
+        let now = chrono::Utc::now();
+        Some(u64::try_from(now.timestamp()).expect("timestamp is always after epoch"))
+    }
+}
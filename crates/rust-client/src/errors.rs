@@ -3,22 +3,28 @@ use alloc::{
     vec::Vec,
 };
 
-use miden_lib::account::interface::AccountInterfaceError;
+use miden_lib::{account::interface::AccountInterfaceError, utils::ScriptBuilderError};
 use miden_objects::{
     AccountError, AssetError, Digest, NoteError, PartialBlockchainError, TransactionInputError,
-    TransactionScriptError, account::AccountId, crypto::merkle::MerkleError, note::NoteId,
+    TransactionScriptError,
+    account::{AccountId, StorageValueName},
+    block::BlockNumber,
+    crypto::merkle::MerkleError,
+    note::NoteId,
+    transaction::TransactionId,
 };
 // RE-EXPORTS
 // ================================================================================================
 pub use miden_tx::AuthenticationError;
 use miden_tx::{
-    TransactionExecutorError, TransactionProverError,
+    TransactionExecutorError, TransactionProverError, TransactionVerifierError,
     utils::{DeserializationError, HexParseError},
 };
 use thiserror::Error;
 
 use crate::{
-    note::NoteScreenerError,
+    keystore::KeyStoreError,
+    note::{NoteScreenerError, NoteTransferProofError},
     rpc::RpcError,
     store::{NoteRecordError, StoreError},
     transaction::TransactionRequestError,
@@ -40,24 +46,53 @@ pub enum ClientError {
     AccountCommitmentMismatch(Digest),
     #[error("account with id {0} is private")]
     AccountIsPrivate(AccountId),
+    #[error("account with id {0} is not a basic fungible faucet")]
+    AccountNotFungibleFaucet(AccountId),
     #[error("account nonce is too low to import")]
     AccountNonceTooLow,
     #[error("asset error")]
     AssetError(#[from] AssetError),
     #[error("account data wasn't found for account id {0}")]
     AccountDataNotFound(AccountId),
+    #[error("account {0} doesn't have a storage map at slot {1}")]
+    AccountStorageMapNotFound(AccountId, u8),
+    #[error("the node's chain doesn't extend the block {0} the store is synced to")]
+    ChainDiverged(BlockNumber),
+    #[error("the chain was re-organized, the store was rolled back to block {0}")]
+    ChainReorg(BlockNumber),
+    #[error("node genesis commitment {1} doesn't match the stored genesis commitment {0}")]
+    GenesisMismatch(Digest, Digest),
     #[error("error creating the partial blockchain")]
     PartialBlockchainError(#[from] PartialBlockchainError),
     #[error("data deserialization error")]
     DataDeserializationError(#[from] DeserializationError),
     #[error("note with id {0} not found on chain")]
     NoteNotFoundOnChain(NoteId),
+    #[error("note with id {0} is not tracked by the client")]
+    NoteNotTracked(NoteId),
+    #[error(
+        "note with id {0} is public, its recipient can get its details by syncing with the network"
+    )]
+    NoteIsPublic(NoteId),
+    #[error("note with id {0} has assets that weren't issued by faucet {1}")]
+    NoteAssetsNotIssuedByFaucet(NoteId, AccountId),
     #[error("error parsing hex")]
     HexParseError(#[from] HexParseError),
+    #[error("keystore error")]
+    KeyStoreError(#[from] KeyStoreError),
     #[error("can't add new account without seed")]
     AddNewAccountWithoutSeed,
     #[error("error with merkle path")]
     MerkleError(#[from] MerkleError),
+    #[error("mmr state import error: {0}")]
+    MmrStateImportError(String),
+    #[error("store archive import error: {0}")]
+    StoreArchiveImportError(String),
+    #[error(
+        "account component template is missing values for placeholders: {}",
+        .0.iter().map(ToString::to_string).collect::<Vec<_>>().join(", ")
+    )]
+    MissingComponentPlaceholders(Vec<StorageValueName>),
     #[error(
         "the transaction didn't produce the output notes with the expected recipient digests ({0:?})"
     )]
@@ -76,18 +111,42 @@ pub enum ClientError {
     RecencyConditionError(String),
     #[error("note screener error")]
     NoteScreenerError(#[from] NoteScreenerError),
+    #[error("note transfer proof error")]
+    NoteTransferProofError(#[from] NoteTransferProofError),
+    #[error(
+        "can't sync to block {0}, it must be after the sync height ({1}) and not after the chain tip ({2})"
+    )]
+    InvalidSyncTarget(BlockNumber, BlockNumber, BlockNumber),
+    #[error("can't execute the transaction against block {0}: {1}")]
+    InvalidReferenceBlock(BlockNumber, String),
     #[error("store error")]
     StoreError(#[from] StoreError),
+    #[error("sync update can't be applied to the store: {0}")]
+    SyncUpdateMismatch(String),
+    #[error(
+        "sync stopped at block {0} before reaching target block {1}, as tracked accounts were updated in between"
+    )]
+    SyncTargetNotReached(BlockNumber, BlockNumber),
     #[error("transaction executor error: {0}")]
     TransactionExecutorError(#[from] TransactionExecutorError),
     #[error("transaction input error")]
     TransactionInputError(#[source] TransactionInputError),
     #[error("transaction prover error")]
     TransactionProvingError(#[from] TransactionProverError),
+    #[error("transaction proof verification failed")]
+    TransactionVerificationError(#[from] TransactionVerifierError),
     #[error("transaction request error")]
     TransactionRequestError(#[from] TransactionRequestError),
+    #[error("transaction {0} wasn't committed after the maximum number of syncs")]
+    TransactionNotCommitted(TransactionId),
+    #[error("transaction {0} was discarded")]
+    TransactionDiscarded(TransactionId),
+    #[error("transaction doesn't require a signature")]
+    TransactionSignatureNotRequired,
     #[error("transaction script builder error")]
     AccountInterfaceError(#[from] AccountInterfaceError),
+    #[error("script compilation error")]
+    ScriptBuilderError(#[from] ScriptBuilderError),
     #[error("transaction script error")]
     TransactionScriptError(#[source] TransactionScriptError),
     #[error("client initialization error: {0}")]
@@ -2,7 +2,7 @@ use alloc::string::String;
 use std::{
     fs::OpenOptions,
     hash::{DefaultHasher, Hash, Hasher},
-    io::{BufRead, BufReader, BufWriter, Write},
+    io::{BufRead, BufReader, BufWriter, ErrorKind, Write},
     path::PathBuf,
     string::ToString,
     sync::Arc,
@@ -20,7 +20,7 @@ use miden_tx::{
 };
 use rand::{Rng, SeedableRng};
 
-use super::KeyStoreError;
+use super::{KeyStore, KeyStoreError};
 
 /// A filesystem-based keystore that stores keys in separate files and provides transaction
 /// authentication functionality. The public key is hashed and the result is used as the filename
@@ -40,7 +40,10 @@ impl<R: Rng> FilesystemKeyStore<R> {
     pub fn with_rng(keys_directory: PathBuf, rng: R) -> Result<Self, KeyStoreError> {
         if !keys_directory.exists() {
             std::fs::create_dir_all(&keys_directory).map_err(|err| {
-                KeyStoreError::StorageError(format!("error creating keys directory: {err:?}"))
+                KeyStoreError::DirectoryNotWritable(
+                    keys_directory.display().to_string(),
+                    format!("error creating keys directory: {err}"),
+                )
             })?;
         }
 
@@ -50,26 +53,41 @@ impl<R: Rng> FilesystemKeyStore<R> {
         })
     }
 
-    /// Adds a secret key to the keystore.
+    /// Adds a secret key to the keystore. Adding a key that is already stored is a no-op.
+    ///
+    /// Returns [`KeyStoreError::DuplicateKey`] if a different key is stored for the same public
+    /// key, and [`KeyStoreError::DirectoryNotWritable`] if the key file can't be created.
     pub fn add_key(&self, key: &AuthSecretKey) -> Result<(), KeyStoreError> {
         let pub_key = match key {
             AuthSecretKey::RpoFalcon512(k) => Word::from(k.public_key()),
         };
 
         let filename = hash_pub_key(pub_key);
+        let key_pair_hex = hex::encode(key.to_bytes());
 
         let file_path = self.keys_directory.join(filename);
-        let file = OpenOptions::new()
-            .write(true)
-            .create(true)
-            .truncate(true)
-            .open(file_path)
-            .map_err(|err| {
-                KeyStoreError::StorageError(format!("error opening secret key file: {err:?}"))
-            })?;
+        let file = match OpenOptions::new().write(true).create_new(true).open(&file_path) {
+            Ok(file) => file,
+            Err(err) if err.kind() == ErrorKind::AlreadyExists => {
+                let stored_key_pair_hex = std::fs::read_to_string(&file_path).map_err(|err| {
+                    KeyStoreError::StorageError(format!("error reading secret key file: {err:?}"))
+                })?;
+
+                return if stored_key_pair_hex.trim() == key_pair_hex {
+                    Ok(())
+                } else {
+                    Err(KeyStoreError::DuplicateKey(pub_key.into()))
+                };
+            },
+            Err(err) => {
+                return Err(KeyStoreError::DirectoryNotWritable(
+                    self.keys_directory.display().to_string(),
+                    format!("error creating secret key file: {err}"),
+                ));
+            },
+        };
 
         let mut writer = BufWriter::new(file);
-        let key_pair_hex = hex::encode(key.to_bytes());
         writer.write_all(key_pair_hex.as_bytes()).map_err(|err| {
             KeyStoreError::StorageError(format!("error writing secret key file: {err:?}"))
         })?;
@@ -109,6 +127,16 @@ impl<R: Rng> FilesystemKeyStore<R> {
     }
 }
 
+impl<R: Rng> KeyStore for FilesystemKeyStore<R> {
+    fn add_key(&self, key: &AuthSecretKey) -> Result<(), KeyStoreError> {
+        FilesystemKeyStore::add_key(self, key)
+    }
+
+    fn get_key(&self, pub_key: Word) -> Result<Option<AuthSecretKey>, KeyStoreError> {
+        FilesystemKeyStore::get_key(self, pub_key)
+    }
+}
+
 // Provide a default implementation for `StdRng` so you can call FilesystemKeyStore::new() without
 // type annotations.
 impl FilesystemKeyStore<rand::rngs::StdRng> {
@@ -0,0 +1,42 @@
+use alloc::collections::BTreeMap;
+
+use miden_objects::{Digest, Word, account::AuthSecretKey};
+use miden_tx::utils::sync::RwLock;
+
+use super::{KeyStore, KeyStoreError};
+
+/// A [`KeyStore`] that keeps the secret keys in memory.
+///
+/// Keys are lost once the keystore is dropped, so it's mostly useful for tests and short-lived
+/// clients.
+pub struct MemoryKeyStore {
+    keys: RwLock<BTreeMap<Digest, AuthSecretKey>>,
+}
+
+impl MemoryKeyStore {
+    /// Creates a new empty [`MemoryKeyStore`].
+    pub fn new() -> Self {
+        Self { keys: RwLock::new(BTreeMap::new()) }
+    }
+}
+
+impl Default for MemoryKeyStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl KeyStore for MemoryKeyStore {
+    fn add_key(&self, key: &AuthSecretKey) -> Result<(), KeyStoreError> {
+        let pub_key = match key {
+            AuthSecretKey::RpoFalcon512(k) => Word::from(k.public_key()),
+        };
+
+        self.keys.write().insert(Digest::from(pub_key), key.clone());
+        Ok(())
+    }
+
+    fn get_key(&self, pub_key: Word) -> Result<Option<AuthSecretKey>, KeyStoreError> {
+        Ok(self.keys.read().get(&Digest::from(pub_key)).cloned())
+    }
+}
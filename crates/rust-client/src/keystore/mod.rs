@@ -1,5 +1,16 @@
-use alloc::string::String;
+use alloc::{
+    boxed::Box,
+    string::{String, ToString},
+    sync::Arc,
+    vec::Vec,
+};
 
+use miden_objects::{
+    Digest, Felt, Word,
+    account::{AccountDelta, AuthSecretKey},
+};
+use miden_tx::{AuthenticationError, auth::TransactionAuthenticator, utils::sync::RwLock};
+use rand::Rng;
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -8,6 +19,10 @@ pub enum KeyStoreError {
     StorageError(String),
     #[error("decoding error: {0}")]
     DecodingError(String),
+    #[error("keystore directory {0} is not writable: {1}")]
+    DirectoryNotWritable(String, String),
+    #[error("a different key is already stored in the keystore for public key {0}")]
+    DuplicateKey(Digest),
 }
 
 #[cfg(feature = "std")]
@@ -15,7 +30,77 @@ mod fs_keystore;
 #[cfg(feature = "std")]
 pub use fs_keystore::FilesystemKeyStore;
 
+mod memory_keystore;
+pub use memory_keystore::MemoryKeyStore;
+
 #[cfg(feature = "idxdb")]
 mod web_keystore;
 #[cfg(feature = "idxdb")]
 pub use web_keystore::WebKeyStore;
+
+// KEYSTORE
+// ================================================================================================
+
+/// A storage for the secret keys used to sign transactions.
+///
+/// Implementing this trait allows plugging custom key storages, such as OS keychains or hardware
+/// security modules, into the client through a [`KeyStoreAuthenticator`].
+pub trait KeyStore {
+    /// Adds a secret key to the keystore.
+    ///
+    /// Adding a key that is already stored should succeed. Implementations may return
+    /// [`KeyStoreError::DuplicateKey`] if a different key is stored for the same public key.
+    fn add_key(&self, key: &AuthSecretKey) -> Result<(), KeyStoreError>;
+
+    /// Retrieves a secret key from the keystore given its public key. Returns `None` if the key
+    /// isn't stored in the keystore.
+    fn get_key(&self, pub_key: Word) -> Result<Option<AuthSecretKey>, KeyStoreError>;
+}
+
+// KEYSTORE AUTHENTICATOR
+// ================================================================================================
+
+/// A [`TransactionAuthenticator`] that signs transactions with the secret keys held by any
+/// [`KeyStore`].
+pub struct KeyStoreAuthenticator<R: Rng> {
+    /// The keystore holding the secret keys.
+    keystore: Box<dyn KeyStore + Send + Sync>,
+    /// The random number generator used to generate signatures.
+    rng: Arc<RwLock<R>>,
+}
+
+impl<R: Rng> KeyStoreAuthenticator<R> {
+    /// Creates a new [`KeyStoreAuthenticator`] using the provided keystore and RNG.
+    pub fn new(keystore: Box<dyn KeyStore + Send + Sync>, rng: R) -> Self {
+        Self {
+            keystore,
+            rng: Arc::new(RwLock::new(rng)),
+        }
+    }
+}
+
+impl<R: Rng> TransactionAuthenticator for KeyStoreAuthenticator<R> {
+    /// Gets a signature over a message, given a public key.
+    ///
+    /// # Errors
+    /// If the public key isn't found in the keystore, [`AuthenticationError::UnknownPublicKey`] is
+    /// returned.
+    fn get_signature(
+        &self,
+        pub_key: Word,
+        message: Word,
+        _account_delta: &AccountDelta,
+    ) -> Result<Vec<Felt>, AuthenticationError> {
+        let mut rng = self.rng.write();
+
+        let secret_key = self
+            .keystore
+            .get_key(pub_key)
+            .map_err(|err| AuthenticationError::other(err.to_string()))?;
+
+        let AuthSecretKey::RpoFalcon512(k) = secret_key
+            .ok_or(AuthenticationError::UnknownPublicKey(Digest::from(pub_key).into()))?;
+
+        miden_tx::auth::signatures::get_falcon_signature(&k, message, &mut *rng)
+    }
+}
@@ -1,4 +1,8 @@
-use alloc::{string::ToString, sync::Arc, vec::Vec};
+use alloc::{
+    string::{String, ToString},
+    sync::Arc,
+    vec::Vec,
+};
 
 use miden_lib::utils::{Deserializable, Serializable};
 use rand::Rng;
@@ -19,12 +23,19 @@ use crate::{
 pub struct WebKeyStore<R: Rng> {
     /// The random number generator used to generate signatures.
     rng: Arc<RwLock<R>>,
+    /// Name of the `IndexedDB` store where the keys are persisted. An empty name refers to the
+    /// default store.
+    store_name: String,
 }
 
 impl<R: Rng> WebKeyStore<R> {
-    /// Creates a new instance of the web keystore with the provided RNG.
-    pub fn new(rng: R) -> Self {
-        WebKeyStore { rng: Arc::new(RwLock::new(rng)) }
+    /// Creates a new instance of the web keystore with the provided RNG. Keys are persisted in the
+    /// `IndexedDB` store with the provided name, or in the default store if no name is provided.
+    pub fn new(rng: R, store_name: Option<String>) -> Self {
+        WebKeyStore {
+            rng: Arc::new(RwLock::new(rng)),
+            store_name: store_name.unwrap_or_default(),
+        }
     }
 
     pub async fn add_key(&self, key: &AuthSecretKey) -> Result<(), KeyStoreError> {
@@ -33,18 +44,21 @@ impl<R: Rng> WebKeyStore<R> {
         };
         let secret_key_hex = hex::encode(key.to_bytes());
 
-        insert_account_auth(pub_key, secret_key_hex).await.map_err(|_| {
-            KeyStoreError::StorageError("Failed to insert item into local storage".to_string())
-        })?;
+        insert_account_auth(&self.store_name, pub_key, secret_key_hex)
+            .await
+            .map_err(|_| {
+                KeyStoreError::StorageError("Failed to insert item into local storage".to_string())
+            })?;
 
         Ok(())
     }
 
     pub fn get_key(&self, pub_key: Word) -> Result<Option<AuthSecretKey>, KeyStoreError> {
         let pub_key_str = Digest::from(pub_key).to_hex();
-        let secret_key_hex = get_account_auth_by_pub_key(pub_key_str).map_err(|_| {
-            KeyStoreError::StorageError("Failed to get item from local storage".to_string())
-        })?;
+        let secret_key_hex =
+            get_account_auth_by_pub_key(&self.store_name, pub_key_str).map_err(|_| {
+                KeyStoreError::StorageError("Failed to get item from local storage".to_string())
+            })?;
 
         let secret_key_bytes = hex::decode(secret_key_hex).map_err(|err| {
             KeyStoreError::DecodingError(format!("error decoding secret key hex: {err:?}"))
@@ -13,6 +13,8 @@
 //! - **Accounts:** Provides types for managing accounts. Once accounts are tracked by the client,
 //!   their state is updated with every transaction and validated during each sync.
 //!
+//! - **Amounts:** Helpers to format and parse fungible asset amounts using the token's decimals.
+//!
 //! - **Notes:** Contains types and utilities for working with notes in the Miden client.
 //!
 //! - **RPC:** Facilitates communication with Miden node, exposing RPC methods for syncing state,
@@ -118,6 +120,8 @@ use alloc::boxed::Box;
 extern crate std;
 
 pub mod account;
+pub mod amount;
+pub mod clock;
 pub mod keystore;
 pub mod note;
 pub mod rpc;
@@ -204,14 +208,18 @@ pub mod testing {
     pub use crate::test_utils::*;
 }
 
-use alloc::sync::Arc;
+use alloc::{collections::BTreeMap, sync::Arc, vec::Vec};
+use core::num::NonZeroU32;
 
+use clock::{Clock, SystemClock};
 use miden_lib::utils::ScriptBuilder;
-use miden_objects::crypto::rand::FeltRng;
+use miden_objects::{Digest, crypto::rand::FeltRng, note::NoteId, transaction::TransactionId};
 use miden_tx::{LocalTransactionProver, auth::TransactionAuthenticator};
+use note::{ConsumableNoteWatcher, KnownScriptHandler};
 use rand::RngCore;
 use rpc::NodeRpcClient;
 use store::Store;
+use sync::SyncMode;
 
 // MIDEN CLIENT
 // ================================================================================================
@@ -245,6 +253,32 @@ pub struct Client {
     /// Maximum number of blocks the client can be behind the network for transactions and account
     /// proofs to be considered valid.
     max_block_number_delta: Option<u32>,
+    /// Maximum number of sync steps whose updates are applied to the store at once. If `None`,
+    /// the whole sync is applied at once.
+    sync_batch_size: Option<NonZeroU32>,
+    /// Whether notes received during sync are only kept if they are relevant to a tracked
+    /// account, instead of whenever their tag is tracked.
+    strict_note_tag_matching: bool,
+    /// Maximum serialized size in bytes of the notes imported by the client. If `None`, there is
+    /// no limit.
+    max_note_bytes: Option<usize>,
+    /// Whether transaction proofs are verified locally before being submitted to the network.
+    verify_before_submit: bool,
+    /// Source of the timestamps added to the states of notes.
+    clock: Arc<dyn Clock>,
+    /// Whether the client skips storing the partial blockchain's MMR nodes during sync.
+    sync_mode: SyncMode,
+    /// Input notes of the transactions built by the client that weren't submitted yet, mapped to
+    /// the ID of the transaction that consumes them. Reservations are kept in memory only, as the
+    /// transactions they refer to aren't persisted until they're submitted.
+    reserved_notes: BTreeMap<NoteId, TransactionId>,
+    /// Watchers that receive the notes that become consumable by an account during sync.
+    consumable_note_watchers: Vec<ConsumableNoteWatcher>,
+    /// Whether the genesis block of the node was already checked to match the stored one.
+    genesis_verified: bool,
+    /// Handlers used by the client's note screeners to compute the relevance of notes with custom
+    /// well-known scripts, by script root.
+    known_scripts: Arc<BTreeMap<Digest, KnownScriptHandler>>,
 }
 
 /// Construction and access methods.
@@ -295,6 +329,16 @@ impl Client {
             exec_options,
             tx_graceful_blocks,
             max_block_number_delta,
+            sync_batch_size: None,
+            strict_note_tag_matching: false,
+            max_note_bytes: None,
+            verify_before_submit: false,
+            clock: Arc::new(SystemClock),
+            sync_mode: SyncMode::Full,
+            reserved_notes: BTreeMap::new(),
+            consumable_note_watchers: Vec::new(),
+            genesis_verified: false,
+            known_scripts: Arc::new(BTreeMap::new()),
         }
     }
 
@@ -308,6 +352,14 @@ impl Client {
         ScriptBuilder::new(self.in_debug_mode())
     }
 
+    /// Returns the current timestamp according to the client's [`Clock`], measured in non-leap
+    /// seconds since Unix epoch, or `None` if the clock can't track time.
+    ///
+    /// Timestamps are added to the states of notes for informational purposes.
+    pub fn get_current_timestamp(&self) -> Option<u64> {
+        self.clock.current_timestamp()
+    }
+
     /// Returns a reference to the client's random number generator. This can be used to generate
     /// randomness for various purposes such as serial numbers, keys, etc.
     pub fn rng(&mut self) -> &mut ClientRng {
@@ -8,20 +8,35 @@
 //!
 //! For more specific information on how the process is performed, refer to the docs for
 //! [`Client::import_note()`].
-use alloc::string::ToString;
+#[cfg(feature = "std")]
+use alloc::vec::Vec;
+use alloc::{string::ToString, vec};
+#[cfg(feature = "std")]
+use std::path::{Path, PathBuf};
 
 use miden_objects::{
     block::BlockNumber,
     note::{Note, NoteDetails, NoteFile, NoteId, NoteInclusionProof, NoteMetadata, NoteTag},
 };
+#[cfg(feature = "std")]
+use miden_tx::utils::Deserializable;
+use miden_tx::utils::Serializable;
+use tracing::warn;
 
 use crate::{
     Client, ClientError,
+    note::NoteScreener,
     rpc::{RpcError, domain::note::FetchedNote},
-    store::{InputNoteRecord, InputNoteState, input_note_states::ExpectedNoteState},
+    store::{
+        InputNoteRecord, InputNoteState, NoteRecordError, input_note_states::ExpectedNoteState,
+    },
     sync::NoteTagRecord,
 };
 
+/// Extension of the files read by [`Client::import_notes_from_dir`].
+#[cfg(feature = "std")]
+const NOTE_FILE_EXTENSION: &str = "mno";
+
 /// Note importing methods.
 impl Client {
     // INPUT NOTE CREATION
@@ -42,6 +57,8 @@ impl Client {
     /// # Errors
     ///
     /// - If an attempt is made to overwrite a note that is currently processing.
+    /// - If the serialized note exceeds the maximum note size set with
+    ///   `ClientBuilder::with_max_note_bytes`.
     pub async fn import_note(&mut self, note_file: NoteFile) -> Result<NoteId, ClientError> {
         let id = match &note_file {
             NoteFile::NoteId(id) => *id,
@@ -49,6 +66,12 @@ impl Client {
             NoteFile::NoteWithProof(note, _) => note.id(),
         };
 
+        match &note_file {
+            NoteFile::NoteId(_) => {},
+            NoteFile::NoteDetails { details, .. } => self.check_note_size(details)?,
+            NoteFile::NoteWithProof(note, _) => self.check_note_size(note)?,
+        }
+
         let previous_note = self.get_input_note(id).await?;
 
         // If the note is already in the store and is in the state processing we return an error.
@@ -77,11 +100,77 @@ impl Client {
                     .await?;
             }
             self.store.upsert_input_notes(&[note]).await?;
+
+            // The note is already imported, so failing to notify the watchers doesn't fail the
+            // import
+            let note_screener = NoteScreener::new(
+                self.store.clone(),
+                self.authenticator.clone(),
+                self.known_scripts.clone(),
+            );
+            if let Err(err) = self.notify_consumable_note_watchers(vec![id], &note_screener).await {
+                warn!("Failed to notify consumable note watchers: {err}");
+            }
         }
 
         Ok(id)
     }
 
+    /// Imports every serialized [`NoteFile`] in the specified directory, one file at a time, and
+    /// returns the result of importing each of them along with its path.
+    ///
+    /// Only files with the `.mno` extension (as created by the CLI's `export` command) are
+    /// considered note files. Other files and subdirectories are skipped and don't appear in the
+    /// returned list. A note file that can't be read, deserialized or imported doesn't stop the
+    /// import of the remaining files, its error is returned in its place instead. Files are
+    /// imported in order of their paths.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ClientError::NoteImportError`] if the directory can't be read.
+    #[cfg(feature = "std")]
+    pub async fn import_notes_from_dir(
+        &mut self,
+        path: impl AsRef<Path>,
+    ) -> Result<Vec<(PathBuf, Result<NoteId, ClientError>)>, ClientError> {
+        let path = path.as_ref();
+        let read_dir_error = |err: std::io::Error| {
+            ClientError::NoteImportError(format!(
+                "failed to read directory {}: {err}",
+                path.display()
+            ))
+        };
+
+        let mut note_file_paths = Vec::new();
+        for entry in std::fs::read_dir(path).map_err(read_dir_error)? {
+            let file_path = entry.map_err(read_dir_error)?.path();
+            if file_path.is_file()
+                && file_path.extension().is_some_and(|extension| extension == NOTE_FILE_EXTENSION)
+            {
+                note_file_paths.push(file_path);
+            }
+        }
+        note_file_paths.sort();
+
+        let mut results = Vec::with_capacity(note_file_paths.len());
+        for file_path in note_file_paths {
+            let result = match std::fs::read(&file_path) {
+                Ok(bytes) => match NoteFile::read_from_bytes(&bytes) {
+                    Ok(note_file) => self.import_note(note_file).await,
+                    Err(err) => Err(ClientError::DataDeserializationError(err)),
+                },
+                Err(err) => Err(ClientError::NoteImportError(format!(
+                    "failed to read note file {}: {err}",
+                    file_path.display()
+                ))),
+            };
+
+            results.push((file_path, result));
+        }
+
+        Ok(results)
+    }
+
     // HELPERS
     // ================================================================================================
 
@@ -92,6 +181,7 @@ impl Client {
     /// # Errors:
     /// - If the note doesn't exist on the node.
     /// - If the note exists but is private.
+    /// - If the note exceeds the maximum note size.
     async fn import_note_record_by_id(
         &self,
         previous_note: Option<InputNoteRecord>,
@@ -114,7 +204,10 @@ impl Client {
             }
         } else {
             let fetched_note = match fetched_note {
-                FetchedNote::Public(note, _) => note,
+                FetchedNote::Public(note, _) => {
+                    self.check_note_size(&note)?;
+                    note
+                },
                 FetchedNote::Private(..) => {
                     return Err(ClientError::NoteImportError(
                         "Incomplete imported note is private".to_string(),
@@ -143,7 +236,7 @@ impl Client {
         let metadata = *note.metadata();
         let mut note_record = previous_note.unwrap_or(InputNoteRecord::new(
             note.into(),
-            self.store.get_current_timestamp(),
+            self.get_current_timestamp(),
             ExpectedNoteState {
                 metadata: Some(metadata),
                 after_block_num: inclusion_proof.location().block_num(),
@@ -206,7 +299,7 @@ impl Client {
         let mut note_record = previous_note.unwrap_or({
             InputNoteRecord::new(
                 details,
-                self.store.get_current_timestamp(),
+                self.get_current_timestamp(),
                 ExpectedNoteState { metadata: None, after_block_num, tag }.into(),
             )
         });
@@ -298,4 +391,16 @@ impl Client {
             request_block_num = sync_notes.block_header.block_num();
         }
     }
+
+    /// Returns an error if the serialized size of the note exceeds the client's maximum note size.
+    fn check_note_size(&self, note: &impl Serializable) -> Result<(), NoteRecordError> {
+        if let Some(max_note_bytes) = self.max_note_bytes {
+            let note_bytes = note.to_bytes().len();
+            if note_bytes > max_note_bytes {
+                return Err(NoteRecordError::NoteTooLarge(note_bytes, max_note_bytes));
+            }
+        }
+
+        Ok(())
+    }
 }
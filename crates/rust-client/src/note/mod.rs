@@ -14,6 +14,7 @@
 //! - Compile note scripts from source code with `compile_note_script`.
 //! - Retrieve an input note by a prefix of its ID using the helper function
 //!   [`get_input_note_with_id_prefix`].
+//! - Create and verify [`NoteTransferProof`]s to back the off-chain transfer of private notes.
 //!
 //! ## Example
 //!
@@ -56,18 +57,24 @@
 //! For more details on the API and error handling, see the documentation for the specific functions
 //! and types in this module.
 
-use alloc::{string::ToString, vec::Vec};
+use alloc::{collections::BTreeMap, string::ToString, vec::Vec};
 
-use miden_objects::account::AccountId;
+use miden_objects::{account::AccountId, asset::Asset};
+use miden_tx::utils::Serializable;
+pub(crate) use note_screener::get_p2ide_recall_height;
 
 use crate::{
     Client, ClientError, IdPrefixFetchError,
-    store::{InputNoteRecord, NoteFilter, OutputNoteRecord},
+    rpc::RpcError,
+    store::{InputNoteRecord, NoteExportType, NoteFilter, OutputNoteRecord},
+    sync::{NoteTagRecord, StateSyncUpdate},
 };
 
 mod import;
 mod note_screener;
 mod note_update_tracker;
+mod transfer_proof;
+mod watch;
 
 // RE-EXPORTS
 // ================================================================================================
@@ -86,10 +93,16 @@ pub use miden_objects::{
         Nullifier,
     },
 };
-pub use note_screener::{NoteConsumability, NoteRelevance, NoteScreener, NoteScreenerError};
+pub use note_screener::{
+    KnownScriptHandler, NoteConsumability, NoteConsumptionStatus, NoteRelevance, NoteScreener,
+    NoteScreenerError,
+};
 pub use note_update_tracker::{
     InputNoteUpdate, NoteUpdateTracker, NoteUpdateType, OutputNoteUpdate,
 };
+pub use transfer_proof::{NoteTransferProof, NoteTransferProofError};
+pub use watch::ConsumableNoteStream;
+pub(crate) use watch::ConsumableNoteWatcher;
 
 /// Note retrieval methods.
 impl Client {
@@ -116,16 +129,27 @@ impl Client {
     /// The note screener runs a series of checks to determine whether the note can be executed as
     /// part of a transaction for a specific account. If the specific account ID can consume it (ie,
     /// if it's compatible with the account), it will be returned as part of the result list.
+    ///
+    /// Notes reserved by a transaction that was built but not submitted yet aren't returned (see
+    /// [`Client::release_reserved_notes`]).
     pub async fn get_consumable_notes(
         &self,
         account_id: Option<AccountId>,
     ) -> Result<Vec<(InputNoteRecord, Vec<NoteConsumability>)>, ClientError> {
         let committed_notes = self.store.get_input_notes(NoteFilter::Committed).await?;
 
-        let note_screener = NoteScreener::new(self.store.clone(), self.authenticator.clone());
+        let note_screener = NoteScreener::new(
+            self.store.clone(),
+            self.authenticator.clone(),
+            self.known_scripts.clone(),
+        );
 
         let mut relevant_notes = Vec::new();
         for input_note in committed_notes {
+            if self.is_note_reserved(input_note.id()) {
+                continue;
+            }
+
             let mut account_relevance =
                 note_screener.check_relevance(&input_note.clone().try_into()?).await?;
 
@@ -148,11 +172,21 @@ impl Client {
     /// The note screener runs a series of checks to determine whether the note can be executed as
     /// part of a transaction for a specific account. If the specific account ID can consume it (ie,
     /// if it's compatible with the account), it will be returned as part of the result list.
+    /// Notes reserved by a transaction that was built but not submitted yet can't be consumed by
+    /// any account.
     pub async fn get_note_consumability(
         &self,
         note: InputNoteRecord,
     ) -> Result<Vec<NoteConsumability>, ClientError> {
-        let note_screener = NoteScreener::new(self.store.clone(), self.authenticator.clone());
+        if self.is_note_reserved(note.id()) {
+            return Ok(Vec::new());
+        }
+
+        let note_screener = NoteScreener::new(
+            self.store.clone(),
+            self.authenticator.clone(),
+            self.known_scripts.clone(),
+        );
         note_screener
             .check_relevance(&note.clone().try_into()?)
             .await
@@ -167,6 +201,25 @@ impl Client {
         Ok(self.store.get_input_notes(NoteFilter::Unique(note_id)).await?.pop())
     }
 
+    /// Returns the nullifier of the input note with the provided [`NoteId`] without consuming it.
+    ///
+    /// The nullifier only depends on the note's details, so it is the same regardless of which
+    /// account ends up consuming the note. It can be used to check proactively whether the note
+    /// has already been consumed on chain.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ClientError::NoteNotTracked`] if the client has no input note with the
+    /// provided ID.
+    pub async fn note_nullifier(&self, note_id: NoteId) -> Result<Nullifier, ClientError> {
+        let note = self
+            .get_input_note(note_id)
+            .await?
+            .ok_or(ClientError::NoteNotTracked(note_id))?;
+
+        Ok(note.nullifier())
+    }
+
     // OUTPUT NOTE DATA RETRIEVAL
     // --------------------------------------------------------------------------------------------
 
@@ -185,6 +238,255 @@ impl Client {
     ) -> Result<Option<OutputNoteRecord>, ClientError> {
         Ok(self.store.get_output_notes(NoteFilter::Unique(note_id)).await?.pop())
     }
+
+    /// Returns the output notes created by the client's transactions that haven't been included
+    /// in a block yet, as of the last sync.
+    ///
+    /// These are the output notes in the [`NoteFilter::Expected`] state. Once a sync finds them
+    /// committed on chain, they're no longer pending.
+    pub async fn get_pending_output_notes(&self) -> Result<Vec<OutputNoteRecord>, ClientError> {
+        self.get_output_notes(NoteFilter::Expected).await
+    }
+
+    /// Checks whether the tracked output note with the provided [`NoteId`] has been committed
+    /// on-chain by querying the node directly, without running a full sync.
+    ///
+    /// If the node returns the note, its inclusion proof is stored and returned. Returns `None`
+    /// if the note is not yet committed.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ClientError::NoteNotTracked`] if the client has no output note with the
+    /// provided ID.
+    pub async fn verify_output_note_committed(
+        &mut self,
+        note_id: NoteId,
+    ) -> Result<Option<NoteInclusionProof>, ClientError> {
+        let output_note = self
+            .get_output_note(note_id)
+            .await?
+            .ok_or(ClientError::NoteNotTracked(note_id))?;
+
+        let Some(fetched_note) = self.rpc_api.get_notes_by_id(&[note_id]).await?.pop() else {
+            return Ok(None);
+        };
+        let inclusion_proof = fetched_note.inclusion_proof().clone();
+
+        let mut note_updates = NoteUpdateTracker::new([], [output_note]);
+        note_updates.apply_output_note_inclusion_proof(note_id, inclusion_proof.clone())?;
+
+        self.store
+            .apply_state_sync(StateSyncUpdate {
+                block_num: self.store.get_sync_height().await?,
+                note_updates,
+                ..Default::default()
+            })
+            .await?;
+
+        Ok(Some(inclusion_proof))
+    }
+
+    /// Returns the serialized details of the private output note with the provided [`NoteId`],
+    /// which are needed by its recipient to consume it.
+    ///
+    /// The bytes contain a [`NoteFile::NoteDetails`] (serial number, assets, script and inputs of
+    /// the note, along with its tag), so the recipient can deserialize them and pass them to
+    /// [`Client::import_note`].
+    ///
+    /// # Errors
+    ///
+    /// - Returns a [`ClientError::NoteNotTracked`] if the client has no output note with the
+    ///   provided ID.
+    /// - Returns a [`ClientError::NoteIsPublic`] if the note is public, as its details are already
+    ///   available to the recipient through the network.
+    /// - Returns a [`ClientError::NoteRecordConversionError`] if the client doesn't know the note's
+    ///   recipient.
+    pub async fn export_output_note_for_recipient(
+        &self,
+        note_id: NoteId,
+    ) -> Result<Vec<u8>, ClientError> {
+        let output_note = self
+            .get_output_note(note_id)
+            .await?
+            .ok_or(ClientError::NoteNotTracked(note_id))?;
+
+        if output_note.metadata().note_type() == NoteType::Public {
+            return Err(ClientError::NoteIsPublic(note_id));
+        }
+
+        let note_file = output_note.into_note_file(&NoteExportType::NoteDetails)?;
+
+        Ok(note_file.to_bytes())
+    }
+
+    // INCLUSION PROOFS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns whether the inclusion proof of the input note with the provided [`NoteId`] can be
+    /// used to authenticate the note at the current sync height.
+    ///
+    /// This is the case if the proof was verified against the header of the block that includes
+    /// the note, and that block is tracked by the client's partial MMR so that it can be
+    /// authenticated against the current chain. Notes without an inclusion proof, or whose proof
+    /// hasn't been verified yet, aren't current.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ClientError::NoteNotTracked`] if the client has no input note with the
+    /// provided ID.
+    pub async fn is_inclusion_proof_current(&self, note_id: NoteId) -> Result<bool, ClientError> {
+        let note = self
+            .get_input_note(note_id)
+            .await?
+            .ok_or(ClientError::NoteNotTracked(note_id))?;
+
+        let (true, Some(inclusion_proof)) = (note.is_authenticated(), note.inclusion_proof())
+        else {
+            return Ok(false);
+        };
+
+        let block_num = inclusion_proof.location().block_num().as_usize();
+        let current_partial_mmr = self.build_current_partial_mmr().await?;
+
+        Ok(block_num < current_partial_mmr.forest() && current_partial_mmr.is_tracked(block_num))
+    }
+
+    /// Fetches the inclusion proof of the input note with the provided [`NoteId`] from the node
+    /// if it isn't current (see [`Client::is_inclusion_proof_current`]), and verifies it against
+    /// the header of the block that includes the note, which gets tracked by the client.
+    ///
+    /// Returns whether the note's inclusion proof is current after the refresh. If the note was
+    /// included after the current sync height, the proof can't be verified yet, so the note's tag
+    /// is tracked for the note to be verified during the next sync.
+    ///
+    /// # Errors
+    ///
+    /// - Returns a [`ClientError::NoteNotTracked`] if the client has no input note with the
+    ///   provided ID.
+    /// - Returns a [`ClientError::NoteNotFoundOnChain`] if the node doesn't have the note.
+    pub async fn refresh_inclusion_proof(&mut self, note_id: NoteId) -> Result<bool, ClientError> {
+        if self.is_inclusion_proof_current(note_id).await? {
+            return Ok(true);
+        }
+
+        let mut note = self
+            .get_input_note(note_id)
+            .await?
+            .ok_or(ClientError::NoteNotTracked(note_id))?;
+
+        let fetched_note = self.rpc_api.get_note_by_id(note_id).await.map_err(|err| match err {
+            RpcError::NoteNotFound(note_id) => ClientError::NoteNotFoundOnChain(note_id),
+            err => ClientError::RpcError(err),
+        })?;
+
+        let metadata = *fetched_note.metadata();
+        let inclusion_proof = fetched_note.inclusion_proof().clone();
+        let block_num = inclusion_proof.location().block_num();
+
+        let mut note_changed = note.inclusion_proof_received(inclusion_proof, metadata)?;
+
+        let tag_record = NoteTagRecord::with_note_source(metadata.tag(), note_id);
+        let is_current = if block_num <= self.get_sync_height().await? {
+            let mut current_partial_mmr = self.build_current_partial_mmr().await?;
+            let block_header = self
+                .get_and_store_authenticated_block(block_num, &mut current_partial_mmr)
+                .await?;
+
+            note_changed |= note.block_header_received(&block_header)?;
+            self.store.remove_note_tag(tag_record).await?;
+
+            note.is_authenticated()
+        } else {
+            self.store.add_note_tag(tag_record).await?;
+
+            false
+        };
+
+        if note_changed {
+            self.store.upsert_input_notes(&[note]).await?;
+        }
+
+        Ok(is_current)
+    }
+
+    // P2IDE RECALL
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the block height after which the P2IDE note with the provided [`NoteId`] can be
+    /// recalled by its sender.
+    ///
+    /// Returns `None` if the note isn't a P2IDE note, if its details or metadata are unknown, or if
+    /// its sender isn't tracked by the client.
+    ///
+    /// # Errors
+    ///
+    /// - Returns a [`ClientError::NoteNotTracked`] if the client has no output or input note with
+    ///   the provided ID.
+    /// - Returns a [`ClientError::NoteScreenerError`] if the note inputs aren't valid P2IDE inputs.
+    pub async fn p2ide_recall_height(
+        &self,
+        note_id: NoteId,
+    ) -> Result<Option<BlockNumber>, ClientError> {
+        let (recipient, metadata) = if let Some(note) = self.get_output_note(note_id).await? {
+            (note.recipient().cloned(), Some(*note.metadata()))
+        } else if let Some(note) = self.get_input_note(note_id).await? {
+            (Some(note.details().recipient().clone()), note.metadata().copied())
+        } else {
+            return Err(ClientError::NoteNotTracked(note_id));
+        };
+
+        let (Some(recipient), Some(metadata)) = (recipient, metadata) else {
+            return Ok(None);
+        };
+
+        if recipient.script().root() != WellKnownNote::P2IDE.script_root()
+            || !self.store.get_account_ids().await?.contains(&metadata.sender())
+        {
+            return Ok(None);
+        }
+
+        let recall_height = get_p2ide_recall_height(note_id, recipient.inputs().values())
+            .map_err(NoteScreenerError::from)?;
+
+        Ok(Some(recall_height))
+    }
+
+    // NOTE VALUATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a single comparable value for the input note with the provided [`NoteId`].
+    ///
+    /// Each fungible asset is weighted by the value its faucet has in `valuation` (assets from
+    /// faucets missing from the table are worth nothing), and each non-fungible asset counts as
+    /// `nft_weight`. The result saturates at [`u64::MAX`].
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ClientError::NoteNotTracked`] if the client has no input note with the provided
+    /// ID.
+    pub async fn note_value(
+        &self,
+        note_id: NoteId,
+        valuation: &BTreeMap<AccountId, u64>,
+        nft_weight: u64,
+    ) -> Result<u64, ClientError> {
+        let note = self
+            .get_input_note(note_id)
+            .await?
+            .ok_or(ClientError::NoteNotTracked(note_id))?;
+
+        let value = note.assets().iter().fold(0u64, |total, asset| {
+            let asset_value = match asset {
+                Asset::Fungible(fungible_asset) => valuation
+                    .get(&fungible_asset.faucet_id())
+                    .map_or(0, |unit_value| unit_value.saturating_mul(fungible_asset.amount())),
+                Asset::NonFungible(_) => nft_weight,
+            };
+            total.saturating_add(asset_value)
+        });
+
+        Ok(value)
+    }
 }
 
 /// Returns the client input note whose ID starts with `note_id_prefix`.
@@ -1,17 +1,25 @@
-use alloc::{sync::Arc, vec::Vec};
-use core::fmt;
+use alloc::{
+    boxed::Box,
+    collections::BTreeMap,
+    format,
+    string::{String, ToString},
+    sync::Arc,
+    vec::Vec,
+};
+use core::fmt::{self, Write};
 
 use miden_lib::{account::interface::AccountInterface, note::well_known_note::WellKnownNote};
 use miden_objects::{
-    AccountError, AssetError,
+    AccountError, AssetError, Digest, Felt,
     account::{Account, AccountId},
     assembly::DefaultSourceManager,
+    block::BlockNumber,
     note::{Note, NoteId},
     transaction::{InputNote, InputNotes},
 };
 use miden_tx::{
     NoteAccountExecution, NoteConsumptionChecker, TransactionExecutor, TransactionExecutorError,
-    auth::TransactionAuthenticator,
+    auth::TransactionAuthenticator, utils::sync::RwLock,
 };
 use thiserror::Error;
 
@@ -44,25 +52,57 @@ impl fmt::Display for NoteRelevance {
     }
 }
 
+/// Describes whether a note can be consumed by a specific account with a standard consume
+/// transaction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NoteConsumptionStatus {
+    /// The note can be consumed by the account.
+    Consumable,
+    /// The note can't be consumed by the account. Contains the failure reason reported while
+    /// executing the note, such as the assertion that failed.
+    Unconsumable(String),
+}
+
+/// Computes the relevance of a note for an account from the note alone (for example, from its
+/// inputs), without executing it. See [`crate::builder::ClientBuilder::register_known_script`].
+pub type KnownScriptHandler = Box<dyn Fn(&Note, &Account) -> Option<NoteRelevance> + Send + Sync>;
+
 /// Provides functionality for testing whether a note is relevant to the client or not.
 ///
 /// Here, relevance is based on whether the note is able to be consumed by an account that is
 /// tracked in the provided `store`. This can be derived in a number of ways, such as looking
 /// at the combination of script root and note inputs. For example, a P2ID note is relevant
 /// for a specific account ID if this ID is its first note input.
+///
+/// The screener remembers the relevance of the notes it checks, so each note is only screened once
+/// by a screener and its clones. Screeners are meant to be short-lived (for example, for the
+/// duration of a sync), as the relevance of a note changes along with the tracked accounts.
+#[derive(Clone)]
 pub struct NoteScreener {
     /// A reference to the client's store, used to fetch necessary data to check consumability.
     store: Arc<dyn Store>,
     /// A reference to the transaction authenticator
     authenticator: Option<Arc<dyn TransactionAuthenticator>>,
+    /// Handlers that compute the relevance of notes with custom well-known scripts, by script
+    /// root.
+    known_scripts: Arc<BTreeMap<Digest, KnownScriptHandler>>,
+    /// Relevance of the notes checked by the screener, by note ID. Shared with the screener's
+    /// clones.
+    checked_notes: Arc<RwLock<BTreeMap<NoteId, Vec<NoteConsumability>>>>,
 }
 
 impl NoteScreener {
     pub fn new(
         store: Arc<dyn Store>,
         authenticator: Option<Arc<dyn TransactionAuthenticator>>,
+        known_scripts: Arc<BTreeMap<Digest, KnownScriptHandler>>,
     ) -> Self {
-        Self { store, authenticator }
+        Self {
+            store,
+            authenticator,
+            known_scripts,
+            checked_notes: Arc::new(RwLock::new(BTreeMap::new())),
+        }
     }
 
     /// Returns a vector of tuples describing the relevance of the provided note to the
@@ -70,13 +110,23 @@ impl NoteScreener {
     ///
     /// Does a fast check for known scripts (P2ID, P2IDE, SWAP). We're currently
     /// unable to execute notes that aren't committed so a slow check for other scripts is
-    /// currently not available.
+    /// currently not available. Notes whose script has one of the screener's known script
+    /// handlers are screened with the handler instead.
     ///
     /// If relevance can't be determined, the screener defaults to setting the note as consumable.
+    ///
+    /// Notes that were already checked by the screener aren't screened again, their previous
+    /// relevance is returned instead.
     pub async fn check_relevance(
         &self,
         note: &Note,
     ) -> Result<Vec<NoteConsumability>, NoteScreenerError> {
+        if let Some(note_relevances) = self.checked_notes.read().get(&note.id()) {
+            return Ok(note_relevances.clone());
+        }
+
+        let known_script_handler = self.known_scripts.get(&note.script().root());
+
         let mut note_relevances = vec![];
         for id in self.store.get_account_ids().await? {
             let account_record = self
@@ -85,6 +135,13 @@ impl NoteScreener {
                 .await?
                 .ok_or(NoteScreenerError::AccountDataNotFound(id))?;
 
+            if let Some(handler) = known_script_handler {
+                if let Some(relevance) = handler(note, account_record.account()) {
+                    note_relevances.push((id, relevance));
+                }
+                continue;
+            }
+
             match self.check_standard_consumability(account_record.account(), note).await {
                 Ok(Some(relevance)) => {
                     note_relevances.push((id, relevance));
@@ -107,6 +164,8 @@ impl NoteScreener {
             }
         }
 
+        self.checked_notes.write().insert(note.id(), note_relevances.clone());
+
         Ok(note_relevances)
     }
 
@@ -117,6 +176,20 @@ impl NoteScreener {
         account: &Account,
         note: &Note,
     ) -> Result<Option<NoteRelevance>, NoteScreenerError> {
+        match self.check_consumption_status(account, note).await? {
+            NoteConsumptionStatus::Consumable => Ok(Some(NoteRelevance::Now)),
+            NoteConsumptionStatus::Unconsumable(_) => Ok(None),
+        }
+    }
+
+    /// Tries to execute a standard consume transaction for the note against the account and
+    /// returns whether it succeeded. If it didn't, the returned status contains the reason the
+    /// note execution failed.
+    pub async fn check_consumption_status(
+        &self,
+        account: &Account,
+        note: &Note,
+    ) -> Result<NoteConsumptionStatus, NoteScreenerError> {
         let transaction_request =
             TransactionRequestBuilder::new().build_consume_notes(vec![note.id()])?;
 
@@ -134,20 +207,36 @@ impl NoteScreener {
 
         data_store.mast_store().load_account_code(account.code());
 
-        if let NoteAccountExecution::Success = consumption_checker
+        let block_ref = self.store.get_sync_height().await?;
+        let note_execution = consumption_checker
             .check_notes_consumability(
                 account.id(),
-                self.store.get_sync_height().await?,
+                block_ref,
                 input_notes,
                 tx_args,
                 Arc::new(DefaultSourceManager::default()),
             )
-            .await?
-        {
-            return Ok(Some(NoteRelevance::Now));
-        }
+            .await?;
 
-        Ok(None)
+        match note_execution {
+            NoteAccountExecution::Success => Ok(NoteConsumptionStatus::Consumable),
+            NoteAccountExecution::Failure { failed_note_id, error, .. } => {
+                // Well-known notes are checked against their inputs without being executed, in
+                // which case there's no execution error
+                let reason = match error {
+                    Some(error) => error_chain_to_string(&error),
+                    None => well_known_note_failure_reason(note, account.id(), block_ref)
+                        .unwrap_or_else(|| {
+                            format!(
+                                "the inputs of note {failed_note_id} don't allow account {} to \
+                                 consume it at block {block_ref}",
+                                account.id()
+                            )
+                        }),
+                };
+                Ok(NoteConsumptionStatus::Unconsumable(reason))
+            },
+        }
     }
 
     /// Special relevance check for P2IDE notes. It checks if the sender account can consume and
@@ -156,26 +245,123 @@ impl NoteScreener {
         note: &Note,
         account_id: &AccountId,
     ) -> Result<Option<NoteRelevance>, NoteScreenerError> {
-        let note_inputs = note.inputs().values();
-        if note_inputs.len() != 4 {
-            return Err(InvalidNoteInputsError::WrongNumInputs(note.id(), 4).into());
-        }
-
-        let recall_height_felt = note_inputs[2];
+        let recall_height = get_p2ide_recall_height(note.id(), note.inputs().values())?;
 
         let sender = note.metadata().sender();
-        let recall_height: u32 = recall_height_felt.as_int().try_into().map_err(|_err| {
-            InvalidNoteInputsError::BlockNumberError(note.id(), recall_height_felt.as_int())
-        })?;
-
         if sender == *account_id {
-            Ok(Some(NoteRelevance::After(recall_height)))
+            Ok(Some(NoteRelevance::After(recall_height.as_u32())))
         } else {
             Ok(None)
         }
     }
 }
 
+/// Returns the block height after which the sender of a P2IDE note can recall it, given the note's
+/// inputs.
+pub(crate) fn get_p2ide_recall_height(
+    note_id: NoteId,
+    note_inputs: &[Felt],
+) -> Result<BlockNumber, InvalidNoteInputsError> {
+    if note_inputs.len() != 4 {
+        return Err(InvalidNoteInputsError::WrongNumInputs(note_id, 4));
+    }
+
+    let recall_height_felt = note_inputs[2];
+    let recall_height: u32 = recall_height_felt.as_int().try_into().map_err(|_err| {
+        InvalidNoteInputsError::BlockNumberError(note_id, recall_height_felt.as_int())
+    })?;
+
+    Ok(recall_height.into())
+}
+
+// HELPERS
+// ================================================================================================
+
+/// Returns the reason why the inputs of a P2ID or P2IDE note don't allow `account_id` to consume
+/// it at `block_ref`, mirroring the checks of [`WellKnownNote::check_note_inputs`].
+///
+/// Returns `None` if the note isn't a P2ID or P2IDE note, or if its inputs pass the checks.
+fn well_known_note_failure_reason(
+    note: &Note,
+    account_id: AccountId,
+    block_ref: BlockNumber,
+) -> Option<String> {
+    let note_id = note.id();
+    let well_known_note = WellKnownNote::from_note(note)?;
+    let note_name = match well_known_note {
+        WellKnownNote::P2ID => "P2ID",
+        WellKnownNote::P2IDE => "P2IDE",
+        WellKnownNote::SWAP => return None,
+    };
+
+    let inputs = note.inputs().values();
+    if inputs.len() != well_known_note.num_expected_inputs() {
+        return Some(format!(
+            "note {note_id} has {} inputs, but {note_name} notes expect {}",
+            inputs.len(),
+            well_known_note.num_expected_inputs()
+        ));
+    }
+
+    let read_height = |input: Felt| u32::try_from(input.as_int()).ok();
+
+    let is_p2ide = matches!(well_known_note, WellKnownNote::P2IDE);
+
+    if is_p2ide {
+        let Some(timelock_height) = read_height(inputs[3]) else {
+            return Some(format!(
+                "the inputs of note {note_id} don't encode a valid timelock height"
+            ));
+        };
+        if block_ref.as_u32() < timelock_height {
+            return Some(format!(
+                "note {note_id} is timelocked until block {timelock_height}, but the current block \
+                 is {block_ref}"
+            ));
+        }
+    }
+
+    let Ok(target_account_id) = AccountId::try_from([inputs[1], inputs[0]]) else {
+        return Some(format!(
+            "the inputs of note {note_id} don't encode a valid target account ID"
+        ));
+    };
+    if target_account_id == account_id {
+        return None;
+    }
+
+    if is_p2ide && note.metadata().sender() == account_id {
+        let Some(recall_height) = read_height(inputs[2]) else {
+            return Some(format!(
+                "the inputs of note {note_id} don't encode a valid recall height"
+            ));
+        };
+        if block_ref.as_u32() < recall_height {
+            return Some(format!(
+                "note {note_id} can't be recalled by its sender until block {recall_height}, but \
+                 the current block is {block_ref}"
+            ));
+        }
+        return None;
+    }
+
+    Some(format!(
+        "note {note_id} targets account {target_account_id}, not account {account_id}"
+    ))
+}
+
+/// Formats the error along with all of its sources, so that the root cause (e.g. the failed
+/// assertion) is part of the message.
+fn error_chain_to_string(err: &dyn core::error::Error) -> String {
+    let mut error_string = err.to_string();
+    let mut source = err.source();
+    while let Some(err) = source {
+        write!(error_string, ": {err}").expect("writing to string should always succeed");
+        source = err.source();
+    }
+    error_string
+}
+
 // NOTE SCREENER ERRORS
 // ================================================================================================
 
@@ -1,9 +1,10 @@
-use alloc::collections::BTreeMap;
+use alloc::{collections::BTreeMap, string::ToString, vec::Vec};
 
 use miden_objects::{
     block::BlockHeader,
     note::{NoteId, NoteInclusionProof, Nullifier},
 };
+use miden_tx::utils::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable};
 
 use crate::{
     ClientError,
@@ -271,6 +272,20 @@ impl NoteUpdateTracker {
         Ok(())
     }
 
+    /// Applies the necessary state transitions to the [`NoteUpdateTracker`] when the inclusion
+    /// proof of a tracked output note is fetched from the node.
+    pub(crate) fn apply_output_note_inclusion_proof(
+        &mut self,
+        note_id: NoteId,
+        inclusion_proof: NoteInclusionProof,
+    ) -> Result<(), ClientError> {
+        if let Some(output_note_record) = self.get_output_note_by_id(note_id) {
+            output_note_record.inclusion_proof_received(inclusion_proof)?;
+        }
+
+        Ok(())
+    }
+
     /// Applies the necessary state transitions to the [`NoteUpdateTracker`] when a note is
     /// nullified in a block.
     ///
@@ -348,3 +363,88 @@ impl NoteUpdateTracker {
             .map(OutputNoteUpdate::inner_mut)
     }
 }
+
+// SERIALIZATION
+// ================================================================================================
+
+impl Serializable for NoteUpdateType {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        match self {
+            NoteUpdateType::None => target.write_u8(0),
+            NoteUpdateType::Insert => target.write_u8(1),
+            NoteUpdateType::Update => target.write_u8(2),
+        }
+    }
+}
+
+impl Deserializable for NoteUpdateType {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        match source.read_u8()? {
+            0 => Ok(NoteUpdateType::None),
+            1 => Ok(NoteUpdateType::Insert),
+            2 => Ok(NoteUpdateType::Update),
+            _ => Err(DeserializationError::InvalidValue("Invalid note update type".to_string())),
+        }
+    }
+}
+
+impl Serializable for InputNoteUpdate {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.note.write_into(target);
+        self.update_type.write_into(target);
+    }
+}
+
+impl Deserializable for InputNoteUpdate {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let note = InputNoteRecord::read_from(source)?;
+        let update_type = NoteUpdateType::read_from(source)?;
+
+        Ok(Self { note, update_type })
+    }
+}
+
+impl Serializable for OutputNoteUpdate {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.note.write_into(target);
+        self.update_type.write_into(target);
+    }
+}
+
+impl Deserializable for OutputNoteUpdate {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let note = OutputNoteRecord::read_from(source)?;
+        let update_type = NoteUpdateType::read_from(source)?;
+
+        Ok(Self { note, update_type })
+    }
+}
+
+impl Serializable for NoteUpdateTracker {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_usize(self.input_notes.len());
+        for note in self.input_notes.values() {
+            note.write_into(target);
+        }
+
+        target.write_usize(self.output_notes.len());
+        for note in self.output_notes.values() {
+            note.write_into(target);
+        }
+    }
+}
+
+impl Deserializable for NoteUpdateTracker {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let input_notes = Vec::<InputNoteUpdate>::read_from(source)?
+            .into_iter()
+            .map(|note| (note.inner().id(), note))
+            .collect();
+        let output_notes = Vec::<OutputNoteUpdate>::read_from(source)?
+            .into_iter()
+            .map(|note| (note.inner().id(), note))
+            .collect();
+
+        Ok(Self { input_notes, output_notes })
+    }
+}
@@ -0,0 +1,242 @@
+//! Provides proofs that back the off-chain transfer of private notes.
+//!
+//! When the details of a private note are handed to its recipient, the recipient has no way of
+//! knowing whether the sender actually created the note or whether it was already consumed. A
+//! [`NoteTransferProof`] bundles the note with a signature over it made with the sender account's
+//! key, which the recipient can check with [`Client::verify_note_transfer_proof()`] along with the
+//! note's nullifier.
+
+use miden_lib::account::interface::{AccountComponentInterface, AccountInterface};
+use miden_objects::{
+    Digest, Word,
+    account::{Account, AccountId, AuthSecretKey},
+    block::BlockNumber,
+    crypto::{
+        dsa::rpo_falcon512::{PublicKey, Signature},
+        hash::rpo::Rpo256,
+    },
+    note::{Note, NoteId},
+};
+use miden_tx::utils::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable};
+use thiserror::Error;
+
+use crate::{
+    Client, ClientError, keystore::KeyStore, rpc::domain::account::FetchedAccount,
+    store::StoreError,
+};
+
+// NOTE TRANSFER PROOF
+// ================================================================================================
+
+/// A note along with a statement, signed by the key of the note's sender account, that the note is
+/// being transferred to its recipient.
+#[derive(Clone, Debug, PartialEq)]
+pub struct NoteTransferProof {
+    /// The transferred note.
+    note: Note,
+    /// The public key of the key that signed the transfer.
+    public_key: PublicKey,
+    /// The signature over the transfer message of the note.
+    signature: Signature,
+}
+
+impl NoteTransferProof {
+    /// Creates a new [`NoteTransferProof`]. The proof isn't verified on creation.
+    pub fn new(note: Note, public_key: PublicKey, signature: Signature) -> Self {
+        Self { note, public_key, signature }
+    }
+
+    /// Returns the transferred note.
+    pub fn note(&self) -> &Note {
+        &self.note
+    }
+
+    /// Returns the public key of the key that signed the transfer.
+    pub fn public_key(&self) -> PublicKey {
+        self.public_key
+    }
+
+    /// Returns the signature over the transfer message of the note.
+    pub fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    /// Returns the message that is signed to transfer the provided note. It commits to the note's
+    /// ID (and therefore to its assets and recipient) and to its metadata.
+    pub fn message(note: &Note) -> Word {
+        Rpo256::merge(&[note.id().inner(), Digest::from(Word::from(*note.metadata()))]).into()
+    }
+}
+
+impl Serializable for NoteTransferProof {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.note.write_into(target);
+        Word::from(self.public_key).write_into(target);
+        self.signature.write_into(target);
+    }
+}
+
+impl Deserializable for NoteTransferProof {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let note = Note::read_from(source)?;
+        let public_key = PublicKey::new(Word::read_from(source)?);
+        let signature = Signature::read_from(source)?;
+
+        Ok(Self { note, public_key, signature })
+    }
+}
+
+// NOTE TRANSFER PROOF ERROR
+// ================================================================================================
+
+/// Errors generated when creating or verifying a [`NoteTransferProof`].
+#[derive(Debug, Error)]
+pub enum NoteTransferProofError {
+    #[error("note with id {0} was already consumed")]
+    NoteAlreadyConsumed(NoteId),
+    #[error("the details of note with id {0} aren't known by the client")]
+    MissingNoteDetails(NoteId),
+    #[error("the transfer signature of note with id {0} is invalid")]
+    InvalidSignature(NoteId),
+    #[error("the transfer of note with id {0} wasn't signed by the key of its sender {1}")]
+    SignerNotSender(NoteId, AccountId),
+    #[error("the public key of sender {1} of note with id {0} couldn't be resolved")]
+    SenderKeyUnknown(NoteId, AccountId),
+    #[error("the keystore has no secret key for sender {1} of note with id {0}")]
+    SigningKeyNotFound(NoteId, AccountId),
+}
+
+// CLIENT METHODS
+// ================================================================================================
+
+/// Note transfer proof methods.
+impl Client {
+    /// Creates a [`NoteTransferProof`] for the output note with the provided [`NoteId`], signed
+    /// with the key of the note's sender account held by `keystore`.
+    ///
+    /// The proof can be handed to the note's recipient along with the note details, so that they
+    /// can check that the note was created by its sender and hasn't been consumed yet.
+    ///
+    /// # Errors
+    ///
+    /// - Returns a [`ClientError::NoteNotTracked`] if the client has no output note with the
+    ///   provided ID.
+    /// - Returns a [`NoteTransferProofError::MissingNoteDetails`] if the client doesn't know the
+    ///   note's recipient.
+    /// - Returns a [`NoteTransferProofError::NoteAlreadyConsumed`] if the note was consumed.
+    /// - Returns a [`NoteTransferProofError::SigningKeyNotFound`] if the sender account has no
+    ///   authentication key or `keystore` doesn't hold its secret key.
+    pub async fn create_note_transfer_proof(
+        &mut self,
+        note_id: NoteId,
+        keystore: &dyn KeyStore,
+    ) -> Result<NoteTransferProof, ClientError> {
+        let output_note = self
+            .get_output_note(note_id)
+            .await?
+            .ok_or(ClientError::NoteNotTracked(note_id))?;
+
+        if output_note.is_consumed() {
+            return Err(NoteTransferProofError::NoteAlreadyConsumed(note_id).into());
+        }
+
+        let note = Note::try_from(output_note)
+            .map_err(|_| NoteTransferProofError::MissingNoteDetails(note_id))?;
+
+        let sender_id = note.metadata().sender();
+        let sender: Account = self
+            .store
+            .get_account(sender_id)
+            .await?
+            .ok_or(StoreError::AccountDataNotFound(sender_id))?
+            .into();
+
+        let Some(AuthSecretKey::RpoFalcon512(secret_key)) = auth_public_key(&sender)
+            .map(|public_key| keystore.get_key(public_key))
+            .transpose()?
+            .flatten()
+        else {
+            return Err(NoteTransferProofError::SigningKeyNotFound(note_id, sender_id).into());
+        };
+
+        let signature = secret_key.sign_with_rng(NoteTransferProof::message(&note), &mut self.rng);
+
+        Ok(NoteTransferProof::new(note, secret_key.public_key(), signature))
+    }
+
+    /// Verifies the provided [`NoteTransferProof`].
+    ///
+    /// The proof is valid if its signature over the note is correct, the signing key belongs to
+    /// the note's sender account and the note's nullifier hasn't been committed on chain. The
+    /// sender's key can only be resolved if the sender account is tracked by the client or is
+    /// public, so proofs of other private senders are rejected.
+    ///
+    /// # Errors
+    ///
+    /// - Returns a [`NoteTransferProofError::InvalidSignature`] if the signature doesn't match the
+    ///   note (for example, because the note was tampered with).
+    /// - Returns a [`NoteTransferProofError::SenderKeyUnknown`] if the public key of the note's
+    ///   sender account can't be resolved.
+    /// - Returns a [`NoteTransferProofError::SignerNotSender`] if the proof wasn't signed by the
+    ///   key of the note's sender account.
+    /// - Returns a [`NoteTransferProofError::NoteAlreadyConsumed`] if the note's nullifier was
+    ///   committed on chain.
+    pub async fn verify_note_transfer_proof(
+        &self,
+        proof: &NoteTransferProof,
+    ) -> Result<(), ClientError> {
+        let note = proof.note();
+
+        if !proof.public_key().verify(NoteTransferProof::message(note), proof.signature()) {
+            return Err(NoteTransferProofError::InvalidSignature(note.id()).into());
+        }
+
+        let sender_id = note.metadata().sender();
+        let sender: Option<Account> = match self.store.get_account(sender_id).await? {
+            Some(account_record) => Some(account_record.into()),
+            None if sender_id.is_public() => {
+                match self.rpc_api.get_account_details(sender_id).await? {
+                    FetchedAccount::Public(account, ..) => Some(account),
+                    FetchedAccount::Private(..) => None,
+                }
+            },
+            None => None,
+        };
+
+        let Some(sender_key) = sender.as_ref().and_then(auth_public_key) else {
+            return Err(NoteTransferProofError::SenderKeyUnknown(note.id(), sender_id).into());
+        };
+
+        if sender_key != Word::from(proof.public_key()) {
+            return Err(NoteTransferProofError::SignerNotSender(note.id(), sender_id).into());
+        }
+
+        if self
+            .rpc_api
+            .get_nullifier_commit_height(&note.nullifier(), BlockNumber::GENESIS)
+            .await?
+            .is_some()
+        {
+            return Err(NoteTransferProofError::NoteAlreadyConsumed(note.id()).into());
+        }
+
+        Ok(())
+    }
+}
+
+// HELPERS
+// ================================================================================================
+
+/// Returns the public key stored by the [`AccountComponentInterface::RpoFalcon512`] component of
+/// the account, if it has one.
+fn auth_public_key(account: &Account) -> Option<Word> {
+    AccountInterface::from(account)
+        .components()
+        .iter()
+        .find_map(|component| match component {
+            AccountComponentInterface::RpoFalcon512(offset) => {
+                account.storage().get_item(*offset).ok().map(Word::from)
+            },
+            _ => None,
+        })
+}
@@ -0,0 +1,158 @@
+//! Provides [`Client::watch_consumable_notes()`], which lets applications react to notes that
+//! become consumable by an account as the client syncs or imports notes, instead of polling
+//! [`Client::get_consumable_notes()`].
+//!
+//! Each call returns a [`ConsumableNoteStream`] that yields the notes found to be consumable by
+//! the account in the syncs and note imports performed after it was created. The stream works in the same way on
+//! every target, so it can be driven by any async runtime.
+
+use alloc::{collections::VecDeque, sync::Arc, vec::Vec};
+use core::{
+    pin::Pin,
+    task::{Context, Poll, Waker},
+};
+
+use futures_core::Stream;
+use miden_objects::{account::AccountId, note::NoteId};
+use miden_tx::utils::sync::RwLock;
+
+use crate::{
+    Client, ClientError,
+    note::NoteScreener,
+    store::{InputNoteRecord, InputNoteState, NoteFilter},
+};
+
+// CONSUMABLE NOTE STREAM
+// ================================================================================================
+
+/// Notes queued for a [`ConsumableNoteStream`] along with the waker of the task polling it.
+#[derive(Default)]
+struct NoteQueue {
+    notes: VecDeque<InputNoteRecord>,
+    waker: Option<Waker>,
+    /// Whether the client side of the queue was dropped, so no more notes will be queued.
+    closed: bool,
+}
+
+/// Stream of the notes that become consumable by an account, created with
+/// [`Client::watch_consumable_notes()`].
+///
+/// The stream stays pending until a sync or a note import commits a new note that can be consumed
+/// by the account, and ends once the client that created it is dropped and all queued notes were
+/// yielded.
+pub struct ConsumableNoteStream {
+    queue: Arc<RwLock<NoteQueue>>,
+}
+
+impl Stream for ConsumableNoteStream {
+    type Item = InputNoteRecord;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut queue = self.queue.write();
+        if let Some(note) = queue.notes.pop_front() {
+            return Poll::Ready(Some(note));
+        }
+
+        if queue.closed {
+            return Poll::Ready(None);
+        }
+
+        queue.waker = Some(cx.waker().clone());
+        Poll::Pending
+    }
+}
+
+/// The client side of a [`ConsumableNoteStream`].
+pub(crate) struct ConsumableNoteWatcher {
+    account_id: AccountId,
+    queue: Arc<RwLock<NoteQueue>>,
+}
+
+impl ConsumableNoteWatcher {
+    /// Returns whether the stream of the watcher was dropped.
+    fn is_closed(&self) -> bool {
+        Arc::strong_count(&self.queue) == 1
+    }
+
+    /// Queues the note to be yielded by the stream and wakes the task polling it.
+    fn push(&self, note: InputNoteRecord) {
+        let mut queue = self.queue.write();
+        queue.notes.push_back(note);
+        if let Some(waker) = queue.waker.take() {
+            waker.wake();
+        }
+    }
+}
+
+impl Drop for ConsumableNoteWatcher {
+    /// Closes the queue and wakes the task polling the stream, so that it ends.
+    fn drop(&mut self) {
+        let mut queue = self.queue.write();
+        queue.closed = true;
+        if let Some(waker) = queue.waker.take() {
+            waker.wake();
+        }
+    }
+}
+
+// CLIENT METHODS
+// ================================================================================================
+
+/// Consumable note watching methods.
+impl Client {
+    /// Returns a [`ConsumableNoteStream`] that yields the notes that become consumable by the
+    /// account with the specified ID in the syncs and note imports performed from now on. Syncs
+    /// replayed with [`Client::apply_serialized_sync()`] are included.
+    ///
+    /// A note is yielded once it's committed and the [`NoteScreener`] finds it relevant to the
+    /// account, in the same way as for [`Client::get_consumable_notes()`]. Notes that were already
+    /// consumable when the stream was created aren't yielded, and neither are notes reserved by a
+    /// transaction that wasn't submitted yet.
+    ///
+    /// The stream can be dropped at any time to stop watching the account's notes.
+    pub fn watch_consumable_notes(&mut self, account_id: AccountId) -> ConsumableNoteStream {
+        let queue = Arc::new(RwLock::new(NoteQueue::default()));
+        self.consumable_note_watchers
+            .push(ConsumableNoteWatcher { account_id, queue: queue.clone() });
+
+        ConsumableNoteStream { queue }
+    }
+
+    /// Sends the notes with the specified IDs that are consumable by a watched account to the
+    /// account's [`ConsumableNoteStream`]s. Watchers whose stream was dropped are removed.
+    ///
+    /// The relevance of the notes is checked with `note_screener`, so notes it already screened
+    /// (for example, while receiving them during a sync) aren't screened again.
+    pub(crate) async fn notify_consumable_note_watchers(
+        &mut self,
+        note_ids: Vec<NoteId>,
+        note_screener: &NoteScreener,
+    ) -> Result<(), ClientError> {
+        self.consumable_note_watchers.retain(|watcher| !watcher.is_closed());
+        if self.consumable_note_watchers.is_empty() || note_ids.is_empty() {
+            return Ok(());
+        }
+
+        let notes = self.store.get_input_notes(NoteFilter::List(note_ids)).await?;
+        for note in notes {
+            if !matches!(note.state(), InputNoteState::Committed(_))
+                || self.is_note_reserved(note.id())
+            {
+                continue;
+            }
+
+            let account_relevance =
+                note_screener.check_relevance(&note.clone().try_into()?).await?;
+            for watcher in &self.consumable_note_watchers {
+                if account_relevance
+                    .iter()
+                    .any(|(account_id, _)| *account_id == watcher.account_id)
+                {
+                    watcher.push(note.clone());
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
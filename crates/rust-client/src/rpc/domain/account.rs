@@ -2,7 +2,7 @@ use alloc::{collections::BTreeMap, string::String, vec::Vec};
 use core::fmt::{self, Debug, Display, Formatter};
 
 use miden_objects::{
-    Digest,
+    Digest, Word,
     account::{Account, AccountCode, AccountHeader, AccountId, AccountStorageHeader},
     block::{AccountWitness, BlockNumber},
     crypto::merkle::{MerklePath, SmtProof},
@@ -384,11 +384,78 @@ impl AccountStorageRequirements {
         AccountStorageRequirements(map)
     }
 
+    /// Returns a new [`AccountStorageRequirementsBuilder`].
+    pub fn builder() -> AccountStorageRequirementsBuilder {
+        AccountStorageRequirementsBuilder::default()
+    }
+
     pub fn inner(&self) -> &BTreeMap<StorageSlotIndex, Vec<StorageMapKey>> {
         &self.0
     }
 }
 
+/// A builder for [`AccountStorageRequirements`].
+///
+/// Required slots and map keys are accumulated and merged into the final requirements. If the
+/// number of storage slots of the account is known, slot indices are validated against it when
+/// building.
+#[derive(Clone, Debug, Default)]
+pub struct AccountStorageRequirementsBuilder {
+    /// Storage slot indices to be requested, along with the map keys for each of them.
+    slots_and_keys: BTreeMap<StorageSlotIndex, Vec<StorageMapKey>>,
+    /// Number of storage slots of the account, if known.
+    num_storage_slots: Option<u8>,
+}
+
+impl AccountStorageRequirementsBuilder {
+    /// Sets the number of storage slots of the account, which is used to validate the required
+    /// slot indices.
+    #[must_use]
+    pub fn num_storage_slots(mut self, num_storage_slots: u8) -> Self {
+        self.num_storage_slots = Some(num_storage_slots);
+        self
+    }
+
+    /// Requires the storage slot at the specified index.
+    #[must_use]
+    pub fn require_slot(mut self, slot_index: StorageSlotIndex) -> Self {
+        self.slots_and_keys.entry(slot_index).or_default();
+        self
+    }
+
+    /// Requires the value of the provided key in the storage map at the specified slot index.
+    /// Keys that were already required for the slot are ignored.
+    #[must_use]
+    pub fn require_map_key(mut self, slot_index: StorageSlotIndex, key: Word) -> Self {
+        let key = StorageMapKey::from(key);
+        let keys = self.slots_and_keys.entry(slot_index).or_default();
+        if !keys.contains(&key) {
+            keys.push(key);
+        }
+        self
+    }
+
+    /// Consumes the builder and returns the [`AccountStorageRequirements`].
+    ///
+    /// # Errors
+    ///
+    /// - If the number of storage slots is known and any of the required slot indices is out of
+    ///   bounds.
+    pub fn build(self) -> Result<AccountStorageRequirements, AccountStorageRequirementsError> {
+        if let Some(num_storage_slots) = self.num_storage_slots
+            && let Some(slot_index) =
+                self.slots_and_keys.keys().find(|slot_index| **slot_index >= num_storage_slots)
+        {
+            return Err(AccountStorageRequirementsError::SlotIndexOutOfBounds {
+                slot_index: *slot_index,
+                num_storage_slots,
+            });
+        }
+
+        Ok(AccountStorageRequirements(self.slots_and_keys))
+    }
+}
+
 impl From<AccountStorageRequirements> for Vec<get_account_proofs_request::StorageRequest> {
     fn from(value: AccountStorageRequirements) -> Vec<get_account_proofs_request::StorageRequest> {
         let mut requests = Vec::with_capacity(value.0.len());
@@ -435,3 +502,65 @@ pub enum AccountProofError {
     )]
     InconsistentCodeCommitment,
 }
+
+#[derive(Debug, Error)]
+pub enum AccountStorageRequirementsError {
+    #[error(
+        "storage slot index {slot_index} is out of bounds for an account with {num_storage_slots} storage slots"
+    )]
+    SlotIndexOutOfBounds {
+        slot_index: StorageSlotIndex,
+        num_storage_slots: u8,
+    },
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use miden_objects::{Felt, Word};
+    use miden_tx::utils::{Deserializable, Serializable};
+
+    use super::{AccountStorageRequirements, AccountStorageRequirementsError, StorageMapKey};
+
+    #[test]
+    fn storage_requirements_builder() {
+        let first_key: Word = [Felt::new(1), Felt::new(2), Felt::new(3), Felt::new(4)];
+        let second_key: Word = [Felt::new(5), Felt::new(6), Felt::new(7), Felt::new(8)];
+
+        let requirements = AccountStorageRequirements::builder()
+            .num_storage_slots(3)
+            .require_slot(0)
+            .require_map_key(1, first_key)
+            .require_map_key(2, second_key)
+            .require_map_key(1, first_key)
+            .build()
+            .unwrap();
+
+        let expected = AccountStorageRequirements::new([
+            (0u8, &[][..]),
+            (1u8, &[StorageMapKey::from(first_key)][..]),
+            (2u8, &[StorageMapKey::from(second_key)][..]),
+        ]);
+        assert_eq!(requirements, expected);
+
+        let deserialized =
+            AccountStorageRequirements::read_from_bytes(&requirements.to_bytes()).unwrap();
+        assert_eq!(requirements, deserialized);
+
+        // Slot indices are validated against the account's storage layout
+        let error = AccountStorageRequirements::builder()
+            .num_storage_slots(2)
+            .require_map_key(2, second_key)
+            .build()
+            .unwrap_err();
+        assert!(matches!(
+            error,
+            AccountStorageRequirementsError::SlotIndexOutOfBounds {
+                slot_index: 2,
+                num_storage_slots: 2
+            }
+        ));
+    }
+}
@@ -9,6 +9,7 @@ pub mod merkle;
 pub mod note;
 pub mod nullifier;
 pub mod smt;
+pub mod status;
 pub mod sync;
 pub mod transaction;
 
@@ -0,0 +1,17 @@
+use alloc::string::String;
+
+use miden_objects::{Digest, block::BlockNumber};
+
+// NODE STATUS
+// ================================================================================================
+
+/// Represents the status of the node, as reported by its `Status` RPC endpoint.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NodeStatus {
+    /// The version of the node's RPC component.
+    pub version: String,
+    /// The block number of the chain tip.
+    pub chain_tip: BlockNumber,
+    /// The commitment of the chain's genesis block header.
+    pub genesis_commitment: Digest,
+}
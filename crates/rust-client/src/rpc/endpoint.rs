@@ -2,6 +2,7 @@ use alloc::string::{String, ToString};
 use core::fmt;
 
 use miden_objects::{NetworkIdError, account::NetworkId};
+use thiserror::Error;
 
 // ENDPOINT
 // ================================================================================================
@@ -34,6 +35,30 @@ impl Endpoint {
         Self { protocol, host, port }
     }
 
+    /// Returns an [`EndpointBuilder`] to build an [`Endpoint`] out of its parts.
+    pub fn builder() -> EndpointBuilder {
+        EndpointBuilder::default()
+    }
+
+    /// Creates an [`Endpoint`] from the scheme, host and port of the provided URL. The port is
+    /// only set if it's not the default one for the URL's scheme.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`EndpointError::EmptyHost`] if the URL doesn't have a host.
+    #[cfg(feature = "std")]
+    pub fn from_url(url: &url::Url) -> Result<Self, EndpointError> {
+        let mut builder = Self::builder()
+            .scheme(url.scheme())
+            .host(url.host_str().ok_or(EndpointError::EmptyHost)?);
+
+        if let Some(port) = url.port() {
+            builder = builder.port(port);
+        }
+
+        builder.build()
+    }
+
     /// Returns the [Endpoint] associated with the testnet network.
     pub fn testnet() -> Self {
         Self::new("https".into(), "rpc.testnet.miden.io".into(), None)
@@ -74,6 +99,92 @@ impl Endpoint {
     }
 }
 
+// ENDPOINT BUILDER
+// ================================================================================================
+
+/// A builder for an [`Endpoint`], which validates each of its parts.
+///
+/// If no scheme is set, `https` is used.
+#[derive(Clone, Debug, Default)]
+pub struct EndpointBuilder {
+    scheme: Option<String>,
+    host: Option<String>,
+    port: Option<u16>,
+}
+
+impl EndpointBuilder {
+    /// Sets the scheme (also called protocol) of the endpoint, such as `http` or `https`.
+    #[must_use]
+    pub fn scheme(mut self, scheme: impl Into<String>) -> Self {
+        self.scheme = Some(scheme.into());
+        self
+    }
+
+    /// Sets the hostname or IP address of the endpoint.
+    #[must_use]
+    pub fn host(mut self, host: impl Into<String>) -> Self {
+        self.host = Some(host.into());
+        self
+    }
+
+    /// Sets the port of the endpoint.
+    #[must_use]
+    pub fn port(mut self, port: u16) -> Self {
+        self.port = Some(port);
+        self
+    }
+
+    /// Builds the [`Endpoint`].
+    ///
+    /// # Errors
+    ///
+    /// - Returns an [`EndpointError::InvalidScheme`] if the scheme is empty or contains characters
+    ///   other than ASCII letters, digits, `+`, `-` and `.`.
+    /// - Returns an [`EndpointError::EmptyHost`] if no host was set or it's empty.
+    /// - Returns an [`EndpointError::InvalidHost`] if the host contains whitespace or a `/`.
+    /// - Returns an [`EndpointError::InvalidPort`] if the port is 0.
+    pub fn build(self) -> Result<Endpoint, EndpointError> {
+        let scheme = self.scheme.unwrap_or_else(|| "https".to_string());
+        if scheme.is_empty()
+            || !scheme
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'))
+        {
+            return Err(EndpointError::InvalidScheme(scheme));
+        }
+
+        let host = self.host.unwrap_or_default();
+        if host.is_empty() {
+            return Err(EndpointError::EmptyHost);
+        }
+        if host.chars().any(|c| c.is_whitespace() || c == '/') {
+            return Err(EndpointError::InvalidHost(host));
+        }
+
+        if self.port == Some(0) {
+            return Err(EndpointError::InvalidPort(0));
+        }
+
+        Ok(Endpoint::new(scheme, host, self.port))
+    }
+}
+
+// ENDPOINT ERROR
+// ================================================================================================
+
+/// Errors that can occur while building an [`Endpoint`].
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum EndpointError {
+    #[error("endpoint host can't be empty")]
+    EmptyHost,
+    #[error("invalid endpoint host `{0}`")]
+    InvalidHost(String),
+    #[error("invalid endpoint port {0}")]
+    InvalidPort(u16),
+    #[error("invalid endpoint scheme `{0}`")]
+    InvalidScheme(String),
+}
+
 impl fmt::Display for Endpoint {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self.port {
@@ -143,8 +254,9 @@ impl TryFrom<&str> for Endpoint {
 
 #[cfg(test)]
 mod test {
-    use alloc::string::ToString;
+    use alloc::string::{String, ToString};
 
+    use super::EndpointError;
     use crate::rpc::Endpoint;
 
     #[test]
@@ -260,4 +372,58 @@ mod test {
 
         assert_eq!(endpoint, expected_endpoint);
     }
+
+    #[test]
+    fn endpoint_builder() {
+        let endpoint = Endpoint::builder()
+            .scheme("https")
+            .host("rpc.example.com")
+            .port(443)
+            .build()
+            .unwrap();
+        assert_eq!(endpoint, Endpoint::new("https".into(), "rpc.example.com".into(), Some(443)));
+
+        let endpoint = Endpoint::builder().host("192.168.0.1").build().unwrap();
+        assert_eq!(endpoint, Endpoint::new("https".into(), "192.168.0.1".into(), None));
+    }
+
+    #[test]
+    fn endpoint_builder_validation() {
+        assert_eq!(Endpoint::builder().build().unwrap_err(), EndpointError::EmptyHost);
+        assert_eq!(Endpoint::builder().host("").build().unwrap_err(), EndpointError::EmptyHost);
+        assert_eq!(
+            Endpoint::builder().host("rpc.example.com/path").build().unwrap_err(),
+            EndpointError::InvalidHost("rpc.example.com/path".to_string())
+        );
+        assert_eq!(
+            Endpoint::builder().host("rpc.example.com").port(0).build().unwrap_err(),
+            EndpointError::InvalidPort(0)
+        );
+        assert_eq!(
+            Endpoint::builder().scheme("").host("rpc.example.com").build().unwrap_err(),
+            EndpointError::InvalidScheme(String::new())
+        );
+        assert_eq!(
+            Endpoint::builder()
+                .scheme("https://")
+                .host("rpc.example.com")
+                .build()
+                .unwrap_err(),
+            EndpointError::InvalidScheme("https://".to_string())
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn endpoint_from_url() {
+        let url = url::Url::parse("http://localhost:57291/").unwrap();
+        assert_eq!(Endpoint::from_url(&url).unwrap(), Endpoint::localhost());
+
+        // The default port for the scheme isn't kept
+        let url = url::Url::parse("https://rpc.testnet.miden.io:443").unwrap();
+        assert_eq!(Endpoint::from_url(&url).unwrap(), Endpoint::testnet());
+
+        let url = url::Url::parse("unix:/run/node.sock").unwrap();
+        assert_eq!(Endpoint::from_url(&url).unwrap_err(), EndpointError::EmptyHost);
+    }
 }
@@ -26,6 +26,8 @@ pub enum RpcError {
     NoteNotFound(NoteId),
     #[error("rpc request failed for {0}: {1}")]
     RequestError(String, String),
+    #[error("failed to submit transaction")]
+    TransactionSubmissionError(#[source] SubmissionError),
 }
 
 impl From<DeserializationError> for RpcError {
@@ -46,6 +48,34 @@ impl From<RpcConversionError> for RpcError {
     }
 }
 
+// SUBMISSION ERROR
+// ================================================================================================
+
+/// Reason why the node didn't accept a submitted transaction.
+#[derive(Debug, Clone, Error, PartialEq, Eq)]
+pub enum SubmissionError {
+    /// Some of the transaction's input notes were already consumed.
+    #[error("input notes of the transaction were already consumed: {0}")]
+    AlreadySpent(String),
+    /// The transaction expired before the node could include it.
+    #[error("transaction expired: {0}")]
+    Expired(String),
+    /// The node rejected the transaction for any other reason, such as it being invalid.
+    #[error("transaction was rejected by the node: {0}")]
+    Rejected(String),
+    /// The node couldn't process the transaction at the moment, but may accept it later.
+    #[error("node is temporarily unable to accept the transaction: {0}")]
+    Temporary(String),
+}
+
+impl SubmissionError {
+    /// Returns whether submitting the same transaction again may succeed. Other rejections are
+    /// permanent, and the transaction needs to be re-executed (if possible) instead.
+    pub fn is_retryable(&self) -> bool {
+        matches!(self, Self::Temporary(_))
+    }
+}
+
 // RPC CONVERSION ERROR
 // ================================================================================================
 
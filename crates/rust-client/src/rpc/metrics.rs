@@ -0,0 +1,108 @@
+//! Hooks used to observe the calls made to the node's RPC API.
+
+use alloc::collections::BTreeMap;
+use core::time::Duration;
+
+use miden_tx::utils::sync::RwLock;
+
+use super::NodeRpcClientEndpoint;
+
+// RPC METRICS
+// ================================================================================================
+
+/// The result of a single call to the node's RPC API.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RpcCallOutcome {
+    /// The node answered the request successfully.
+    Success,
+    /// The request failed, either because the node returned an error status or because it could
+    /// not be reached.
+    Failure,
+}
+
+/// Receives a notification after each call made to the node's RPC API.
+///
+/// Implementations can be used to export latency and error rates of the node connection to a
+/// monitoring system. They are invoked from within the RPC client, so they should return quickly
+/// and never block.
+pub trait RpcMetrics: Send + Sync {
+    /// Records a call to the `endpoint` method that took `duration` to complete with the given
+    /// `outcome`.
+    fn record_call(
+        &self,
+        endpoint: NodeRpcClientEndpoint,
+        duration: Duration,
+        outcome: RpcCallOutcome,
+    );
+}
+
+/// An [`RpcMetrics`] implementation that ignores every call. Used by default.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct NoopRpcMetrics;
+
+impl RpcMetrics for NoopRpcMetrics {
+    fn record_call(
+        &self,
+        _endpoint: NodeRpcClientEndpoint,
+        _duration: Duration,
+        _outcome: RpcCallOutcome,
+    ) {
+    }
+}
+
+/// Aggregated statistics for the calls made to a single RPC method.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct RpcCallCounts {
+    /// Number of calls that succeeded.
+    pub successes: u64,
+    /// Number of calls that failed.
+    pub failures: u64,
+    /// Sum of the durations of all recorded calls.
+    pub total_duration: Duration,
+}
+
+/// An [`RpcMetrics`] implementation that keeps per-method call counts in memory.
+pub struct CountingRpcMetrics {
+    counts: RwLock<BTreeMap<NodeRpcClientEndpoint, RpcCallCounts>>,
+}
+
+impl CountingRpcMetrics {
+    /// Returns a new [`CountingRpcMetrics`] with no recorded calls.
+    pub fn new() -> Self {
+        Self { counts: RwLock::new(BTreeMap::new()) }
+    }
+
+    /// Returns the statistics recorded for the `endpoint` method.
+    pub fn counts(&self, endpoint: NodeRpcClientEndpoint) -> RpcCallCounts {
+        self.counts.read().get(&endpoint).copied().unwrap_or_default()
+    }
+
+    /// Returns the statistics recorded for every method that was called at least once.
+    pub fn all_counts(&self) -> BTreeMap<NodeRpcClientEndpoint, RpcCallCounts> {
+        self.counts.read().clone()
+    }
+}
+
+impl Default for CountingRpcMetrics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RpcMetrics for CountingRpcMetrics {
+    fn record_call(
+        &self,
+        endpoint: NodeRpcClientEndpoint,
+        duration: Duration,
+        outcome: RpcCallOutcome,
+    ) {
+        let mut counts = self.counts.write();
+        let entry = counts.entry(endpoint).or_default();
+
+        match outcome {
+            RpcCallOutcome::Success => entry.successes += 1,
+            RpcCallOutcome::Failure => entry.failures += 1,
+        }
+        entry.total_duration += duration;
+    }
+}
@@ -46,9 +46,11 @@ use domain::{
     account::{AccountProofs, FetchedAccount},
     note::{FetchedNote, NoteSyncInfo},
     nullifier::NullifierUpdate,
+    status::NodeStatus,
     sync::StateSyncInfo,
 };
 use miden_objects::{
+    Digest,
     account::{Account, AccountCode, AccountDelta, AccountHeader, AccountId},
     block::{BlockHeader, BlockNumber, ProvenBlock},
     crypto::merkle::{MmrProof, SmtProof},
@@ -61,10 +63,13 @@ use miden_objects::{
 pub mod domain;
 
 mod errors;
-pub use errors::RpcError;
+pub use errors::{RpcError, SubmissionError};
+
+mod metrics;
+pub use metrics::{CountingRpcMetrics, NoopRpcMetrics, RpcCallCounts, RpcCallOutcome, RpcMetrics};
 
 mod endpoint;
-pub use endpoint::Endpoint;
+pub use endpoint::{Endpoint, EndpointBuilder, EndpointError};
 
 #[cfg(not(feature = "testing"))]
 mod generated;
@@ -118,6 +123,11 @@ pub trait NodeRpcClient: Send + Sync {
     /// the `/GetBlockByNumber` RPC endpoint.
     async fn get_block_by_number(&self, block_num: BlockNumber) -> Result<ProvenBlock, RpcError>;
 
+    /// Fetches the node's version and chain tip using the `/Status` RPC endpoint, along with the
+    /// commitment of the genesis block. This can be used to check that the node is reachable
+    /// and on the expected network before syncing.
+    async fn status(&self) -> Result<NodeStatus, RpcError>;
+
     /// Fetches note-related data for a list of [NoteId] using the `/GetNotesById` rpc endpoint.
     ///
     /// For any NoteType::Private note, the return data is only the
@@ -271,6 +281,37 @@ pub trait NodeRpcClient: Send + Sync {
         Ok(public_accounts)
     }
 
+    /// Fetches the latest state of a tracked public account by applying the changes made to it
+    /// since `from_block` to its locally stored state.
+    ///
+    /// The `local_account` parameter is the state of the account as of `from_block`, and
+    /// `commitment` is the account commitment at `to_block` as reported by the node. If the node
+    /// can't provide the delta or the resulting state doesn't match `commitment`, the full account
+    /// details are fetched instead. Returns `None` if the node has no newer public state for the
+    /// account.
+    ///
+    /// The default implementation of this method uses [`NodeRpcClient::get_account_state_delta`]
+    /// and falls back to [`NodeRpcClient::get_updated_public_accounts`].
+    async fn get_updated_public_account(
+        &self,
+        mut local_account: Account,
+        from_block: BlockNumber,
+        to_block: BlockNumber,
+        commitment: Digest,
+    ) -> Result<Option<Account>, RpcError> {
+        let local_header = AccountHeader::from(&local_account);
+
+        if let Ok(account_delta) =
+            self.get_account_state_delta(local_account.id(), from_block, to_block).await
+            && local_account.apply_delta(&account_delta).is_ok()
+            && local_account.commitment() == commitment
+        {
+            return Ok(Some(local_account));
+        }
+
+        Ok(self.get_updated_public_accounts(&[&local_header]).await?.pop())
+    }
+
     /// Given a block number, fetches the block header corresponding to that height from the node
     /// along with the MMR proof.
     ///
@@ -299,7 +340,7 @@ pub trait NodeRpcClient: Send + Sync {
 // ================================================================================================
 //
 /// RPC methods for the Miden protocol.
-#[derive(Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
 pub enum NodeRpcClientEndpoint {
     CheckNullifiers,
     CheckNullifiersByPrefix,
@@ -308,6 +349,8 @@ pub enum NodeRpcClientEndpoint {
     GetAccountProofs,
     GetBlockByNumber,
     GetBlockHeaderByNumber,
+    GetNotesById,
+    Status,
     SyncState,
     SubmitProvenTx,
     SyncNotes,
@@ -327,6 +370,8 @@ impl fmt::Display for NodeRpcClientEndpoint {
             NodeRpcClientEndpoint::GetBlockHeaderByNumber => {
                 write!(f, "get_block_header_by_number")
             },
+            NodeRpcClientEndpoint::GetNotesById => write!(f, "get_notes_by_id"),
+            NodeRpcClientEndpoint::Status => write!(f, "status"),
             NodeRpcClientEndpoint::SyncState => write!(f, "sync_state"),
             NodeRpcClientEndpoint::SubmitProvenTx => write!(f, "submit_proven_transaction"),
             NodeRpcClientEndpoint::SyncNotes => write!(f, "sync_notes"),
@@ -2,6 +2,7 @@ use alloc::{
     boxed::Box,
     collections::{BTreeMap, BTreeSet},
     string::{String, ToString},
+    sync::Arc,
     vec::Vec,
 };
 
@@ -18,12 +19,13 @@ use miden_tx::utils::{Serializable, sync::RwLock};
 use tracing::info;
 
 use super::{
-    Endpoint, FetchedAccount, NodeRpcClient, NodeRpcClientEndpoint, NoteSyncInfo, RpcError,
-    StateSyncInfo,
+    Endpoint, FetchedAccount, NodeRpcClient, NodeRpcClientEndpoint, NoopRpcMetrics, NoteSyncInfo,
+    RpcCallOutcome, RpcError, RpcMetrics, StateSyncInfo, SubmissionError,
     domain::{
         account::{AccountProof, AccountProofs, AccountUpdateSummary},
         note::FetchedNote,
         nullifier::NullifierUpdate,
+        status::NodeStatus,
     },
     generated::requests::{
         CheckNullifiersByPrefixRequest, CheckNullifiersRequest, GetAccountDetailsRequest,
@@ -53,10 +55,14 @@ use api_client::api_client_wrapper::ApiClient;
 ///
 /// In both cases, the [`TonicRpcClient`] depends on the types inside the `generated` module, which
 /// are generated by the build script and also depend on the target architecture.
+///
+/// Every call made to the node is reported to the client's [`RpcMetrics`], which ignores them
+/// unless a different implementation is set with [`TonicRpcClient::with_metrics`].
 pub struct TonicRpcClient {
     client: RwLock<Option<ApiClient>>,
     endpoint: String,
     timeout_ms: u64,
+    metrics: Arc<dyn RpcMetrics>,
 }
 
 impl TonicRpcClient {
@@ -67,9 +73,18 @@ impl TonicRpcClient {
             client: RwLock::new(None),
             endpoint: endpoint.to_string(),
             timeout_ms,
+            metrics: Arc::new(NoopRpcMetrics),
         }
     }
 
+    /// Sets the [`RpcMetrics`] that will be notified of the method, duration and outcome of every
+    /// call made to the node.
+    #[must_use]
+    pub fn with_metrics(mut self, metrics: Arc<dyn RpcMetrics>) -> Self {
+        self.metrics = metrics;
+        self
+    }
+
     /// Takes care of establishing the RPC connection if not connected yet. It ensures that the
     /// `rpc_api` field is initialized and returns a write guard to it.
     async fn ensure_connected(&self) -> Result<ApiClient, RpcError> {
@@ -81,6 +96,39 @@ impl TonicRpcClient {
 
         Ok(self.client.read().as_ref().expect("rpc_api should be initialized").clone())
     }
+
+    /// Awaits a call to the `endpoint` method and reports its duration and outcome to the
+    /// configured [`RpcMetrics`]. A failed call is converted into an [`RpcError::RequestError`].
+    async fn call_endpoint<T>(
+        &self,
+        endpoint: NodeRpcClientEndpoint,
+        call: impl Future<Output = Result<tonic::Response<T>, tonic::Status>>,
+    ) -> Result<tonic::Response<T>, RpcError> {
+        self.call_endpoint_with_status(endpoint, call)
+            .await
+            .map_err(|err| RpcError::RequestError(endpoint.to_string(), err.to_string()))
+    }
+
+    /// Same as [`TonicRpcClient::call_endpoint`], but returns the [`tonic::Status`] of a failed
+    /// call so that it can be inspected by the caller.
+    async fn call_endpoint_with_status<T>(
+        &self,
+        endpoint: NodeRpcClientEndpoint,
+        call: impl Future<Output = Result<tonic::Response<T>, tonic::Status>>,
+    ) -> Result<tonic::Response<T>, tonic::Status> {
+        let start = chrono::Utc::now();
+        let result = call.await;
+        let duration = (chrono::Utc::now() - start).to_std().unwrap_or_default();
+
+        let outcome = if result.is_ok() {
+            RpcCallOutcome::Success
+        } else {
+            RpcCallOutcome::Failure
+        };
+        self.metrics.record_call(endpoint, duration, outcome);
+
+        result
+    }
 }
 
 #[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
@@ -96,12 +144,13 @@ impl NodeRpcClient for TonicRpcClient {
 
         let mut rpc_api = self.ensure_connected().await?;
 
-        let api_response = rpc_api.submit_proven_transaction(request).await.map_err(|err| {
-            RpcError::RequestError(
-                NodeRpcClientEndpoint::SubmitProvenTx.to_string(),
-                err.to_string(),
+        let api_response = self
+            .call_endpoint_with_status(
+                NodeRpcClientEndpoint::SubmitProvenTx,
+                rpc_api.submit_proven_transaction(request),
             )
-        })?;
+            .await
+            .map_err(|status| RpcError::TransactionSubmissionError(submission_error(&status)))?;
 
         Ok(BlockNumber::from(api_response.into_inner().block_height))
     }
@@ -120,12 +169,12 @@ impl NodeRpcClient for TonicRpcClient {
 
         let mut rpc_api = self.ensure_connected().await?;
 
-        let api_response = rpc_api.get_block_header_by_number(request).await.map_err(|err| {
-            RpcError::RequestError(
-                NodeRpcClientEndpoint::GetBlockHeaderByNumber.to_string(),
-                err.to_string(),
+        let api_response = self
+            .call_endpoint(
+                NodeRpcClientEndpoint::GetBlockHeaderByNumber,
+                rpc_api.get_block_header_by_number(request),
             )
-        })?;
+            .await?;
 
         let response = api_response.into_inner();
 
@@ -155,6 +204,30 @@ impl NodeRpcClient for TonicRpcClient {
         Ok((block_header, mmr_proof))
     }
 
+    async fn status(&self) -> Result<NodeStatus, RpcError> {
+        let mut rpc_api = self.ensure_connected().await?;
+
+        let response = self
+            .call_endpoint(NodeRpcClientEndpoint::Status, rpc_api.status(()))
+            .await?
+            .into_inner();
+
+        let chain_tip = response
+            .store_status
+            .ok_or(RpcError::ExpectedDataMissing("StoreStatus".into()))?
+            .chain_tip
+            .into();
+
+        let (genesis_header, _) =
+            self.get_block_header_by_number(Some(BlockNumber::GENESIS), false).await?;
+
+        Ok(NodeStatus {
+            version: response.version,
+            chain_tip,
+            genesis_commitment: genesis_header.commitment(),
+        })
+    }
+
     async fn get_notes_by_id(&self, note_ids: &[NoteId]) -> Result<Vec<FetchedNote>, RpcError> {
         let request = GetNotesByIdRequest {
             note_ids: note_ids.iter().map(|id| id.inner().into()).collect(),
@@ -162,12 +235,9 @@ impl NodeRpcClient for TonicRpcClient {
 
         let mut rpc_api = self.ensure_connected().await?;
 
-        let api_response = rpc_api.get_notes_by_id(request).await.map_err(|err| {
-            RpcError::RequestError(
-                NodeRpcClientEndpoint::GetBlockHeaderByNumber.to_string(),
-                err.to_string(),
-            )
-        })?;
+        let api_response = self
+            .call_endpoint(NodeRpcClientEndpoint::GetNotesById, rpc_api.get_notes_by_id(request))
+            .await?;
 
         let response_notes = api_response
             .into_inner()
@@ -199,9 +269,9 @@ impl NodeRpcClient for TonicRpcClient {
 
         let mut rpc_api = self.ensure_connected().await?;
 
-        let response = rpc_api.sync_state(request).await.map_err(|err| {
-            RpcError::RequestError(NodeRpcClientEndpoint::SyncState.to_string(), err.to_string())
-        })?;
+        let response = self
+            .call_endpoint(NodeRpcClientEndpoint::SyncState, rpc_api.sync_state(request))
+            .await?;
         response.into_inner().try_into()
     }
 
@@ -221,12 +291,12 @@ impl NodeRpcClient for TonicRpcClient {
 
         let mut rpc_api = self.ensure_connected().await?;
 
-        let response = rpc_api.get_account_details(request).await.map_err(|err| {
-            RpcError::RequestError(
-                NodeRpcClientEndpoint::GetAccountDetails.to_string(),
-                err.to_string(),
+        let response = self
+            .call_endpoint(
+                NodeRpcClientEndpoint::GetAccountDetails,
+                rpc_api.get_account_details(request),
             )
-        })?;
+            .await?;
         let response = response.into_inner();
         let account_info = response.details.ok_or(RpcError::ExpectedDataMissing(
             "GetAccountDetails response should have an `account`".to_string(),
@@ -296,15 +366,12 @@ impl NodeRpcClient for TonicRpcClient {
 
         let mut rpc_api = self.ensure_connected().await?;
 
-        let response = rpc_api
-            .get_account_proofs(request)
-            .await
-            .map_err(|err| {
-                RpcError::RequestError(
-                    NodeRpcClientEndpoint::GetAccountProofs.to_string(),
-                    err.to_string(),
-                )
-            })?
+        let response = self
+            .call_endpoint(
+                NodeRpcClientEndpoint::GetAccountProofs,
+                rpc_api.get_account_proofs(request),
+            )
+            .await?
             .into_inner();
 
         let mut account_proofs = Vec::with_capacity(response.account_proofs.len());
@@ -357,9 +424,9 @@ impl NodeRpcClient for TonicRpcClient {
 
         let mut rpc_api = self.ensure_connected().await?;
 
-        let response = rpc_api.sync_notes(request).await.map_err(|err| {
-            RpcError::RequestError(NodeRpcClientEndpoint::SyncNotes.to_string(), err.to_string())
-        })?;
+        let response = self
+            .call_endpoint(NodeRpcClientEndpoint::SyncNotes, rpc_api.sync_notes(request))
+            .await?;
 
         response.into_inner().try_into()
     }
@@ -377,12 +444,12 @@ impl NodeRpcClient for TonicRpcClient {
 
         let mut rpc_api = self.ensure_connected().await?;
 
-        let response = rpc_api.check_nullifiers_by_prefix(request).await.map_err(|err| {
-            RpcError::RequestError(
-                NodeRpcClientEndpoint::CheckNullifiersByPrefix.to_string(),
-                err.to_string(),
+        let response = self
+            .call_endpoint(
+                NodeRpcClientEndpoint::CheckNullifiersByPrefix,
+                rpc_api.check_nullifiers_by_prefix(request),
             )
-        })?;
+            .await?;
         let response = response.into_inner();
         let nullifiers = response
             .nullifiers
@@ -401,12 +468,12 @@ impl NodeRpcClient for TonicRpcClient {
 
         let mut rpc_api = self.ensure_connected().await?;
 
-        let response = rpc_api.check_nullifiers(request).await.map_err(|err| {
-            RpcError::RequestError(
-                NodeRpcClientEndpoint::CheckNullifiers.to_string(),
-                err.to_string(),
+        let response = self
+            .call_endpoint(
+                NodeRpcClientEndpoint::CheckNullifiers,
+                rpc_api.check_nullifiers(request),
             )
-        })?;
+            .await?;
 
         let response = response.into_inner();
         let proofs = response.proofs.iter().map(TryInto::try_into).collect::<Result<_, _>>()?;
@@ -428,12 +495,12 @@ impl NodeRpcClient for TonicRpcClient {
 
         let mut rpc_api = self.ensure_connected().await?;
 
-        let response = rpc_api.get_account_state_delta(request).await.map_err(|err| {
-            RpcError::RequestError(
-                NodeRpcClientEndpoint::GetAccountStateDelta.to_string(),
-                err.to_string(),
+        let response = self
+            .call_endpoint(
+                NodeRpcClientEndpoint::GetAccountStateDelta,
+                rpc_api.get_account_state_delta(request),
             )
-        })?;
+            .await?;
 
         let response = response.into_inner();
         let delta = AccountDelta::read_from_bytes(&response.delta.ok_or(
@@ -448,12 +515,12 @@ impl NodeRpcClient for TonicRpcClient {
 
         let mut rpc_api = self.ensure_connected().await?;
 
-        let response = rpc_api.get_block_by_number(request).await.map_err(|err| {
-            RpcError::RequestError(
-                NodeRpcClientEndpoint::GetBlockByNumber.to_string(),
-                err.to_string(),
+        let response = self
+            .call_endpoint(
+                NodeRpcClientEndpoint::GetBlockByNumber,
+                rpc_api.get_block_by_number(request),
             )
-        })?;
+            .await?;
 
         let response = response.into_inner();
         let block =
@@ -465,12 +532,132 @@ impl NodeRpcClient for TonicRpcClient {
     }
 }
 
+// HELPERS
+// ================================================================================================
+
+/// Reason reported in the [`ErrorInfo`] details of a rejected submission when some of the
+/// transaction's input notes were already consumed.
+const ALREADY_SPENT_REASON: &str = "INPUT_NOTES_ALREADY_CONSUMED";
+/// Reason reported in the [`ErrorInfo`] details of a rejected submission when the transaction
+/// expired before the node could include it.
+const EXPIRED_REASON: &str = "TRANSACTION_EXPIRED";
+
+/// Structured details of a gRPC status, encoded as a `google.rpc.Status` message.
+#[derive(Clone, PartialEq, prost::Message)]
+struct StatusDetails {
+    #[prost(message, repeated, tag = "3")]
+    details: Vec<StatusDetail>,
+}
+
+/// A single detail of a gRPC status, encoded as a `google.protobuf.Any` message.
+#[derive(Clone, PartialEq, prost::Message)]
+struct StatusDetail {
+    #[prost(string, tag = "1")]
+    type_url: String,
+    #[prost(bytes = "vec", tag = "2")]
+    value: Vec<u8>,
+}
+
+/// Describes the cause of an error, encoded as a `google.rpc.ErrorInfo` message.
+#[derive(Clone, PartialEq, prost::Message)]
+struct ErrorInfo {
+    #[prost(string, tag = "1")]
+    reason: String,
+}
+
+/// Returns the reason of the [`ErrorInfo`] included in the details of the status, if any.
+fn error_reason(status: &tonic::Status) -> Option<String> {
+    let details = <StatusDetails as prost::Message>::decode(status.details()).ok()?;
+
+    details
+        .details
+        .into_iter()
+        .find(|detail| detail.type_url.ends_with("google.rpc.ErrorInfo"))
+        .and_then(|detail| <ErrorInfo as prost::Message>::decode(detail.value.as_slice()).ok())
+        .map(|info| info.reason)
+}
+
+/// Classifies the status returned by the node when it doesn't accept a submitted transaction.
+///
+/// Statuses whose code signals the node is unavailable or overloaded are retryable. Otherwise,
+/// the reason is taken from the [`ErrorInfo`] in the status details. Any other status is treated
+/// as a permanent rejection.
+fn submission_error(status: &tonic::Status) -> SubmissionError {
+    let message = status.message().to_string();
+
+    match status.code() {
+        tonic::Code::Unavailable
+        | tonic::Code::DeadlineExceeded
+        | tonic::Code::ResourceExhausted
+        | tonic::Code::Aborted => SubmissionError::Temporary(message),
+        _ => match error_reason(status).as_deref() {
+            Some(ALREADY_SPENT_REASON) => SubmissionError::AlreadySpent(message),
+            Some(EXPIRED_REASON) => SubmissionError::Expired(message),
+            _ => SubmissionError::Rejected(message),
+        },
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use std::boxed::Box;
+    use std::{boxed::Box, string::ToString, vec};
+
+    use prost::Message;
+
+    use super::{
+        ALREADY_SPENT_REASON, EXPIRED_REASON, ErrorInfo, StatusDetail, StatusDetails,
+        TonicRpcClient, submission_error,
+    };
+    use crate::rpc::{Endpoint, NodeRpcClient, SubmissionError};
+
+    /// Builds a status whose details include an `ErrorInfo` with the provided reason.
+    fn status_with_reason(code: tonic::Code, message: &str, reason: &str) -> tonic::Status {
+        let error_info = ErrorInfo { reason: reason.to_string() };
+        let details = StatusDetails {
+            details: vec![StatusDetail {
+                type_url: "type.googleapis.com/google.rpc.ErrorInfo".to_string(),
+                value: error_info.encode_to_vec(),
+            }],
+        };
+
+        tonic::Status::with_details(code, message, details.encode_to_vec().into())
+    }
 
-    use super::TonicRpcClient;
-    use crate::rpc::{Endpoint, NodeRpcClient};
+    #[test]
+    fn submission_errors_are_classified() {
+        let status = status_with_reason(
+            tonic::Code::InvalidArgument,
+            "transaction verification failed",
+            ALREADY_SPENT_REASON,
+        );
+        assert!(matches!(submission_error(&status), SubmissionError::AlreadySpent(_)));
+
+        let status = status_with_reason(
+            tonic::Code::InvalidArgument,
+            "transaction verification failed",
+            EXPIRED_REASON,
+        );
+        assert!(matches!(submission_error(&status), SubmissionError::Expired(_)));
+
+        // The message isn't used to identify the reason
+        let status = tonic::Status::invalid_argument("Input notes are already consumed: [0x12]");
+        assert!(matches!(submission_error(&status), SubmissionError::Rejected(_)));
+
+        let status =
+            status_with_reason(tonic::Code::InvalidArgument, "invalid proof", "INVALID_PROOF");
+        assert!(matches!(submission_error(&status), SubmissionError::Rejected(_)));
+
+        // Statuses without a known code or reason aren't retried
+        let status = tonic::Status::internal("Internal error");
+        let error = submission_error(&status);
+        assert!(matches!(error, SubmissionError::Rejected(_)));
+        assert!(!error.is_retryable());
+
+        let status = tonic::Status::unavailable("mempool is full");
+        let error = submission_error(&status);
+        assert!(matches!(error, SubmissionError::Temporary(_)));
+        assert!(error.is_retryable());
+    }
 
     fn assert_send_sync<T: Send + Sync>() {}
 
@@ -0,0 +1,216 @@
+//! Provides a portable archive of the client's store, to back it up or to migrate it to a store
+//! with a different backend.
+
+use alloc::{string::ToString, vec::Vec};
+
+use miden_objects::{Word, account::Account, block::BlockNumber};
+use miden_tx::utils::{
+    ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable, SliceReader,
+};
+
+use super::{InputNoteRecord, NoteFilter, OutputNoteRecord, StoreError, TransactionFilter};
+use crate::{
+    Client, ClientError,
+    note::NoteUpdateTracker,
+    sync::{BlockUpdates, MmrState, NoteTagRecord, StateSyncUpdate},
+    transaction::TransactionRecord,
+};
+
+/// Bytes that identify a store archive.
+const STORE_ARCHIVE_MAGIC: &[u8; 4] = b"mcsa";
+
+/// Version of the store archive format produced by [`Client::export_store_archive`]. Archives
+/// with a different version can't be imported.
+pub const STORE_ARCHIVE_VERSION: u16 = 1;
+
+/// Store archive import and export methods.
+impl Client {
+    /// Exports the contents of the client's store to a portable archive, which can be restored
+    /// into an empty store with [`Client::import_store_archive`], regardless of the store's
+    /// backend.
+    ///
+    /// The archive contains:
+    /// - The latest state of each tracked account, along with its seed.
+    /// - All input and output notes, and all transactions.
+    /// - The tracked note tags.
+    /// - The client's view of the chain MMR (see [`Client::export_mmr_state`]).
+    ///
+    /// Past account states and cached data (foreign account code and token metadata) aren't
+    /// included.
+    pub async fn export_store_archive(&self) -> Result<Vec<u8>, ClientError> {
+        let mut accounts = Vec::new();
+        for account_id in self.store.get_account_ids().await? {
+            let account_record = self.try_get_account(account_id).await?;
+            accounts.push((account_record.account().clone(), account_record.seed().copied()));
+        }
+
+        let archive = StoreArchive {
+            accounts,
+            input_notes: self.store.get_input_notes(NoteFilter::All).await?,
+            output_notes: self.store.get_output_notes(NoteFilter::All).await?,
+            transactions: self.store.get_transactions(TransactionFilter::All).await?,
+            note_tags: self.store.get_note_tags().await?,
+            mmr_state: self.build_mmr_state().await?,
+        };
+
+        let mut bytes = STORE_ARCHIVE_MAGIC.to_vec();
+        bytes.write_u16(STORE_ARCHIVE_VERSION);
+        archive.write_into(&mut bytes);
+
+        Ok(bytes)
+    }
+
+    /// Restores an archive previously exported with [`Client::export_store_archive`] into the
+    /// client's store, which must not hold any data besides the genesis block header.
+    ///
+    /// The whole archive is decoded before the store is modified, and its contents are imported
+    /// in a single operation (see [`Store::import_archive`](super::Store::import_archive)), so a
+    /// failed import leaves the store unchanged and can be retried.
+    ///
+    /// Locked accounts are restored as regular accounts, and get locked again on the next sync if
+    /// their state still doesn't match the network's.
+    ///
+    /// # Errors
+    ///
+    /// - Returns a [`ClientError::StoreArchiveImportError`] if `archive` isn't a store archive, if
+    ///   its version isn't [`STORE_ARCHIVE_VERSION`], or if the client's store isn't empty.
+    /// - Returns a [`ClientError::DataDeserializationError`] if the contents of the archive can't
+    ///   be deserialized, or if there are bytes left after them.
+    pub async fn import_store_archive(&mut self, archive: &[u8]) -> Result<(), ClientError> {
+        let Some(archive) = archive.strip_prefix(STORE_ARCHIVE_MAGIC) else {
+            return Err(ClientError::StoreArchiveImportError("not a store archive".to_string()));
+        };
+
+        let mut reader = SliceReader::new(archive);
+        let version = reader.read_u16()?;
+        if version != STORE_ARCHIVE_VERSION {
+            return Err(ClientError::StoreArchiveImportError(format!(
+                "unsupported archive version {version}, expected version {STORE_ARCHIVE_VERSION}"
+            )));
+        }
+
+        let store_archive = StoreArchive::read_from(&mut reader)?;
+        if reader.has_more_bytes() {
+            return Err(DeserializationError::UnconsumedBytes.into());
+        }
+
+        self.store.import_archive(store_archive.into()).await.map_err(|err| match err {
+            StoreError::StoreNotEmpty => ClientError::StoreArchiveImportError(
+                "the store must be empty to import an archive".to_string(),
+            ),
+            err => err.into(),
+        })
+    }
+}
+
+// STORE ARCHIVE IMPORT
+// ================================================================================================
+
+/// Contents of a store archive, as they are imported into a store by
+/// [`Store::import_archive`](super::Store::import_archive).
+pub struct StoreArchiveImport {
+    /// Latest state of the archived accounts, along with their seeds.
+    pub accounts: Vec<(Account, Option<Word>)>,
+    /// Update that stores the archived notes along with the chain data they reference, and sets
+    /// the sync height.
+    pub state_sync_update: StateSyncUpdate,
+    /// The archived transactions.
+    pub transactions: Vec<TransactionRecord>,
+    /// The archived note tags.
+    pub note_tags: Vec<NoteTagRecord>,
+}
+
+impl From<StoreArchive> for StoreArchiveImport {
+    fn from(archive: StoreArchive) -> Self {
+        let StoreArchive {
+            accounts,
+            input_notes,
+            output_notes,
+            transactions,
+            note_tags,
+            mmr_state,
+        } = archive;
+
+        let MmrState { block_headers, authentication_nodes } = mmr_state;
+        let block_num = block_headers
+            .last()
+            .map_or(BlockNumber::GENESIS, |(block_header, ..)| block_header.block_num());
+
+        // Notes go through the same path as a sync update, so that each backend stores them along
+        // with the chain data they reference
+        let state_sync_update = StateSyncUpdate {
+            block_num,
+            block_updates: BlockUpdates::new(block_headers, authentication_nodes),
+            note_updates: NoteUpdateTracker::for_transaction_updates(
+                input_notes,
+                Vec::new(),
+                output_notes,
+            ),
+            ..Default::default()
+        };
+
+        Self {
+            accounts,
+            state_sync_update,
+            transactions,
+            note_tags,
+        }
+    }
+}
+
+// STORE ARCHIVE
+// ================================================================================================
+
+/// Serializable contents of the client's store.
+struct StoreArchive {
+    /// Latest state of the tracked accounts, along with their seeds.
+    accounts: Vec<(Account, Option<Word>)>,
+    input_notes: Vec<InputNoteRecord>,
+    output_notes: Vec<OutputNoteRecord>,
+    transactions: Vec<TransactionRecord>,
+    note_tags: Vec<NoteTagRecord>,
+    mmr_state: MmrState,
+}
+
+impl Serializable for StoreArchive {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_usize(self.accounts.len());
+        for (account, seed) in &self.accounts {
+            account.write_into(target);
+            seed.write_into(target);
+        }
+
+        self.input_notes.write_into(target);
+        self.output_notes.write_into(target);
+        self.transactions.write_into(target);
+        self.note_tags.write_into(target);
+        self.mmr_state.write_into(target);
+    }
+}
+
+impl Deserializable for StoreArchive {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let num_accounts = source.read_usize()?;
+        let mut accounts = Vec::new();
+        for _ in 0..num_accounts {
+            let account = Account::read_from(source)?;
+            let seed = Option::<Word>::read_from(source)?;
+            accounts.push((account, seed));
+        }
+
+        let input_notes = Vec::<InputNoteRecord>::read_from(source)?;
+        let output_notes = Vec::<OutputNoteRecord>::read_from(source)?;
+        let transactions = Vec::<TransactionRecord>::read_from(source)?;
+        let note_tags = Vec::<NoteTagRecord>::read_from(source)?;
+        let mmr_state = MmrState::read_from(source)?;
+
+        Ok(Self {
+            accounts,
+            input_notes,
+            output_notes,
+            transactions,
+            note_tags,
+            mmr_state,
+        })
+    }
+}
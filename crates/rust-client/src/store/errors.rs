@@ -3,6 +3,7 @@ use alloc::string::String;
 use miden_objects::{
     AccountError, AccountIdError, AssetVaultError, Digest, NoteError, TransactionScriptError,
     account::AccountId,
+    block::BlockNumber,
     crypto::merkle::MmrError,
     utils::{DeserializationError, HexParseError},
 };
@@ -32,10 +33,14 @@ pub enum StoreError {
     AccountCommitmentAlreadyExists(Digest),
     #[error("account commitment mismatch for account {0}")]
     AccountCommitmentMismatch(AccountId),
+    #[error("account {0} changed after block {1}, whose state can't be restored")]
+    AccountChangedAfterBlock(AccountId, BlockNumber),
     #[error("public key {0} not found")]
     AccountKeyNotFound(String),
     #[error("account storage data with root {0} not found")]
     AccountStorageNotFound(Digest),
+    #[error("block header for block {0} not found")]
+    BlockHeaderNotFound(BlockNumber),
     #[error("partial blockchain node at index {0} not found")]
     PartialBlockchainNodeNotFound(u64),
     #[error("error deserializing data from the store")]
@@ -56,6 +61,8 @@ pub enum StoreError {
     ParsingError(String),
     #[error("failed to retrieve data from the database: {0}")]
     QueryError(String),
+    #[error("the store holds data besides the genesis block")]
+    StoreNotEmpty,
     #[error("error instantiating transaction script")]
     TransactionScriptError(#[from] TransactionScriptError),
     #[error("account vault data for root {0} not found")]
@@ -30,13 +30,17 @@ use core::fmt::Debug;
 use miden_objects::{
     Digest, Word,
     account::{Account, AccountCode, AccountHeader, AccountId},
+    asset::Asset,
     block::{BlockHeader, BlockNumber},
     crypto::merkle::{InOrderIndex, MmrPeaks},
     note::{NoteId, NoteTag, Nullifier},
-    transaction::TransactionId,
+    transaction::{OutputNote, TransactionId},
 };
 
+#[cfg(any(feature = "sqlite", feature = "idxdb"))]
+use crate::transaction::{DiscardCause, TransactionStatus};
 use crate::{
+    account::TokenMetadata,
     sync::{NoteTagRecord, StateSyncUpdate},
     transaction::{TransactionRecord, TransactionStoreUpdate},
 };
@@ -63,6 +67,8 @@ pub mod web_store;
 
 mod account;
 pub use account::{AccountRecord, AccountStatus, AccountUpdates};
+mod archive;
+pub use archive::{STORE_ARCHIVE_VERSION, StoreArchiveImport};
 mod note_record;
 pub use note_record::{
     InputNoteRecord, InputNoteState, NoteExportType, NoteRecordError, OutputNoteRecord,
@@ -89,10 +95,16 @@ pub trait Store: Send + Sync {
     /// Unix epoch. If the store implementation is incapable of tracking time, it should return
     /// `None`.
     ///
-    /// This method is used to add time metadata to notes' states. This information doesn't have a
-    /// functional impact on the client's operation, it's shown to the user for informational
-    /// purposes.
-    fn get_current_timestamp(&self) -> Option<u64>;
+    /// The client no longer uses this method, as its timestamps come from the [`Clock`] it's
+    /// built with.
+    ///
+    /// [`Clock`]: crate::clock::Clock
+    #[deprecated(
+        note = "the client's timestamps come from its `Clock`, see `ClientBuilder::with_clock`"
+    )]
+    fn get_current_timestamp(&self) -> Option<u64> {
+        None
+    }
 
     // TRANSACTIONS
     // --------------------------------------------------------------------------------------------
@@ -103,6 +115,39 @@ pub trait Store: Send + Sync {
         filter: TransactionFilter,
     ) -> Result<Vec<TransactionRecord>, StoreError>;
 
+    /// Retrieves the input notes consumed and the output notes created by the specified
+    /// transaction. Input notes are matched by the nullifiers recorded in the transaction and
+    /// output notes by their IDs, so only notes tracked by the store are returned. If the
+    /// transaction isn't tracked, both lists are empty.
+    ///
+    /// The default implementation of this method uses [`Store::get_transactions`],
+    /// [`Store::get_input_notes_by_nullifier`] and [`Store::get_output_notes`].
+    async fn get_transaction_notes(
+        &self,
+        transaction_id: TransactionId,
+    ) -> Result<(Vec<InputNoteRecord>, Vec<OutputNoteRecord>), StoreError> {
+        let Some(transaction) =
+            self.get_transactions(TransactionFilter::Ids(vec![transaction_id])).await?.pop()
+        else {
+            return Ok((Vec::new(), Vec::new()));
+        };
+
+        let nullifiers: Vec<Nullifier> = transaction
+            .details
+            .input_note_nullifiers
+            .iter()
+            .copied()
+            .map(Nullifier::from)
+            .collect();
+        let output_note_ids: Vec<NoteId> =
+            transaction.details.output_notes.iter().map(OutputNote::id).collect();
+
+        let input_notes = self.get_input_notes_by_nullifier(&nullifiers).await?;
+        let output_notes = self.get_output_notes(NoteFilter::List(output_note_ids)).await?;
+
+        Ok((input_notes, output_notes))
+    }
+
     /// Applies a transaction, atomically updating the current state based on the
     /// [`TransactionStoreUpdate`].
     ///
@@ -115,6 +160,25 @@ pub trait Store: Send + Sync {
     /// - Inserting the transaction into the store to track.
     async fn apply_transaction(&self, tx_update: TransactionStoreUpdate) -> Result<(), StoreError>;
 
+    /// Inserts the provided transaction records into the store. If a transaction with the same ID
+    /// already exists, it will be replaced.
+    ///
+    /// Unlike [`Store::apply_transaction`], the accounts and notes related to the transactions
+    /// aren't modified.
+    async fn upsert_transactions(
+        &self,
+        transactions: &[TransactionRecord],
+    ) -> Result<(), StoreError>;
+
+    /// Removes the transactions with the specified IDs from the store and updates the provided
+    /// input notes, which were released by the removed transactions. Both changes are applied
+    /// atomically. IDs of transactions that aren't tracked are ignored.
+    async fn remove_transactions(
+        &self,
+        transaction_ids: &[TransactionId],
+        released_notes: &[InputNoteRecord],
+    ) -> Result<(), StoreError>;
+
     // NOTES
     // --------------------------------------------------------------------------------------------
 
@@ -139,6 +203,17 @@ pub trait Store: Send + Sync {
             .collect::<Result<Vec<_>, _>>()
     }
 
+    /// Retrieves the input notes with the provided nullifiers from the store. Nullifiers that
+    /// don't match any tracked input note are ignored.
+    ///
+    /// The default implementation of this method uses [`Store::get_input_notes`].
+    async fn get_input_notes_by_nullifier(
+        &self,
+        nullifiers: &[Nullifier],
+    ) -> Result<Vec<InputNoteRecord>, StoreError> {
+        self.get_input_notes(NoteFilter::Nullifiers(nullifiers.to_vec())).await
+    }
+
     /// Inserts the provided input notes into the database. If a note with the same ID already
     /// exists, it will be replaced.
     async fn upsert_input_notes(&self, notes: &[InputNoteRecord]) -> Result<(), StoreError>;
@@ -249,6 +324,14 @@ pub trait Store: Send + Sync {
     async fn get_account(&self, account_id: AccountId)
     -> Result<Option<AccountRecord>, StoreError>;
 
+    /// Retrieves the assets in the vault of the account's latest state, without loading the rest
+    /// of the account (code and storage).
+    ///
+    /// # Errors
+    ///
+    /// Returns a `StoreError::AccountDataNotFound` if there is no account for the provided ID.
+    async fn get_account_assets(&self, account_id: AccountId) -> Result<Vec<Asset>, StoreError>;
+
     /// Inserts an [`Account`] along with the seed used to create it.
     async fn insert_account(
         &self,
@@ -270,6 +353,20 @@ pub trait Store: Send + Sync {
         account_ids: Vec<AccountId>,
     ) -> Result<BTreeMap<AccountId, AccountCode>, StoreError>;
 
+    /// Upserts the [`TokenMetadata`] of a fungible faucet. This value will be used as a cache to
+    /// avoid reading the faucet's storage on every lookup.
+    async fn upsert_token_metadata(
+        &self,
+        faucet_id: AccountId,
+        metadata: TokenMetadata,
+    ) -> Result<(), StoreError>;
+
+    /// Retrieves the cached [`TokenMetadata`] of a fungible faucet, if any.
+    async fn get_token_metadata(
+        &self,
+        faucet_id: AccountId,
+    ) -> Result<Option<TokenMetadata>, StoreError>;
+
     /// Updates an existing [`Account`] with a new state.
     ///
     /// # Errors
@@ -315,6 +412,50 @@ pub trait Store: Send + Sync {
     /// - Storing new MMR authentication nodes.
     /// - Updating the tracked public accounts.
     async fn apply_state_sync(&self, state_sync_update: StateSyncUpdate) -> Result<(), StoreError>;
+
+    /// Reverts the store to the state it had when it was synced up to `block_num`, discarding the
+    /// changes made by later blocks that are no longer part of the canonical chain. A rollback
+    /// involves:
+    ///
+    /// - Setting the sync height to `block_num`. Its block header must be in the store.
+    /// - Removing the block headers after `block_num` and the MMR authentication nodes that aren't
+    ///   part of the chain up to `block_num`.
+    /// - Reverting input and output notes that were committed or consumed after `block_num`. Notes
+    ///   that go back to the expected state have their tags tracked again.
+    ///
+    /// Account states aren't reverted. Because of that, the rollback fails with
+    /// [`StoreError::AccountChangedAfterBlock`] if a tracked account has a transaction committed
+    /// after `block_num`, and the store is left unchanged. The states of public accounts that were
+    /// updated from the network are kept as they are.
+    async fn rollback_to(&self, block_num: BlockNumber) -> Result<(), StoreError>;
+
+    // ARCHIVE
+    // --------------------------------------------------------------------------------------------
+
+    /// Imports the contents of a store archive (see [`crate::Client::import_store_archive`]).
+    ///
+    /// The store must not hold any data besides the genesis block header. Either the whole archive
+    /// is imported or, if the import fails, the store is left unchanged.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StoreError::StoreNotEmpty`] if the store holds any other data.
+    async fn import_archive(&self, archive: StoreArchiveImport) -> Result<(), StoreError>;
+
+    // COUNTS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the number of accounts, input notes (by state), output notes and transactions in
+    /// the store, without loading the records themselves.
+    async fn counts(&self) -> Result<StoreCounts, StoreError>;
+
+    /// Returns each unique note tag that the client is interested in along with the number of
+    /// input notes that have it, without loading the notes themselves.
+    ///
+    /// Every stored note with the tag is counted, which includes the notes kept because of a
+    /// [`TagSubscription::Prefix`](crate::sync::TagSubscription::Prefix) subscription that aren't
+    /// relevant to any tracked account. Tags that don't match any note have a count of 0.
+    async fn get_note_tag_counts(&self) -> Result<BTreeMap<NoteTag, usize>, StoreError>;
 }
 
 // PARTIAL BLOCKCHAIN NODE FILTER
@@ -329,6 +470,55 @@ pub enum PartialBlockchainFilter {
     List(Vec<InOrderIndex>),
 }
 
+/// Returns whether the MMR node with the given in-order index is part of an MMR with `forest`
+/// leaves.
+#[cfg(any(feature = "sqlite", feature = "idxdb"))]
+pub(crate) fn is_partial_blockchain_node_in_forest(index: InOrderIndex, forest: u64) -> bool {
+    let index: u64 = index.inner();
+    let depth = index.trailing_zeros();
+    let first_leaf = (index >> (depth + 1)) << depth;
+
+    // Trees of the MMR are sorted by decreasing size, so the ones with at least 2^depth leaves
+    // cover the first `(forest >> depth) << depth` leaves
+    first_leaf + (1 << depth) <= (forest >> depth) << depth
+}
+
+/// Returns the first of the `tracked_accounts` with a transaction committed after `block_num`,
+/// which prevents the store from being rolled back to that block.
+#[cfg(any(feature = "sqlite", feature = "idxdb"))]
+pub(crate) fn account_changed_after(
+    transactions: &[TransactionRecord],
+    tracked_accounts: &[AccountId],
+    block_num: BlockNumber,
+) -> Option<AccountId> {
+    transactions
+        .iter()
+        .filter(|transaction| {
+            matches!(
+                transaction.status,
+                TransactionStatus::Committed(commit_height) if commit_height > block_num
+            )
+        })
+        .map(|transaction| transaction.details.account_id)
+        .find(|account_id| tracked_accounts.contains(account_id))
+}
+
+/// Returns whether the transaction expired at or before the provided block number without being
+/// committed, as matched by [`TransactionFilter::Expired`]. Transactions discarded for a reason
+/// other than their expiration are not considered expired.
+#[cfg(any(feature = "sqlite", feature = "idxdb"))]
+pub(crate) fn is_expired_transaction(
+    transaction: &TransactionRecord,
+    block_num: BlockNumber,
+) -> bool {
+    let uncommitted = match transaction.status {
+        TransactionStatus::Pending | TransactionStatus::Discarded(DiscardCause::Expired) => true,
+        TransactionStatus::Committed(_) | TransactionStatus::Discarded(_) => false,
+    };
+
+    uncommitted && transaction.details.expiration_block_num <= block_num
+}
+
 // TRANSACTION FILTERS
 // ================================================================================================
 
@@ -349,6 +539,10 @@ pub enum TransactionFilter {
     /// A transaction is considered expired if is uncommitted and the transaction's block number
     /// is less than the provided block number.
     ExpiredBefore(BlockNumber),
+    /// Return a list of the uncommitted transactions whose expiration block is at or before the
+    /// provided [`BlockNumber`]. Transactions discarded for a cause other than expiration are not
+    /// considered.
+    Expired(BlockNumber),
 }
 
 // NOTE FILTER
@@ -385,3 +579,74 @@ pub enum NoteFilter {
     /// to output notes.
     Unverified,
 }
+
+// STORE COUNTS
+// ================================================================================================
+
+/// Number of records of each type in the store, as returned by [`Store::counts`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StoreCounts {
+    /// Number of tracked accounts. Each account is counted once, regardless of how many of its
+    /// states are stored.
+    pub accounts: usize,
+    /// Number of input notes in each state.
+    pub input_notes: InputNoteCounts,
+    /// Number of output notes.
+    pub output_notes: usize,
+    /// Number of transactions.
+    pub transactions: usize,
+}
+
+/// Number of input notes in each state, as part of [`StoreCounts`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct InputNoteCounts {
+    /// Number of notes in the [`InputNoteState::Expected`] state.
+    pub expected: usize,
+    /// Number of notes in the [`InputNoteState::Unverified`] state.
+    pub unverified: usize,
+    /// Number of notes in the [`InputNoteState::Committed`] state.
+    pub committed: usize,
+    /// Number of notes in the [`InputNoteState::Invalid`] state.
+    pub invalid: usize,
+    /// Number of notes that are being consumed by a local transaction, either authenticated or
+    /// not.
+    pub processing: usize,
+    /// Number of notes that were consumed, either by a local transaction or externally.
+    pub consumed: usize,
+}
+
+impl InputNoteCounts {
+    /// Returns the total number of input notes.
+    pub fn total(&self) -> usize {
+        self.expected
+            + self.unverified
+            + self.committed
+            + self.invalid
+            + self.processing
+            + self.consumed
+    }
+
+    /// Adds `count` notes to the counter of the state with the provided discriminant.
+    #[cfg(any(feature = "sqlite", feature = "idxdb"))]
+    pub(crate) fn add(&mut self, state_discriminant: u8, count: usize) -> Result<(), StoreError> {
+        let counter = match state_discriminant {
+            InputNoteState::STATE_EXPECTED => &mut self.expected,
+            InputNoteState::STATE_UNVERIFIED => &mut self.unverified,
+            InputNoteState::STATE_COMMITTED => &mut self.committed,
+            InputNoteState::STATE_INVALID => &mut self.invalid,
+            InputNoteState::STATE_PROCESSING_AUTHENTICATED
+            | InputNoteState::STATE_PROCESSING_UNAUTHENTICATED => &mut self.processing,
+            InputNoteState::STATE_CONSUMED_AUTHENTICATED_LOCAL
+            | InputNoteState::STATE_CONSUMED_UNAUTHENTICATED_LOCAL
+            | InputNoteState::STATE_CONSUMED_EXTERNAL => &mut self.consumed,
+            _ => {
+                return Err(StoreError::ParsingError(format!(
+                    "invalid input note state discriminant {state_discriminant}"
+                )));
+            },
+        };
+
+        *counter += count;
+        Ok(())
+    }
+}
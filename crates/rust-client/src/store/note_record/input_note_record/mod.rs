@@ -4,7 +4,9 @@ use miden_objects::{
     Digest,
     account::AccountId,
     block::{BlockHeader, BlockNumber},
-    note::{Note, NoteAssets, NoteDetails, NoteId, NoteInclusionProof, NoteMetadata, Nullifier},
+    note::{
+        Note, NoteAssets, NoteDetails, NoteId, NoteInclusionProof, NoteMetadata, NoteTag, Nullifier,
+    },
     transaction::{InputNote, TransactionId},
     utils::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable},
 };
@@ -86,6 +88,12 @@ impl InputNoteRecord {
         self.state.metadata()
     }
 
+    /// Returns the note's tag, which will be available depending on the note's current state.
+    /// Expected notes without metadata have the tag they were imported with, if any.
+    pub fn tag(&self) -> Option<NoteTag> {
+        self.state.tag()
+    }
+
     /// Returns the note nullifier.
     pub fn nullifier(&self) -> Nullifier {
         self.details.nullifier()
@@ -240,6 +248,116 @@ impl InputNoteRecord {
             Ok(false)
         }
     }
+
+    /// Modifies the state of the note record to undo the changes made by the blocks after
+    /// `block_num`, which are no longer part of the canonical chain. Returns `true` if the state
+    /// was changed.
+    ///
+    /// Notes that were included after `block_num` lose their inclusion proof and notes that were
+    /// consumed after it go back to being processed. The state of notes consumed externally
+    /// before being consumed isn't known, so they go back to the expected state.
+    #[cfg(any(feature = "sqlite", feature = "idxdb"))]
+    pub(crate) fn rolled_back_to(&mut self, block_num: BlockNumber) -> bool {
+        let is_rolled_back = |inclusion_proof: &NoteInclusionProof| {
+            inclusion_proof.location().block_num() > block_num
+        };
+
+        let state = match &self.state {
+            InputNoteState::ConsumedAuthenticatedLocal(state)
+                if BlockNumber::from(state.nullifier_block_height) > block_num =>
+            {
+                ProcessingAuthenticatedNoteState {
+                    metadata: state.metadata,
+                    inclusion_proof: state.inclusion_proof.clone(),
+                    block_note_root: state.block_note_root,
+                    submission_data: state.submission_data,
+                }
+                .into()
+            },
+            InputNoteState::ConsumedUnauthenticatedLocal(state)
+                if BlockNumber::from(state.nullifier_block_height) > block_num =>
+            {
+                ProcessingUnauthenticatedNoteState {
+                    metadata: state.metadata,
+                    after_block_num: BlockNumber::GENESIS,
+                    submission_data: state.submission_data,
+                }
+                .into()
+            },
+            InputNoteState::ConsumedExternal(state)
+                if BlockNumber::from(state.nullifier_block_height) > block_num =>
+            {
+                ExpectedNoteState {
+                    metadata: None,
+                    after_block_num: BlockNumber::GENESIS,
+                    tag: None,
+                }
+                .into()
+            },
+            state => state.clone(),
+        };
+
+        let state = match state {
+            InputNoteState::Unverified(UnverifiedNoteState { metadata, inclusion_proof })
+            | InputNoteState::Committed(CommittedNoteState { metadata, inclusion_proof, .. })
+            | InputNoteState::Invalid(InvalidNoteState {
+                metadata,
+                invalid_inclusion_proof: inclusion_proof,
+                ..
+            }) if is_rolled_back(&inclusion_proof) => ExpectedNoteState {
+                metadata: Some(metadata),
+                after_block_num: block_num,
+                tag: Some(metadata.tag()),
+            }
+            .into(),
+            InputNoteState::ProcessingAuthenticated(state)
+                if is_rolled_back(&state.inclusion_proof) =>
+            {
+                ProcessingUnauthenticatedNoteState {
+                    metadata: state.metadata,
+                    after_block_num: block_num,
+                    submission_data: state.submission_data,
+                }
+                .into()
+            },
+            state => state,
+        };
+
+        if state == self.state {
+            false
+        } else {
+            self.state = state;
+            true
+        }
+    }
+
+    /// Modifies the state of the note record to reflect that the transaction with ID
+    /// `transaction_id`, which was processing the note, won't be committed. The note goes back to
+    /// the state it was in before being processed so that it can be consumed again. Returns `true`
+    /// if the state was changed.
+    pub(crate) fn consumption_discarded(&mut self, transaction_id: TransactionId) -> bool {
+        if self.consumer_transaction_id() != Some(&transaction_id) {
+            return false;
+        }
+
+        self.state = match &self.state {
+            InputNoteState::ProcessingAuthenticated(state) => CommittedNoteState {
+                metadata: state.metadata,
+                inclusion_proof: state.inclusion_proof.clone(),
+                block_note_root: state.block_note_root,
+            }
+            .into(),
+            InputNoteState::ProcessingUnauthenticated(state) => ExpectedNoteState {
+                metadata: Some(state.metadata),
+                after_block_num: state.after_block_num,
+                tag: Some(state.metadata.tag()),
+            }
+            .into(),
+            _ => return false,
+        };
+
+        true
+    }
 }
 
 // SERIALIZATION
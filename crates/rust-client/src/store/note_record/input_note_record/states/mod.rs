@@ -5,7 +5,7 @@ use chrono::{Local, TimeZone};
 use miden_objects::{
     account::AccountId,
     block::BlockHeader,
-    note::{NoteId, NoteInclusionProof, NoteMetadata},
+    note::{NoteId, NoteInclusionProof, NoteMetadata, NoteTag},
     transaction::TransactionId,
 };
 pub use miden_tx::utils::{
@@ -89,6 +89,16 @@ impl InputNoteState {
         self.inner().metadata()
     }
 
+    /// Returns the note's tag, taken from its metadata or, for expected notes without metadata,
+    /// from the tag they were imported with.
+    pub(crate) fn tag(&self) -> Option<NoteTag> {
+        match (self.metadata(), self) {
+            (Some(metadata), _) => Some(metadata.tag()),
+            (None, InputNoteState::Expected(state)) => state.tag,
+            _ => None,
+        }
+    }
+
     pub(crate) fn inclusion_proof(&self) -> Option<&NoteInclusionProof> {
         self.inner().inclusion_proof()
     }
@@ -53,6 +53,9 @@ pub enum NoteRecordError {
     /// Note record isn't consumable.
     #[error("note not consumable: {0}")]
     NoteNotConsumable(String),
+    /// Serialized note exceeds the maximum note size.
+    #[error("note size of {0} bytes exceeds the maximum of {1} bytes")]
+    NoteTooLarge(usize, usize),
     /// Invalid inclusion proof.
     #[error("invalid inclusion proof")]
     InvalidInclusionProof,
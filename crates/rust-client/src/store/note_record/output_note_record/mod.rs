@@ -150,6 +150,36 @@ impl OutputNoteRecord {
             Ok(false)
         }
     }
+
+    /// Modifies the state of the note record to undo the changes made by the blocks after
+    /// `block_num`, which are no longer part of the canonical chain. Returns `true` if the state
+    /// was changed.
+    ///
+    /// Notes that were committed or consumed after `block_num` go back to the expected state.
+    #[cfg(any(feature = "sqlite", feature = "idxdb"))]
+    pub(crate) fn rolled_back_to(&mut self, block_num: BlockNumber) -> bool {
+        let new_state = match &self.state {
+            OutputNoteState::Consumed { block_height, recipient }
+                if BlockNumber::from(*block_height) > block_num =>
+            {
+                OutputNoteState::ExpectedFull { recipient: recipient.clone() }
+            },
+            OutputNoteState::CommittedPartial { inclusion_proof }
+                if inclusion_proof.location().block_num() > block_num =>
+            {
+                OutputNoteState::ExpectedPartial
+            },
+            OutputNoteState::CommittedFull { recipient, inclusion_proof }
+                if inclusion_proof.location().block_num() > block_num =>
+            {
+                OutputNoteState::ExpectedFull { recipient: recipient.clone() }
+            },
+            _ => return false,
+        };
+
+        self.state = new_state;
+        true
+    }
 }
 
 // CONVERSIONS
@@ -270,6 +300,37 @@ impl OutputNoteRecord {
     }
 }
 
+// SERIALIZATION
+// ================================================================================================
+
+impl Serializable for OutputNoteRecord {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.recipient_digest.write_into(target);
+        self.assets.write_into(target);
+        self.metadata.write_into(target);
+        self.state.write_into(target);
+        self.expected_height.write_into(target);
+    }
+}
+
+impl Deserializable for OutputNoteRecord {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let recipient_digest = Digest::read_from(source)?;
+        let assets = NoteAssets::read_from(source)?;
+        let metadata = NoteMetadata::read_from(source)?;
+        let state = OutputNoteState::read_from(source)?;
+        let expected_height = BlockNumber::read_from(source)?;
+
+        Ok(OutputNoteRecord::new(
+            recipient_digest,
+            assets,
+            metadata,
+            state,
+            expected_height,
+        ))
+    }
+}
+
 // OUTPUT NOTE STATE
 // ================================================================================================
 
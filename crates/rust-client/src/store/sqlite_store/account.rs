@@ -12,10 +12,11 @@ use miden_objects::{
     asset::{Asset, AssetVault},
 };
 use miden_tx::utils::{Deserializable, Serializable};
-use rusqlite::{Connection, Transaction, named_params, params, types::Value};
+use rusqlite::{Connection, OptionalExtension, Transaction, named_params, params, types::Value};
 
 use super::{SqliteStore, column_value_as_u64, u64_to_value};
 use crate::{
+    account::TokenMetadata,
     insert_sql,
     store::{AccountRecord, AccountStatus, StoreError},
     subst,
@@ -118,17 +119,33 @@ impl SqliteStore {
             .transpose()
     }
 
+    pub(crate) fn get_account_assets(
+        conn: &mut Connection,
+        account_id: AccountId,
+    ) -> Result<Vec<Asset>, StoreError> {
+        const QUERY: &str = "SELECT account_vaults.assets \
+                            FROM accounts \
+                            JOIN account_vaults ON accounts.vault_root = account_vaults.root \
+                            WHERE accounts.id = ? \
+                            ORDER BY accounts.nonce DESC \
+                            LIMIT 1";
+
+        let assets = conn
+            .prepare(QUERY)?
+            .query_row(params![account_id.to_hex()], |row| row.get::<_, Vec<u8>>(0))
+            .optional()?
+            .ok_or(StoreError::AccountDataNotFound(account_id))?;
+
+        Ok(Vec::<Asset>::read_from_bytes(&assets)?)
+    }
+
     pub(crate) fn insert_account(
         conn: &mut Connection,
         account: &Account,
         account_seed: Option<Word>,
     ) -> Result<(), StoreError> {
         let tx = conn.transaction()?;
-
-        insert_account_code(&tx, account.code())?;
-        insert_account_storage(&tx, account.storage())?;
-        insert_account_asset_vault(&tx, account.vault())?;
-        insert_account_record(&tx, account, account_seed)?;
+        insert_account_tx(&tx, account, account_seed)?;
 
         Ok(tx.commit()?)
     }
@@ -208,6 +225,33 @@ impl SqliteStore {
             })
             .collect::<Result<BTreeMap<AccountId, AccountCode>, _>>()
     }
+
+    pub fn upsert_token_metadata(
+        conn: &mut Connection,
+        faucet_id: AccountId,
+        metadata: &TokenMetadata,
+    ) -> Result<(), StoreError> {
+        const QUERY: &str = insert_sql!(token_metadata { faucet_id, metadata } | REPLACE);
+
+        conn.execute(QUERY, params![faucet_id.to_hex(), metadata.to_bytes()])?;
+        Ok(())
+    }
+
+    pub fn get_token_metadata(
+        conn: &mut Connection,
+        faucet_id: AccountId,
+    ) -> Result<Option<TokenMetadata>, StoreError> {
+        const QUERY: &str = "SELECT metadata FROM token_metadata WHERE faucet_id = ?";
+
+        conn.prepare(QUERY)?
+            .query_row(params![faucet_id.to_hex()], |row| row.get::<_, Vec<u8>>(0))
+            .optional()?
+            .map(|metadata| {
+                TokenMetadata::read_from_bytes(&metadata)
+                    .map_err(StoreError::DataDeserializationError)
+            })
+            .transpose()
+    }
 }
 
 // HELPERS
@@ -227,6 +271,19 @@ pub(crate) fn update_account(
     insert_account_record(tx, new_account_state, None)
 }
 
+/// Inserts the account, along with its code, storage and vault, within the provided database
+/// transaction.
+pub(super) fn insert_account_tx(
+    tx: &Transaction<'_>,
+    account: &Account,
+    account_seed: Option<Word>,
+) -> Result<(), StoreError> {
+    insert_account_code(tx, account.code())?;
+    insert_account_storage(tx, account.storage())?;
+    insert_account_asset_vault(tx, account.vault())?;
+    insert_account_record(tx, account, account_seed)
+}
+
 pub(super) fn insert_account_record(
     tx: &Transaction<'_>,
     account: &Account,
@@ -0,0 +1,64 @@
+use rusqlite::Connection;
+
+use super::{
+    SqliteStore,
+    account::insert_account_tx,
+    sync::{add_note_tag_tx, apply_state_sync_tx},
+    transaction::upsert_transaction_record,
+};
+use crate::store::{StoreArchiveImport, StoreError};
+
+impl SqliteStore {
+    pub(super) fn import_archive(
+        conn: &mut Connection,
+        archive: StoreArchiveImport,
+    ) -> Result<(), StoreError> {
+        // Every table that gets written to while the client is in use, except for the genesis
+        // block header, which is inserted when the client is created
+        const IS_EMPTY_QUERY: &str = "SELECT NOT (
+                EXISTS (SELECT 1 FROM accounts)
+                OR EXISTS (SELECT 1 FROM account_code)
+                OR EXISTS (SELECT 1 FROM account_storage)
+                OR EXISTS (SELECT 1 FROM account_vaults)
+                OR EXISTS (SELECT 1 FROM foreign_account_code)
+                OR EXISTS (SELECT 1 FROM input_notes)
+                OR EXISTS (SELECT 1 FROM output_notes)
+                OR EXISTS (SELECT 1 FROM notes_scripts)
+                OR EXISTS (SELECT 1 FROM transactions)
+                OR EXISTS (SELECT 1 FROM transaction_scripts)
+                OR EXISTS (SELECT 1 FROM tags)
+                OR EXISTS (SELECT 1 FROM token_metadata)
+                OR EXISTS (SELECT 1 FROM partial_blockchain_nodes)
+                OR EXISTS (SELECT 1 FROM block_headers WHERE block_num > 0)
+                OR EXISTS (SELECT 1 FROM state_sync WHERE block_num > 0)
+            )";
+
+        let StoreArchiveImport {
+            accounts,
+            state_sync_update,
+            transactions,
+            note_tags,
+        } = archive;
+
+        let tx = conn.transaction()?;
+
+        if !tx.query_row(IS_EMPTY_QUERY, [], |row| row.get::<_, bool>(0))? {
+            return Err(StoreError::StoreNotEmpty);
+        }
+
+        for (account, seed) in &accounts {
+            insert_account_tx(&tx, account, *seed)?;
+        }
+        apply_state_sync_tx(&tx, state_sync_update, None)?;
+        for transaction in &transactions {
+            upsert_transaction_record(&tx, transaction)?;
+        }
+        for tag in &note_tags {
+            add_note_tag_tx(&tx, tag)?;
+        }
+
+        tx.commit()?;
+
+        Ok(())
+    }
+}
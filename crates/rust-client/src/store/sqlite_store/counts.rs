@@ -0,0 +1,57 @@
+use alloc::{collections::BTreeMap, vec::Vec};
+
+use miden_objects::{note::NoteTag, utils::Deserializable};
+use rusqlite::Connection;
+
+use super::SqliteStore;
+use crate::store::{InputNoteCounts, StoreCounts, StoreError};
+
+impl SqliteStore {
+    pub(super) fn counts(conn: &mut Connection) -> Result<StoreCounts, StoreError> {
+        const TOTALS_QUERY: &str = "SELECT
+                (SELECT COUNT(DISTINCT id) FROM accounts),
+                (SELECT COUNT(*) FROM output_notes),
+                (SELECT COUNT(*) FROM transactions)";
+        const INPUT_NOTES_QUERY: &str =
+            "SELECT state_discriminant, COUNT(*) FROM input_notes GROUP BY state_discriminant";
+
+        let (accounts, output_notes, transactions) = conn.query_row(TOTALS_QUERY, [], |row| {
+            Ok((row.get::<_, usize>(0)?, row.get::<_, usize>(1)?, row.get::<_, usize>(2)?))
+        })?;
+
+        let mut input_notes = InputNoteCounts::default();
+        let mut stmt = conn.prepare(INPUT_NOTES_QUERY)?;
+        let mut rows = stmt.query([])?;
+        while let Some(row) = rows.next()? {
+            input_notes.add(row.get(0)?, row.get(1)?)?;
+        }
+
+        Ok(StoreCounts {
+            accounts,
+            input_notes,
+            output_notes,
+            transactions,
+        })
+    }
+
+    pub(super) fn get_note_tag_counts(
+        conn: &mut Connection,
+    ) -> Result<BTreeMap<NoteTag, usize>, StoreError> {
+        // A tag is tracked once per source, so the tags are deduplicated before being joined with
+        // the notes
+        const QUERY: &str = "SELECT tags.tag, COUNT(input_notes.note_id)
+                FROM (SELECT DISTINCT tag FROM tags) AS tags
+                LEFT JOIN input_notes ON input_notes.tag = tags.tag
+                GROUP BY tags.tag";
+
+        let mut counts = BTreeMap::new();
+        let mut stmt = conn.prepare(QUERY)?;
+        let mut rows = stmt.query([])?;
+        while let Some(row) = rows.next()? {
+            let tag: Vec<u8> = row.get(0)?;
+            counts.insert(NoteTag::read_from_bytes(&tag)?, row.get(1)?);
+        }
+
+        Ok(counts)
+    }
+}
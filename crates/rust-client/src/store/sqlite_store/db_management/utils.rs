@@ -6,9 +6,10 @@ use std::{
 
 use miden_objects::crypto::hash::blake::{Blake3_160, Blake3Digest};
 use rusqlite::{Connection, OptionalExtension, Result, ToSql, Transaction, params, types::FromSql};
-use rusqlite_migration::{M, Migrations, SchemaVersion};
+use rusqlite_migration::{HookError, M, Migrations, SchemaVersion};
 
 use super::errors::SqliteStoreError;
+use crate::store::sqlite_store::note::set_input_note_tags;
 
 // MACROS
 // ================================================================================================
@@ -58,7 +59,16 @@ macro_rules! insert_sql {
 
 type Hash = Blake3Digest<20>;
 
-const MIGRATION_SCRIPTS: [&str; 1] = [include_str!("../store.sql")];
+const MIGRATION_SCRIPTS: [&str; 6] = [
+    include_str!("../store.sql"),
+    include_str!("../migrations/0002_note_tags_index.sql"),
+    include_str!("../migrations/0003_note_tag_subscriptions.sql"),
+    include_str!("../migrations/0004_token_metadata.sql"),
+    include_str!("../migrations/0005_note_nullifier_indexes.sql"),
+    include_str!("../migrations/0006_input_note_tags.sql"),
+];
+/// Index of the migration script that adds the `tag` column to the input notes table.
+const INPUT_NOTE_TAGS_MIGRATION: usize = 5;
 static MIGRATION_HASHES: LazyLock<Vec<Hash>> = LazyLock::new(compute_migration_hashes);
 static MIGRATIONS: LazyLock<Migrations> = LazyLock::new(prepare_migrations);
 
@@ -71,6 +81,15 @@ const DB_SCHEMA_VERSION_FIELD: &str = "db-schema-version";
 
 /// Applies the migrations to the database.
 pub fn apply_migrations(conn: &mut Connection) -> Result<(), SqliteStoreError> {
+    apply_migrations_up_to(conn, MIGRATION_SCRIPTS.len())
+}
+
+/// Applies the migrations to the database, up to (and including) the `version`-th migration
+/// script.
+pub fn apply_migrations_up_to(
+    conn: &mut Connection,
+    version: usize,
+) -> Result<(), SqliteStoreError> {
     let version_before = MIGRATIONS.current_version(conn)?;
 
     if let SchemaVersion::Inside(ver) = version_before {
@@ -101,12 +120,12 @@ pub fn apply_migrations(conn: &mut Connection) -> Result<(), SqliteStoreError> {
         }
     }
 
-    MIGRATIONS.to_latest(conn)?;
+    MIGRATIONS.to_version(conn, version)?;
 
     let version_after = MIGRATIONS.current_version(conn)?;
 
     if version_before != version_after {
-        let new_hash = hex::encode(&*MIGRATION_HASHES[MIGRATION_HASHES.len() - 1]);
+        let new_hash = hex::encode(&*MIGRATION_HASHES[version - 1]);
         set_settings_value(conn, DB_MIGRATION_HASH_FIELD, &new_hash)?;
     }
 
@@ -117,7 +136,16 @@ pub fn apply_migrations(conn: &mut Connection) -> Result<(), SqliteStoreError> {
 }
 
 fn prepare_migrations() -> Migrations<'static> {
-    Migrations::new(MIGRATION_SCRIPTS.map(up).to_vec())
+    let mut migrations = MIGRATION_SCRIPTS.map(up).to_vec();
+
+    // The tags of the existing input notes can only be read from their serialized states
+    migrations[INPUT_NOTE_TAGS_MIGRATION] =
+        M::up_with_hook(MIGRATION_SCRIPTS[INPUT_NOTE_TAGS_MIGRATION], |tx: &Transaction| {
+            set_input_note_tags(tx).map_err(|err| HookError::Hook(err.to_string()))
+        })
+        .foreign_key_check();
+
+    Migrations::new(migrations)
 }
 
 fn compute_migration_hashes() -> Vec<Hash> {
@@ -0,0 +1,9 @@
+-- Remove duplicated tag records, keeping the oldest one for each tag and source
+DELETE FROM tags
+WHERE rowid NOT IN (
+    SELECT MIN(rowid) FROM tags GROUP BY tag, source
+);
+
+-- Each tag can only be tracked once per source. The index is also used when querying the unique
+-- tags, as `tag` is its leftmost column.
+CREATE UNIQUE INDEX idx_tags_tag_source ON tags(tag, source);
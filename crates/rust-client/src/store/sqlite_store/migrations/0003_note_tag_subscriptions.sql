@@ -0,0 +1,3 @@
+-- Tags tracked before subscriptions were introduced keep every received note, which is the
+-- behaviour of the `Prefix` subscription (serialized as 0)
+ALTER TABLE tags ADD COLUMN subscription BLOB NOT NULL DEFAULT X'00';
@@ -0,0 +1,6 @@
+-- Create token_metadata table, used as a cache of the metadata of fungible faucets
+CREATE TABLE token_metadata (
+    faucet_id TEXT NOT NULL,    -- ID of the fungible faucet
+    metadata BLOB NOT NULL,     -- Serialized token metadata (symbol and decimals)
+    PRIMARY KEY (faucet_id)
+);
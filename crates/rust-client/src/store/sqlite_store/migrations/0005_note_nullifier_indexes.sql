@@ -0,0 +1,4 @@
+-- Notes are looked up by nullifier when checking which tracked notes were consumed, so index the
+-- nullifier columns to avoid scanning the notes tables
+CREATE INDEX idx_input_notes_nullifier ON input_notes(nullifier);
+CREATE INDEX idx_output_notes_nullifier ON output_notes(nullifier);
@@ -0,0 +1,5 @@
+-- The tag of an input note is part of its serialized state, so it's also stored in its own column
+-- to count the notes of each tracked tag. The tags of the notes stored before this migration are
+-- set by the migration hook, which reads them from the serialized states.
+ALTER TABLE input_notes ADD COLUMN tag BLOB NULL;
+CREATE INDEX idx_input_notes_tag ON input_notes(tag);
@@ -18,40 +18,74 @@ use db_management::{
 use miden_objects::{
     Digest, Word,
     account::{Account, AccountCode, AccountHeader, AccountId},
+    asset::Asset,
     block::{BlockHeader, BlockNumber},
     crypto::merkle::{InOrderIndex, MmrPeaks},
     note::{NoteTag, Nullifier},
+    transaction::TransactionId,
 };
 use rusqlite::{Connection, types::Value};
 use tonic::async_trait;
 
 use super::{
     AccountRecord, AccountStatus, InputNoteRecord, NoteFilter, OutputNoteRecord,
-    PartialBlockchainFilter, Store, TransactionFilter,
+    PartialBlockchainFilter, Store, StoreArchiveImport, StoreCounts, TransactionFilter,
 };
 use crate::{
+    account::TokenMetadata,
     store::StoreError,
     sync::{NoteTagRecord, StateSyncUpdate},
     transaction::{TransactionRecord, TransactionStoreUpdate},
 };
 
 mod account;
+mod archive;
 mod chain_data;
+mod counts;
 mod db_management;
 mod errors;
 mod note;
 mod sync;
 mod transaction;
 
+#[cfg(feature = "test-failpoints")]
+pub use sync::SyncFailpoint;
+#[cfg(not(feature = "test-failpoints"))]
+use sync::SyncFailpoint;
+
+// SQLITE STORE CONFIG
+// ================================================================================================
+
+/// Configuration options of a [`SqliteStore`].
+#[derive(Clone, Debug, Default)]
+pub struct SqliteStoreConfig {
+    /// Maximum number of connections kept open by the store's pool. Smaller pools use less
+    /// memory, while larger ones allow more reads to run in parallel. If `None`, the pool is sized
+    /// based on the number of available CPUs.
+    pub max_connections: Option<usize>,
+}
+
+impl SqliteStoreConfig {
+    /// Sets the maximum number of connections kept open by the store's pool.
+    #[must_use]
+    pub fn with_max_connections(mut self, max_connections: usize) -> Self {
+        self.max_connections = Some(max_connections);
+        self
+    }
+}
+
 // SQLITE STORE
 // ================================================================================================
 
 /// Represents a pool of connections with an `SQLite` database. The pool is used to interact
 /// concurrently with the underlying database in a safe and efficient manner.
 ///
-/// Current table definitions can be found at `store.sql` migration file.
+/// Initial table definitions can be found at `store.sql` migration file, with later changes in the
+/// `migrations` directory.
 pub struct SqliteStore {
     pub(crate) pool: Pool,
+    #[cfg(feature = "test-failpoints")]
+    sync_failpoint: std::sync::RwLock<Option<SyncFailpoint>>,
 }
 
 impl SqliteStore {
@@ -60,10 +94,31 @@ impl SqliteStore {
 
     /// Returns a new instance of [Store] instantiated with the specified configuration options.
     pub async fn new(database_filepath: PathBuf) -> Result<Self, StoreError> {
+        Self::with_config(database_filepath, SqliteStoreConfig::default()).await
+    }
+
+    /// Returns a new instance of [Store] backed by the database at `database_filepath`, with its
+    /// connection pool sized according to `config`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`StoreError::DatabaseError`] if `config` sets a maximum of zero connections.
+    pub async fn with_config(
+        database_filepath: PathBuf,
+        config: SqliteStoreConfig,
+    ) -> Result<Self, StoreError> {
         let sqlite_pool_manager = SqlitePoolManager::new(database_filepath.clone());
-        let pool = Pool::builder(sqlite_pool_manager)
-            .build()
-            .map_err(|e| StoreError::DatabaseError(e.to_string()))?;
+        let mut pool_builder = Pool::builder(sqlite_pool_manager);
+        if let Some(max_connections) = config.max_connections {
+            if max_connections == 0 {
+                return Err(StoreError::DatabaseError(
+                    "the maximum number of connections must be at least 1".to_string(),
+                ));
+            }
+            pool_builder = pool_builder.max_size(max_connections);
+        }
+
+        let pool = pool_builder.build().map_err(|e| StoreError::DatabaseError(e.to_string()))?;
 
         let conn = pool.get().await.map_err(|e| StoreError::DatabaseError(e.to_string()))?;
 
@@ -72,7 +127,30 @@ impl SqliteStore {
             .await
             .map_err(|e| StoreError::DatabaseError(e.to_string()))?;
 
-        Ok(SqliteStore { pool })
+        Ok(SqliteStore {
+            pool,
+            #[cfg(feature = "test-failpoints")]
+            sync_failpoint: std::sync::RwLock::new(None),
+        })
+    }
+
+    /// Makes the following state syncs fail at the specified point, or removes the failpoint if
+    /// `None` is passed.
+    #[cfg(feature = "test-failpoints")]
+    pub fn set_sync_failpoint(&self, failpoint: Option<SyncFailpoint>) {
+        *self.sync_failpoint.write().expect("failpoint lock shouldn't be poisoned") = failpoint;
+    }
+
+    /// Returns the point at which state syncs should fail, if any.
+    fn sync_failpoint(&self) -> Option<SyncFailpoint> {
+        #[cfg(feature = "test-failpoints")]
+        {
+            *self.sync_failpoint.read().expect("failpoint lock shouldn't be poisoned")
+        }
+        #[cfg(not(feature = "test-failpoints"))]
+        {
+            None
+        }
     }
 
     /// Interacts with the database by executing the provided function on a connection from the
@@ -130,12 +208,23 @@ impl Store for SqliteStore {
     }
 
     async fn apply_state_sync(&self, state_sync_update: StateSyncUpdate) -> Result<(), StoreError> {
+        let failpoint = self.sync_failpoint();
         self.interact_with_connection(move |conn| {
-            SqliteStore::apply_state_sync(conn, state_sync_update)
+            SqliteStore::apply_state_sync(conn, state_sync_update, failpoint)
         })
         .await
     }
 
+    async fn rollback_to(&self, block_num: BlockNumber) -> Result<(), StoreError> {
+        self.interact_with_connection(move |conn| SqliteStore::rollback_to(conn, block_num))
+            .await
+    }
+
+    async fn import_archive(&self, archive: StoreArchiveImport) -> Result<(), StoreError> {
+        self.interact_with_connection(move |conn| SqliteStore::import_archive(conn, archive))
+            .await
+    }
+
     async fn get_transactions(
         &self,
         transaction_filter: TransactionFilter,
@@ -151,6 +240,30 @@ impl Store for SqliteStore {
             .await
     }
 
+    async fn upsert_transactions(
+        &self,
+        transactions: &[TransactionRecord],
+    ) -> Result<(), StoreError> {
+        let transactions = transactions.to_vec();
+        self.interact_with_connection(move |conn| {
+            SqliteStore::upsert_transactions(conn, &transactions)
+        })
+        .await
+    }
+
+    async fn remove_transactions(
+        &self,
+        transaction_ids: &[TransactionId],
+        released_notes: &[InputNoteRecord],
+    ) -> Result<(), StoreError> {
+        let transaction_ids = transaction_ids.to_vec();
+        let released_notes = released_notes.to_vec();
+        self.interact_with_connection(move |conn| {
+            SqliteStore::remove_transactions(conn, &transaction_ids, &released_notes)
+        })
+        .await
+    }
+
     async fn get_input_notes(
         &self,
         filter: NoteFilter,
@@ -295,6 +408,11 @@ impl Store for SqliteStore {
             .await
     }
 
+    async fn get_account_assets(&self, account_id: AccountId) -> Result<Vec<Asset>, StoreError> {
+        self.interact_with_connection(move |conn| SqliteStore::get_account_assets(conn, account_id))
+            .await
+    }
+
     async fn upsert_foreign_account_code(
         &self,
         account_id: AccountId,
@@ -316,10 +434,37 @@ impl Store for SqliteStore {
         .await
     }
 
+    async fn upsert_token_metadata(
+        &self,
+        faucet_id: AccountId,
+        metadata: TokenMetadata,
+    ) -> Result<(), StoreError> {
+        self.interact_with_connection(move |conn| {
+            SqliteStore::upsert_token_metadata(conn, faucet_id, &metadata)
+        })
+        .await
+    }
+
+    async fn get_token_metadata(
+        &self,
+        faucet_id: AccountId,
+    ) -> Result<Option<TokenMetadata>, StoreError> {
+        self.interact_with_connection(move |conn| SqliteStore::get_token_metadata(conn, faucet_id))
+            .await
+    }
+
     async fn get_unspent_input_note_nullifiers(&self) -> Result<Vec<Nullifier>, StoreError> {
         self.interact_with_connection(SqliteStore::get_unspent_input_note_nullifiers)
             .await
     }
+
+    async fn counts(&self) -> Result<StoreCounts, StoreError> {
+        self.interact_with_connection(SqliteStore::counts).await
+    }
+
+    async fn get_note_tag_counts(&self) -> Result<BTreeMap<NoteTag, usize>, StoreError> {
+        self.interact_with_connection(SqliteStore::get_note_tag_counts).await
+    }
 }
 
 // UTILS
@@ -358,10 +503,14 @@ pub fn u64_to_value(v: u64) -> Value {
 
 #[cfg(test)]
 pub mod tests {
-    use std::boxed::Box;
+    use alloc::vec::Vec;
+    use std::{boxed::Box, sync::Arc};
 
-    use super::SqliteStore;
-    use crate::{store::Store, tests::create_test_store_path};
+    use super::{SqliteStore, SqliteStoreConfig};
+    use crate::{
+        store::{NoteFilter, Store, StoreError},
+        tests::create_test_store_path,
+    };
 
     fn assert_send_sync<T: Send + Sync>() {}
 
@@ -385,6 +534,33 @@ pub mod tests {
         tokio::task::spawn(async move { dyn_trait_send_fut(client).await });
     }
 
+    #[tokio::test]
+    async fn zero_max_connections_is_rejected() {
+        let config = SqliteStoreConfig::default().with_max_connections(0);
+        let result = SqliteStore::with_config(create_test_store_path(), config).await;
+
+        assert!(matches!(result, Err(StoreError::DatabaseError(_))));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn single_connection_pool_serializes_queries() {
+        let config = SqliteStoreConfig::default().with_max_connections(1);
+        let store =
+            Arc::new(SqliteStore::with_config(create_test_store_path(), config).await.unwrap());
+
+        // All queries share the only connection, so they have to wait for each other
+        let handles: Vec<_> = (0..8)
+            .map(|_| {
+                let store = store.clone();
+                tokio::spawn(async move { store.get_input_notes(NoteFilter::All).await })
+            })
+            .collect();
+
+        for handle in handles {
+            assert!(handle.await.unwrap().unwrap().is_empty());
+        }
+    }
+
     pub(crate) async fn create_test_store() -> SqliteStore {
         SqliteStore::new(create_test_store_path()).await.unwrap()
     }
@@ -41,6 +41,7 @@ struct SerializedInputNoteData {
     pub nullifier: String,
     pub state_discriminant: u8,
     pub state: Vec<u8>,
+    pub tag: Option<Vec<u8>>,
     pub created_at: u64,
 }
 
@@ -344,11 +345,14 @@ pub(super) fn upsert_input_note_tx(
         nullifier,
         state_discriminant,
         state,
+        tag,
         created_at,
     } = serialize_input_note(note);
 
+    // Scripts are identified by their root, so notes sharing a script (e.g. all P2ID notes)
+    // reference the same row and an existing script never needs to be rewritten
     const SCRIPT_QUERY: &str =
-        insert_sql!(notes_scripts { script_root, serialized_note_script } | REPLACE);
+        insert_sql!(notes_scripts { script_root, serialized_note_script } | IGNORE);
     tx.execute(SCRIPT_QUERY, params![script_root, script,])?;
 
     const NOTE_QUERY: &str = insert_sql!(
@@ -361,6 +365,7 @@ pub(super) fn upsert_input_note_tx(
             nullifier,
             state_discriminant,
             state,
+            tag,
             created_at,
         } | REPLACE
     );
@@ -376,6 +381,7 @@ pub(super) fn upsert_input_note_tx(
             nullifier,
             state_discriminant,
             state,
+            tag,
             created_at,
         ],
     )
@@ -383,6 +389,22 @@ pub(super) fn upsert_input_note_tx(
     .map(|_| ())
 }
 
+/// Sets the `tag` column of every stored input note from its serialized state. Used to fill in the
+/// column for the notes stored before it was added.
+pub(super) fn set_input_note_tags(tx: &Transaction<'_>) -> Result<(), StoreError> {
+    let notes = tx
+        .prepare("SELECT note_id, state FROM input_notes")?
+        .query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, Vec<u8>>(1)?)))?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    for (note_id, state) in notes {
+        let tag = InputNoteState::read_from_bytes(&state)?.tag().map(|tag| tag.to_bytes());
+        tx.execute("UPDATE input_notes SET tag = ? WHERE note_id = ?", params![tag, note_id])?;
+    }
+
+    Ok(())
+}
+
 /// Inserts the provided input note into the database.
 pub fn upsert_output_note_tx(
     tx: &Transaction<'_>,
@@ -495,6 +517,7 @@ fn serialize_input_note(note: &InputNoteRecord) -> SerializedInputNoteData {
 
     let state_discriminant = note.state().discriminant();
     let state = note.state().to_bytes();
+    let tag = note.tag().map(|tag| tag.to_bytes());
 
     SerializedInputNoteData {
         id,
@@ -506,6 +529,7 @@ fn serialize_input_note(note: &InputNoteRecord) -> SerializedInputNoteData {
         nullifier,
         state_discriminant,
         state,
+        tag,
         created_at,
     }
 }
@@ -593,3 +617,200 @@ pub(crate) fn apply_note_updates_tx(
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use alloc::{collections::BTreeMap, format, string::String, vec, vec::Vec};
+
+    use miden_lib::note::create_p2id_note;
+    use miden_objects::{
+        Felt, ZERO,
+        account::AccountId,
+        asset::FungibleAsset,
+        crypto::rand::RpoRandomCoin,
+        note::NoteType,
+        testing::account_id::{
+            ACCOUNT_ID_PUBLIC_FUNGIBLE_FAUCET, ACCOUNT_ID_REGULAR_PUBLIC_ACCOUNT_IMMUTABLE_CODE,
+        },
+    };
+    use rusqlite::{Connection, params, params_from_iter};
+
+    use super::{SerializedInputNoteData, serialize_input_note};
+    use crate::{
+        insert_sql,
+        store::{
+            InputNoteRecord, NoteFilter, Store,
+            sqlite_store::{
+                SqliteStore,
+                db_management::utils::{apply_migrations, apply_migrations_up_to},
+                tests::create_test_store,
+            },
+        },
+        subst,
+        sync::{NoteTagRecord, NoteTagSource, TagSubscription},
+    };
+
+    fn create_test_notes(count: usize) -> Vec<InputNoteRecord> {
+        let faucet_id = AccountId::try_from(ACCOUNT_ID_PUBLIC_FUNGIBLE_FAUCET).unwrap();
+        let target_id =
+            AccountId::try_from(ACCOUNT_ID_REGULAR_PUBLIC_ACCOUNT_IMMUTABLE_CODE).unwrap();
+        let mut rng = RpoRandomCoin::new([Felt::new(7); 4]);
+
+        (0..count)
+            .map(|_| {
+                create_p2id_note(
+                    faucet_id,
+                    target_id,
+                    vec![FungibleAsset::new(faucet_id, 10).unwrap().into()],
+                    NoteType::Private,
+                    ZERO,
+                    &mut rng,
+                )
+                .unwrap()
+                .into()
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn input_notes_share_scripts() {
+        let store = create_test_store().await;
+
+        let notes = create_test_notes(100);
+
+        store.upsert_input_notes(&notes).await.unwrap();
+        // Upserting the same notes again doesn't add new scripts either
+        store.upsert_input_notes(&notes).await.unwrap();
+
+        let script_rows: usize = store
+            .interact_with_connection(|conn| {
+                Ok(conn.query_row("SELECT COUNT(*) FROM notes_scripts", [], |row| row.get(0))?)
+            })
+            .await
+            .unwrap();
+        assert_eq!(script_rows, 1);
+
+        // Every note is read back with the shared script
+        let stored_notes = store.get_input_notes(NoteFilter::All).await.unwrap();
+        assert_eq!(stored_notes.len(), notes.len());
+        for note in &notes {
+            let stored_note = stored_notes.iter().find(|stored| stored.id() == note.id()).unwrap();
+            assert_eq!(stored_note.details().script(), note.details().script());
+        }
+    }
+
+    #[tokio::test]
+    async fn get_input_notes_by_nullifier() {
+        let store = create_test_store().await;
+
+        let notes = create_test_notes(10);
+        store.upsert_input_notes(&notes).await.unwrap();
+
+        let expected_notes = [&notes[2], &notes[7]];
+        let nullifiers = expected_notes.iter().map(|note| note.nullifier()).collect::<Vec<_>>();
+
+        let mut found_notes = store.get_input_notes_by_nullifier(&nullifiers).await.unwrap();
+        found_notes.sort_by_key(|note| expected_notes.iter().position(|n| n.id() == note.id()));
+        assert_eq!(
+            found_notes.iter().map(InputNoteRecord::id).collect::<Vec<_>>(),
+            expected_notes.iter().map(|note| note.id()).collect::<Vec<_>>()
+        );
+
+        // Nullifiers of untracked notes don't match any note
+        let untracked_note = &create_test_notes(11)[10];
+        assert!(
+            store
+                .get_input_notes_by_nullifier(&[untracked_note.nullifier()])
+                .await
+                .unwrap()
+                .is_empty()
+        );
+
+        // The lookup uses the nullifier index instead of scanning the table
+        let query_plan: Vec<String> = store
+            .interact_with_connection(move |conn| {
+                let (query, params) = NoteFilter::Nullifiers(nullifiers).to_query_input_notes();
+                Ok(conn
+                    .prepare(&format!("EXPLAIN QUERY PLAN {query}"))?
+                    .query_map(params_from_iter(params), |row| row.get(3))?
+                    .collect::<Result<Vec<String>, _>>()?)
+            })
+            .await
+            .unwrap();
+        assert!(
+            query_plan.iter().any(|step| step.contains("idx_input_notes_nullifier")),
+            "unexpected query plan: {query_plan:?}"
+        );
+    }
+
+    #[test]
+    fn input_note_tags_are_set_by_migration() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        apply_migrations_up_to(&mut conn, 5).unwrap();
+
+        // Notes stored before the migration don't have a tag column
+        let notes = create_test_notes(2);
+        for note in &notes {
+            let SerializedInputNoteData {
+                id,
+                assets,
+                serial_number,
+                inputs,
+                script_root,
+                script,
+                nullifier,
+                state_discriminant,
+                state,
+                created_at,
+                ..
+            } = serialize_input_note(note);
+            conn.execute(
+                insert_sql!(notes_scripts { script_root, serialized_note_script } | IGNORE),
+                params![script_root, script],
+            )
+            .unwrap();
+            conn.execute(
+                insert_sql!(input_notes {
+                    note_id,
+                    assets,
+                    serial_number,
+                    inputs,
+                    script_root,
+                    nullifier,
+                    state_discriminant,
+                    state,
+                    created_at,
+                }),
+                params![
+                    id,
+                    assets,
+                    serial_number,
+                    inputs,
+                    script_root,
+                    nullifier,
+                    state_discriminant,
+                    state,
+                    created_at,
+                ],
+            )
+            .unwrap();
+        }
+
+        apply_migrations(&mut conn).unwrap();
+
+        let tag = notes[0].tag().unwrap();
+        SqliteStore::add_note_tag(
+            &mut conn,
+            NoteTagRecord {
+                tag,
+                source: NoteTagSource::User,
+                subscription: TagSubscription::Prefix,
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            SqliteStore::get_note_tag_counts(&mut conn).unwrap(),
+            BTreeMap::from([(tag, 2)])
+        );
+    }
+}
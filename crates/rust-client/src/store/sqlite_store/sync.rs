@@ -1,6 +1,6 @@
 #![allow(clippy::items_after_statements)]
 
-use alloc::{collections::BTreeSet, vec::Vec};
+use alloc::{collections::BTreeSet, format, vec::Vec};
 
 use miden_objects::{Digest, block::BlockNumber, note::NoteTag};
 use miden_tx::utils::{Deserializable, Serializable};
@@ -10,31 +10,62 @@ use super::{SqliteStore, account::undo_account_state};
 use crate::{
     insert_sql,
     store::{
-        StoreError,
+        InputNoteState, NoteFilter, PartialBlockchainFilter, StoreError, TransactionFilter,
+        account_changed_after,
+        input_note_states::ExpectedNoteState,
+        is_partial_blockchain_node_in_forest,
         sqlite_store::{
             account::{lock_account_on_unexpected_commitment, update_account},
-            note::apply_note_updates_tx,
+            note::{apply_note_updates_tx, upsert_input_note_tx, upsert_output_note_tx},
             transaction::upsert_transaction_record,
         },
     },
     subst,
-    sync::{NoteTagRecord, NoteTagSource, StateSyncUpdate},
+    sync::{NoteTagRecord, NoteTagSource, StateSyncUpdate, TagSubscription},
 };
 
+// SYNC FAILPOINTS
+// ================================================================================================
+
+/// Points of [`SqliteStore::apply_state_sync`] at which a failure can be injected, to test that a
+/// state sync that fails midway leaves the store unchanged.
+///
+/// Failpoints can only be set with the `test-failpoints` feature.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SyncFailpoint {
+    /// Fails right after the new block headers are inserted.
+    AfterBlockHeaders,
+    /// Fails right after the note updates are applied.
+    AfterNoteUpdates,
+}
+
+impl SyncFailpoint {
+    /// Returns an error if `failpoint` is the point that was `reached`.
+    fn check(failpoint: Option<SyncFailpoint>, reached: SyncFailpoint) -> Result<(), StoreError> {
+        if failpoint == Some(reached) {
+            return Err(StoreError::DatabaseError(format!("sync failpoint {reached:?} triggered")));
+        }
+
+        Ok(())
+    }
+}
+
 impl SqliteStore {
     pub(crate) fn get_note_tags(conn: &mut Connection) -> Result<Vec<NoteTagRecord>, StoreError> {
-        const QUERY: &str = "SELECT tag, source FROM tags";
+        const QUERY: &str = "SELECT tag, source, subscription FROM tags";
 
         conn.prepare(QUERY)?
-            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))
             .expect("no binding parameters used in query")
             .map(|result| {
-                Ok(result?).and_then(|(tag, source): (Vec<u8>, Vec<u8>)| {
+                Ok(result?).and_then(|(tag, source, subscription): (Vec<u8>, Vec<u8>, Vec<u8>)| {
                     Ok(NoteTagRecord {
                         tag: NoteTag::read_from_bytes(&tag)
                             .map_err(StoreError::DataDeserializationError)?,
                         source: NoteTagSource::read_from_bytes(&source)
                             .map_err(StoreError::DataDeserializationError)?,
+                        subscription: TagSubscription::read_from_bytes(&subscription)
+                            .map_err(StoreError::DataDeserializationError)?,
                     })
                 })
             })
@@ -61,16 +92,12 @@ impl SqliteStore {
         conn: &mut Connection,
         tag: NoteTagRecord,
     ) -> Result<bool, StoreError> {
-        if Self::get_note_tags(conn)?.contains(&tag) {
-            return Ok(false);
-        }
-
         let tx = conn.transaction()?;
-        add_note_tag_tx(&tx, &tag)?;
+        let added = add_note_tag_tx(&tx, &tag)?;
 
         tx.commit()?;
 
-        Ok(true)
+        Ok(added)
     }
 
     pub(super) fn remove_note_tag(
@@ -103,95 +130,178 @@ impl SqliteStore {
     pub(super) fn apply_state_sync(
         conn: &mut Connection,
         state_sync_update: StateSyncUpdate,
+        failpoint: Option<SyncFailpoint>,
     ) -> Result<(), StoreError> {
-        let StateSyncUpdate {
-            block_num,
-            block_updates,
-            note_updates,
-            transaction_updates,
-            account_updates,
-        } = state_sync_update;
-
         let tx = conn.transaction()?;
+        apply_state_sync_tx(&tx, state_sync_update, failpoint)?;
 
-        // Update state sync block number
-        const BLOCK_NUMBER_QUERY: &str = "UPDATE state_sync SET block_num = ?";
-        tx.execute(BLOCK_NUMBER_QUERY, params![i64::from(block_num.as_u32())])?;
-
-        for (block_header, block_has_relevant_notes, new_mmr_peaks) in block_updates.block_headers()
-        {
-            Self::insert_block_header_tx(
-                &tx,
-                block_header,
-                new_mmr_peaks,
-                *block_has_relevant_notes,
-            )?;
-        }
+        // Commit the updates
+        tx.commit()?;
 
-        // Insert new authentication nodes (inner nodes of the PartialBlockchain)
-        Self::insert_partial_blockchain_nodes_tx(&tx, block_updates.new_authentication_nodes())?;
-
-        // Update notes
-        apply_note_updates_tx(&tx, &note_updates)?;
-
-        // Remove tags
-        let tags_to_remove = note_updates
-            .updated_input_notes()
-            .filter_map(|note_update| {
-                let note = note_update.inner();
-                if note.is_committed() {
-                    Some(NoteTagRecord {
-                        tag: note.metadata().expect("Committed notes should have metadata").tag(),
-                        source: NoteTagSource::Note(note.id()),
-                    })
-                } else {
-                    None
-                }
-            })
-            .collect::<Vec<_>>();
+        Ok(())
+    }
 
-        for tag in tags_to_remove {
-            remove_note_tag_tx(&tx, tag)?;
+    pub(super) fn rollback_to(
+        conn: &mut Connection,
+        block_num: BlockNumber,
+    ) -> Result<(), StoreError> {
+        if Self::get_block_headers(conn, &BTreeSet::from([block_num]))?.is_empty() {
+            return Err(StoreError::BlockHeaderNotFound(block_num));
         }
 
-        for transaction_record in transaction_updates
-            .committed_transactions()
-            .chain(transaction_updates.discarded_transactions())
+        // Account states aren't reverted, so the tracked accounts can't have changed after the block
+        let transactions = Self::get_transactions(conn, &TransactionFilter::All)?;
+        if let Some(account_id) =
+            account_changed_after(&transactions, &Self::get_account_ids(conn)?, block_num)
         {
-            upsert_transaction_record(&tx, transaction_record)?;
+            return Err(StoreError::AccountChangedAfterBlock(account_id, block_num));
         }
 
-        // Remove the accounts that are originated from the discarded transactions
-        let account_hashes_to_delete: Vec<Digest> = transaction_updates
-            .discarded_transactions()
-            .map(|tx| tx.details.final_account_state)
-            .collect();
+        let forest = u64::from(block_num.as_u32()) + 1;
+        let orphaned_node_ids: Vec<i64> =
+            Self::get_partial_blockchain_nodes(conn, &PartialBlockchainFilter::All)?
+                .into_keys()
+                .filter(|index| !is_partial_blockchain_node_in_forest(*index, forest))
+                .map(|index| i64::try_from(index.inner()).expect("id is a valid i64"))
+                .collect();
+
+        let mut input_notes = Self::get_input_notes(conn, &NoteFilter::All)?;
+        input_notes.retain_mut(|note| note.rolled_back_to(block_num));
+        let mut output_notes = Self::get_output_notes(conn, &NoteFilter::All)?;
+        output_notes.retain_mut(|note| note.rolled_back_to(block_num));
+
+        let tx = conn.transaction()?;
+
+        const BLOCK_NUMBER_QUERY: &str = "UPDATE state_sync SET block_num = ?";
+        tx.execute(BLOCK_NUMBER_QUERY, params![i64::from(block_num.as_u32())])?;
 
-        undo_account_state(&tx, &account_hashes_to_delete)?;
+        const BLOCK_HEADERS_QUERY: &str = "DELETE FROM block_headers WHERE block_num > ?";
+        tx.execute(BLOCK_HEADERS_QUERY, params![i64::from(block_num.as_u32())])?;
 
-        // Update public accounts on the db that have been updated onchain
-        for account in account_updates.updated_public_accounts() {
-            update_account(&tx, account)?;
+        const NODES_QUERY: &str = "DELETE FROM partial_blockchain_nodes WHERE id = ?";
+        for id in orphaned_node_ids {
+            tx.execute(NODES_QUERY, params![id])?;
         }
 
-        for (account_id, digest) in account_updates.mismatched_private_accounts() {
-            lock_account_on_unexpected_commitment(&tx, account_id, digest)?;
+        for note in &input_notes {
+            upsert_input_note_tx(&tx, note)?;
+
+            // Expected notes need their tag to be tracked again to be found by the sync
+            if let InputNoteState::Expected(ExpectedNoteState { tag: Some(tag), .. }) = note.state()
+            {
+                add_note_tag_tx(&tx, &NoteTagRecord::with_note_source(*tag, note.id()))?;
+            }
+        }
+
+        for note in &output_notes {
+            upsert_output_note_tx(&tx, note)?;
         }
 
-        // Commit the updates
         tx.commit()?;
 
         Ok(())
     }
 }
 
-pub(super) fn add_note_tag_tx(tx: &Transaction<'_>, tag: &NoteTagRecord) -> Result<(), StoreError> {
-    const QUERY: &str = insert_sql!(tags { tag, source });
-    tx.execute(QUERY, params![tag.tag.to_bytes(), tag.source.to_bytes()])?;
+/// Applies the state sync update within the provided database transaction.
+pub(super) fn apply_state_sync_tx(
+    tx: &Transaction<'_>,
+    state_sync_update: StateSyncUpdate,
+    failpoint: Option<SyncFailpoint>,
+) -> Result<(), StoreError> {
+    let StateSyncUpdate {
+        block_num,
+        block_updates,
+        note_updates,
+        transaction_updates,
+        account_updates,
+    } = state_sync_update;
+
+    // Update state sync block number
+    const BLOCK_NUMBER_QUERY: &str = "UPDATE state_sync SET block_num = ?";
+    tx.execute(BLOCK_NUMBER_QUERY, params![i64::from(block_num.as_u32())])?;
+
+    for (block_header, block_has_relevant_notes, new_mmr_peaks) in block_updates.block_headers() {
+        SqliteStore::insert_block_header_tx(
+            tx,
+            block_header,
+            new_mmr_peaks,
+            *block_has_relevant_notes,
+        )?;
+    }
+
+    // Insert new authentication nodes (inner nodes of the PartialBlockchain)
+    SqliteStore::insert_partial_blockchain_nodes_tx(tx, block_updates.new_authentication_nodes())?;
+
+    SyncFailpoint::check(failpoint, SyncFailpoint::AfterBlockHeaders)?;
+
+    // Update notes
+    apply_note_updates_tx(tx, &note_updates)?;
+
+    SyncFailpoint::check(failpoint, SyncFailpoint::AfterNoteUpdates)?;
+
+    // Remove tags
+    let tags_to_remove = note_updates
+        .updated_input_notes()
+        .filter_map(|note_update| {
+            let note = note_update.inner();
+            if note.is_committed() {
+                Some(NoteTagRecord::with_note_source(
+                    note.metadata().expect("Committed notes should have metadata").tag(),
+                    note.id(),
+                ))
+            } else {
+                None
+            }
+        })
+        .collect::<Vec<_>>();
+
+    for tag in tags_to_remove {
+        remove_note_tag_tx(tx, tag)?;
+    }
+
+    for transaction_record in transaction_updates
+        .committed_transactions()
+        .chain(transaction_updates.discarded_transactions())
+    {
+        upsert_transaction_record(tx, transaction_record)?;
+    }
+
+    // Remove the accounts that are originated from the discarded transactions
+    let account_hashes_to_delete: Vec<Digest> = transaction_updates
+        .discarded_transactions()
+        .map(|tx| tx.details.final_account_state)
+        .collect();
+
+    undo_account_state(tx, &account_hashes_to_delete)?;
+
+    // Update public accounts on the db that have been updated onchain
+    for account in account_updates.updated_public_accounts() {
+        update_account(tx, account)?;
+    }
+
+    for (account_id, digest) in account_updates.mismatched_private_accounts() {
+        lock_account_on_unexpected_commitment(tx, account_id, digest)?;
+    }
 
     Ok(())
 }
 
+/// Adds the tag record to the database. Returns `false` if the tag was already being tracked for
+/// the same source.
+pub(super) fn add_note_tag_tx(
+    tx: &Transaction<'_>,
+    tag: &NoteTagRecord,
+) -> Result<bool, StoreError> {
+    const QUERY: &str = insert_sql!(tags { tag, source, subscription } | IGNORE);
+    let added_tags = tx.execute(
+        QUERY,
+        params![tag.tag.to_bytes(), tag.source.to_bytes(), tag.subscription.to_bytes()],
+    )?;
+
+    Ok(added_tags > 0)
+}
+
 pub(super) fn remove_note_tag_tx(
     tx: &Transaction<'_>,
     tag: NoteTagRecord,
@@ -201,3 +311,99 @@ pub(super) fn remove_note_tag_tx(
 
     Ok(removed_tags)
 }
+
+#[cfg(test)]
+mod test {
+    use alloc::vec;
+
+    use miden_objects::{
+        account::AccountId, note::NoteTag,
+        testing::account_id::ACCOUNT_ID_REGULAR_PUBLIC_ACCOUNT_IMMUTABLE_CODE,
+    };
+    use miden_tx::utils::Serializable;
+    use rusqlite::{Connection, params};
+
+    use crate::{
+        insert_sql,
+        store::sqlite_store::{
+            SqliteStore,
+            db_management::utils::{apply_migrations, apply_migrations_up_to},
+        },
+        subst,
+        sync::{NoteTagRecord, NoteTagSource, TagSubscription},
+    };
+
+    #[test]
+    fn note_tags_survive_index_migration() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        apply_migrations_up_to(&mut conn, 1).unwrap();
+
+        let account_id =
+            AccountId::try_from(ACCOUNT_ID_REGULAR_PUBLIC_ACCOUNT_IMMUTABLE_CODE).unwrap();
+        let tag = NoteTag::from_account_id(account_id);
+        let user_record = NoteTagRecord {
+            tag,
+            source: NoteTagSource::User,
+            subscription: TagSubscription::Prefix,
+        };
+        let account_record = NoteTagRecord::with_account_source(tag, account_id);
+
+        // Before the migration nothing prevented the same record from being stored twice
+        for record in [&user_record, &user_record, &account_record] {
+            conn.execute(
+                insert_sql!(tags { tag, source }),
+                params![record.tag.to_bytes(), record.source.to_bytes()],
+            )
+            .unwrap();
+        }
+
+        apply_migrations(&mut conn).unwrap();
+
+        let tags = SqliteStore::get_note_tags(&mut conn).unwrap();
+        assert_eq!(tags.len(), 2);
+        assert!(tags.contains(&user_record));
+        assert!(tags.contains(&account_record));
+        assert_eq!(SqliteStore::get_unique_note_tags(&mut conn).unwrap().len(), 1);
+
+        // Adding an already tracked record is a no-op
+        assert!(!SqliteStore::add_note_tag(&mut conn, user_record).unwrap());
+        assert_eq!(SqliteStore::get_note_tags(&mut conn).unwrap().len(), 2);
+
+        // Removing a record only removes the tag for that source
+        assert_eq!(SqliteStore::remove_note_tag(&mut conn, user_record).unwrap(), 1);
+        assert_eq!(SqliteStore::get_note_tags(&mut conn).unwrap(), vec![account_record]);
+        assert!(SqliteStore::get_unique_note_tags(&mut conn).unwrap().contains(&tag));
+
+        assert_eq!(SqliteStore::remove_note_tag(&mut conn, account_record).unwrap(), 1);
+        assert!(SqliteStore::get_unique_note_tags(&mut conn).unwrap().is_empty());
+
+        // The tag can be tracked again once removed
+        assert!(SqliteStore::add_note_tag(&mut conn, user_record).unwrap());
+        assert_eq!(SqliteStore::get_unique_note_tags(&mut conn).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn note_tag_subscription_is_stored() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        apply_migrations(&mut conn).unwrap();
+
+        let account_id =
+            AccountId::try_from(ACCOUNT_ID_REGULAR_PUBLIC_ACCOUNT_IMMUTABLE_CODE).unwrap();
+        let exact_record = NoteTagRecord {
+            tag: NoteTag::from_account_id(account_id),
+            source: NoteTagSource::User,
+            subscription: TagSubscription::Exact,
+        };
+
+        assert!(SqliteStore::add_note_tag(&mut conn, exact_record).unwrap());
+        assert_eq!(SqliteStore::get_note_tags(&mut conn).unwrap(), vec![exact_record]);
+
+        // The subscription of an already tracked tag isn't changed
+        let prefix_record = NoteTagRecord {
+            subscription: TagSubscription::Prefix,
+            ..exact_record
+        };
+        assert!(!SqliteStore::add_note_tag(&mut conn, prefix_record).unwrap());
+        assert_eq!(SqliteStore::get_note_tags(&mut conn).unwrap(), vec![exact_record]);
+    }
+}
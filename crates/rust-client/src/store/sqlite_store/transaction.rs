@@ -10,16 +10,19 @@ use miden_objects::{
     Digest,
     block::BlockNumber,
     crypto::utils::{Deserializable, Serializable},
-    transaction::{ToInputNoteCommitments, TransactionScript},
+    transaction::{ToInputNoteCommitments, TransactionId, TransactionScript},
 };
 use rusqlite::{Connection, Transaction, params, types::Value};
 
 use super::{
-    SqliteStore, account::update_account, note::apply_note_updates_tx, sync::add_note_tag_tx,
+    SqliteStore,
+    account::update_account,
+    note::{apply_note_updates_tx, upsert_input_note_tx},
+    sync::add_note_tag_tx,
 };
 use crate::{
     insert_sql,
-    store::{StoreError, TransactionFilter},
+    store::{InputNoteRecord, StoreError, TransactionFilter, is_expired_transaction},
     subst,
     transaction::{
         DiscardCause, TransactionDetails, TransactionRecord, TransactionStatus,
@@ -51,7 +54,11 @@ impl TransactionFilter {
             FROM transactions AS tx LEFT JOIN transaction_scripts AS script ON tx.script_root = script.script_root";
         match self {
             TransactionFilter::All => QUERY.to_string(),
-            TransactionFilter::Uncommitted => format!("{QUERY} WHERE tx.commit_height IS NULL"),
+            // The expiration block of `Expired` is part of the serialized details, so its
+            // predicate is applied after the uncommitted transactions are fetched
+            TransactionFilter::Uncommitted | TransactionFilter::Expired(_) => {
+                format!("{QUERY} WHERE tx.commit_height IS NULL")
+            },
             TransactionFilter::Ids(_) => {
                 // Use SQLite's array parameter binding
                 format!("{QUERY} WHERE tx.id IN rarray(?)")
@@ -117,6 +124,14 @@ impl SqliteStore {
                     .map(|result| Ok(result?).and_then(parse_transaction))
                     .collect::<Result<Vec<TransactionRecord>, _>>()
             },
+            TransactionFilter::Expired(block_num) => Ok(conn
+                .prepare(&filter.to_query())?
+                .query_map([], parse_transaction_columns)?
+                .map(|result| Ok(result?).and_then(parse_transaction))
+                .collect::<Result<Vec<TransactionRecord>, StoreError>>()?
+                .into_iter()
+                .filter(|transaction| is_expired_transaction(transaction, *block_num))
+                .collect()),
             _ => {
                 // For other filters, no parameters are needed
                 conn.prepare(&filter.to_query())?
@@ -147,7 +162,7 @@ impl SqliteStore {
 
         let details = TransactionDetails {
             account_id: executed_transaction.account_id(),
-            init_account_state: executed_transaction.initial_account().commitment(),
+            init_account_state: Some(executed_transaction.initial_account().commitment()),
             final_account_state: executed_transaction.final_account().commitment(),
             input_note_nullifiers: nullifiers,
             output_notes: output_notes.clone(),
@@ -180,6 +195,46 @@ impl SqliteStore {
 
         Ok(())
     }
+
+    /// Inserts the transaction records, replacing the ones with the same ID.
+    pub fn upsert_transactions(
+        conn: &mut Connection,
+        transactions: &[TransactionRecord],
+    ) -> Result<(), StoreError> {
+        let tx = conn.transaction()?;
+
+        for transaction in transactions {
+            upsert_transaction_record(&tx, transaction)?;
+        }
+
+        tx.commit()?;
+
+        Ok(())
+    }
+
+    /// Removes the transactions with the specified IDs from the store and updates the notes they
+    /// released in a single database transaction. Transaction scripts are kept, as they may be
+    /// shared with other transactions.
+    pub fn remove_transactions(
+        conn: &mut Connection,
+        transaction_ids: &[TransactionId],
+        released_notes: &[InputNoteRecord],
+    ) -> Result<(), StoreError> {
+        const QUERY: &str = "DELETE FROM transactions WHERE id IN rarray(?)";
+
+        let tx = conn.transaction()?;
+
+        for note in released_notes {
+            upsert_input_note_tx(&tx, note)?;
+        }
+
+        let id_strings =
+            transaction_ids.iter().map(|id| Value::Text(id.to_string())).collect::<Vec<_>>();
+
+        tx.execute(QUERY, params![Rc::new(id_strings)])?;
+
+        Ok(tx.commit()?)
+    }
 }
 
 /// Updates the transaction record in the database, inserting it if it doesn't exist.
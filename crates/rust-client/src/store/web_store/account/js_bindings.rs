@@ -9,50 +9,65 @@ extern "C" {
     // GETS
     // ================================================================================================
     #[wasm_bindgen(js_name = getAccountIds)]
-    pub fn idxdb_get_account_ids() -> js_sys::Promise;
+    pub fn idxdb_get_account_ids(store_name: &str) -> js_sys::Promise;
 
     #[wasm_bindgen(js_name = getAllAccountHeaders)]
-    pub fn idxdb_get_account_headers() -> js_sys::Promise;
+    pub fn idxdb_get_account_headers(store_name: &str) -> js_sys::Promise;
 
     #[wasm_bindgen(js_name = getAccountHeader)]
-    pub fn idxdb_get_account_header(account_id: String) -> js_sys::Promise;
+    pub fn idxdb_get_account_header(store_name: &str, account_id: String) -> js_sys::Promise;
 
     #[wasm_bindgen(js_name = getAccountHeaderByCommitment)]
-    pub fn idxdb_get_account_header_by_commitment(account_commitment: String) -> js_sys::Promise;
+    pub fn idxdb_get_account_header_by_commitment(
+        store_name: &str,
+        account_commitment: String,
+    ) -> js_sys::Promise;
 
     #[wasm_bindgen(js_name = getAccountCode)]
-    pub fn idxdb_get_account_code(code_root: String) -> js_sys::Promise;
+    pub fn idxdb_get_account_code(store_name: &str, code_root: String) -> js_sys::Promise;
 
     #[wasm_bindgen(js_name = getAccountStorage)]
-    pub fn idxdb_get_account_storage(storage_root: String) -> js_sys::Promise;
+    pub fn idxdb_get_account_storage(store_name: &str, storage_root: String) -> js_sys::Promise;
 
     #[wasm_bindgen(js_name = getAccountAssetVault)]
-    pub fn idxdb_get_account_asset_vault(vault_root: String) -> js_sys::Promise;
+    pub fn idxdb_get_account_asset_vault(store_name: &str, vault_root: String) -> js_sys::Promise;
 
     #[wasm_bindgen(js_name = getAccountAuthByPubKey)]
-    pub fn idxdb_get_account_auth_by_pub_key(pub_key: String) -> JsValue;
+    pub fn idxdb_get_account_auth_by_pub_key(store_name: &str, pub_key: String) -> JsValue;
 
     #[wasm_bindgen(js_name = fetchAndCacheAccountAuthByPubKey)]
-    pub fn idxdb_fetch_and_cache_account_auth_by_pub_key(pub_key: String) -> js_sys::Promise;
+    pub fn idxdb_fetch_and_cache_account_auth_by_pub_key(
+        store_name: &str,
+        pub_key: String,
+    ) -> js_sys::Promise;
 
     // INSERTS
     // ================================================================================================
 
     #[wasm_bindgen(js_name = insertAccountCode)]
-    pub fn idxdb_insert_account_code(code_root: String, code: Vec<u8>) -> js_sys::Promise;
+    pub fn idxdb_insert_account_code(
+        store_name: &str,
+        code_root: String,
+        code: Vec<u8>,
+    ) -> js_sys::Promise;
 
     #[wasm_bindgen(js_name = insertAccountStorage)]
     pub fn idxdb_insert_account_storage(
+        store_name: &str,
         storage_root: String,
         storage_slots: Vec<u8>,
     ) -> js_sys::Promise;
 
     #[wasm_bindgen(js_name = insertAccountAssetVault)]
-    pub fn idxdb_insert_account_asset_vault(vault_root: String, assets: Vec<u8>)
-    -> js_sys::Promise;
+    pub fn idxdb_insert_account_asset_vault(
+        store_name: &str,
+        vault_root: String,
+        assets: Vec<u8>,
+    ) -> js_sys::Promise;
 
     #[wasm_bindgen(js_name = insertAccountRecord)]
     pub fn idxdb_insert_account_record(
+        store_name: &str,
         id: String,
         code_root: String,
         storage_root: String,
@@ -64,27 +79,48 @@ extern "C" {
     ) -> js_sys::Promise;
 
     #[wasm_bindgen(js_name = insertAccountAuth)]
-    pub fn idxdb_insert_account_auth(pub_key: String, secret_key: String) -> js_sys::Promise;
+    pub fn idxdb_insert_account_auth(
+        store_name: &str,
+        pub_key: String,
+        secret_key: String,
+    ) -> js_sys::Promise;
 
     #[wasm_bindgen(js_name = upsertForeignAccountCode)]
     pub fn idxdb_upsert_foreign_account_code(
+        store_name: &str,
         account_id: String,
         code: Vec<u8>,
         code_root: String,
     ) -> js_sys::Promise;
 
     #[wasm_bindgen(js_name = getForeignAccountCode)]
-    pub fn idxdb_get_foreign_account_code(account_ids: Vec<String>) -> js_sys::Promise;
+    pub fn idxdb_get_foreign_account_code(
+        store_name: &str,
+        account_ids: Vec<String>,
+    ) -> js_sys::Promise;
+
+    #[wasm_bindgen(js_name = upsertTokenMetadata)]
+    pub fn idxdb_upsert_token_metadata(
+        store_name: &str,
+        faucet_id: String,
+        metadata: Vec<u8>,
+    ) -> js_sys::Promise;
+
+    #[wasm_bindgen(js_name = getTokenMetadata)]
+    pub fn idxdb_get_token_metadata(store_name: &str, faucet_id: String) -> js_sys::Promise;
 
     // UPDATES
     // ================================================================================================
 
     #[wasm_bindgen(js_name = lockAccount)]
-    pub fn idxdb_lock_account(account_id: String) -> js_sys::Promise;
+    pub fn idxdb_lock_account(store_name: &str, account_id: String) -> js_sys::Promise;
 
     // DELETES
     // ================================================================================================
 
     #[wasm_bindgen(js_name = undoAccountStates)]
-    pub fn idxdb_undo_account_states(account_hashes: Vec<String>) -> js_sys::Promise;
+    pub fn idxdb_undo_account_states(
+        store_name: &str,
+        account_hashes: Vec<String>,
+    ) -> js_sys::Promise;
 }
@@ -14,21 +14,25 @@ use serde_wasm_bindgen::from_value;
 use wasm_bindgen_futures::JsFuture;
 
 use super::WebStore;
-use crate::store::{AccountRecord, AccountStatus, StoreError};
+use crate::{
+    account::TokenMetadata,
+    store::{AccountRecord, AccountStatus, StoreError},
+};
 
 mod js_bindings;
 use js_bindings::{
     idxdb_fetch_and_cache_account_auth_by_pub_key, idxdb_get_account_asset_vault,
     idxdb_get_account_code, idxdb_get_account_header, idxdb_get_account_header_by_commitment,
     idxdb_get_account_headers, idxdb_get_account_ids, idxdb_get_account_storage,
-    idxdb_get_foreign_account_code, idxdb_lock_account, idxdb_undo_account_states,
-    idxdb_upsert_foreign_account_code,
+    idxdb_get_foreign_account_code, idxdb_get_token_metadata, idxdb_lock_account,
+    idxdb_undo_account_states, idxdb_upsert_foreign_account_code, idxdb_upsert_token_metadata,
 };
 
 mod models;
 use models::{
     AccountAuthIdxdbObject, AccountCodeIdxdbObject, AccountRecordIdxdbObject,
     AccountStorageIdxdbObject, AccountVaultIdxdbObject, ForeignAccountCodeIdxdbObject,
+    TokenMetadataIdxdbObject,
 };
 
 pub(crate) mod utils;
@@ -39,7 +43,7 @@ use utils::{
 
 impl WebStore {
     pub(super) async fn get_account_ids(&self) -> Result<Vec<AccountId>, StoreError> {
-        let promise = idxdb_get_account_ids();
+        let promise = idxdb_get_account_ids(&self.store_name);
         let js_value = JsFuture::from(promise).await.map_err(|js_error| {
             StoreError::DatabaseError(format!("failed to fetch account ids: {js_error:?}",))
         })?;
@@ -57,7 +61,7 @@ impl WebStore {
     pub(super) async fn get_account_headers(
         &self,
     ) -> Result<Vec<(AccountHeader, AccountStatus)>, StoreError> {
-        let promise = idxdb_get_account_headers();
+        let promise = idxdb_get_account_headers(&self.store_name);
         let js_value = JsFuture::from(promise).await.map_err(|js_error| {
             StoreError::DatabaseError(format!("failed to fetch account headers: {js_error:?}",))
         })?;
@@ -78,7 +82,7 @@ impl WebStore {
         account_id: AccountId,
     ) -> Result<Option<(AccountHeader, AccountStatus)>, StoreError> {
         let account_id_str = account_id.to_string();
-        let promise = idxdb_get_account_header(account_id_str);
+        let promise = idxdb_get_account_header(&self.store_name, account_id_str);
 
         let js_value = JsFuture::from(promise).await.map_err(|js_error| {
             StoreError::DatabaseError(format!("failed to fetch account header: {js_error:?}",))
@@ -104,7 +108,8 @@ impl WebStore {
     ) -> Result<Option<AccountHeader>, StoreError> {
         let account_commitment_str = account_commitment.to_string();
 
-        let promise = idxdb_get_account_header_by_commitment(account_commitment_str);
+        let promise =
+            idxdb_get_account_header_by_commitment(&self.store_name, account_commitment_str);
         let js_value = JsFuture::from(promise).await.map_err(|js_error| {
             StoreError::DatabaseError(format!(
                 "failed to fetch account header by commitment: {js_error:?}",
@@ -149,10 +154,22 @@ impl WebStore {
         Ok(Some(AccountRecord::new(account, status)))
     }
 
+    pub(super) async fn get_account_assets(
+        &self,
+        account_id: AccountId,
+    ) -> Result<Vec<Asset>, StoreError> {
+        let (account_header, _) = self
+            .get_account_header(account_id)
+            .await?
+            .ok_or(StoreError::AccountDataNotFound(account_id))?;
+
+        self.get_vault_assets(account_header.vault_root()).await
+    }
+
     pub(super) async fn get_account_code(&self, root: Digest) -> Result<AccountCode, StoreError> {
         let root_serialized = root.to_string();
 
-        let promise = idxdb_get_account_code(root_serialized);
+        let promise = idxdb_get_account_code(&self.store_name, root_serialized);
         let js_value = JsFuture::from(promise).await.map_err(|js_error| {
             StoreError::DatabaseError(format!("failed to fetch account code: {js_error:?}",))
         })?;
@@ -172,7 +189,7 @@ impl WebStore {
     ) -> Result<AccountStorage, StoreError> {
         let commitment_serialized = commitment.to_string();
 
-        let promise = idxdb_get_account_storage(commitment_serialized);
+        let promise = idxdb_get_account_storage(&self.store_name, commitment_serialized);
         let js_value = JsFuture::from(promise).await.map_err(|js_error| {
             StoreError::DatabaseError(format!("failed to fetch account storage: {js_error:?}",))
         })?;
@@ -189,7 +206,7 @@ impl WebStore {
     ) -> Result<Vec<Asset>, StoreError> {
         let commitment_serialized = commitment.to_string();
 
-        let promise = idxdb_get_account_asset_vault(commitment_serialized);
+        let promise = idxdb_get_account_asset_vault(&self.store_name, commitment_serialized);
         let js_value = JsFuture::from(promise).await.map_err(|js_error| {
             StoreError::DatabaseError(format!("failed to fetch vault assets: {js_error:?}",))
         })?;
@@ -204,21 +221,29 @@ impl WebStore {
         account: &Account,
         account_seed: Option<Word>,
     ) -> Result<(), StoreError> {
-        insert_account_code(account.code()).await.map_err(|js_error| {
-            StoreError::DatabaseError(format!("failed to insert account code: {js_error:?}",))
-        })?;
+        insert_account_code(&self.store_name, account.code())
+            .await
+            .map_err(|js_error| {
+                StoreError::DatabaseError(format!("failed to insert account code: {js_error:?}",))
+            })?;
 
-        insert_account_storage(account.storage()).await.map_err(|js_error| {
-            StoreError::DatabaseError(format!("failed to insert account storage:{js_error:?}",))
-        })?;
+        insert_account_storage(&self.store_name, account.storage())
+            .await
+            .map_err(|js_error| {
+                StoreError::DatabaseError(format!("failed to insert account storage:{js_error:?}",))
+            })?;
 
-        insert_account_asset_vault(account.vault()).await.map_err(|js_error| {
-            StoreError::DatabaseError(format!("failed to insert account vault:{js_error:?}",))
-        })?;
+        insert_account_asset_vault(&self.store_name, account.vault()).await.map_err(
+            |js_error| {
+                StoreError::DatabaseError(format!("failed to insert account vault:{js_error:?}",))
+            },
+        )?;
 
-        insert_account_record(account, account_seed).await.map_err(|js_error| {
-            StoreError::DatabaseError(format!("failed to insert account record: {js_error:?}",))
-        })?;
+        insert_account_record(&self.store_name, account, account_seed).await.map_err(
+            |js_error| {
+                StoreError::DatabaseError(format!("failed to insert account record: {js_error:?}",))
+            },
+        )?;
 
         Ok(())
     }
@@ -228,13 +253,13 @@ impl WebStore {
         new_account_state: &Account,
     ) -> Result<(), StoreError> {
         let account_id_str = new_account_state.id().to_string();
-        let promise = idxdb_get_account_header(account_id_str);
+        let promise = idxdb_get_account_header(&self.store_name, account_id_str);
 
         if JsFuture::from(promise).await.is_err() {
             return Err(StoreError::AccountDataNotFound(new_account_state.id()));
         }
 
-        update_account(new_account_state)
+        update_account(&self.store_name, new_account_state)
             .await
             .map_err(|_| StoreError::DatabaseError("failed to update account".to_string()))
     }
@@ -243,7 +268,7 @@ impl WebStore {
         &self,
         pub_key: String,
     ) -> Result<Option<String>, StoreError> {
-        let promise = idxdb_fetch_and_cache_account_auth_by_pub_key(pub_key);
+        let promise = idxdb_fetch_and_cache_account_auth_by_pub_key(&self.store_name, pub_key);
 
         let js_value = JsFuture::from(promise).await.map_err(|js_error| {
             StoreError::DatabaseError(format!(
@@ -272,7 +297,7 @@ impl WebStore {
         let code = code.to_bytes();
         let account_id = account_id.to_string();
 
-        let promise = idxdb_upsert_foreign_account_code(account_id, code, root);
+        let promise = idxdb_upsert_foreign_account_code(&self.store_name, account_id, code, root);
         JsFuture::from(promise).await.map_err(|js_error| {
             StoreError::DatabaseError(format!(
                 "failed to upsert foreign account code: {js_error:?}",
@@ -287,7 +312,7 @@ impl WebStore {
         account_ids: Vec<AccountId>,
     ) -> Result<BTreeMap<AccountId, AccountCode>, StoreError> {
         let account_ids = account_ids.iter().map(ToString::to_string).collect::<Vec<_>>();
-        let promise = idxdb_get_foreign_account_code(account_ids);
+        let promise = idxdb_get_foreign_account_code(&self.store_name, account_ids);
         let js_value = JsFuture::from(promise).await.map_err(|js_error| {
             StoreError::DatabaseError(
                 format!("failed to fetch foreign account code: {js_error:?}",),
@@ -318,13 +343,49 @@ impl WebStore {
         Ok(foreign_account_code)
     }
 
+    pub(crate) async fn upsert_token_metadata(
+        &self,
+        faucet_id: AccountId,
+        metadata: TokenMetadata,
+    ) -> Result<(), StoreError> {
+        let promise = idxdb_upsert_token_metadata(
+            &self.store_name,
+            faucet_id.to_string(),
+            metadata.to_bytes(),
+        );
+        JsFuture::from(promise).await.map_err(|js_error| {
+            StoreError::DatabaseError(format!("failed to upsert token metadata: {js_error:?}",))
+        })?;
+
+        Ok(())
+    }
+
+    pub(crate) async fn get_token_metadata(
+        &self,
+        faucet_id: AccountId,
+    ) -> Result<Option<TokenMetadata>, StoreError> {
+        let promise = idxdb_get_token_metadata(&self.store_name, faucet_id.to_string());
+        let js_value = JsFuture::from(promise).await.map_err(|js_error| {
+            StoreError::DatabaseError(format!("failed to fetch token metadata: {js_error:?}",))
+        })?;
+
+        if js_value.is_null() || js_value.is_undefined() {
+            return Ok(None);
+        }
+
+        let token_metadata_idxdb: TokenMetadataIdxdbObject = from_value(js_value)
+            .map_err(|err| StoreError::DatabaseError(format!("failed to deserialize {err:?}")))?;
+
+        Ok(Some(TokenMetadata::read_from_bytes(&token_metadata_idxdb.metadata)?))
+    }
+
     pub(crate) async fn undo_account_states(
         &self,
         account_states: &[Digest],
     ) -> Result<(), StoreError> {
         let account_commitments =
             account_states.iter().map(ToString::to_string).collect::<Vec<_>>();
-        let promise = idxdb_undo_account_states(account_commitments);
+        let promise = idxdb_undo_account_states(&self.store_name, account_commitments);
         JsFuture::from(promise).await.map_err(|js_error| {
             StoreError::DatabaseError(format!("failed to undo account states: {js_error:?}",))
         })?;
@@ -349,7 +410,7 @@ impl WebStore {
         }
 
         let account_id_str = account_id.to_string();
-        let promise = idxdb_lock_account(account_id_str);
+        let promise = idxdb_lock_account(&self.store_name, account_id_str);
         JsFuture::from(promise).await.map_err(|js_error| {
             StoreError::DatabaseError(format!("failed to lock account: {js_error:?}",))
         })?;
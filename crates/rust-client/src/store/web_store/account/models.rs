@@ -54,6 +54,13 @@ pub struct ForeignAccountCodeIdxdbObject {
     pub code: Vec<u8>,
 }
 
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenMetadataIdxdbObject {
+    #[serde(deserialize_with = "base64_to_vec_u8_required", default)]
+    pub metadata: Vec<u8>,
+}
+
 fn base64_to_vec_u8_required<'de, D>(deserializer: D) -> Result<Vec<u8>, D::Error>
 where
     D: Deserializer<'de>,
@@ -24,46 +24,62 @@ use super::{
 };
 use crate::store::{AccountStatus, StoreError};
 
-pub async fn insert_account_code(account_code: &AccountCode) -> Result<(), JsValue> {
+pub async fn insert_account_code(
+    store_name: &str,
+    account_code: &AccountCode,
+) -> Result<(), JsValue> {
     let root = account_code.commitment().to_string();
     let code = account_code.to_bytes();
 
-    let promise = idxdb_insert_account_code(root, code);
+    let promise = idxdb_insert_account_code(store_name, root, code);
     JsFuture::from(promise).await?;
 
     Ok(())
 }
 
-pub async fn insert_account_storage(account_storage: &AccountStorage) -> Result<(), JsValue> {
+pub async fn insert_account_storage(
+    store_name: &str,
+    account_storage: &AccountStorage,
+) -> Result<(), JsValue> {
     let root = account_storage.commitment().to_string();
 
     let storage = account_storage.to_bytes();
 
-    let promise = idxdb_insert_account_storage(root, storage);
+    let promise = idxdb_insert_account_storage(store_name, root, storage);
     JsFuture::from(promise).await?;
 
     Ok(())
 }
 
-pub async fn insert_account_asset_vault(asset_vault: &AssetVault) -> Result<(), JsValue> {
+pub async fn insert_account_asset_vault(
+    store_name: &str,
+    asset_vault: &AssetVault,
+) -> Result<(), JsValue> {
     let commitment = asset_vault.root().to_string();
     let assets = asset_vault.assets().collect::<Vec<Asset>>().to_bytes();
 
-    let promise = idxdb_insert_account_asset_vault(commitment, assets);
+    let promise = idxdb_insert_account_asset_vault(store_name, commitment, assets);
     JsFuture::from(promise).await?;
 
     Ok(())
 }
 
-pub async fn insert_account_auth(pub_key: String, secret_key: String) -> Result<(), JsValue> {
-    let promise = idxdb_insert_account_auth(pub_key, secret_key);
+pub async fn insert_account_auth(
+    store_name: &str,
+    pub_key: String,
+    secret_key: String,
+) -> Result<(), JsValue> {
+    let promise = idxdb_insert_account_auth(store_name, pub_key, secret_key);
     JsFuture::from(promise).await?;
 
     Ok(())
 }
 
-pub fn get_account_auth_by_pub_key(pub_key: String) -> Result<String, StoreError> {
-    let js_value = idxdb_get_account_auth_by_pub_key(pub_key.clone());
+pub fn get_account_auth_by_pub_key(
+    store_name: &str,
+    pub_key: String,
+) -> Result<String, StoreError> {
+    let js_value = idxdb_get_account_auth_by_pub_key(store_name, pub_key.clone());
     let account_auth_idxdb: Option<AccountAuthIdxdbObject> = from_value(js_value)
         .map_err(|err| StoreError::DatabaseError(format!("failed to deserialize {err:?}")))?;
 
@@ -74,6 +90,7 @@ pub fn get_account_auth_by_pub_key(pub_key: String) -> Result<String, StoreError
 }
 
 pub async fn insert_account_record(
+    store_name: &str,
     account: &Account,
     account_seed: Option<Word>,
 ) -> Result<(), JsValue> {
@@ -87,6 +104,7 @@ pub async fn insert_account_record(
     let commitment = account.commitment().to_string();
 
     let promise = idxdb_insert_account_record(
+        store_name,
         account_id_str,
         code_root,
         storage_root,
@@ -131,8 +149,8 @@ pub fn parse_account_record_idxdb_object(
     Ok((account_header, status))
 }
 
-pub async fn update_account(new_account_state: &Account) -> Result<(), JsValue> {
-    insert_account_storage(new_account_state.storage()).await?;
-    insert_account_asset_vault(new_account_state.vault()).await?;
-    insert_account_record(new_account_state, None).await
+pub async fn update_account(store_name: &str, new_account_state: &Account) -> Result<(), JsValue> {
+    insert_account_storage(store_name, new_account_state.storage()).await?;
+    insert_account_asset_vault(store_name, new_account_state.vault()).await?;
+    insert_account_record(store_name, new_account_state, None).await
 }
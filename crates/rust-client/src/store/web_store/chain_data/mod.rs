@@ -52,6 +52,7 @@ impl WebStore {
         } = serialize_block_header(block_header, &partial_blockchain_peaks, has_client_notes)?;
 
         let promise = idxdb_insert_block_header(
+            &self.store_name,
             block_num,
             header,
             partial_blockchain_peaks,
@@ -73,7 +74,7 @@ impl WebStore {
             .map(|block_number| i64::from(block_number.as_u32()).to_string())
             .collect();
 
-        let promise = idxdb_get_block_headers(formatted_block_numbers_list);
+        let promise = idxdb_get_block_headers(&self.store_name, formatted_block_numbers_list);
         let js_value = JsFuture::from(promise).await.map_err(|js_error| {
             StoreError::DatabaseError(format!("failed to get block headers: {js_error:?}",))
         })?;
@@ -97,7 +98,7 @@ impl WebStore {
     }
 
     pub(crate) async fn get_tracked_block_headers(&self) -> Result<Vec<BlockHeader>, StoreError> {
-        let promise = idxdb_get_tracked_block_headers();
+        let promise = idxdb_get_tracked_block_headers(&self.store_name);
         let js_value = JsFuture::from(promise).await.map_err(|js_error| {
             StoreError::DatabaseError(format!("failed to get tracked block headers: {js_error:?}",))
         })?;
@@ -122,7 +123,7 @@ impl WebStore {
     ) -> Result<BTreeMap<InOrderIndex, Digest>, StoreError> {
         match filter {
             PartialBlockchainFilter::All => {
-                let promise = idxdb_get_partial_blockchain_nodes_all();
+                let promise = idxdb_get_partial_blockchain_nodes_all(&self.store_name);
                 let js_value = JsFuture::from(promise).await.map_err(|js_error| {
                     StoreError::DatabaseError(format!(
                         "failed to get all partial blockchain nodes: {js_error:?}",
@@ -134,7 +135,7 @@ impl WebStore {
                 let formatted_list: Vec<String> =
                     ids.iter().map(|id| (Into::<u64>::into(*id)).to_string()).collect();
 
-                let promise = idxdb_get_partial_blockchain_nodes(formatted_list);
+                let promise = idxdb_get_partial_blockchain_nodes(&self.store_name, formatted_list);
                 let js_value = JsFuture::from(promise).await.map_err(|js_error| {
                     StoreError::DatabaseError(format!(
                         "failed to get partial blockchain nodes: {js_error:?}",
@@ -151,7 +152,8 @@ impl WebStore {
     ) -> Result<MmrPeaks, StoreError> {
         let block_num_as_str = block_num.to_string();
 
-        let promise = idxdb_get_partial_blockchain_peaks_by_block_num(block_num_as_str);
+        let promise =
+            idxdb_get_partial_blockchain_peaks_by_block_num(&self.store_name, block_num_as_str);
         let js_value = JsFuture::from(promise).await.map_err(|js_error| {
             StoreError::DatabaseError(format!(
                 "failed to get partial blockchain peaks by block number: {js_error:?}",
@@ -183,7 +185,11 @@ impl WebStore {
             serialized_nodes.push(node);
         }
 
-        let promise = idxdb_insert_partial_blockchain_nodes(serialized_node_ids, serialized_nodes);
+        let promise = idxdb_insert_partial_blockchain_nodes(
+            &self.store_name,
+            serialized_node_ids,
+            serialized_nodes,
+        );
         JsFuture::from(promise).await.map_err(|js_error| {
             StoreError::DatabaseError(format!(
                 "failed to insert partial blockchain nodes: {js_error:?}",
@@ -194,7 +200,7 @@ impl WebStore {
     }
 
     pub(crate) async fn prune_irrelevant_blocks(&self) -> Result<(), StoreError> {
-        let promise = idxdb_prune_irrelevant_blocks();
+        let promise = idxdb_prune_irrelevant_blocks(&self.store_name);
         JsFuture::from(promise).await.map_err(|js_error| {
             StoreError::DatabaseError(format!("failed to prune block header: {js_error:?}",))
         })?;
@@ -0,0 +1,12 @@
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::{js_sys, wasm_bindgen};
+
+// Counts IndexedDB Operations
+#[wasm_bindgen(module = "/src/store/web_store/js/counts.js")]
+extern "C" {
+    #[wasm_bindgen(js_name = getCounts)]
+    pub fn idxdb_get_counts(store_name: &str) -> js_sys::Promise;
+
+    #[wasm_bindgen(js_name = getNoteTagCounts)]
+    pub fn idxdb_get_note_tag_counts(store_name: &str) -> js_sys::Promise;
+}
@@ -0,0 +1,72 @@
+use alloc::{collections::BTreeMap, format, string::String, vec::Vec};
+
+use base64::{Engine as _, engine::general_purpose};
+use miden_objects::{note::NoteTag, utils::Deserializable};
+use serde::Deserialize;
+use serde_wasm_bindgen::from_value;
+use wasm_bindgen_futures::JsFuture;
+
+use super::WebStore;
+use crate::store::{InputNoteCounts, StoreCounts, StoreError};
+
+mod js_bindings;
+use js_bindings::{idxdb_get_counts, idxdb_get_note_tag_counts};
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct StoreCountsIdxdbObject {
+    accounts: usize,
+    /// Pairs of input note state discriminants and the number of notes in that state.
+    input_notes: Vec<(u8, usize)>,
+    output_notes: usize,
+    transactions: usize,
+}
+
+#[derive(Deserialize)]
+struct NoteTagCountIdxdbObject {
+    /// Base64 encoding of the serialized tag.
+    tag: String,
+    count: usize,
+}
+
+impl WebStore {
+    pub(crate) async fn counts(&self) -> Result<StoreCounts, StoreError> {
+        let promise = idxdb_get_counts(&self.store_name);
+        let js_value = JsFuture::from(promise).await.map_err(|js_error| {
+            StoreError::DatabaseError(format!("failed to get store counts: {js_error:?}"))
+        })?;
+        let counts_idxdb: StoreCountsIdxdbObject = from_value(js_value)
+            .map_err(|err| StoreError::DatabaseError(format!("failed to deserialize {err:?}")))?;
+
+        let mut input_notes = InputNoteCounts::default();
+        for (state_discriminant, count) in counts_idxdb.input_notes {
+            input_notes.add(state_discriminant, count)?;
+        }
+
+        Ok(StoreCounts {
+            accounts: counts_idxdb.accounts,
+            input_notes,
+            output_notes: counts_idxdb.output_notes,
+            transactions: counts_idxdb.transactions,
+        })
+    }
+
+    pub(crate) async fn get_note_tag_counts(&self) -> Result<BTreeMap<NoteTag, usize>, StoreError> {
+        let promise = idxdb_get_note_tag_counts(&self.store_name);
+        let js_value = JsFuture::from(promise).await.map_err(|js_error| {
+            StoreError::DatabaseError(format!("failed to get note tag counts: {js_error:?}"))
+        })?;
+        let counts_idxdb: Vec<NoteTagCountIdxdbObject> = from_value(js_value)
+            .map_err(|err| StoreError::DatabaseError(format!("failed to deserialize {err:?}")))?;
+
+        counts_idxdb
+            .into_iter()
+            .map(|count_idxdb| {
+                let tag = general_purpose::STANDARD.decode(&count_idxdb.tag).map_err(|err| {
+                    StoreError::DatabaseError(format!("failed to decode note tag: {err}"))
+                })?;
+                Ok((NoteTag::read_from_bytes(&tag)?, count_idxdb.count))
+            })
+            .collect()
+    }
+}
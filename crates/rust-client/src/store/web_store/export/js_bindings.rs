@@ -4,5 +4,5 @@ use wasm_bindgen_futures::{js_sys, wasm_bindgen};
 #[wasm_bindgen(module = "/src/store/web_store/js/export.js")]
 extern "C" {
     #[wasm_bindgen(js_name = exportStore)]
-    pub fn idxdb_export_store() -> js_sys::Promise;
+    pub fn idxdb_export_store(store_name: &str) -> js_sys::Promise;
 }
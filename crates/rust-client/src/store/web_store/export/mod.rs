@@ -8,7 +8,7 @@ use wasm_bindgen_futures::JsFuture;
 
 impl WebStore {
     pub async fn export_store(&self) -> Result<JsValue, StoreError> {
-        let promise = idxdb_export_store();
+        let promise = idxdb_export_store(&self.store_name);
         let js_value = JsFuture::from(promise)
             .await
             .map_err(|err| StoreError::DatabaseError(format!("Failed to export store: {err:?}")))?;
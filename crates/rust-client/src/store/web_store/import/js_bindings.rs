@@ -4,6 +4,9 @@ use wasm_bindgen_futures::{js_sys, wasm_bindgen};
 #[wasm_bindgen(module = "/src/store/web_store/js/import.js")]
 extern "C" {
     #[wasm_bindgen(js_name = forceImportStore)]
-    pub fn idxdb_force_import_store(store_dump: JsValue) -> js_sys::Promise;
+    pub fn idxdb_force_import_store(store_name: &str, store_dump: JsValue) -> js_sys::Promise;
+
+    #[wasm_bindgen(js_name = isStoreEmpty)]
+    pub fn idxdb_is_store_empty(store_name: &str) -> js_sys::Promise;
 
 }
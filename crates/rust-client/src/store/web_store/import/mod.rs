@@ -1,17 +1,62 @@
 use super::WebStore;
-use crate::store::StoreError;
+use crate::store::{StoreArchiveImport, StoreError};
 
 mod js_bindings;
-use js_bindings::idxdb_force_import_store;
+use js_bindings::{idxdb_force_import_store, idxdb_is_store_empty};
 use wasm_bindgen::JsValue;
 use wasm_bindgen_futures::JsFuture;
 
 impl WebStore {
     pub async fn force_import_store(&self, store_dump: JsValue) -> Result<(), StoreError> {
-        let promise = idxdb_force_import_store(store_dump);
+        let promise = idxdb_force_import_store(&self.store_name, store_dump);
         JsFuture::from(promise)
             .await
             .map_err(|err| StoreError::DatabaseError(format!("Failed to import store: {err:?}")))?;
         Ok(())
     }
+
+    pub(crate) async fn import_archive(
+        &self,
+        archive: StoreArchiveImport,
+    ) -> Result<(), StoreError> {
+        let promise = idxdb_is_store_empty(&self.store_name);
+        let is_empty = JsFuture::from(promise).await.map_err(|err| {
+            StoreError::DatabaseError(format!(
+                "Failed to check whether the store is empty: {err:?}"
+            ))
+        })?;
+        if !is_empty.as_bool().unwrap_or(false) {
+            return Err(StoreError::StoreNotEmpty);
+        }
+
+        // The archive is imported through several IndexedDB transactions, so the store is
+        // restored from a snapshot if any of them fails
+        let snapshot = self.export_store().await?;
+        if let Err(err) = self.import_archive_contents(archive).await {
+            self.force_import_store(snapshot).await?;
+            return Err(err);
+        }
+
+        Ok(())
+    }
+
+    async fn import_archive_contents(&self, archive: StoreArchiveImport) -> Result<(), StoreError> {
+        let StoreArchiveImport {
+            accounts,
+            state_sync_update,
+            transactions,
+            note_tags,
+        } = archive;
+
+        for (account, seed) in &accounts {
+            self.insert_account(account, *seed).await?;
+        }
+        self.apply_state_sync(state_sync_update).await?;
+        self.upsert_transactions(&transactions).await?;
+        for tag in note_tags {
+            self.add_note_tag(tag).await?;
+        }
+
+        Ok(())
+    }
 }
@@ -1,14 +1,8 @@
-import {
-  accountCodes,
-  accountStorages,
-  accountVaults,
-  accountAuths,
-  accounts,
-  foreignAccountCode,
-} from "./schema.js";
+import { getDatabase } from "./schema.js";
 
 // GET FUNCTIONS
-export async function getAccountIds() {
+export async function getAccountIds(storeName) {
+  const { accounts } = getDatabase(storeName);
   try {
     let allIds = new Set(); // Use a Set to ensure uniqueness
 
@@ -24,7 +18,8 @@ export async function getAccountIds() {
   }
 }
 
-export async function getAllAccountHeaders() {
+export async function getAllAccountHeaders(storeName) {
+  const { accounts } = getDatabase(storeName);
   try {
     // Use a Map to track the latest record for each id based on nonce
     const latestRecordsMap = new Map();
@@ -74,7 +69,8 @@ export async function getAllAccountHeaders() {
   }
 }
 
-export async function getAccountHeader(accountId) {
+export async function getAccountHeader(storeName, accountId) {
+  const { accounts } = getDatabase(storeName);
   try {
     // Fetch all records matching the given id
     const allMatchingRecords = await accounts
@@ -125,7 +121,11 @@ export async function getAccountHeader(accountId) {
   }
 }
 
-export async function getAccountHeaderByCommitment(accountCommitment) {
+export async function getAccountHeaderByCommitment(
+  storeName,
+  accountCommitment
+) {
+  const { accounts } = getDatabase(storeName);
   try {
     // Fetch all records matching the given commitment
     const allMatchingRecords = await accounts
@@ -168,7 +168,8 @@ export async function getAccountHeaderByCommitment(accountCommitment) {
   }
 }
 
-export async function getAccountCode(codeRoot) {
+export async function getAccountCode(storeName, codeRoot) {
+  const { accountCodes } = getDatabase(storeName);
   try {
     // Fetch all records matching the given root
     const allMatchingRecords = await accountCodes
@@ -202,7 +203,8 @@ export async function getAccountCode(codeRoot) {
   }
 }
 
-export async function getAccountStorage(storageRoot) {
+export async function getAccountStorage(storeName, storageRoot) {
+  const { accountStorages } = getDatabase(storeName);
   try {
     // Fetch all records matching the given root
     const allMatchingRecords = await accountStorages
@@ -235,7 +237,8 @@ export async function getAccountStorage(storageRoot) {
   }
 }
 
-export async function getAccountAssetVault(vaultRoot) {
+export async function getAccountAssetVault(storeName, vaultRoot) {
+  const { accountVaults } = getDatabase(storeName);
   try {
     // Fetch all records matching the given root
     const allMatchingRecords = await accountVaults
@@ -269,9 +272,9 @@ export async function getAccountAssetVault(vaultRoot) {
   }
 }
 
-export function getAccountAuthByPubKey(pubKey) {
+export function getAccountAuthByPubKey(storeName, pubKey) {
   // Try to get the account auth from the cache
-  let cachedSecretKey = ACCOUNT_AUTH_MAP.get(pubKey);
+  let cachedSecretKey = accountAuthCache(storeName).get(pubKey);
 
   // If it's not in the cache, throw an error
   if (!cachedSecretKey) {
@@ -285,8 +288,18 @@ export function getAccountAuthByPubKey(pubKey) {
   return data;
 }
 
+// Cached account auths, keyed by store name so that stores don't share keys.
 var ACCOUNT_AUTH_MAP = new Map();
-export async function fetchAndCacheAccountAuthByPubKey(pubKey) {
+function accountAuthCache(storeName) {
+  const key = storeName ?? "";
+  if (!ACCOUNT_AUTH_MAP.has(key)) {
+    ACCOUNT_AUTH_MAP.set(key, new Map());
+  }
+  return ACCOUNT_AUTH_MAP.get(key);
+}
+
+export async function fetchAndCacheAccountAuthByPubKey(storeName, pubKey) {
+  const { accountAuths } = getDatabase(storeName);
   try {
     // Fetch all records matching the given id
     const allMatchingRecords = await accountAuths
@@ -303,7 +316,7 @@ export async function fetchAndCacheAccountAuthByPubKey(pubKey) {
     const authRecord = allMatchingRecords[0];
 
     // Store the auth info in the map
-    ACCOUNT_AUTH_MAP.set(authRecord.pubKey, authRecord.secretKey);
+    accountAuthCache(storeName).set(authRecord.pubKey, authRecord.secretKey);
 
     return {
       secretKey: authRecord.secretKey,
@@ -319,7 +332,8 @@ export async function fetchAndCacheAccountAuthByPubKey(pubKey) {
 
 // INSERT FUNCTIONS
 
-export async function insertAccountCode(codeRoot, code) {
+export async function insertAccountCode(storeName, codeRoot, code) {
+  const { accountCodes } = getDatabase(storeName);
   try {
     // Create a Blob from the ArrayBuffer
     const codeBlob = new Blob([new Uint8Array(code)]);
@@ -341,7 +355,12 @@ export async function insertAccountCode(codeRoot, code) {
   }
 }
 
-export async function insertAccountStorage(storageRoot, storageSlots) {
+export async function insertAccountStorage(
+  storeName,
+  storageRoot,
+  storageSlots
+) {
+  const { accountStorages } = getDatabase(storeName);
   try {
     const storageSlotsBlob = new Blob([new Uint8Array(storageSlots)]);
 
@@ -362,7 +381,8 @@ export async function insertAccountStorage(storageRoot, storageSlots) {
   }
 }
 
-export async function insertAccountAssetVault(vaultRoot, assets) {
+export async function insertAccountAssetVault(storeName, vaultRoot, assets) {
+  const { accountVaults } = getDatabase(storeName);
   try {
     const assetsBlob = new Blob([new Uint8Array(assets)]);
 
@@ -384,6 +404,7 @@ export async function insertAccountAssetVault(vaultRoot, assets) {
 }
 
 export async function insertAccountRecord(
+  storeName,
   accountId,
   codeRoot,
   storageRoot,
@@ -393,6 +414,7 @@ export async function insertAccountRecord(
   accountSeed,
   commitment
 ) {
+  const { accounts } = getDatabase(storeName);
   try {
     let accountSeedBlob = null;
     if (accountSeed) {
@@ -420,7 +442,8 @@ export async function insertAccountRecord(
   }
 }
 
-export async function insertAccountAuth(pubKey, secretKey) {
+export async function insertAccountAuth(storeName, pubKey, secretKey) {
+  const { accountAuths } = getDatabase(storeName);
   try {
     // Prepare the data object to insert
     const data = {
@@ -439,9 +462,15 @@ export async function insertAccountAuth(pubKey, secretKey) {
   }
 }
 
-export async function upsertForeignAccountCode(accountId, code, codeRoot) {
+export async function upsertForeignAccountCode(
+  storeName,
+  accountId,
+  code,
+  codeRoot
+) {
+  const { foreignAccountCode } = getDatabase(storeName);
   try {
-    await insertAccountCode(codeRoot, code);
+    await insertAccountCode(storeName, codeRoot, code);
 
     const data = {
       accountId,
@@ -458,7 +487,8 @@ export async function upsertForeignAccountCode(accountId, code, codeRoot) {
   }
 }
 
-export async function getForeignAccountCode(accountIds) {
+export async function getForeignAccountCode(storeName, accountIds) {
+  const { accountCodes, foreignAccountCode } = getDatabase(storeName);
   try {
     const foreignAccounts = await foreignAccountCode
       .where("accountId")
@@ -499,7 +529,45 @@ export async function getForeignAccountCode(accountIds) {
   }
 }
 
-export async function lockAccount(accountId) {
+export async function upsertTokenMetadata(storeName, faucetId, metadata) {
+  const { tokenMetadata } = getDatabase(storeName);
+  try {
+    const data = {
+      faucetId,
+      metadata: uint8ArrayToBase64(metadata),
+    };
+
+    await tokenMetadata.put(data);
+  } catch (error) {
+    console.error(
+      `Error updating token metadata: (${faucetId}):`,
+      error.toString()
+    );
+    throw error;
+  }
+}
+
+export async function getTokenMetadata(storeName, faucetId) {
+  const { tokenMetadata } = getDatabase(storeName);
+  try {
+    const record = await tokenMetadata.get(faucetId);
+
+    if (!record) {
+      return null;
+    }
+
+    return { metadata: record.metadata };
+  } catch (error) {
+    console.error(
+      `Error fetching token metadata: (${faucetId}):`,
+      error.toString()
+    );
+    throw error;
+  }
+}
+
+export async function lockAccount(storeName, accountId) {
+  const { accounts } = getDatabase(storeName);
   try {
     await accounts.where("id").equals(accountId).modify({ locked: true });
   } catch (error) {
@@ -510,7 +578,8 @@ export async function lockAccount(accountId) {
 
 // Delete functions
 
-export async function undoAccountStates(accountCommitments) {
+export async function undoAccountStates(storeName, accountCommitments) {
+  const { accounts } = getDatabase(storeName);
   try {
     await accounts
       .where("accountCommitment")
@@ -1,12 +1,14 @@
-import { blockHeaders, partialBlockchainNodes, stateSync } from "./schema.js";
+import { getDatabase } from "./schema.js";
 
 // INSERT FUNCTIONS
 export async function insertBlockHeader(
+  storeName,
   blockNum,
   header,
   partialBlockchainPeaks,
   hasClientNotes
 ) {
+  const { blockHeaders } = getDatabase(storeName);
   try {
     const headerBlob = new Blob([new Uint8Array(header)]);
     const partialBlockchainPeaksBlob = new Blob([
@@ -43,7 +45,8 @@ export async function insertBlockHeader(
   }
 }
 
-export async function insertPartialBlockchainNodes(ids, nodes) {
+export async function insertPartialBlockchainNodes(storeName, ids, nodes) {
+  const { partialBlockchainNodes } = getDatabase(storeName);
   try {
     // Check if the arrays are not of the same length
     if (ids.length !== nodes.length) {
@@ -72,7 +75,8 @@ export async function insertPartialBlockchainNodes(ids, nodes) {
 }
 
 // GET FUNCTIONS
-export async function getBlockHeaders(blockNumbers) {
+export async function getBlockHeaders(storeName, blockNumbers) {
+  const { blockHeaders } = getDatabase(storeName);
   try {
     const results = await blockHeaders.bulkGet(blockNumbers);
 
@@ -111,7 +115,8 @@ export async function getBlockHeaders(blockNumbers) {
   }
 }
 
-export async function getTrackedBlockHeaders() {
+export async function getTrackedBlockHeaders(storeName) {
+  const { blockHeaders } = getDatabase(storeName);
   try {
     // Fetch all records matching the given root
     const allMatchingRecords = await blockHeaders
@@ -151,7 +156,8 @@ export async function getTrackedBlockHeaders() {
   }
 }
 
-export async function getPartialBlockchainPeaksByBlockNum(blockNum) {
+export async function getPartialBlockchainPeaksByBlockNum(storeName, blockNum) {
+  const { blockHeaders } = getDatabase(storeName);
   try {
     const blockHeader = await blockHeaders.get(blockNum);
 
@@ -173,7 +179,8 @@ export async function getPartialBlockchainPeaksByBlockNum(blockNum) {
   }
 }
 
-export async function getPartialBlockchainNodesAll() {
+export async function getPartialBlockchainNodesAll(storeName) {
+  const { partialBlockchainNodes } = getDatabase(storeName);
   try {
     const partialBlockchainNodesAll = await partialBlockchainNodes.toArray();
     return partialBlockchainNodesAll;
@@ -183,7 +190,8 @@ export async function getPartialBlockchainNodesAll() {
   }
 }
 
-export async function getPartialBlockchainNodes(ids) {
+export async function getPartialBlockchainNodes(storeName, ids) {
+  const { partialBlockchainNodes } = getDatabase(storeName);
   try {
     const results = await partialBlockchainNodes.bulkGet(ids);
 
@@ -194,7 +202,8 @@ export async function getPartialBlockchainNodes(ids) {
   }
 }
 
-export async function pruneIrrelevantBlocks() {
+export async function pruneIrrelevantBlocks(storeName) {
+  const { stateSync, blockHeaders } = getDatabase(storeName);
   try {
     const syncHeight = await stateSync.get(1);
 
@@ -0,0 +1,47 @@
+import { getDatabase } from "./schema.js";
+
+export async function getCounts(storeName) {
+  const { accounts, inputNotes, outputNotes, transactions } =
+    getDatabase(storeName);
+  try {
+    // Only the index keys are iterated, so no records need to be loaded
+    const accountIds = await accounts.orderBy("id").uniqueKeys();
+
+    const inputNoteCounts = new Map();
+    await inputNotes.orderBy("stateDiscriminant").eachKey((discriminant) => {
+      inputNoteCounts.set(
+        discriminant,
+        (inputNoteCounts.get(discriminant) ?? 0) + 1
+      );
+    });
+
+    return {
+      accounts: accountIds.length,
+      inputNotes: Array.from(inputNoteCounts.entries()),
+      outputNotes: await outputNotes.count(),
+      transactions: await transactions.count(),
+    };
+  } catch (error) {
+    console.error("Error fetching store counts:", error.toString());
+    throw error;
+  }
+}
+
+export async function getNoteTagCounts(storeName) {
+  const { tags, inputNotes } = getDatabase(storeName);
+  try {
+    // A tag is tracked once per source, so each unique tag is counted once. The
+    // notes are counted through the tag index without loading them.
+    const uniqueTags = await tags.orderBy("tag").uniqueKeys();
+
+    return await Promise.all(
+      uniqueTags.map(async (tag) => ({
+        tag,
+        count: await inputNotes.where("tag").equals(tag).count(),
+      }))
+    );
+  } catch (error) {
+    console.error("Error fetching note tag counts:", error.toString());
+    throw error;
+  }
+}
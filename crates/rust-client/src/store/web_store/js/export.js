@@ -1,4 +1,4 @@
-import { db } from "./schema.js";
+import { getDatabase } from "./schema.js";
 
 async function recursivelyTransformForExport(obj) {
   if (obj instanceof Blob) {
@@ -26,7 +26,8 @@ async function recursivelyTransformForExport(obj) {
   return obj;
 }
 
-export async function exportStore() {
+export async function exportStore(storeName) {
+  const { db } = getDatabase(storeName);
   const dbJson = {};
   for (const table of db.tables) {
     const records = await table.toArray();
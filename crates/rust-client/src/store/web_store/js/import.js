@@ -1,4 +1,4 @@
-import { db, openDatabase } from "./schema.js";
+import { getDatabase, openDatabase } from "./schema.js";
 
 async function recursivelyTransformForImport(obj) {
   if (obj && typeof obj === "object") {
@@ -22,10 +22,11 @@ async function recursivelyTransformForImport(obj) {
   return obj; // Return unchanged if it's neither Blob, Array, nor Object
 }
 
-export async function forceImportStore(jsonStr) {
+export async function forceImportStore(storeName, jsonStr) {
+  const { db } = getDatabase(storeName);
   try {
     if (!db.isOpen) {
-      await openDatabase();
+      await openDatabase(storeName);
     }
 
     let dbJson = JSON.parse(jsonStr);
@@ -77,6 +78,65 @@ export async function forceImportStore(jsonStr) {
   }
 }
 
+// Returns whether the store holds no data besides the genesis block header.
+// Keys in the `accountAuth` table aren't checked, as they aren't part of a store
+// archive.
+export async function isStoreEmpty(storeName) {
+  const {
+    accountCodes,
+    accountStorages,
+    accountVaults,
+    accounts,
+    transactions,
+    transactionScripts,
+    inputNotes,
+    outputNotes,
+    notesScripts,
+    stateSync,
+    blockHeaders,
+    partialBlockchainNodes,
+    tags,
+    foreignAccountCode,
+    tokenMetadata,
+  } = getDatabase(storeName);
+  try {
+    const tables = [
+      accountCodes,
+      accountStorages,
+      accountVaults,
+      accounts,
+      transactions,
+      transactionScripts,
+      inputNotes,
+      outputNotes,
+      notesScripts,
+      partialBlockchainNodes,
+      tags,
+      foreignAccountCode,
+      tokenMetadata,
+    ];
+    for (const table of tables) {
+      if ((await table.count()) > 0) {
+        return false;
+      }
+    }
+
+    const syncRecord = await stateSync.get(1);
+    if (syncRecord && syncRecord.blockNum !== "0") {
+      return false;
+    }
+
+    const nonGenesisHeaders = await blockHeaders
+      .where("blockNum")
+      .notEqual("0")
+      .count();
+    return nonGenesisHeaders === 0;
+  } catch (err) {
+    console.error("Failed to check whether the store is empty: ", err.toString());
+    throw err;
+  }
+}
+
 function base64ToUint8Array(base64) {
   const binaryString = atob(base64);
   const len = binaryString.length;
@@ -1,12 +1,7 @@
-import {
-  db,
-  inputNotes,
-  outputNotes,
-  notesScripts,
-  transactions,
-} from "./schema.js";
-
-export async function getOutputNotes(states) {
+import { getDatabase, PENDING_NOTE_TAG } from "./schema.js";
+
+export async function getOutputNotes(storeName, states) {
+  const { outputNotes } = getDatabase(storeName);
   try {
     let notes;
 
@@ -27,7 +22,8 @@ export async function getOutputNotes(states) {
   }
 }
 
-export async function getInputNotes(states) {
+export async function getInputNotes(storeName, states) {
+  const { inputNotes } = getDatabase(storeName);
   try {
     let notes;
 
@@ -41,42 +37,73 @@ export async function getInputNotes(states) {
         .toArray();
     }
 
-    return await processInputNotes(notes);
+    return await processInputNotes(storeName, notes);
   } catch (err) {
     console.error("Failed to get input notes: ", err.toString());
     throw err;
   }
 }
 
-export async function getInputNotesFromIds(noteIds) {
+export async function getInputNotesCursor(
+  storeName,
+  states,
+  afterNoteId,
+  pageSize
+) {
+  const { inputNotes } = getDatabase(storeName);
+  try {
+    // Walk the notes in note ID order so pages are stable across calls
+    let collection = afterNoteId
+      ? inputNotes.where("noteId").above(afterNoteId)
+      : inputNotes.orderBy("noteId");
+
+    if (states.length !== 0) {
+      collection = collection.filter((note) =>
+        states.includes(note.stateDiscriminant)
+      );
+    }
+
+    const notes = await collection.limit(pageSize).toArray();
+
+    return await processInputNotes(storeName, notes);
+  } catch (err) {
+    console.error("Failed to get input notes page: ", err.toString());
+    throw err;
+  }
+}
+
+export async function getInputNotesFromIds(storeName, noteIds) {
+  const { inputNotes } = getDatabase(storeName);
   try {
     let notes;
 
     // Fetch the records based on a list of IDs
     notes = await inputNotes.where("noteId").anyOf(noteIds).toArray();
 
-    return await processInputNotes(notes);
+    return await processInputNotes(storeName, notes);
   } catch (err) {
     console.error("Failed to get input notes: ", err.toString());
     throw err;
   }
 }
 
-export async function getInputNotesFromNullifiers(nullifiers) {
+export async function getInputNotesFromNullifiers(storeName, nullifiers) {
+  const { inputNotes } = getDatabase(storeName);
   try {
     let notes;
 
     // Fetch the records based on a list of IDs
     notes = await inputNotes.where("nullifier").anyOf(nullifiers).toArray();
 
-    return await processInputNotes(notes);
+    return await processInputNotes(storeName, notes);
   } catch (err) {
     console.error("Failed to get input notes: ", err.toString());
     throw err;
   }
 }
 
-export async function getOutputNotesFromNullifiers(nullifiers) {
+export async function getOutputNotesFromNullifiers(storeName, nullifiers) {
+  const { outputNotes } = getDatabase(storeName);
   try {
     let notes;
 
@@ -90,7 +117,8 @@ export async function getOutputNotesFromNullifiers(nullifiers) {
   }
 }
 
-export async function getOutputNotesFromIds(noteIds) {
+export async function getOutputNotesFromIds(storeName, noteIds) {
+  const { outputNotes } = getDatabase(storeName);
   try {
     let notes;
 
@@ -104,7 +132,8 @@ export async function getOutputNotesFromIds(noteIds) {
   }
 }
 
-export async function getUnspentInputNoteNullifiers() {
+export async function getUnspentInputNoteNullifiers(storeName) {
+  const { inputNotes } = getDatabase(storeName);
   try {
     const notes = await inputNotes
       .where("stateDiscriminant")
@@ -122,7 +151,24 @@ export async function getUnspentInputNoteNullifiers() {
   }
 }
 
+export async function getInputNoteIdsWithPendingTag(storeName) {
+  const { inputNotes } = getDatabase(storeName);
+  try {
+    return await inputNotes
+      .where("tag")
+      .equals(PENDING_NOTE_TAG)
+      .primaryKeys();
+  } catch (err) {
+    console.error(
+      "Failed to get input notes with pending tag: ",
+      err.toString()
+    );
+    throw err;
+  }
+}
+
 export async function upsertInputNote(
+  storeName,
   noteId,
   assets,
   serialNumber,
@@ -132,8 +178,10 @@ export async function upsertInputNote(
   nullifier,
   serializedCreatedAt,
   stateDiscriminant,
-  state
+  state,
+  tag
 ) {
+  const { db, inputNotes, notesScripts } = getDatabase(storeName);
   return db.transaction("rw", inputNotes, notesScripts, async (tx) => {
     try {
       let assetsBlob = new Blob([new Uint8Array(assets)]);
@@ -151,6 +199,8 @@ export async function upsertInputNote(
         nullifier: nullifier,
         state: stateBlob,
         stateDiscriminant: stateDiscriminant,
+        // Stored as in the tags table so that notes can be counted by tag
+        tag: tag ? uint8ArrayToBase64(new Uint8Array(tag)) : null,
         createdAt: serializedCreatedAt,
       };
 
@@ -175,6 +225,7 @@ export async function upsertInputNote(
 }
 
 export async function upsertOutputNote(
+  storeName,
   noteId,
   assets,
   recipientDigest,
@@ -184,6 +235,7 @@ export async function upsertOutputNote(
   stateDiscriminant,
   state
 ) {
+  const { db, outputNotes, notesScripts } = getDatabase(storeName);
   return db.transaction("rw", outputNotes, notesScripts, async (tx) => {
     try {
       let assetsBlob = new Blob([new Uint8Array(assets)]);
@@ -211,7 +263,8 @@ export async function upsertOutputNote(
   });
 }
 
-async function processInputNotes(notes) {
+async function processInputNotes(storeName, notes) {
+  const { transactions, notesScripts } = getDatabase(storeName);
   // Fetch all scripts from the scripts table for joining
   const transactionRecords = await transactions.toArray();
   const transactionMap = new Map(
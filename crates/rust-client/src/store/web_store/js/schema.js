@@ -2,10 +2,10 @@ import Dexie from "dexie";
 
 const DATABASE_NAME = "MidenClientDB";
 
-export async function openDatabase() {
+export async function openDatabase(storeName) {
   console.log("Opening database...");
   try {
-    await db.open();
+    await getDatabase(storeName).db.open();
     console.log("Database opened successfully");
     return true;
   } catch (err) {
@@ -30,78 +30,110 @@ const Table = {
   PartialBlockchainNodes: "partialBlockchainNodes",
   Tags: "tags",
   ForeignAccountCode: "foreignAccountCode",
+  TokenMetadata: "tokenMetadata",
 };
 
-const db = new Dexie(DATABASE_NAME);
-db.version(1).stores({
-  [Table.AccountCode]: indexes("root"),
-  [Table.AccountStorage]: indexes("root"),
-  [Table.AccountVaults]: indexes("root"),
-  [Table.AccountAuth]: indexes("pubKey"),
-  [Table.Accounts]: indexes(
-    "&accountCommitment",
-    "id",
-    "codeRoot",
-    "storageRoot",
-    "vaultRoot"
-  ),
-  [Table.Transactions]: indexes("id"),
-  [Table.TransactionScripts]: indexes("scriptRoot"),
-  [Table.InputNotes]: indexes("noteId", "nullifier", "stateDiscriminant"),
-  [Table.OutputNotes]: indexes(
-    "noteId",
-    "recipientDigest",
-    "stateDiscriminant",
-    "nullifier"
-  ),
-  [Table.NotesScripts]: indexes("scriptRoot"),
-  [Table.StateSync]: indexes("id"),
-  [Table.BlockHeaders]: indexes("blockNum", "hasClientNotes"),
-  [Table.PartialBlockchainNodes]: indexes("id"),
-  [Table.Tags]: indexes("id++", "tag", "source_note_id", "source_account_id"),
-  [Table.ForeignAccountCode]: indexes("accountId"),
-});
+// Value of the tag index of input notes whose tag hasn't been set yet
+export const PENDING_NOTE_TAG = "pending";
 
-function indexes(...items) {
-  return items.join(",");
+// Databases are namespaced by a store name so that multiple clients can coexist
+// in the same browser. The default (unnamed) store keeps the original name.
+const databases = new Map();
+
+function databaseName(storeName) {
+  return storeName ? `${DATABASE_NAME}_${storeName}` : DATABASE_NAME;
 }
 
-db.on("populate", () => {
-  // Populate the stateSync table with default values
-  db.stateSync.put({ id: 1, blockNum: "0" });
-});
+function createDatabase(storeName) {
+  const db = new Dexie(databaseName(storeName));
+  db.version(1).stores({
+    [Table.AccountCode]: indexes("root"),
+    [Table.AccountStorage]: indexes("root"),
+    [Table.AccountVaults]: indexes("root"),
+    [Table.AccountAuth]: indexes("pubKey"),
+    [Table.Accounts]: indexes(
+      "&accountCommitment",
+      "id",
+      "codeRoot",
+      "storageRoot",
+      "vaultRoot"
+    ),
+    [Table.Transactions]: indexes("id"),
+    [Table.TransactionScripts]: indexes("scriptRoot"),
+    [Table.InputNotes]: indexes("noteId", "nullifier", "stateDiscriminant"),
+    [Table.OutputNotes]: indexes(
+      "noteId",
+      "recipientDigest",
+      "stateDiscriminant",
+      "nullifier"
+    ),
+    [Table.NotesScripts]: indexes("scriptRoot"),
+    [Table.StateSync]: indexes("id"),
+    [Table.BlockHeaders]: indexes("blockNum", "hasClientNotes"),
+    [Table.PartialBlockchainNodes]: indexes("id"),
+    [Table.Tags]: indexes("id++", "tag", "source_note_id", "source_account_id"),
+    [Table.ForeignAccountCode]: indexes("accountId"),
+  });
+  db.version(2).stores({
+    [Table.TokenMetadata]: indexes("faucetId"),
+  });
+  // The tag of an input note is only part of its serialized state, so notes
+  // stored before the tag index was added are marked and get their tag set by
+  // the store when it's opened
+  db.version(3)
+    .stores({
+      [Table.InputNotes]: indexes(
+        "noteId",
+        "nullifier",
+        "stateDiscriminant",
+        "tag"
+      ),
+    })
+    .upgrade((tx) =>
+      tx
+        .table(Table.InputNotes)
+        .toCollection()
+        .modify((note) => {
+          note.tag = PENDING_NOTE_TAG;
+        })
+    );
 
-const accountCodes = db.table(Table.AccountCode);
-const accountStorages = db.table(Table.AccountStorage);
-const accountVaults = db.table(Table.AccountVaults);
-const accountAuths = db.table(Table.AccountAuth);
-const accounts = db.table(Table.Accounts);
-const transactions = db.table(Table.Transactions);
-const transactionScripts = db.table(Table.TransactionScripts);
-const inputNotes = db.table(Table.InputNotes);
-const outputNotes = db.table(Table.OutputNotes);
-const notesScripts = db.table(Table.NotesScripts);
-const stateSync = db.table(Table.StateSync);
-const blockHeaders = db.table(Table.BlockHeaders);
-const partialBlockchainNodes = db.table(Table.PartialBlockchainNodes);
-const tags = db.table(Table.Tags);
-const foreignAccountCode = db.table(Table.ForeignAccountCode);
+  db.on("populate", () => {
+    // Populate the stateSync table with default values
+    db.stateSync.put({ id: 1, blockNum: "0" });
+  });
 
-export {
-  db,
-  accountCodes,
-  accountStorages,
-  accountVaults,
-  accountAuths,
-  accounts,
-  transactions,
-  transactionScripts,
-  inputNotes,
-  outputNotes,
-  notesScripts,
-  stateSync,
-  blockHeaders,
-  partialBlockchainNodes,
-  tags,
-  foreignAccountCode,
-};
+  return {
+    db,
+    accountCodes: db.table(Table.AccountCode),
+    accountStorages: db.table(Table.AccountStorage),
+    accountVaults: db.table(Table.AccountVaults),
+    accountAuths: db.table(Table.AccountAuth),
+    accounts: db.table(Table.Accounts),
+    transactions: db.table(Table.Transactions),
+    transactionScripts: db.table(Table.TransactionScripts),
+    inputNotes: db.table(Table.InputNotes),
+    outputNotes: db.table(Table.OutputNotes),
+    notesScripts: db.table(Table.NotesScripts),
+    stateSync: db.table(Table.StateSync),
+    blockHeaders: db.table(Table.BlockHeaders),
+    partialBlockchainNodes: db.table(Table.PartialBlockchainNodes),
+    tags: db.table(Table.Tags),
+    foreignAccountCode: db.table(Table.ForeignAccountCode),
+    tokenMetadata: db.table(Table.TokenMetadata),
+  };
+}
+
+// Returns the database (and its tables) for the provided store name, creating
+// it if needed.
+export function getDatabase(storeName) {
+  const name = databaseName(storeName);
+  if (!databases.has(name)) {
+    databases.set(name, createDatabase(storeName));
+  }
+  return databases.get(name);
+}
+
+function indexes(...items) {
+  return items.join(",");
+}
@@ -1,15 +1,7 @@
-import {
-  db,
-  stateSync,
-  inputNotes,
-  outputNotes,
-  transactions,
-  blockHeaders,
-  partialBlockchainNodes,
-  tags,
-} from "./schema.js";
-
-export async function getNoteTags() {
+import { getDatabase } from "./schema.js";
+
+export async function getNoteTags(storeName) {
+  const { tags } = getDatabase(storeName);
   try {
     let records = await tags.toArray();
 
@@ -28,7 +20,8 @@ export async function getNoteTags() {
   }
 }
 
-export async function getSyncHeight() {
+export async function getSyncHeight(storeName) {
+  const { stateSync } = getDatabase(storeName);
   try {
     const record = await stateSync.get(1); // Since id is the primary key and always 1
     if (record) {
@@ -45,14 +38,23 @@ export async function getSyncHeight() {
   }
 }
 
-export async function addNoteTag(tag, sourceNoteId, sourceAccountId) {
+export async function addNoteTag(
+  storeName,
+  tag,
+  sourceNoteId,
+  sourceAccountId,
+  subscription
+) {
+  const { tags } = getDatabase(storeName);
   try {
     let tagArray = new Uint8Array(tag);
     let tagBase64 = uint8ArrayToBase64(tagArray);
+    let subscriptionBase64 = uint8ArrayToBase64(new Uint8Array(subscription));
     await tags.add({
       tag: tagBase64,
       sourceNoteId: sourceNoteId ? sourceNoteId : "",
       sourceAccountId: sourceAccountId ? sourceAccountId : "",
+      subscription: subscriptionBase64,
     });
   } catch (err) {
     console.error("Failed to add note tag: ", err.toString());
@@ -60,7 +62,13 @@ export async function addNoteTag(tag, sourceNoteId, sourceAccountId) {
   }
 }
 
-export async function removeNoteTag(tag, sourceNoteId, sourceAccountId) {
+export async function removeNoteTag(
+  storeName,
+  tag,
+  sourceNoteId,
+  sourceAccountId
+) {
+  const { tags } = getDatabase(storeName);
   try {
     let tagArray = new Uint8Array(tag);
     let tagBase64 = uint8ArrayToBase64(tagArray);
@@ -79,6 +87,7 @@ export async function removeNoteTag(tag, sourceNoteId, sourceAccountId) {
 }
 
 export async function applyStateSync(
+  storeName,
   blockNum,
   newBlockHeadersAsFlattenedVec,
   newBlockNums,
@@ -88,6 +97,16 @@ export async function applyStateSync(
   nodes,
   inputNoteIds
 ) {
+  const {
+    db,
+    transactions,
+    inputNotes,
+    outputNotes,
+    stateSync,
+    blockHeaders,
+    partialBlockchainNodes,
+    tags,
+  } = getDatabase(storeName);
   const newBlockHeaders = reconstructFlattenedVec(
     newBlockHeadersAsFlattenedVec
   );
@@ -121,6 +140,85 @@ export async function applyStateSync(
   );
 }
 
+export async function rollbackToBlock(
+  storeName,
+  blockNum,
+  orphanedNodeIds,
+  inputNoteIds,
+  inputNoteStateDiscriminants,
+  inputNoteStatesAsFlattenedVec,
+  outputNoteIds,
+  outputNoteStateDiscriminants,
+  outputNoteStatesAsFlattenedVec,
+  restoredTagsAsFlattenedVec,
+  restoredTagNoteIds,
+  restoredTagSubscriptionsAsFlattenedVec
+) {
+  const {
+    db,
+    stateSync,
+    blockHeaders,
+    partialBlockchainNodes,
+    inputNotes,
+    outputNotes,
+    tags,
+  } = getDatabase(storeName);
+  const inputNoteStates = reconstructFlattenedVec(inputNoteStatesAsFlattenedVec);
+  const outputNoteStates = reconstructFlattenedVec(
+    outputNoteStatesAsFlattenedVec
+  );
+  const restoredTags = reconstructFlattenedVec(restoredTagsAsFlattenedVec);
+  const restoredTagSubscriptions = reconstructFlattenedVec(
+    restoredTagSubscriptionsAsFlattenedVec
+  );
+
+  return await db.transaction(
+    "rw",
+    stateSync,
+    blockHeaders,
+    partialBlockchainNodes,
+    inputNotes,
+    outputNotes,
+    tags,
+    async (tx) => {
+      await updateSyncHeight(tx, blockNum);
+
+      // Block numbers are stored as strings, so they need to be compared as numbers
+      const rolledBackBlockNums = (await tx.blockHeaders.toArray())
+        .filter((record) => parseInt(record.blockNum) > parseInt(blockNum))
+        .map((record) => record.blockNum);
+
+      await tx.blockHeaders.bulkDelete(rolledBackBlockNums);
+      await tx.partialBlockchainNodes.bulkDelete(orphanedNodeIds);
+
+      for (let i = 0; i < inputNoteIds.length; i++) {
+        await tx.inputNotes.update(inputNoteIds[i], {
+          stateDiscriminant: inputNoteStateDiscriminants[i],
+          state: new Blob([new Uint8Array(inputNoteStates[i])]),
+        });
+      }
+
+      for (let i = 0; i < outputNoteIds.length; i++) {
+        await tx.outputNotes.update(outputNoteIds[i], {
+          stateDiscriminant: outputNoteStateDiscriminants[i],
+          state: new Blob([new Uint8Array(outputNoteStates[i])]),
+        });
+      }
+
+      for (let i = 0; i < restoredTags.length; i++) {
+        await tx.tags.add({
+          tag: uint8ArrayToBase64(new Uint8Array(restoredTags[i])),
+          sourceNoteId: restoredTagNoteIds[i],
+          sourceAccountId: "",
+          subscription: uint8ArrayToBase64(
+            new Uint8Array(restoredTagSubscriptions[i])
+          ),
+        });
+      }
+    }
+  );
+}
+
 async function updateSyncHeight(tx, blockNum) {
   try {
     await tx.stateSync.update(1, { blockNum: blockNum });
@@ -214,7 +312,7 @@ function uint8ArrayToBase64(bytes) {
 }
 
 // Helper function to reconstruct arrays from flattened data
-function reconstructFlattenedVec(flattenedVec) {
+export function reconstructFlattenedVec(flattenedVec) {
   const data = flattenedVec.data();
   const lengths = flattenedVec.lengths();
 
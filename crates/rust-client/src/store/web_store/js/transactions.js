@@ -1,7 +1,9 @@
-import { transactions, transactionScripts } from "./schema.js";
+import { getDatabase } from "./schema.js";
+import { reconstructFlattenedVec } from "./sync.js";
 
 const IDS_FILTER_PREFIX = "Ids:";
-export async function getTransactions(filter) {
+export async function getTransactions(storeName, filter) {
+  const { transactions, transactionScripts } = getDatabase(storeName);
   let transactionRecords;
 
   try {
@@ -100,7 +102,8 @@ export async function getTransactions(filter) {
   }
 }
 
-export async function insertTransactionScript(scriptRoot, txScript) {
+export async function insertTransactionScript(storeName, scriptRoot, txScript) {
+  const { transactionScripts } = getDatabase(storeName);
   try {
     // check if script root already exists
     let record = await transactionScripts
@@ -141,6 +144,7 @@ export async function insertTransactionScript(scriptRoot, txScript) {
 }
 
 export async function upsertTransactionRecord(
+  storeName,
   transactionId,
   details,
   scriptRoot,
@@ -148,6 +152,7 @@ export async function upsertTransactionRecord(
   committed,
   discardCause
 ) {
+  const { transactions } = getDatabase(storeName);
   try {
     let detailsBlob = new Blob([new Uint8Array(details)]);
 
@@ -178,6 +183,35 @@ export async function upsertTransactionRecord(
   }
 }
 
+export async function removeTransactions(
+  storeName,
+  transactionIds,
+  releasedNoteIds,
+  releasedNoteStateDiscriminants,
+  releasedNoteStatesAsFlattenedVec
+) {
+  const { db, transactions, inputNotes } = getDatabase(storeName);
+  const releasedNoteStates = reconstructFlattenedVec(
+    releasedNoteStatesAsFlattenedVec
+  );
+
+  try {
+    await db.transaction("rw", transactions, inputNotes, async (tx) => {
+      for (let i = 0; i < releasedNoteIds.length; i++) {
+        await tx.inputNotes.update(releasedNoteIds[i], {
+          stateDiscriminant: releasedNoteStateDiscriminants[i],
+          state: new Blob([new Uint8Array(releasedNoteStates[i])]),
+        });
+      }
+
+      await tx.transactions.where("id").anyOf(transactionIds).delete();
+    });
+  } catch (err) {
+    console.error("Failed to remove transactions: ", err.toString());
+    throw err;
+  }
+}
+
 function uint8ArrayToBase64(bytes) {
   const binary = bytes.reduce(
     (acc, byte) => acc + String.fromCharCode(byte),
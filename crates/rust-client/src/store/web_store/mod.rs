@@ -10,15 +10,18 @@
 use alloc::{
     boxed::Box,
     collections::{BTreeMap, BTreeSet},
+    string::{String, ToString},
     vec::Vec,
 };
 
 use miden_objects::{
     Digest, Word,
     account::{Account, AccountCode, AccountHeader, AccountId},
+    asset::Asset,
     block::{BlockHeader, BlockNumber},
     crypto::merkle::{InOrderIndex, MmrPeaks},
-    note::Nullifier,
+    note::{NoteTag, Nullifier},
+    transaction::TransactionId,
 };
 use tonic::async_trait;
 use wasm_bindgen::prelude::*;
@@ -26,9 +29,10 @@ use wasm_bindgen_futures::{JsFuture, js_sys, wasm_bindgen};
 
 use super::{
     AccountRecord, AccountStatus, InputNoteRecord, NoteFilter, OutputNoteRecord,
-    PartialBlockchainFilter, Store, StoreError, TransactionFilter,
+    PartialBlockchainFilter, Store, StoreArchiveImport, StoreCounts, StoreError, TransactionFilter,
 };
 use crate::{
+    account::TokenMetadata,
     sync::{NoteTagRecord, StateSyncUpdate},
     transaction::{TransactionRecord, TransactionStoreUpdate},
 };
@@ -38,6 +42,7 @@ compile_error!("The `idxdb` feature is only supported when targeting wasm32.");
 
 pub mod account;
 pub mod chain_data;
+pub mod counts;
 pub mod export;
 pub mod import;
 pub mod note;
@@ -48,15 +53,34 @@ pub mod transaction;
 #[wasm_bindgen(module = "/src/store/web_store/js/schema.js")]
 extern "C" {
     #[wasm_bindgen(js_name = openDatabase)]
-    fn setup_indexed_db() -> js_sys::Promise;
+    fn setup_indexed_db(store_name: &str) -> js_sys::Promise;
 }
 
-pub struct WebStore {}
+pub struct WebStore {
+    /// Name used to namespace the `IndexedDB` database, so that multiple stores can coexist in
+    /// the same browser. An empty name refers to the default database.
+    store_name: String,
+}
 
 impl WebStore {
-    pub async fn new() -> Result<WebStore, JsValue> {
-        JsFuture::from(setup_indexed_db()).await?;
-        Ok(WebStore {})
+    /// Opens (or creates) the `IndexedDB` database for the provided store name. If no name is
+    /// provided, the default database is used.
+    pub async fn new(store_name: Option<String>) -> Result<WebStore, JsValue> {
+        let store_name = store_name.unwrap_or_default();
+        JsFuture::from(setup_indexed_db(&store_name)).await?;
+
+        let store = WebStore { store_name };
+        store
+            .set_pending_input_note_tags()
+            .await
+            .map_err(|err| JsValue::from_str(&err.to_string()))?;
+
+        Ok(store)
+    }
+
+    /// Returns the name used to namespace the store's `IndexedDB` database.
+    pub fn store_name(&self) -> &str {
+        &self.store_name
     }
 }
 
@@ -89,6 +113,14 @@ impl Store for WebStore {
         self.apply_state_sync(state_sync_update).await
     }
 
+    async fn rollback_to(&self, block_num: BlockNumber) -> Result<(), StoreError> {
+        self.rollback_to(block_num).await
+    }
+
+    async fn import_archive(&self, archive: StoreArchiveImport) -> Result<(), StoreError> {
+        self.import_archive(archive).await
+    }
+
     // TRANSACTIONS
     // --------------------------------------------------------------------------------------------
 
@@ -103,6 +135,21 @@ impl Store for WebStore {
         self.apply_transaction(tx_update).await
     }
 
+    async fn upsert_transactions(
+        &self,
+        transactions: &[TransactionRecord],
+    ) -> Result<(), StoreError> {
+        self.upsert_transactions(transactions).await
+    }
+
+    async fn remove_transactions(
+        &self,
+        transaction_ids: &[TransactionId],
+        released_notes: &[InputNoteRecord],
+    ) -> Result<(), StoreError> {
+        self.remove_transactions(transaction_ids, released_notes).await
+    }
+
     // NOTES
     // --------------------------------------------------------------------------------------------
     async fn get_input_notes(
@@ -216,6 +263,10 @@ impl Store for WebStore {
         self.get_account(account_id).await
     }
 
+    async fn get_account_assets(&self, account_id: AccountId) -> Result<Vec<Asset>, StoreError> {
+        self.get_account_assets(account_id).await
+    }
+
     async fn upsert_foreign_account_code(
         &self,
         account_id: AccountId,
@@ -231,7 +282,33 @@ impl Store for WebStore {
         self.get_foreign_account_code(account_ids).await
     }
 
+    async fn upsert_token_metadata(
+        &self,
+        faucet_id: AccountId,
+        metadata: TokenMetadata,
+    ) -> Result<(), StoreError> {
+        self.upsert_token_metadata(faucet_id, metadata).await
+    }
+
+    async fn get_token_metadata(
+        &self,
+        faucet_id: AccountId,
+    ) -> Result<Option<TokenMetadata>, StoreError> {
+        self.get_token_metadata(faucet_id).await
+    }
+
     async fn get_unspent_input_note_nullifiers(&self) -> Result<Vec<Nullifier>, StoreError> {
         self.get_unspent_input_note_nullifiers().await
     }
+
+    // COUNTS
+    // --------------------------------------------------------------------------------------------
+
+    async fn counts(&self) -> Result<StoreCounts, StoreError> {
+        self.counts().await
+    }
+
+    async fn get_note_tag_counts(&self) -> Result<BTreeMap<NoteTag, usize>, StoreError> {
+        self.get_note_tag_counts().await
+    }
 }
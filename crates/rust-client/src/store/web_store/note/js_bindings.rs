@@ -11,31 +11,55 @@ extern "C" {
     // ================================================================================================
 
     #[wasm_bindgen(js_name = getInputNotes)]
-    pub fn idxdb_get_input_notes(states: Vec<u8>) -> js_sys::Promise;
+    pub fn idxdb_get_input_notes(store_name: &str, states: Vec<u8>) -> js_sys::Promise;
+
+    #[wasm_bindgen(js_name = getInputNotesCursor)]
+    pub fn idxdb_get_input_notes_cursor(
+        store_name: &str,
+        states: Vec<u8>,
+        after_note_id: Option<String>,
+        page_size: u32,
+    ) -> js_sys::Promise;
 
     #[wasm_bindgen(js_name = getInputNotesFromIds)]
-    pub fn idxdb_get_input_notes_from_ids(note_ids: Vec<String>) -> js_sys::Promise;
+    pub fn idxdb_get_input_notes_from_ids(
+        store_name: &str,
+        note_ids: Vec<String>,
+    ) -> js_sys::Promise;
 
     #[wasm_bindgen(js_name = getInputNotesFromNullifiers)]
-    pub fn idxdb_get_input_notes_from_nullifiers(nullifiers: Vec<String>) -> js_sys::Promise;
+    pub fn idxdb_get_input_notes_from_nullifiers(
+        store_name: &str,
+        nullifiers: Vec<String>,
+    ) -> js_sys::Promise;
 
     #[wasm_bindgen(js_name = getOutputNotes)]
-    pub fn idxdb_get_output_notes(states: Vec<u8>) -> js_sys::Promise;
+    pub fn idxdb_get_output_notes(store_name: &str, states: Vec<u8>) -> js_sys::Promise;
 
     #[wasm_bindgen(js_name = getOutputNotesFromIds)]
-    pub fn idxdb_get_output_notes_from_ids(note_ids: Vec<String>) -> js_sys::Promise;
+    pub fn idxdb_get_output_notes_from_ids(
+        store_name: &str,
+        note_ids: Vec<String>,
+    ) -> js_sys::Promise;
 
     #[wasm_bindgen(js_name = getOutputNotesFromNullifiers)]
-    pub fn idxdb_get_output_notes_from_nullifiers(nullifiers: Vec<String>) -> js_sys::Promise;
+    pub fn idxdb_get_output_notes_from_nullifiers(
+        store_name: &str,
+        nullifiers: Vec<String>,
+    ) -> js_sys::Promise;
 
     #[wasm_bindgen(js_name = getUnspentInputNoteNullifiers)]
-    pub fn idxdb_get_unspent_input_note_nullifiers() -> js_sys::Promise;
+    pub fn idxdb_get_unspent_input_note_nullifiers(store_name: &str) -> js_sys::Promise;
+
+    #[wasm_bindgen(js_name = getInputNoteIdsWithPendingTag)]
+    pub fn idxdb_get_input_note_ids_with_pending_tag(store_name: &str) -> js_sys::Promise;
 
     // INSERTS
     // ================================================================================================
 
     #[wasm_bindgen(js_name = upsertInputNote)]
     pub fn idxdb_upsert_input_note(
+        store_name: &str,
         note_id: String,
         assets: Vec<u8>,
         serial_number: Vec<u8>,
@@ -46,10 +70,12 @@ extern "C" {
         serialized_created_at: String,
         state_discriminant: u8,
         state: Vec<u8>,
+        tag: Option<Vec<u8>>,
     ) -> js_sys::Promise;
 
     #[wasm_bindgen(js_name = upsertOutputNote)]
     pub fn idxdb_upsert_output_note(
+        store_name: &str,
         note_id: String,
         assets: Vec<u8>,
         recipient_digest: String,
@@ -4,7 +4,10 @@ use alloc::{
 };
 
 use js_sys::{Array, Promise};
-use miden_objects::{Digest, note::Nullifier};
+use miden_objects::{
+    Digest,
+    note::{NoteId, Nullifier},
+};
 use serde_wasm_bindgen::from_value;
 use wasm_bindgen::JsValue;
 use wasm_bindgen_futures::{JsFuture, js_sys, wasm_bindgen};
@@ -16,9 +19,10 @@ use crate::store::{
 
 mod js_bindings;
 use js_bindings::{
-    idxdb_get_input_notes, idxdb_get_input_notes_from_ids, idxdb_get_input_notes_from_nullifiers,
-    idxdb_get_output_notes, idxdb_get_output_notes_from_ids,
-    idxdb_get_output_notes_from_nullifiers, idxdb_get_unspent_input_note_nullifiers,
+    idxdb_get_input_note_ids_with_pending_tag, idxdb_get_input_notes, idxdb_get_input_notes_cursor,
+    idxdb_get_input_notes_from_ids, idxdb_get_input_notes_from_nullifiers, idxdb_get_output_notes,
+    idxdb_get_output_notes_from_ids, idxdb_get_output_notes_from_nullifiers,
+    idxdb_get_unspent_input_note_nullifiers,
 };
 
 mod models;
@@ -32,8 +36,9 @@ impl WebStore {
         &self,
         filter: NoteFilter,
     ) -> Result<Vec<InputNoteRecord>, StoreError> {
-        let js_value =
-            JsFuture::from(filter.to_input_notes_promise()).await.map_err(|js_error| {
+        let js_value = JsFuture::from(filter.to_input_notes_promise(&self.store_name))
+            .await
+            .map_err(|js_error| {
                 StoreError::DatabaseError(format!("failed to get input notes: {js_error:?}"))
             })?;
         let input_notes_idxdb: Vec<InputNoteIdxdbObject> = from_value(js_value)
@@ -45,12 +50,70 @@ impl WebStore {
             .collect::<Result<Vec<_>, _>>() // Collect results into a single Result
     }
 
+    /// Retrieves at most `page_size` input notes matching the filter, ordered by note ID and
+    /// starting right after `after_note_id` (or from the first note if `None`).
+    ///
+    /// Unlike [`WebStore::get_input_notes`], state-based filters are resolved with an IndexedDB
+    /// cursor, so only the requested page is loaded into memory.
+    pub async fn get_input_notes_page(
+        &self,
+        filter: &NoteFilter,
+        after_note_id: Option<NoteId>,
+        page_size: u32,
+    ) -> Result<Vec<InputNoteRecord>, StoreError> {
+        let Some(states) = filter.to_input_note_states() else {
+            // Filters by identifier are bounded by the caller, so paginate them in memory
+            let after_note_id = after_note_id.map(|id| id.inner().to_string());
+            let mut notes: Vec<(String, InputNoteRecord)> = self
+                .get_input_notes(filter.clone())
+                .await?
+                .into_iter()
+                .map(|note| (note.id().inner().to_string(), note))
+                .collect();
+            notes.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+            return Ok(notes
+                .into_iter()
+                .filter(|(id, _)| after_note_id.as_ref().is_none_or(|after| id > after))
+                .map(|(_, note)| note)
+                .take(page_size as usize)
+                .collect());
+        };
+
+        let after_note_id = after_note_id.map(|id| id.inner().to_string());
+        let promise =
+            idxdb_get_input_notes_cursor(&self.store_name, states, after_note_id, page_size);
+        let js_value = JsFuture::from(promise).await.map_err(|js_error| {
+            StoreError::DatabaseError(format!("failed to get input notes page: {js_error:?}"))
+        })?;
+        let input_notes_idxdb: Vec<InputNoteIdxdbObject> = from_value(js_value)
+            .map_err(|err| StoreError::DatabaseError(format!("failed to deserialize {err:?}")))?;
+
+        input_notes_idxdb
+            .into_iter()
+            .map(parse_input_note_idxdb_object)
+            .collect::<Result<Vec<_>, _>>()
+    }
+
+    /// Returns a cursor over the input notes matching the filter that yields them in pages of
+    /// `page_size` notes.
+    pub fn input_notes_cursor(&self, filter: NoteFilter, page_size: u32) -> InputNotesCursor<'_> {
+        InputNotesCursor {
+            store: self,
+            filter,
+            page_size,
+            last_note_id: None,
+            exhausted: false,
+        }
+    }
+
     pub(crate) async fn get_output_notes(
         &self,
         filter: NoteFilter,
     ) -> Result<Vec<OutputNoteRecord>, StoreError> {
-        let js_value =
-            JsFuture::from(filter.to_output_note_promise()).await.map_err(|js_error| {
+        let js_value = JsFuture::from(filter.to_output_note_promise(&self.store_name))
+            .await
+            .map_err(|js_error| {
                 StoreError::DatabaseError(format!("failed to get output notes: {js_error:?}"))
             })?;
 
@@ -66,7 +129,7 @@ impl WebStore {
     pub(crate) async fn get_unspent_input_note_nullifiers(
         &self,
     ) -> Result<Vec<Nullifier>, StoreError> {
-        let promise = idxdb_get_unspent_input_note_nullifiers();
+        let promise = idxdb_get_unspent_input_note_nullifiers(&self.store_name);
         let js_value = JsFuture::from(promise).await.map_err(|js_error| {
             StoreError::DatabaseError(format!(
                 "failed to get unspent input note nullifiers: {js_error:?}"
@@ -81,78 +144,147 @@ impl WebStore {
             .collect::<Result<Vec<Nullifier>, _>>()
     }
 
+    /// Sets the tag of the input notes stored before notes were indexed by tag, which is only
+    /// part of their serialized state, by upserting them again.
+    pub(crate) async fn set_pending_input_note_tags(&self) -> Result<(), StoreError> {
+        let promise = idxdb_get_input_note_ids_with_pending_tag(&self.store_name);
+        let js_value = JsFuture::from(promise).await.map_err(|js_error| {
+            StoreError::DatabaseError(format!(
+                "failed to get input notes with pending tag: {js_error:?}"
+            ))
+        })?;
+        let note_ids_as_str: Vec<String> = from_value(js_value)
+            .map_err(|err| StoreError::DatabaseError(format!("failed to deserialize {err:?}")))?;
+        if note_ids_as_str.is_empty() {
+            return Ok(());
+        }
+
+        let note_ids = note_ids_as_str
+            .iter()
+            .map(|id| NoteId::try_from_hex(id))
+            .collect::<Result<Vec<_>, _>>()?;
+        let notes = self.get_input_notes(NoteFilter::List(note_ids)).await?;
+
+        self.upsert_input_notes(&notes).await
+    }
+
     pub(crate) async fn upsert_input_notes(
         &self,
         notes: &[InputNoteRecord],
     ) -> Result<(), StoreError> {
         for note in notes {
-            upsert_input_note_tx(note).await?;
+            upsert_input_note_tx(&self.store_name, note).await?;
         }
 
         Ok(())
     }
 }
 
-impl NoteFilter {
-    fn to_input_notes_promise(&self) -> Promise {
-        match self {
-            NoteFilter::All
-            | NoteFilter::Consumed
-            | NoteFilter::Committed
-            | NoteFilter::Expected
-            | NoteFilter::Processing
-            | NoteFilter::Unspent
-            | NoteFilter::Unverified => {
-                let states: Vec<u8> = match self {
-                    NoteFilter::All => vec![],
-                    NoteFilter::Consumed => vec![
-                        InputNoteState::STATE_CONSUMED_AUTHENTICATED_LOCAL,
-                        InputNoteState::STATE_CONSUMED_UNAUTHENTICATED_LOCAL,
-                        InputNoteState::STATE_CONSUMED_EXTERNAL,
-                    ],
-                    NoteFilter::Committed => vec![InputNoteState::STATE_COMMITTED],
-                    NoteFilter::Expected => vec![InputNoteState::STATE_EXPECTED],
-                    NoteFilter::Processing => {
-                        vec![
-                            InputNoteState::STATE_PROCESSING_AUTHENTICATED,
-                            InputNoteState::STATE_PROCESSING_UNAUTHENTICATED,
-                        ]
-                    },
-                    NoteFilter::Unverified => vec![InputNoteState::STATE_UNVERIFIED],
-                    NoteFilter::Unspent => vec![
-                        InputNoteState::STATE_EXPECTED,
-                        InputNoteState::STATE_COMMITTED,
-                        InputNoteState::STATE_UNVERIFIED,
-                        InputNoteState::STATE_PROCESSING_AUTHENTICATED,
-                        InputNoteState::STATE_PROCESSING_UNAUTHENTICATED,
-                    ],
-                    _ => unreachable!(), // Safety net, should never be reached
-                };
+// INPUT NOTES CURSOR
+// ================================================================================================
+
+/// Iterates over the input notes stored in a [`WebStore`] one page at a time, so that large
+/// stores don't need to be loaded into memory at once.
+pub struct InputNotesCursor<'store> {
+    store: &'store WebStore,
+    filter: NoteFilter,
+    page_size: u32,
+    last_note_id: Option<NoteId>,
+    exhausted: bool,
+}
+
+impl InputNotesCursor<'_> {
+    /// Returns the next page of input notes, or `None` once all matching notes have been
+    /// returned.
+    pub async fn next_page(&mut self) -> Result<Option<Vec<InputNoteRecord>>, StoreError> {
+        if self.exhausted || self.page_size == 0 {
+            return Ok(None);
+        }
+
+        let page = self
+            .store
+            .get_input_notes_page(&self.filter, self.last_note_id, self.page_size)
+            .await?;
+
+        if page.len() < self.page_size as usize {
+            self.exhausted = true;
+        }
+
+        match page.last() {
+            Some(note) => {
+                self.last_note_id = Some(note.id());
+                Ok(Some(page))
+            },
+            None => {
+                self.exhausted = true;
+                Ok(None)
+            },
+        }
+    }
+}
 
-                // Assuming `js_fetch_notes` is your JavaScript function that handles simple string
-                // filters
-                idxdb_get_input_notes(states)
+impl NoteFilter {
+    /// Returns the input note states matched by this filter, or `None` if the filter selects
+    /// notes by identifier instead of by state.
+    fn to_input_note_states(&self) -> Option<Vec<u8>> {
+        let states = match self {
+            NoteFilter::All => vec![],
+            NoteFilter::Consumed => vec![
+                InputNoteState::STATE_CONSUMED_AUTHENTICATED_LOCAL,
+                InputNoteState::STATE_CONSUMED_UNAUTHENTICATED_LOCAL,
+                InputNoteState::STATE_CONSUMED_EXTERNAL,
+            ],
+            NoteFilter::Committed => vec![InputNoteState::STATE_COMMITTED],
+            NoteFilter::Expected => vec![InputNoteState::STATE_EXPECTED],
+            NoteFilter::Processing => {
+                vec![
+                    InputNoteState::STATE_PROCESSING_AUTHENTICATED,
+                    InputNoteState::STATE_PROCESSING_UNAUTHENTICATED,
+                ]
             },
+            NoteFilter::Unverified => vec![InputNoteState::STATE_UNVERIFIED],
+            NoteFilter::Unspent => vec![
+                InputNoteState::STATE_EXPECTED,
+                InputNoteState::STATE_COMMITTED,
+                InputNoteState::STATE_UNVERIFIED,
+                InputNoteState::STATE_PROCESSING_AUTHENTICATED,
+                InputNoteState::STATE_PROCESSING_UNAUTHENTICATED,
+            ],
+            NoteFilter::List(_) | NoteFilter::Unique(_) | NoteFilter::Nullifiers(_) => {
+                return None;
+            },
+        };
+
+        Some(states)
+    }
+
+    fn to_input_notes_promise(&self, store_name: &str) -> Promise {
+        if let Some(states) = self.to_input_note_states() {
+            return idxdb_get_input_notes(store_name, states);
+        }
+
+        match self {
             NoteFilter::List(ids) => {
                 let note_ids_as_str: Vec<String> =
                     ids.iter().map(|id| id.inner().to_string()).collect();
-                idxdb_get_input_notes_from_ids(note_ids_as_str)
+                idxdb_get_input_notes_from_ids(store_name, note_ids_as_str)
             },
             NoteFilter::Unique(id) => {
                 let note_id_as_str = id.inner().to_string();
                 let note_ids = vec![note_id_as_str];
-                idxdb_get_input_notes_from_ids(note_ids)
+                idxdb_get_input_notes_from_ids(store_name, note_ids)
             },
             NoteFilter::Nullifiers(nullifiers) => {
                 let nullifiers_as_str =
                     nullifiers.iter().map(ToString::to_string).collect::<Vec<String>>();
 
-                idxdb_get_input_notes_from_nullifiers(nullifiers_as_str)
+                idxdb_get_input_notes_from_nullifiers(store_name, nullifiers_as_str)
             },
+            _ => unreachable!(), // State filters are handled above
         }
     }
 
-    fn to_output_note_promise(&self) -> Promise {
+    fn to_output_note_promise(&self, store_name: &str) -> Promise {
         match self {
             NoteFilter::All
             | NoteFilter::Consumed
@@ -177,7 +309,7 @@ impl NoteFilter {
                     _ => unreachable!(), // Safety net, should never be reached
                 };
 
-                idxdb_get_output_notes(states)
+                idxdb_get_output_notes(store_name, states)
             },
             NoteFilter::Processing | NoteFilter::Unverified => {
                 Promise::resolve(&JsValue::from(Array::new()))
@@ -185,18 +317,18 @@ impl NoteFilter {
             NoteFilter::List(ids) => {
                 let note_ids_as_str: Vec<String> =
                     ids.iter().map(|id| id.inner().to_string()).collect();
-                idxdb_get_output_notes_from_ids(note_ids_as_str)
+                idxdb_get_output_notes_from_ids(store_name, note_ids_as_str)
             },
             NoteFilter::Unique(id) => {
                 let note_id_as_str = id.inner().to_string();
                 let note_ids = vec![note_id_as_str];
-                idxdb_get_output_notes_from_ids(note_ids)
+                idxdb_get_output_notes_from_ids(store_name, note_ids)
             },
             NoteFilter::Nullifiers(nullifiers) => {
                 let nullifiers_as_str =
                     nullifiers.iter().map(ToString::to_string).collect::<Vec<String>>();
 
-                idxdb_get_output_notes_from_nullifiers(nullifiers_as_str)
+                idxdb_get_output_notes_from_nullifiers(store_name, nullifiers_as_str)
             },
         }
     }
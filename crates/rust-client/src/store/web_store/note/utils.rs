@@ -34,6 +34,7 @@ pub struct SerializedInputNoteData {
     pub nullifier: String,
     pub state_discriminant: u8,
     pub state: Vec<u8>,
+    pub tag: Option<Vec<u8>>,
     pub created_at: String,
 }
 
@@ -65,6 +66,7 @@ pub(crate) fn serialize_input_note(note: &InputNoteRecord) -> SerializedInputNot
 
     let state_discriminant = note.state().discriminant();
     let state = note.state().to_bytes();
+    let tag = note.tag().map(|tag| tag.to_bytes());
     let created_at = Utc::now().timestamp().to_string();
 
     SerializedInputNoteData {
@@ -77,14 +79,19 @@ pub(crate) fn serialize_input_note(note: &InputNoteRecord) -> SerializedInputNot
         nullifier,
         state_discriminant,
         state,
+        tag,
         created_at,
     }
 }
 
-pub async fn upsert_input_note_tx(note: &InputNoteRecord) -> Result<(), StoreError> {
+pub async fn upsert_input_note_tx(
+    store_name: &str,
+    note: &InputNoteRecord,
+) -> Result<(), StoreError> {
     let serialized_data = serialize_input_note(note);
 
     let promise = idxdb_upsert_input_note(
+        store_name,
         serialized_data.note_id,
         serialized_data.note_assets,
         serialized_data.serial_number,
@@ -95,6 +102,7 @@ pub async fn upsert_input_note_tx(note: &InputNoteRecord) -> Result<(), StoreErr
         serialized_data.created_at,
         serialized_data.state_discriminant,
         serialized_data.state,
+        serialized_data.tag,
     );
     JsFuture::from(promise).await.map_err(|js_error| {
         StoreError::DatabaseError(format!("failed to upsert input note: {js_error:?}"))
@@ -126,10 +134,14 @@ pub(crate) fn serialize_output_note(note: &OutputNoteRecord) -> SerializedOutput
     }
 }
 
-pub async fn upsert_output_note_tx(note: &OutputNoteRecord) -> Result<(), StoreError> {
+pub async fn upsert_output_note_tx(
+    store_name: &str,
+    note: &OutputNoteRecord,
+) -> Result<(), StoreError> {
     let serialized_data = serialize_output_note(note);
 
     let result = JsFuture::from(idxdb_upsert_output_note(
+        store_name,
         serialized_data.note_id,
         serialized_data.note_assets,
         serialized_data.recipient_digest,
@@ -194,14 +206,15 @@ pub fn parse_output_note_idxdb_object(
 }
 
 pub(crate) async fn apply_note_updates_tx(
+    store_name: &str,
     note_updates: &NoteUpdateTracker,
 ) -> Result<(), StoreError> {
     for input_note in note_updates.updated_input_notes() {
-        upsert_input_note_tx(input_note.inner()).await?;
+        upsert_input_note_tx(store_name, input_note.inner()).await?;
     }
 
     for output_note in note_updates.updated_output_notes() {
-        upsert_output_note_tx(output_note.inner()).await?;
+        upsert_output_note_tx(store_name, output_note.inner()).await?;
     }
 
     Ok(())
@@ -13,23 +13,26 @@ extern "C" {
     // ================================================================================================
 
     #[wasm_bindgen(js_name = getSyncHeight)]
-    pub fn idxdb_get_sync_height() -> js_sys::Promise;
+    pub fn idxdb_get_sync_height(store_name: &str) -> js_sys::Promise;
 
     #[wasm_bindgen(js_name = getNoteTags)]
-    pub fn idxdb_get_note_tags() -> js_sys::Promise;
+    pub fn idxdb_get_note_tags(store_name: &str) -> js_sys::Promise;
 
     // INSERTS
     // ================================================================================================
 
     #[wasm_bindgen(js_name = addNoteTag)]
     pub fn idxdb_add_note_tag(
+        store_name: &str,
         tag: Vec<u8>,
         source_note_id: Option<String>,
         source_account_id: Option<String>,
+        subscription: Vec<u8>,
     ) -> js_sys::Promise;
 
     #[wasm_bindgen(js_name = applyStateSync)]
     pub fn idxdb_apply_state_sync(
+        store_name: &str,
         block_num: String,
         flattened_new_block_headers: FlattenedU8Vec,
         new_block_nums: Vec<String>,
@@ -44,11 +47,31 @@ extern "C" {
     // ================================================================================================
     #[wasm_bindgen(js_name = removeNoteTag)]
     pub fn idxdb_remove_note_tag(
+        store_name: &str,
         tag: Vec<u8>,
         source_note_id: Option<String>,
         source_account_id: Option<String>,
     ) -> js_sys::Promise;
 
     #[wasm_bindgen(js_name = discardTransactions)]
-    pub fn idxdb_discard_transactions(transactions: Vec<String>) -> js_sys::Promise;
+    pub fn idxdb_discard_transactions(
+        store_name: &str,
+        transactions: Vec<String>,
+    ) -> js_sys::Promise;
+
+    #[wasm_bindgen(js_name = rollbackToBlock)]
+    pub fn idxdb_rollback_to_block(
+        store_name: &str,
+        block_num: String,
+        orphaned_node_ids: Vec<String>,
+        input_note_ids: Vec<String>,
+        input_note_state_discriminants: Vec<u8>,
+        flattened_input_note_states: FlattenedU8Vec,
+        output_note_ids: Vec<String>,
+        output_note_state_discriminants: Vec<u8>,
+        flattened_output_note_states: FlattenedU8Vec,
+        flattened_restored_tags: FlattenedU8Vec,
+        restored_tag_note_ids: Vec<String>,
+        flattened_restored_tag_subscriptions: FlattenedU8Vec,
+    ) -> js_sys::Promise;
 }
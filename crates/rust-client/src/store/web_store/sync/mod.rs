@@ -1,4 +1,5 @@
 use alloc::{
+    collections::BTreeSet,
     string::{String, ToString},
     vec::Vec,
 };
@@ -20,25 +21,29 @@ use super::{
     transaction::utils::upsert_transaction_record,
 };
 use crate::{
-    store::StoreError,
-    sync::{NoteTagRecord, NoteTagSource, StateSyncUpdate},
+    store::{
+        InputNoteState, NoteFilter, PartialBlockchainFilter, StoreError, TransactionFilter,
+        account_changed_after, input_note_states::ExpectedNoteState,
+        is_partial_blockchain_node_in_forest,
+    },
+    sync::{NoteTagRecord, NoteTagSource, StateSyncUpdate, TagSubscription},
 };
 
 mod js_bindings;
 use js_bindings::{
     idxdb_add_note_tag, idxdb_apply_state_sync, idxdb_get_note_tags, idxdb_get_sync_height,
-    idxdb_remove_note_tag,
+    idxdb_remove_note_tag, idxdb_rollback_to_block,
 };
 
 mod models;
 use models::{NoteTagIdxdbObject, SyncHeightIdxdbObject};
 
-mod flattened_vec;
+pub(super) mod flattened_vec;
 use flattened_vec::flatten_nested_u8_vec;
 
 impl WebStore {
     pub(crate) async fn get_note_tags(&self) -> Result<Vec<NoteTagRecord>, StoreError> {
-        let promise = idxdb_get_note_tags();
+        let promise = idxdb_get_note_tags(&self.store_name);
         let js_value = JsFuture::from(promise).await.map_err(|js_error| {
             StoreError::DatabaseError(format!("failed to get note tags: {js_error:?}"))
         })?;
@@ -59,9 +64,16 @@ impl WebStore {
                     _ => return Err(StoreError::ParsingError("Invalid NoteTagSource".to_string())),
                 };
 
+                // Tags stored before subscriptions were introduced keep every received note
+                let subscription = match t.subscription {
+                    Some(subscription) => TagSubscription::read_from_bytes(&subscription)?,
+                    None => TagSubscription::Prefix,
+                };
+
                 Ok(NoteTagRecord {
                     tag: NoteTag::read_from_bytes(&t.tag)?,
                     source,
+                    subscription,
                 })
             })
             .collect::<Result<Vec<_>, _>>()?;
@@ -70,7 +82,7 @@ impl WebStore {
     }
 
     pub(super) async fn get_sync_height(&self) -> Result<BlockNumber, StoreError> {
-        let promise = idxdb_get_sync_height();
+        let promise = idxdb_get_sync_height(&self.store_name);
         let js_value = JsFuture::from(promise).await.map_err(|js_error| {
             StoreError::DatabaseError(format!("failed to get sync height: {js_error:?}"))
         })?;
@@ -82,7 +94,12 @@ impl WebStore {
     }
 
     pub(super) async fn add_note_tag(&self, tag: NoteTagRecord) -> Result<bool, StoreError> {
-        if self.get_note_tags().await?.contains(&tag) {
+        if self
+            .get_note_tags()
+            .await?
+            .iter()
+            .any(|record| record.tag == tag.tag && record.source == tag.source)
+        {
             return Ok(false);
         }
 
@@ -92,7 +109,13 @@ impl WebStore {
             NoteTagSource::User => (None, None),
         };
 
-        let promise = idxdb_add_note_tag(tag.tag.to_bytes(), source_note_id, source_account_id);
+        let promise = idxdb_add_note_tag(
+            &self.store_name,
+            tag.tag.to_bytes(),
+            source_note_id,
+            source_account_id,
+            tag.subscription.to_bytes(),
+        );
         JsFuture::from(promise).await.map_err(|js_error| {
             StoreError::DatabaseError(format!("failed to add note tag: {js_error:?}"))
         })?;
@@ -107,7 +130,12 @@ impl WebStore {
             NoteTagSource::User => (None, None),
         };
 
-        let promise = idxdb_remove_note_tag(tag.tag.to_bytes(), source_note_id, source_account_id);
+        let promise = idxdb_remove_note_tag(
+            &self.store_name,
+            tag.tag.to_bytes(),
+            source_note_id,
+            source_account_id,
+        );
         let js_value = JsFuture::from(promise).await.map_err(|js_error| {
             StoreError::DatabaseError(format!("failed to remove note tag: {js_error:?}"))
         })?;
@@ -154,7 +182,7 @@ impl WebStore {
 
         // TODO: LOP INTO idxdb_apply_state_sync call
         // Update notes
-        apply_note_updates_tx(&note_updates).await?;
+        apply_note_updates_tx(&self.store_name, &note_updates).await?;
 
         // Tags to remove
         let note_tags_to_remove_as_str: Vec<String> = note_updates
@@ -179,13 +207,13 @@ impl WebStore {
             .committed_transactions()
             .chain(transaction_updates.discarded_transactions())
         {
-            upsert_transaction_record(transaction_record).await?;
+            upsert_transaction_record(&self.store_name, transaction_record).await?;
         }
 
         // TODO: LOP INTO idxdb_apply_state_sync call
         // Update public accounts on the db that have been updated onchain
         for account in account_updates.updated_public_accounts() {
-            update_account(&account.clone()).await.map_err(|err| {
+            update_account(&self.store_name, &account.clone()).await.map_err(|err| {
                 StoreError::DatabaseError(format!("failed to update account: {err:?}"))
             })?;
         }
@@ -207,6 +235,7 @@ impl WebStore {
         self.undo_account_states(&account_states_to_rollback).await?;
 
         let promise = idxdb_apply_state_sync(
+            &self.store_name,
             block_num.to_string(),
             flatten_nested_u8_vec(block_headers_as_bytes),
             block_nums_as_str,
@@ -222,4 +251,91 @@ impl WebStore {
 
         Ok(())
     }
+
+    pub(super) async fn rollback_to(&self, block_num: BlockNumber) -> Result<(), StoreError> {
+        if self.get_block_headers(&BTreeSet::from([block_num])).await?.is_empty() {
+            return Err(StoreError::BlockHeaderNotFound(block_num));
+        }
+
+        // Account states aren't reverted, so the tracked accounts can't have changed after the block
+        let transactions = self.get_transactions(TransactionFilter::All).await?;
+        if let Some(account_id) =
+            account_changed_after(&transactions, &self.get_account_ids().await?, block_num)
+        {
+            return Err(StoreError::AccountChangedAfterBlock(account_id, block_num));
+        }
+
+        let forest = u64::from(block_num.as_u32()) + 1;
+        let orphaned_node_ids: Vec<String> = self
+            .get_partial_blockchain_nodes(PartialBlockchainFilter::All)
+            .await?
+            .into_keys()
+            .filter(|index| !is_partial_blockchain_node_in_forest(*index, forest))
+            .map(|index| index.inner().to_string())
+            .collect();
+
+        // The updated records are written along with the removal of the rolled back blocks in a
+        // single database transaction
+        let mut input_note_ids = Vec::new();
+        let mut input_note_state_discriminants = Vec::new();
+        let mut input_note_states = Vec::new();
+        let mut restored_tags = Vec::new();
+        for mut note in self.get_input_notes(NoteFilter::All).await? {
+            if !note.rolled_back_to(block_num) {
+                continue;
+            }
+
+            // Expected notes need their tag to be tracked again to be found by the sync
+            if let InputNoteState::Expected(ExpectedNoteState { tag: Some(tag), .. }) = note.state()
+            {
+                restored_tags.push((note.id(), NoteTagRecord::with_note_source(*tag, note.id())));
+            }
+
+            input_note_ids.push(note.id().inner().to_string());
+            input_note_state_discriminants.push(note.state().discriminant());
+            input_note_states.push(note.state().to_bytes());
+        }
+
+        let mut output_note_ids = Vec::new();
+        let mut output_note_state_discriminants = Vec::new();
+        let mut output_note_states = Vec::new();
+        for mut note in self.get_output_notes(NoteFilter::All).await? {
+            if note.rolled_back_to(block_num) {
+                output_note_ids.push(note.id().inner().to_string());
+                output_note_state_discriminants.push(note.state().discriminant());
+                output_note_states.push(note.state().to_bytes());
+            }
+        }
+
+        let tracked_tags = self.get_note_tags().await?;
+        restored_tags.retain(|(_, tag)| {
+            !tracked_tags
+                .iter()
+                .any(|record| record.tag == tag.tag && record.source == tag.source)
+        });
+
+        let promise = idxdb_rollback_to_block(
+            &self.store_name,
+            block_num.to_string(),
+            orphaned_node_ids,
+            input_note_ids,
+            input_note_state_discriminants,
+            flatten_nested_u8_vec(input_note_states),
+            output_note_ids,
+            output_note_state_discriminants,
+            flatten_nested_u8_vec(output_note_states),
+            flatten_nested_u8_vec(
+                restored_tags.iter().map(|(_, record)| record.tag.to_bytes()).collect(),
+            ),
+            restored_tags.iter().map(|(note_id, _)| note_id.to_hex()).collect(),
+            flatten_nested_u8_vec(
+                restored_tags.iter().map(|(_, record)| record.subscription.to_bytes()).collect(),
+            ),
+        );
+        JsFuture::from(promise).await.map_err(|js_error| {
+            StoreError::DatabaseError(format!("failed to roll back to block: {js_error:?}"))
+        })?;
+
+        Ok(())
+    }
 }
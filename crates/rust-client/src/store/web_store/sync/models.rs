@@ -16,6 +16,8 @@ pub struct NoteTagIdxdbObject {
     pub tag: Vec<u8>,
     pub source_note_id: Option<String>,
     pub source_account_id: Option<String>,
+    #[serde(deserialize_with = "base64_to_vec_u8_optional", default)]
+    pub subscription: Option<Vec<u8>>,
 }
 
 fn base64_to_vec_u8_required<'de, D>(deserializer: D) -> Result<Vec<u8>, D::Error>
@@ -27,3 +29,17 @@ where
         .decode(&base64_str)
         .map_err(|e| Error::custom(format!("Base64 decode error: {e}")))
 }
+
+fn base64_to_vec_u8_optional<'de, D>(deserializer: D) -> Result<Option<Vec<u8>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let base64_str: Option<String> = Option::deserialize(deserializer)?;
+    match base64_str {
+        Some(str) => general_purpose::STANDARD
+            .decode(&str)
+            .map(Some)
+            .map_err(|e| Error::custom(format!("Base64 decode error: {e}"))),
+        None => Ok(None),
+    }
+}
@@ -3,6 +3,8 @@ use alloc::{string::String, vec::Vec};
 use wasm_bindgen::prelude::*;
 use wasm_bindgen_futures::{js_sys, wasm_bindgen};
 
+use crate::store::web_store::sync::flattened_vec::FlattenedU8Vec;
+
 // Transactions IndexedDB Operations
 #[wasm_bindgen(module = "/src/store/web_store/js/transactions.js")]
 
@@ -11,16 +13,18 @@ extern "C" {
     // ================================================================================================
 
     #[wasm_bindgen(js_name = getTransactions)]
-    pub fn idxdb_get_transactions(filter: String) -> js_sys::Promise;
+    pub fn idxdb_get_transactions(store_name: &str, filter: String) -> js_sys::Promise;
 
     #[wasm_bindgen(js_name = insertTransactionScript)]
     pub fn idxdb_insert_transaction_script(
+        store_name: &str,
         script_root: Vec<u8>,
         tx_script: Option<Vec<u8>>,
     ) -> js_sys::Promise;
 
     #[wasm_bindgen(js_name = upsertTransactionRecord)]
     pub fn idxdb_upsert_transaction_record(
+        store_name: &str,
         transaction_id: String,
         details: Vec<u8>,
         script_root: Option<Vec<u8>>,
@@ -28,4 +32,16 @@ extern "C" {
         committed: Option<String>,
         discard_cause: Option<Vec<u8>>,
     ) -> js_sys::Promise;
+
+    // DELETES
+    // ================================================================================================
+
+    #[wasm_bindgen(js_name = removeTransactions)]
+    pub fn idxdb_remove_transactions(
+        store_name: &str,
+        transaction_ids: Vec<String>,
+        released_note_ids: Vec<String>,
+        released_note_state_discriminants: Vec<u8>,
+        flattened_released_note_states: FlattenedU8Vec,
+    ) -> js_sys::Promise;
 }
@@ -3,14 +3,21 @@ use alloc::{
     vec::Vec,
 };
 
-use miden_objects::{Digest, block::BlockNumber, transaction::TransactionScript};
-use miden_tx::utils::Deserializable;
+use miden_objects::{
+    Digest,
+    block::BlockNumber,
+    transaction::{TransactionId, TransactionScript},
+};
+use miden_tx::utils::{Deserializable, Serializable};
 use serde_wasm_bindgen::from_value;
 use wasm_bindgen_futures::JsFuture;
 
-use super::{WebStore, account::utils::update_account, note::utils::apply_note_updates_tx};
+use super::{
+    WebStore, account::utils::update_account, note::utils::apply_note_updates_tx,
+    sync::flattened_vec::flatten_nested_u8_vec,
+};
 use crate::{
-    store::{StoreError, TransactionFilter},
+    store::{InputNoteRecord, StoreError, TransactionFilter, is_expired_transaction},
     transaction::{
         DiscardCause, TransactionDetails, TransactionRecord, TransactionStatus,
         TransactionStoreUpdate,
@@ -18,20 +25,20 @@ use crate::{
 };
 
 mod js_bindings;
-use js_bindings::idxdb_get_transactions;
+use js_bindings::{idxdb_get_transactions, idxdb_remove_transactions};
 
 mod models;
 use models::TransactionIdxdbObject;
 
 pub mod utils;
-use utils::insert_proven_transaction_data;
+use utils::{insert_proven_transaction_data, upsert_transaction_record};
 
 impl WebStore {
     pub async fn get_transactions(
         &self,
         filter: TransactionFilter,
     ) -> Result<Vec<TransactionRecord>, StoreError> {
-        let filter_as_str = match filter {
+        let filter_as_str = match &filter {
             TransactionFilter::All => "All",
             TransactionFilter::Uncommitted => "Uncommitted",
             TransactionFilter::Ids(ids) => &{
@@ -42,9 +49,12 @@ impl WebStore {
             TransactionFilter::ExpiredBefore(block_number) => {
                 &format!("ExpiredPending:{block_number}")
             },
+            // The expiration block is part of the serialized details, so the expiration predicate
+            // is applied after the uncommitted transactions are fetched
+            TransactionFilter::Expired(_) => "Uncommitted",
         };
 
-        let promise = idxdb_get_transactions(filter_as_str.to_string());
+        let promise = idxdb_get_transactions(&self.store_name, filter_as_str.to_string());
         let js_value = JsFuture::from(promise).await.map_err(|js_error| {
             StoreError::DatabaseError(format!("failed to get transactions: {js_error:?}"))
         })?;
@@ -84,7 +94,13 @@ impl WebStore {
             })
             .collect();
 
-        transaction_records
+        match filter {
+            TransactionFilter::Expired(block_num) => Ok(transaction_records?
+                .into_iter()
+                .filter(|transaction| is_expired_transaction(transaction, block_num))
+                .collect()),
+            _ => transaction_records,
+        }
     }
 
     pub async fn apply_transaction(
@@ -93,18 +109,21 @@ impl WebStore {
     ) -> Result<(), StoreError> {
         // Transaction Data
         insert_proven_transaction_data(
+            &self.store_name,
             tx_update.executed_transaction(),
             tx_update.submission_height(),
         )
         .await?;
 
         // Account Data
-        update_account(tx_update.updated_account()).await.map_err(|err| {
-            StoreError::DatabaseError(format!("failed to update account: {err:?}"))
-        })?;
+        update_account(&self.store_name, tx_update.updated_account())
+            .await
+            .map_err(|err| {
+                StoreError::DatabaseError(format!("failed to update account: {err:?}"))
+            })?;
 
         // Updates for notes
-        apply_note_updates_tx(tx_update.note_updates()).await?;
+        apply_note_updates_tx(&self.store_name, tx_update.note_updates()).await?;
 
         for tag_record in tx_update.new_tags() {
             self.add_note_tag(*tag_record).await?;
@@ -112,4 +131,45 @@ impl WebStore {
 
         Ok(())
     }
+
+    pub async fn upsert_transactions(
+        &self,
+        transactions: &[TransactionRecord],
+    ) -> Result<(), StoreError> {
+        for transaction in transactions {
+            upsert_transaction_record(&self.store_name, transaction).await?;
+        }
+
+        Ok(())
+    }
+
+    pub async fn remove_transactions(
+        &self,
+        transaction_ids: &[TransactionId],
+        released_notes: &[InputNoteRecord],
+    ) -> Result<(), StoreError> {
+        let transaction_ids = transaction_ids.iter().map(ToString::to_string).collect();
+
+        let mut released_note_ids = Vec::new();
+        let mut released_note_state_discriminants = Vec::new();
+        let mut released_note_states = Vec::new();
+        for note in released_notes {
+            released_note_ids.push(note.id().inner().to_string());
+            released_note_state_discriminants.push(note.state().discriminant());
+            released_note_states.push(note.state().to_bytes());
+        }
+
+        let promise = idxdb_remove_transactions(
+            &self.store_name,
+            transaction_ids,
+            released_note_ids,
+            released_note_state_discriminants,
+            flatten_nested_u8_vec(released_note_states),
+        );
+        JsFuture::from(promise).await.map_err(|js_error| {
+            StoreError::DatabaseError(format!("failed to remove transactions: {js_error:?}"))
+        })?;
+
+        Ok(())
+    }
 }
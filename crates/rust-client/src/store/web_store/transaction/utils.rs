@@ -35,6 +35,7 @@ pub struct SerializedTransactionData {
 /// Converts an `ExecutedTransaction` into a `TransactionRecord` and inserts it into the store.
 /// `submission_height` is the block number at which the transaction was submitted to the network.
 pub async fn insert_proven_transaction_data(
+    store_name: &str,
     executed_transaction: &ExecutedTransaction,
     submission_height: BlockNumber,
 ) -> Result<(), StoreError> {
@@ -49,7 +50,7 @@ pub async fn insert_proven_transaction_data(
 
     let details = TransactionDetails {
         account_id: executed_transaction.account_id(),
-        init_account_state: executed_transaction.initial_account().commitment(),
+        init_account_state: Some(executed_transaction.initial_account().commitment()),
         final_account_state: executed_transaction.final_account().commitment(),
         input_note_nullifiers: nullifiers,
         output_notes: output_notes.clone(),
@@ -65,7 +66,7 @@ pub async fn insert_proven_transaction_data(
         TransactionStatus::Pending,
     );
 
-    upsert_transaction_record(&transaction_record).await?;
+    upsert_transaction_record(store_name, &transaction_record).await?;
 
     Ok(())
 }
@@ -98,18 +99,20 @@ pub(super) fn serialize_transaction_record(
 
 /// Updates the transaction record in the database, inserting it if it doesn't exist.
 pub(crate) async fn upsert_transaction_record(
+    store_name: &str,
     transaction: &TransactionRecord,
 ) -> Result<(), StoreError> {
     let serialized_data = serialize_transaction_record(transaction);
 
     if let Some(root) = serialized_data.script_root.clone() {
-        let promise = idxdb_insert_transaction_script(root, serialized_data.tx_script);
+        let promise = idxdb_insert_transaction_script(store_name, root, serialized_data.tx_script);
         JsFuture::from(promise).await.map_err(|js_error| {
             StoreError::DatabaseError(format!("failed to insert script: {js_error:?}"))
         })?;
     }
 
     let promise = idxdb_upsert_transaction_record(
+        store_name,
         serialized_data.id,
         serialized_data.details,
         serialized_data.script_root.clone(),
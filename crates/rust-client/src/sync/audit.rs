@@ -0,0 +1,210 @@
+//! Provides a diagnostic check of the client's store against the state of the network.
+//!
+//! Unlike [`Client::sync_state()`], [`Client::audit_against_node()`] never modifies the store: it
+//! only reports the [`AuditDiscrepancy`]s it finds, which can be used to detect a corrupted or
+//! tampered store.
+
+use alloc::{
+    collections::BTreeSet,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use miden_objects::{
+    Digest, ZERO,
+    account::AccountId,
+    block::BlockNumber,
+    crypto::merkle::PartialMmr,
+    note::{NoteId, NoteInclusionProof, NoteMetadata, compute_note_commitment},
+};
+
+use crate::{
+    Client, ClientError,
+    store::{NoteFilter, TransactionFilter},
+    transaction::TransactionStatus,
+};
+
+// AUDIT REPORT
+// ================================================================================================
+
+/// A mismatch between the client's store and the state of the network, found by
+/// [`Client::audit_against_node()`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AuditDiscrepancy {
+    /// The commitment of the account in the store differs from the one the node has.
+    AccountCommitmentMismatch {
+        account_id: AccountId,
+        local_commitment: Digest,
+        node_commitment: Digest,
+    },
+    /// The node couldn't be queried for the state of the account, so its commitment couldn't be
+    /// compared.
+    AccountNotRetrieved { account_id: AccountId, error: String },
+    /// The inclusion proof of the committed note doesn't match the note root of the header of the
+    /// block that includes it.
+    InvalidNoteInclusionProof { note_id: NoteId, block_num: BlockNumber },
+    /// The header of the block that includes the committed note can't be authenticated against
+    /// the client's partial MMR.
+    UnauthenticatedNoteBlock { note_id: NoteId, block_num: BlockNumber },
+}
+
+/// The result of [`Client::audit_against_node()`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AuditReport {
+    /// Number of accounts whose commitment was checked against the node.
+    audited_accounts: usize,
+    /// Number of committed notes whose inclusion proof was verified.
+    audited_notes: usize,
+    /// Discrepancies found between the store and the network.
+    discrepancies: Vec<AuditDiscrepancy>,
+}
+
+impl AuditReport {
+    /// Returns the number of accounts whose commitment was checked against the node.
+    pub fn audited_accounts(&self) -> usize {
+        self.audited_accounts
+    }
+
+    /// Returns the number of committed notes whose inclusion proof was verified.
+    pub fn audited_notes(&self) -> usize {
+        self.audited_notes
+    }
+
+    /// Returns the discrepancies found between the store and the network.
+    pub fn discrepancies(&self) -> &[AuditDiscrepancy] {
+        &self.discrepancies
+    }
+
+    /// Returns whether no discrepancies were found.
+    pub fn is_consistent(&self) -> bool {
+        self.discrepancies.is_empty()
+    }
+}
+
+// CLIENT METHODS
+// ================================================================================================
+
+/// Store audit methods.
+impl Client {
+    /// Checks the client's store against the node and the client's partial MMR, and returns an
+    /// [`AuditReport`] with the discrepancies found. The store isn't modified.
+    ///
+    /// - The commitment of each tracked account is compared against the one returned by the node.
+    ///   Accounts that were never committed (their nonce is zero) and accounts with pending
+    ///   transactions are skipped, as their local state is expected to differ from the node's.
+    ///   Failed requests to the node are reported as discrepancies, so that the rest of the store
+    ///   is still audited.
+    /// - The inclusion proof of each committed input and output note is verified against the note
+    ///   root of the header of its block, and that header is authenticated against the client's
+    ///   current partial MMR.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the store can't be read.
+    pub async fn audit_against_node(&self) -> Result<AuditReport, ClientError> {
+        let mut report = AuditReport::default();
+
+        let accounts_with_pending_transactions: BTreeSet<AccountId> = self
+            .store
+            .get_transactions(TransactionFilter::Uncommitted)
+            .await?
+            .into_iter()
+            .filter(|tx| tx.status == TransactionStatus::Pending)
+            .map(|tx| tx.details.account_id)
+            .collect();
+
+        for (header, _) in self.store.get_account_headers().await? {
+            if header.nonce() == ZERO || accounts_with_pending_transactions.contains(&header.id()) {
+                continue;
+            }
+
+            report.audited_accounts += 1;
+            let node_commitment = match self.rpc_api.get_account_details(header.id()).await {
+                Ok(fetched_account) => fetched_account.commitment(),
+                Err(err) => {
+                    report.discrepancies.push(AuditDiscrepancy::AccountNotRetrieved {
+                        account_id: header.id(),
+                        error: err.to_string(),
+                    });
+                    continue;
+                },
+            };
+
+            if node_commitment != header.commitment() {
+                report.discrepancies.push(AuditDiscrepancy::AccountCommitmentMismatch {
+                    account_id: header.id(),
+                    local_commitment: header.commitment(),
+                    node_commitment,
+                });
+            }
+        }
+
+        let mut committed_notes = Vec::new();
+        for note in self.store.get_input_notes(NoteFilter::Committed).await? {
+            if let (Some(inclusion_proof), Some(metadata)) =
+                (note.inclusion_proof(), note.metadata())
+            {
+                committed_notes.push((note.id(), inclusion_proof.clone(), *metadata));
+            }
+        }
+        for note in self.store.get_output_notes(NoteFilter::Committed).await? {
+            if let Some(inclusion_proof) = note.inclusion_proof() {
+                committed_notes.push((note.id(), inclusion_proof.clone(), *note.metadata()));
+            }
+        }
+
+        let current_partial_mmr = self.build_current_partial_mmr().await?;
+        for (note_id, inclusion_proof, metadata) in committed_notes {
+            report.audited_notes += 1;
+
+            if let Some(discrepancy) = self
+                .audit_note_inclusion(note_id, &inclusion_proof, &metadata, &current_partial_mmr)
+                .await?
+            {
+                report.discrepancies.push(discrepancy);
+            }
+        }
+
+        Ok(report)
+    }
+
+    /// Verifies the inclusion proof of a committed note against the header of its block, and the
+    /// header against the client's partial MMR.
+    async fn audit_note_inclusion(
+        &self,
+        note_id: NoteId,
+        inclusion_proof: &NoteInclusionProof,
+        metadata: &NoteMetadata,
+        current_partial_mmr: &PartialMmr,
+    ) -> Result<Option<AuditDiscrepancy>, ClientError> {
+        let block_num = inclusion_proof.location().block_num();
+
+        let Some((block_header, _)) = self.store.get_block_header_by_num(block_num).await? else {
+            return Ok(Some(AuditDiscrepancy::UnauthenticatedNoteBlock { note_id, block_num }));
+        };
+
+        let is_block_authenticated = block_num.as_usize() < current_partial_mmr.forest()
+            && current_partial_mmr.open(block_num.as_usize()).ok().flatten().is_some_and(
+                |mmr_proof| {
+                    current_partial_mmr.peaks().verify(block_header.commitment(), mmr_proof).is_ok()
+                },
+            );
+        if !is_block_authenticated {
+            return Ok(Some(AuditDiscrepancy::UnauthenticatedNoteBlock { note_id, block_num }));
+        }
+
+        let is_proof_valid = inclusion_proof
+            .note_path()
+            .verify(
+                inclusion_proof.location().node_index_in_block().into(),
+                compute_note_commitment(note_id, metadata),
+                &block_header.note_root(),
+            )
+            .is_ok();
+        if !is_proof_valid {
+            return Ok(Some(AuditDiscrepancy::InvalidNoteInclusionProof { note_id, block_num }));
+        }
+
+        Ok(None)
+    }
+}
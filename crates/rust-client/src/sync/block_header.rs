@@ -12,6 +12,7 @@ use crate::{
     Client, ClientError,
     rpc::NodeRpcClient,
     store::{PartialBlockchainFilter, StoreError},
+    sync::SyncMode,
 };
 
 /// Network information management methods.
@@ -27,6 +28,83 @@ impl Client {
         }
     }
 
+    /// Checks that the node the client is connected to is on the same chain as the store, by
+    /// comparing the node's genesis block against the stored `genesis` block.
+    ///
+    /// The node's genesis block can't change, so it is only requested until the check succeeds
+    /// once for this client instance.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ClientError::GenesisMismatch`] if the commitments of both blocks differ.
+    pub(crate) async fn verify_genesis(
+        &mut self,
+        genesis: &BlockHeader,
+    ) -> Result<(), ClientError> {
+        if self.genesis_verified {
+            return Ok(());
+        }
+
+        let (node_genesis, _) = self
+            .rpc_api
+            .get_block_header_by_number(Some(BlockNumber::GENESIS), false)
+            .await?;
+
+        if node_genesis.commitment() != genesis.commitment() {
+            return Err(ClientError::GenesisMismatch(
+                genesis.commitment(),
+                node_genesis.commitment(),
+            ));
+        }
+
+        self.genesis_verified = true;
+        Ok(())
+    }
+
+    /// Rolls the store back after the node's chain was found to no longer include the block the
+    /// store is synced to.
+    ///
+    /// The store is rolled back to the latest stored block that is still part of the node's chain,
+    /// so that the next sync applies the updates of the canonical chain from there. Returns the
+    /// block the store was rolled back to.
+    pub(crate) async fn roll_back_chain_reorg(&self) -> Result<BlockNumber, ClientError> {
+        let sync_height = self.store.get_sync_height().await?;
+        let (chain_tip, _) = self.rpc_api.get_block_header_by_number(None, false).await?;
+
+        let mut stored_blocks = self.store.get_tracked_block_headers().await?;
+        stored_blocks.retain(|block| block.block_num() < sync_height);
+        stored_blocks.sort_by_key(BlockHeader::block_num);
+
+        let mut common_block_num = BlockNumber::GENESIS;
+        for block in stored_blocks.iter().rev() {
+            if self.is_in_node_chain(block, chain_tip.block_num()).await? {
+                common_block_num = block.block_num();
+                break;
+            }
+        }
+
+        warn!("Chain re-org detected, rolling back the store to block {common_block_num}");
+        self.store.rollback_to(common_block_num).await?;
+
+        Ok(common_block_num)
+    }
+
+    /// Returns whether `block` is part of the node's chain, whose tip is at `chain_tip`.
+    async fn is_in_node_chain(
+        &self,
+        block: &BlockHeader,
+        chain_tip: BlockNumber,
+    ) -> Result<bool, ClientError> {
+        if block.block_num() > chain_tip {
+            return Ok(false);
+        }
+
+        let (node_block, _) =
+            self.rpc_api.get_block_header_by_number(Some(block.block_num()), false).await?;
+
+        Ok(node_block.commitment() == block.commitment())
+    }
+
     /// Calls `get_block_header_by_number` requesting the genesis block and storing it
     /// in the local database.
     async fn retrieve_and_store_genesis(&mut self) -> Result<BlockHeader, ClientError> {
@@ -41,6 +119,41 @@ impl Client {
         Ok(genesis_block)
     }
 
+    /// Fetches and stores the MMR authentication data for every block that contains notes
+    /// relevant to the client, so that a client that was synced in light mode (see
+    /// [`crate::builder::ClientBuilder::light_mode`]) can consume its notes as authenticated
+    /// inputs.
+    ///
+    /// The genesis block is retrieved if the store doesn't have it yet. The current block doesn't
+    /// need authentication data, as it is the reference block of new transactions, and neither
+    /// do blocks that the client's partial MMR already tracks.
+    ///
+    /// Light mode is disabled for the rest of the client's lifetime, so that MMR nodes keep being
+    /// stored on subsequent syncs. The client should be built without light mode afterwards, as
+    /// otherwise the blocks synced in light mode would need to be backfilled again.
+    pub async fn backfill_mmr(&mut self) -> Result<(), ClientError> {
+        self.sync_mode = SyncMode::Full;
+        self.ensure_genesis_in_place().await?;
+
+        let mut current_partial_mmr = self.build_current_partial_mmr().await?;
+        for block_header in self.store.get_tracked_block_headers().await? {
+            if current_partial_mmr.is_tracked(block_header.block_num().as_usize()) {
+                continue;
+            }
+
+            let (_, path_nodes) = fetch_block_header(
+                self.rpc_api.clone(),
+                block_header.block_num(),
+                &mut current_partial_mmr,
+            )
+            .await?;
+
+            self.store.insert_partial_blockchain_nodes(&path_nodes).await?;
+        }
+
+        Ok(())
+    }
+
     // HELPERS
     // --------------------------------------------------------------------------------------------
 
@@ -0,0 +1,245 @@
+//! Provides [`Client::backfill_account_history()`], which reconstructs the transaction history of
+//! an account from the network.
+//!
+//! When an account is added to the client, only its current state is stored, so transactions
+//! executed against it before that aren't known. Backfilling walks the chain from a starting block
+//! and stores a [`TransactionRecord`] for every committed transaction of the account.
+
+use alloc::{boxed::Box, collections::BTreeSet, string::ToString, vec::Vec};
+
+use miden_objects::{
+    Digest,
+    account::AccountId,
+    block::BlockNumber,
+    note::{NoteHeader, Nullifier},
+    transaction::{OutputNote, OutputNotes, TransactionId},
+};
+
+use crate::{
+    Client, ClientError,
+    rpc::{RpcError, domain::transaction::TransactionInclusion},
+    store::TransactionFilter,
+    transaction::{TransactionDetails, TransactionRecord, TransactionStatus},
+};
+
+// BACKFILL PROGRESS
+// ================================================================================================
+
+/// Reports the progress of an ongoing [`Client::backfill_account_history_with_progress()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HistoryBackfillProgress {
+    /// Block number up to which the account's transactions have been looked up.
+    pub current_block: BlockNumber,
+    /// Block up to which the history is backfilled, which is the client's sync height.
+    pub target_block: BlockNumber,
+    /// Number of transactions of the account found so far.
+    pub transactions_found: usize,
+}
+
+/// Callback invoked with the [`HistoryBackfillProgress`] after each response received from the
+/// node.
+pub type OnHistoryBackfillProgress = Box<dyn FnMut(HistoryBackfillProgress)>;
+
+// CLIENT METHODS
+// ================================================================================================
+
+/// Account history methods.
+impl Client {
+    /// Reconstructs the transaction history of the tracked account with the specified ID, storing
+    /// a committed [`TransactionRecord`] for each of its transactions included between
+    /// `from_block` and the client's sync height. Returns the IDs of the transactions that were
+    /// added to the store.
+    ///
+    /// Transactions that are already stored are kept as they are. For the rest, the details are
+    /// taken from the transaction headers of the blocks that include them, so their script isn't
+    /// known, their output notes only contain the note headers, and their reference, submission
+    /// and expiration blocks are set to the block that includes them.
+    ///
+    /// The node doesn't report the initial state of the transaction that created the account. It
+    /// is only known if the client still tracks the state the account was created from (that is,
+    /// its tracked nonce is zero). Otherwise, the initial state of that transaction is stored as
+    /// unknown.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ClientError::AccountDataNotFound`] if the account isn't tracked by the client, or
+    /// an error if a request to the node fails.
+    pub async fn backfill_account_history(
+        &mut self,
+        account_id: AccountId,
+        from_block: BlockNumber,
+    ) -> Result<Vec<TransactionId>, ClientError> {
+        self.backfill_account_history_inner(account_id, from_block, None).await
+    }
+
+    /// Reconstructs the transaction history of an account in the same way as
+    /// [`Client::backfill_account_history`], calling `on_progress` after each response received
+    /// from the node.
+    pub async fn backfill_account_history_with_progress(
+        &mut self,
+        account_id: AccountId,
+        from_block: BlockNumber,
+        on_progress: impl FnMut(HistoryBackfillProgress) + 'static,
+    ) -> Result<Vec<TransactionId>, ClientError> {
+        self.backfill_account_history_inner(account_id, from_block, Some(Box::new(on_progress)))
+            .await
+    }
+
+    async fn backfill_account_history_inner(
+        &mut self,
+        account_id: AccountId,
+        from_block: BlockNumber,
+        mut on_progress: Option<OnHistoryBackfillProgress>,
+    ) -> Result<Vec<TransactionId>, ClientError> {
+        let account_record = self
+            .store
+            .get_account(account_id)
+            .await?
+            .ok_or(ClientError::AccountDataNotFound(account_id))?;
+        // A tracked state with a zero nonce is the one the account was created from
+        let account = account_record.account();
+        let creation_state = account.is_new().then(|| account.commitment());
+
+        let target_block = self.store.get_sync_height().await?;
+        let inclusions = self
+            .get_transaction_inclusions(account_id, from_block, target_block, &mut on_progress)
+            .await?;
+
+        let stored_ids: BTreeSet<TransactionId> = self
+            .store
+            .get_transactions(TransactionFilter::Ids(
+                inclusions.iter().map(|inclusion| inclusion.transaction_id).collect(),
+            ))
+            .await?
+            .into_iter()
+            .map(|transaction| transaction.id)
+            .collect();
+
+        let mut transactions = vec![];
+        for inclusion in inclusions {
+            if stored_ids.contains(&inclusion.transaction_id) {
+                continue;
+            }
+
+            transactions
+                .push(self.build_committed_transaction_record(&inclusion, creation_state).await?);
+        }
+
+        self.store.upsert_transactions(&transactions).await?;
+
+        Ok(transactions.into_iter().map(|transaction| transaction.id).collect())
+    }
+
+    /// Walks the chain from `from_block` up to `target_block` and returns the inclusions of the
+    /// transactions executed against the account.
+    async fn get_transaction_inclusions(
+        &self,
+        account_id: AccountId,
+        from_block: BlockNumber,
+        target_block: BlockNumber,
+        on_progress: &mut Option<OnHistoryBackfillProgress>,
+    ) -> Result<Vec<TransactionInclusion>, ClientError> {
+        let mut inclusions = vec![];
+        if from_block > target_block {
+            return Ok(inclusions);
+        }
+
+        // The node returns the transactions included after the requested block
+        let mut current_block: BlockNumber = from_block.as_u32().saturating_sub(1).into();
+        while current_block < target_block {
+            let response =
+                self.rpc_api.sync_state(current_block, &[account_id], &BTreeSet::new()).await?;
+
+            inclusions.extend(response.transactions.into_iter().filter(|inclusion| {
+                inclusion.account_id == account_id
+                    && inclusion.block_num >= from_block.as_u32()
+                    && inclusion.block_num <= target_block.as_u32()
+            }));
+
+            let next_block = response.block_header.block_num();
+            if let Some(on_progress) = on_progress.as_mut() {
+                on_progress(HistoryBackfillProgress {
+                    current_block: next_block.min(target_block),
+                    target_block,
+                    transactions_found: inclusions.len(),
+                });
+            }
+
+            if next_block <= current_block || next_block == response.chain_tip {
+                break;
+            }
+            current_block = next_block;
+        }
+
+        Ok(inclusions)
+    }
+
+    /// Builds a committed [`TransactionRecord`] from the header of the included transaction, which
+    /// is retrieved from the block that includes it. `creation_state` is the state the account was
+    /// created from, if known.
+    async fn build_committed_transaction_record(
+        &self,
+        inclusion: &TransactionInclusion,
+        creation_state: Option<Digest>,
+    ) -> Result<TransactionRecord, ClientError> {
+        let block_num = BlockNumber::from(inclusion.block_num);
+        let block = self.rpc_api.get_block_by_number(block_num).await?;
+
+        let header = block
+            .transactions()
+            .as_slice()
+            .iter()
+            .find(|header| header.id() == inclusion.transaction_id)
+            .ok_or_else(|| {
+                RpcError::ExpectedDataMissing(format!(
+                    "TransactionHeader of transaction {}",
+                    inclusion.transaction_id
+                ))
+            })?;
+
+        // The transaction header only lists the IDs of its output notes, so their metadata is
+        // taken from the notes created in the block
+        let output_notes = header
+            .output_notes()
+            .iter()
+            .map(|note_id| {
+                block
+                    .output_notes()
+                    .find(|(_, note)| note.id() == *note_id)
+                    .map(|(_, note)| {
+                        OutputNote::Header(NoteHeader::new(*note_id, *note.metadata()))
+                    })
+                    .ok_or_else(|| {
+                        RpcError::ExpectedDataMissing(format!("Output note {note_id} in block"))
+                    })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let output_notes = OutputNotes::new(output_notes)
+            .map_err(|err| RpcError::InvalidResponse(err.to_string()))?;
+
+        // The node reports an empty initial state for the transaction that created the account
+        let init_account_state = if header.initial_state_commitment() == Digest::default() {
+            creation_state
+        } else {
+            Some(header.initial_state_commitment())
+        };
+
+        let details = TransactionDetails {
+            account_id: header.account_id(),
+            init_account_state,
+            final_account_state: header.final_state_commitment(),
+            input_note_nullifiers: header.input_notes().iter().map(Nullifier::inner).collect(),
+            output_notes,
+            block_num,
+            submission_height: block_num,
+            expiration_block_num: block_num,
+        };
+
+        Ok(TransactionRecord::new(
+            inclusion.transaction_id,
+            details,
+            None,
+            TransactionStatus::Committed(block_num),
+        ))
+    }
+}
@@ -0,0 +1,212 @@
+use alloc::{string::ToString, vec::Vec};
+use core::num::NonZeroUsize;
+
+use miden_objects::{
+    Digest,
+    block::BlockHeader,
+    crypto::merkle::{InOrderIndex, MmrPeaks, PartialMmr},
+};
+use miden_tx::utils::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable};
+
+use super::{BlockUpdates, StateSyncUpdate};
+use crate::{
+    Client, ClientError,
+    store::{PartialBlockchainFilter, StoreError},
+};
+
+/// MMR state import and export methods.
+impl Client {
+    /// Exports the client's current view of the chain MMR so that it can be imported by another
+    /// client with [`Client::import_mmr_state`].
+    ///
+    /// The exported data contains the block header at the current sync height, the block headers
+    /// that are relevant to the client along with the MMR peaks at each of their heights, and all
+    /// tracked MMR authentication nodes.
+    pub async fn export_mmr_state(&self) -> Result<Vec<u8>, ClientError> {
+        Ok(self.build_mmr_state().await?.to_bytes())
+    }
+
+    /// Imports an MMR state previously exported with [`Client::export_mmr_state`], so that the
+    /// client can start syncing from the exported block instead of from genesis.
+    ///
+    /// The imported data is validated against `trusted_block_header`, which should be obtained
+    /// from a trusted source (such as the node) and correspond to the exported sync height.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ClientError::MmrStateImportError`] if:
+    /// - The exported chain tip doesn't match the trusted block header.
+    /// - The exported MMR peaks don't match the chain commitment of the trusted block header.
+    /// - Any of the exported block headers can't be authenticated against the exported MMR.
+    /// - The client is already synced to the exported block or further.
+    pub async fn import_mmr_state(
+        &mut self,
+        mmr_state: &[u8],
+        trusted_block_header: &BlockHeader,
+    ) -> Result<(), ClientError> {
+        let MmrState { block_headers, authentication_nodes } =
+            MmrState::read_from_bytes(mmr_state)?;
+
+        let Some((chain_tip, chain_tip_has_notes, chain_tip_peaks)) = block_headers.last() else {
+            return Err(ClientError::MmrStateImportError("no block headers found".to_string()));
+        };
+
+        if chain_tip.commitment() != trusted_block_header.commitment() {
+            return Err(ClientError::MmrStateImportError(format!(
+                "chain tip {} doesn't match the trusted block header {}",
+                chain_tip.block_num(),
+                trusted_block_header.block_num()
+            )));
+        }
+
+        if chain_tip_peaks.hash_peaks() != trusted_block_header.chain_commitment() {
+            return Err(ClientError::MmrStateImportError(
+                "MMR peaks don't match the chain commitment of the trusted block header"
+                    .to_string(),
+            ));
+        }
+
+        let block_num = chain_tip.block_num();
+        if self.store.get_sync_height().await? >= block_num {
+            return Err(ClientError::MmrStateImportError(format!(
+                "client is already synced up to block {block_num} or further"
+            )));
+        }
+
+        // Rebuild the partial MMR in the same way the client does when syncing, and check that
+        // every imported block can be authenticated against it
+        let mut partial_mmr = PartialMmr::from_peaks(chain_tip_peaks.clone());
+        partial_mmr.add(chain_tip.commitment(), *chain_tip_has_notes);
+        let partial_mmr = PartialMmr::from_parts(
+            partial_mmr.peaks(),
+            authentication_nodes.iter().copied().collect(),
+            *chain_tip_has_notes,
+        );
+
+        for (block_header, ..) in &block_headers[..block_headers.len() - 1] {
+            let authenticated = partial_mmr
+                .open(block_header.block_num().as_usize())
+                .map_err(StoreError::MmrError)?
+                .is_some_and(|proof| {
+                    partial_mmr.peaks().verify(block_header.commitment(), proof).is_ok()
+                });
+
+            if !authenticated {
+                return Err(ClientError::MmrStateImportError(format!(
+                    "block header {} can't be authenticated",
+                    block_header.block_num()
+                )));
+            }
+        }
+
+        let state_sync_update = StateSyncUpdate {
+            block_num,
+            block_updates: BlockUpdates::new(block_headers, authentication_nodes),
+            ..Default::default()
+        };
+
+        self.store.apply_state_sync(state_sync_update).await?;
+
+        Ok(())
+    }
+
+    /// Builds the [`MmrState`] with the client's current view of the chain MMR. If the block
+    /// header at the current sync height isn't in the store, it's left out.
+    pub(crate) async fn build_mmr_state(&self) -> Result<MmrState, ClientError> {
+        let sync_height = self.store.get_sync_height().await?;
+
+        let mut block_headers = Vec::new();
+        for block_header in self.store.get_tracked_block_headers().await? {
+            if block_header.block_num() == sync_height {
+                continue;
+            }
+
+            let peaks = self
+                .store
+                .get_partial_blockchain_peaks_by_block_num(block_header.block_num())
+                .await?;
+            block_headers.push((block_header, true, peaks));
+        }
+
+        if let Some((chain_tip, chain_tip_has_notes)) =
+            self.store.get_block_header_by_num(sync_height).await?
+        {
+            let chain_tip_peaks =
+                self.store.get_partial_blockchain_peaks_by_block_num(sync_height).await?;
+            block_headers.push((chain_tip, chain_tip_has_notes, chain_tip_peaks));
+        }
+
+        let authentication_nodes = self
+            .store
+            .get_partial_blockchain_nodes(PartialBlockchainFilter::All)
+            .await?
+            .into_iter()
+            .collect();
+
+        Ok(MmrState { block_headers, authentication_nodes })
+    }
+}
+
+// MMR STATE
+// ================================================================================================
+
+/// Serializable view of the client's chain MMR.
+pub(crate) struct MmrState {
+    /// Block headers tracked by the client, along with a flag indicating whether the block
+    /// contains relevant notes and the MMR peaks at the block's height. The last element
+    /// corresponds to the block at the client's sync height.
+    pub(crate) block_headers: Vec<(BlockHeader, bool, MmrPeaks)>,
+    /// Authentication nodes for the tracked blocks.
+    pub(crate) authentication_nodes: Vec<(InOrderIndex, Digest)>,
+}
+
+impl Serializable for MmrState {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_usize(self.block_headers.len());
+        for (block_header, has_client_notes, peaks) in &self.block_headers {
+            block_header.write_into(target);
+            target.write_bool(*has_client_notes);
+            peaks.peaks().write_into(target);
+        }
+
+        target.write_usize(self.authentication_nodes.len());
+        for (index, node) in &self.authentication_nodes {
+            target.write_u64(index.inner());
+            node.write_into(target);
+        }
+    }
+}
+
+impl Deserializable for MmrState {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let num_block_headers = source.read_usize()?;
+        let mut block_headers = Vec::new();
+        for _ in 0..num_block_headers {
+            let block_header = BlockHeader::read_from(source)?;
+            let has_client_notes = source.read_bool()?;
+            // Peaks stored for a block correspond to the MMR with all of the previous blocks
+            let peaks = MmrPeaks::new(
+                block_header.block_num().as_usize(),
+                Vec::<Digest>::read_from(source)?,
+            )
+            .map_err(|err| DeserializationError::InvalidValue(err.to_string()))?;
+
+            block_headers.push((block_header, has_client_notes, peaks));
+        }
+
+        let num_nodes = source.read_usize()?;
+        let mut authentication_nodes = Vec::new();
+        for _ in 0..num_nodes {
+            let index = usize::try_from(source.read_u64()?)
+                .ok()
+                .and_then(NonZeroUsize::new)
+                .map(InOrderIndex::new)
+                .ok_or(DeserializationError::InvalidValue("invalid in-order index".to_string()))?;
+            let node = Digest::read_from(source)?;
+
+            authentication_nodes.push((index, node));
+        }
+
+        Ok(Self { block_headers, authentication_nodes })
+    }
+}
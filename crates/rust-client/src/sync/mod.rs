@@ -54,29 +54,48 @@
 //! `committed_note_updates` and `consumed_note_updates`) to understand how the sync data is
 //! processed and applied to the local store.
 
-use alloc::{boxed::Box, collections::BTreeSet, vec::Vec};
-use core::cmp::max;
+use alloc::{boxed::Box, collections::BTreeSet, format, rc::Rc, sync::Arc, vec::Vec};
+use core::{
+    cell::{Cell, RefCell},
+    cmp::max,
+};
 
 use miden_objects::{
+    Digest,
     account::AccountId,
     block::BlockNumber,
     note::{NoteId, NoteTag},
     transaction::{PartialBlockchain, TransactionId},
 };
-use miden_tx::utils::{Deserializable, DeserializationError, Serializable};
+use miden_tx::utils::{
+    ByteReader, Deserializable, DeserializationError, Serializable, SliceReader,
+};
+use tracing::{Span, field::Empty, instrument, warn};
 
 use crate::{
     Client, ClientError,
     note::NoteScreener,
+    rpc::domain::status::NodeStatus,
     store::{NoteFilter, TransactionFilter},
 };
+mod audit;
+pub use audit::{AuditDiscrepancy, AuditReport};
+
 mod block_header;
 
+mod history;
+pub use history::{HistoryBackfillProgress, OnHistoryBackfillProgress};
+
+mod mmr_state;
+pub(crate) use mmr_state::MmrState;
+
 mod tag;
-pub use tag::{NoteTagRecord, NoteTagSource};
+pub use tag::{NoteTagRecord, NoteTagSource, TagSubscription};
 
 mod state_sync;
-pub use state_sync::{OnNoteReceived, StateSync, on_note_received};
+pub use state_sync::{
+    OnNoteReceived, OnSyncProgress, StateSync, on_note_received, on_note_received_strict,
+};
 
 mod state_sync_update;
 pub use state_sync_update::{
@@ -93,9 +112,25 @@ impl Client {
         self.store.get_sync_height().await.map_err(Into::into)
     }
 
+    /// Returns the status of the node the client is connected to, including its chain tip.
+    ///
+    /// This can be used to check that the node is reachable before syncing.
+    pub async fn node_status(&self) -> Result<NodeStatus, ClientError> {
+        self.rpc_api.status().await.map_err(Into::into)
+    }
+
     /// Syncs the client's state with the current state of the Miden network and returns a
     /// [`SyncSummary`] corresponding to the local state update.
     ///
+    /// Before syncing, note tags that were registered for accounts that are no longer tracked by
+    /// the client are removed so that they don't get added to the sync request.
+    ///
+    /// The node's genesis block is also compared against the one in the store, and the sync fails
+    /// with [`ClientError::GenesisMismatch`] if the node is on a different chain. If the node's
+    /// updates show that the block the store was last synced to is no longer part of the node's
+    /// chain, the store is rolled back to the latest block still on it and the sync fails with
+    /// [`ClientError::ChainReorg`]. Syncing again then applies the updates of the canonical chain.
+    ///
     /// The sync process is done in multiple steps:
     /// 1. A request is sent to the node to get the state updates. This request includes tracked
     ///    account IDs and the tags of notes that might have changed or that might be of interest to
@@ -113,26 +148,277 @@ impl Client {
     /// 7. The MMR is updated with the new peaks and authentication nodes.
     /// 8. All updates are applied to the store to be persisted.
     pub async fn sync_state(&mut self) -> Result<SyncSummary, ClientError> {
-        _ = self.ensure_genesis_in_place().await?;
+        self.sync_state_inner(None, None).await
+    }
+
+    /// Syncs the client's state in the same way as [`Client::sync_state`], but only up to
+    /// `target_block`, leaving the updates of later blocks for a future sync. This is mostly useful
+    /// to reproduce the state of the client at a specific block.
+    ///
+    /// The node only reports state updates up to blocks that contain notes relevant to the client,
+    /// so the headers of the blocks between the last of those before `target_block` and
+    /// `target_block` are requested one by one to extend the MMR. The node's account updates
+    /// aren't tied to a block either, so if a tracked account was updated in that range the sync
+    /// stops at the earlier block instead.
+    ///
+    /// # Errors
+    ///
+    /// - Returns [`ClientError::InvalidSyncTarget`] if `target_block` isn't after the current sync
+    ///   height or is after the node's chain tip.
+    /// - Returns [`ClientError::SyncTargetNotReached`] if the sync stopped before `target_block`
+    ///   because a tracked account was updated. The updates up to the block that was reached are
+    ///   still applied to the store, and the remaining ones are applied by the next sync.
+    pub async fn sync_to_block(
+        &mut self,
+        target_block: BlockNumber,
+    ) -> Result<SyncSummary, ClientError> {
+        let sync_height = self.store.get_sync_height().await?;
+        let chain_tip = self.node_status().await?.chain_tip;
+        if target_block <= sync_height || target_block > chain_tip {
+            return Err(ClientError::InvalidSyncTarget(target_block, sync_height, chain_tip));
+        }
+
+        let sync_summary = self.sync_state_inner(None, Some(target_block)).await?;
+        if sync_summary.block_num != target_block {
+            return Err(ClientError::SyncTargetNotReached(sync_summary.block_num, target_block));
+        }
+
+        Ok(sync_summary)
+    }
+
+    /// Syncs the client's state in the same way as [`Client::sync_state`], calling `on_progress`
+    /// after each block received from the node is applied to the sync update.
+    ///
+    /// The reported [`SyncProgress`] contains the block the sync has reached, the chain tip being
+    /// synced to and the number of notes updated so far.
+    pub async fn sync_state_with_progress(
+        &mut self,
+        on_progress: impl FnMut(SyncProgress) + 'static,
+    ) -> Result<SyncSummary, ClientError> {
+        self.sync_state_inner(Some(Box::new(on_progress)), None).await
+    }
+
+    /// Requests the state updates up to the chain tip from the node, in the same way as
+    /// [`Client::sync_state`], and returns them serialized without applying them to the store.
+    ///
+    /// The returned bytes can be replayed with [`Client::apply_serialized_sync`] on a store that
+    /// is in the same state as this client's, without contacting the node. Along with the update,
+    /// they record the genesis commitment of the chain and the sync height the update starts at,
+    /// so that it can't be applied to a store of a different chain or at a different height.
+    pub async fn capture_sync_update(&mut self) -> Result<Vec<u8>, ClientError> {
+        let genesis = self.ensure_genesis_in_place().await?;
+        self.verify_genesis(&genesis).await?;
+
+        let block_from = self.store.get_sync_height().await?;
+        let note_screener = NoteScreener::new(
+            self.store.clone(),
+            self.authenticator.clone(),
+            self.known_scripts.clone(),
+        );
+        let state_sync_update = self.request_state_sync_update(note_screener, None, None).await?;
+
+        let mut bytes = Vec::new();
+        genesis.commitment().write_into(&mut bytes);
+        block_from.write_into(&mut bytes);
+        state_sync_update.write_into(&mut bytes);
+
+        Ok(bytes)
+    }
+
+    /// Applies a sync update captured with [`Client::capture_sync_update`] to the store and
+    /// returns the corresponding [`SyncSummary`].
+    ///
+    /// This allows a sync to be replayed deterministically, without contacting the node. The
+    /// update is expected to be applied to a store that is in the same state as the one it was
+    /// captured from, as its MMR authentication nodes and note updates are relative to it.
+    ///
+    /// # Errors
+    ///
+    /// - Returns [`ClientError::DataDeserializationError`] if the bytes don't contain a valid
+    ///   captured update.
+    /// - Returns [`ClientError::SyncUpdateMismatch`] if the update was captured from a store of a
+    ///   different chain, or from a store that wasn't synced to the current sync height.
+    pub async fn apply_serialized_sync(
+        &mut self,
+        bytes: &[u8],
+    ) -> Result<SyncSummary, ClientError> {
+        let mut reader = SliceReader::new(bytes);
+        let genesis_commitment = Digest::read_from(&mut reader)?;
+        let block_from = BlockNumber::read_from(&mut reader)?;
+        let state_sync_update = StateSyncUpdate::read_from(&mut reader)?;
+        if reader.has_more_bytes() {
+            return Err(DeserializationError::UnconsumedBytes.into());
+        }
+
+        let genesis = self.ensure_genesis_in_place().await?;
+        if genesis.commitment() != genesis_commitment {
+            return Err(ClientError::SyncUpdateMismatch(format!(
+                "the update is for the chain with genesis commitment {genesis_commitment}, but the \
+                 store's genesis commitment is {}",
+                genesis.commitment()
+            )));
+        }
+
+        let sync_height = self.store.get_sync_height().await?;
+        if block_from != sync_height {
+            return Err(ClientError::SyncUpdateMismatch(format!(
+                "the update starts at block {block_from}, but the store is synced to block \
+                 {sync_height}"
+            )));
+        }
+
+        let note_screener = NoteScreener::new(
+            self.store.clone(),
+            self.authenticator.clone(),
+            self.known_scripts.clone(),
+        );
+        self.apply_state_sync_update(state_sync_update, &note_screener).await
+    }
+
+    /// Syncs the client's state until the chain tip, or `target_block` if set, is reached. If a
+    /// sync batch size is set, the sync is done in batches that are applied to the store one after
+    /// the other.
+    ///
+    /// The sync runs inside a `sync_state` span that records the range of blocks it covers.
+    #[instrument(name = "sync_state", skip_all, fields(block_from = Empty, block_to = Empty))]
+    async fn sync_state_inner(
+        &mut self,
+        on_progress: Option<OnSyncProgress>,
+        target_block: Option<BlockNumber>,
+    ) -> Result<SyncSummary, ClientError> {
+        // The stored genesis block is the one of the chain the store was populated from, so the
+        // node is checked to be on that same chain before applying any of its updates
+        let genesis = self.ensure_genesis_in_place().await?;
+        self.verify_genesis(&genesis).await?;
+        self.remove_orphaned_account_tags().await?;
+
+        // The same callback is used for every batch, so the number of notes applied in previous
+        // batches needs to be added to the progress reported by each one
+        let on_progress = on_progress.map(|on_progress| Rc::new(RefCell::new(on_progress)));
+        let notes_applied = Rc::new(Cell::new(0));
+
+        let mut sync_summary = SyncSummary::new_empty(self.store.get_sync_height().await?);
+        Span::current().record("block_from", sync_summary.block_num.as_u32());
+        loop {
+            let batch_on_progress = on_progress.clone().map(|on_progress| {
+                let previous_notes_applied = notes_applied.get();
+                let notes_applied = notes_applied.clone();
+                Box::new(move |mut progress: SyncProgress| {
+                    progress.notes_applied += previous_notes_applied;
+                    notes_applied.set(progress.notes_applied);
+                    (*on_progress.borrow_mut())(progress);
+                }) as OnSyncProgress
+            });
+
+            // The node's updates don't apply on top of a store synced to a re-organized block
+            let batch_summary = match self.sync_state_batch(batch_on_progress, target_block).await {
+                Err(ClientError::ChainDiverged(_)) => {
+                    return Err(ClientError::ChainReorg(self.roll_back_chain_reorg().await?));
+                },
+                result => result?,
+            };
+
+            let reached_chain_tip =
+                self.sync_batch_size.is_none() || batch_summary.block_num == sync_summary.block_num;
+
+            // As in a single sync, notes committed in a previous batch and consumed in this one are
+            // only reported as consumed
+            sync_summary
+                .committed_notes
+                .retain(|note_id| !batch_summary.consumed_notes.contains(note_id));
+            sync_summary.combine_with(batch_summary);
+
+            if reached_chain_tip {
+                break;
+            }
+        }
+
+        Span::current().record("block_to", sync_summary.block_num.as_u32());
+        Ok(sync_summary)
+    }
+
+    /// Syncs the client's state up to the chain tip or `target_block`, or for at most the
+    /// configured sync batch size of steps, and applies the resulting update to the store.
+    async fn sync_state_batch(
+        &mut self,
+        on_progress: Option<OnSyncProgress>,
+        target_block: Option<BlockNumber>,
+    ) -> Result<SyncSummary, ClientError> {
+        let note_screener = NoteScreener::new(
+            self.store.clone(),
+            self.authenticator.clone(),
+            self.known_scripts.clone(),
+        );
+
+        // The same screener notifies the watchers, so notes screened during the sync aren't
+        // screened again
+        let state_sync_update = self
+            .request_state_sync_update(note_screener.clone(), on_progress, target_block)
+            .await?;
+        self.apply_state_sync_update(state_sync_update, &note_screener).await
+    }
+
+    /// Requests the state updates up to the chain tip or `target_block`, or for at most the
+    /// configured sync batch size of steps, screening the received notes with `note_screener`.
+    async fn request_state_sync_update(
+        &self,
+        note_screener: NoteScreener,
+        on_progress: Option<OnSyncProgress>,
+        target_block: Option<BlockNumber>,
+    ) -> Result<StateSyncUpdate, ClientError> {
+        // Notes received for tags with an exact subscription are only kept if they are relevant,
+        // so only prefix subscriptions are passed to the note callback
+        let prefix_note_tags: Arc<BTreeSet<NoteTag>> = Arc::new(
+            self.store
+                .get_note_tags()
+                .await?
+                .into_iter()
+                .filter(|record| record.subscription == TagSubscription::Prefix)
+                .map(|record| record.tag)
+                .collect(),
+        );
 
-        let note_screener = NoteScreener::new(self.store.clone(), self.authenticator.clone());
         let state_sync = StateSync::new(
             self.rpc_api.clone(),
             Box::new({
                 let store_clone = self.store.clone();
-                move |committed_note, public_note, note_screener, note_tags| {
-                    Box::pin(on_note_received(
-                        store_clone.clone(),
-                        committed_note,
-                        public_note,
-                        note_screener,
-                        note_tags,
-                    ))
+                let strict_note_tag_matching = self.strict_note_tag_matching;
+                move |committed_note, public_note, note_screener, _note_tags| {
+                    if strict_note_tag_matching {
+                        Box::pin(on_note_received_strict(
+                            store_clone.clone(),
+                            committed_note,
+                            public_note,
+                            note_screener,
+                        ))
+                    } else {
+                        Box::pin(on_note_received(
+                            store_clone.clone(),
+                            committed_note,
+                            public_note,
+                            note_screener,
+                            prefix_note_tags.clone(),
+                        ))
+                    }
                 }
             }),
             self.tx_graceful_blocks,
             note_screener,
-        );
+        )
+        .with_store(self.store.clone());
+
+        let state_sync = match on_progress {
+            Some(on_progress) => state_sync.with_progress_callback(on_progress),
+            None => state_sync,
+        };
+        let state_sync = match self.sync_batch_size {
+            Some(sync_batch_size) => state_sync.with_max_steps(sync_batch_size),
+            None => state_sync,
+        };
+        let state_sync = match target_block {
+            Some(target_block) => state_sync.with_target_block(target_block),
+            None => state_sync,
+        };
 
         // Get current state of the client
         let accounts = self
@@ -170,7 +456,7 @@ impl Client {
             .map(|(header, _has_notes)| header);
 
         // Get the sync update from the network
-        let state_sync_update = state_sync
+        state_sync
             .sync_state(
                 PartialBlockchain::new(current_partial_mmr, block_headers)?,
                 accounts,
@@ -179,10 +465,25 @@ impl Client {
                 unspent_output_notes,
                 uncommitted_transactions,
             )
-            .await?;
+            .await
+    }
 
+    /// Applies a [`StateSyncUpdate`] to the store and returns the corresponding [`SyncSummary`].
+    ///
+    /// Once the update is applied, the notes that became consumable are sent to the watchers of
+    /// the accounts that can consume them, as checked by `note_screener`.
+    async fn apply_state_sync_update(
+        &mut self,
+        mut state_sync_update: StateSyncUpdate,
+        note_screener: &NoteScreener,
+    ) -> Result<SyncSummary, ClientError> {
         let sync_summary: SyncSummary = (&state_sync_update).into();
 
+        // In light mode, MMR nodes aren't stored since the client doesn't authenticate notes
+        if self.sync_mode == SyncMode::Light {
+            state_sync_update.block_updates.clear_authentication_nodes();
+        }
+
         // Apply received and computed updates to the store
         self.store
             .apply_state_sync(state_sync_update)
@@ -192,10 +493,53 @@ impl Client {
         // Remove irrelevant block headers
         self.store.prune_irrelevant_blocks().await?;
 
+        // The update is already applied, so failing to notify the watchers doesn't fail the sync
+        let received_note_ids: BTreeSet<NoteId> = sync_summary
+            .new_public_notes
+            .iter()
+            .chain(&sync_summary.committed_notes)
+            .copied()
+            .collect();
+        if let Err(err) = self
+            .notify_consumable_note_watchers(received_note_ids.into_iter().collect(), note_screener)
+            .await
+        {
+            warn!("Failed to notify consumable note watchers: {err}");
+        }
+
         Ok(sync_summary)
     }
 }
 
+// SYNC MODE
+// ================================================================================================
+
+/// Determines which parts of the state received during sync are stored by the client.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SyncMode {
+    /// Every update is stored, including the MMR nodes needed to authenticate the client's notes.
+    #[default]
+    Full,
+    /// The MMR nodes of the partial blockchain aren't stored. Notes, nullifiers and accounts are
+    /// still tracked, but the client can't build inclusion proofs for its notes.
+    Light,
+}
+
+// SYNC PROGRESS
+// ================================================================================================
+
+/// Reports the progress of an ongoing sync operation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SyncProgress {
+    /// Block number up to which the sync update has been applied.
+    pub current_block: BlockNumber,
+    /// Block that the client is syncing to, which is the chain tip unless the sync was started
+    /// with [`Client::sync_to_block`].
+    pub target_block: BlockNumber,
+    /// Number of notes that have been updated so far during the sync.
+    pub notes_applied: usize,
+}
+
 // SYNC SUMMARY
 // ================================================================================================
 
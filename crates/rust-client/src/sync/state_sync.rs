@@ -4,7 +4,7 @@ use alloc::{
     sync::Arc,
     vec::Vec,
 };
-use core::{future::Future, pin::Pin};
+use core::{future::Future, num::NonZeroU32, pin::Pin};
 
 use miden_objects::{
     Digest,
@@ -17,14 +17,15 @@ use miden_objects::{
 use tracing::info;
 
 use super::{
-    AccountUpdates, BlockUpdates, StateSyncUpdate, state_sync_update::TransactionUpdateTracker,
+    AccountUpdates, BlockUpdates, StateSyncUpdate, SyncProgress,
+    state_sync_update::TransactionUpdateTracker,
 };
 use crate::{
     ClientError,
     note::{NoteScreener, NoteUpdateTracker},
     rpc::{
         NodeRpcClient,
-        domain::{note::CommittedNote, transaction::TransactionInclusion},
+        domain::{note::CommittedNote, sync::StateSyncInfo, transaction::TransactionInclusion},
     },
     store::{InputNoteRecord, NoteFilter, OutputNoteRecord, Store, StoreError},
     transaction::TransactionRecord,
@@ -54,6 +55,10 @@ pub type OnNoteReceived = Box<
     ) -> Pin<Box<dyn Future<Output = Result<bool, ClientError>>>>,
 >;
 
+/// Callback that gets executed after each step of the sync process is applied, receiving the
+/// [`SyncProgress`] made so far.
+pub type OnSyncProgress = Box<dyn FnMut(SyncProgress)>;
+
 // STATE SYNC
 // ================================================================================================
 
@@ -73,6 +78,16 @@ pub struct StateSync {
     tx_graceful_blocks: Option<u32>,
     /// The note screener used to check the relevance of notes.
     note_screener: Arc<NoteScreener>,
+    /// Optional callback to be executed after each sync step to report progress.
+    on_progress: Option<OnSyncProgress>,
+    /// Maximum number of sync steps to perform before returning the accumulated updates. If
+    /// `None`, the sync continues until the chain tip is reached.
+    max_steps: Option<NonZeroU32>,
+    /// Optional store used to load the local state of updated public accounts, so that only the
+    /// changes made to them are requested from the node.
+    store: Option<Arc<dyn Store>>,
+    /// Block to stop the sync at. If `None`, the sync continues until the chain tip is reached.
+    target_block: Option<BlockNumber>,
 }
 
 impl StateSync {
@@ -96,9 +111,48 @@ impl StateSync {
             tx_graceful_blocks,
             #[allow(clippy::arc_with_non_send_sync)]
             note_screener: Arc::new(note_screener),
+            on_progress: None,
+            max_steps: None,
+            store: None,
+            target_block: None,
         }
     }
 
+    /// Limits the number of sync steps (that is, requests to the node) performed before the
+    /// accumulated updates are returned. The returned update might then not reach the chain tip,
+    /// in which case the sync should be continued after applying it.
+    #[must_use]
+    pub fn with_max_steps(mut self, max_steps: NonZeroU32) -> Self {
+        self.max_steps = Some(max_steps);
+        self
+    }
+
+    /// Sets a callback to be executed after each step of the sync process, reporting the block
+    /// the sync has reached, the chain tip it is syncing to and the number of notes updated so
+    /// far.
+    #[must_use]
+    pub fn with_progress_callback(mut self, on_progress: OnSyncProgress) -> Self {
+        self.on_progress = Some(on_progress);
+        self
+    }
+
+    /// Stops the sync at `target_block` instead of the chain tip, leaving the updates of later
+    /// blocks for a future sync. See [`crate::Client::sync_to_block`] for the caveats of syncing to
+    /// a block other than the chain tip.
+    #[must_use]
+    pub fn with_target_block(mut self, target_block: BlockNumber) -> Self {
+        self.target_block = Some(target_block);
+        self
+    }
+
+    /// Sets the store holding the tracked accounts. When set, updated public accounts are
+    /// fetched as deltas over their locally stored state instead of being fetched in full.
+    #[must_use]
+    pub fn with_store(mut self, store: Arc<dyn Store>) -> Self {
+        self.store = Some(store);
+        self
+    }
+
     /// Syncs the state of the client with the chain tip of the node, returning the updates that
     /// should be applied to the store.
     ///
@@ -125,7 +179,7 @@ impl StateSync {
     /// * `unspent_input_notes` - The current state of unspent input notes tracked by the client.
     /// * `unspent_output_notes` - The current state of unspent output notes tracked by the client.
     pub async fn sync_state(
-        self,
+        mut self,
         current_partial_blockchain: PartialBlockchain,
         accounts: Vec<AccountHeader>,
         note_tags: BTreeSet<NoteTag>,
@@ -145,10 +199,12 @@ impl StateSync {
 
         let mut partial_mmr = current_partial_blockchain.mmr().clone();
         let note_tags = Arc::new(note_tags);
+        let mut steps = 0;
         loop {
             if !self
                 .sync_state_step(
                     &mut state_sync_update,
+                    block_num,
                     &mut partial_mmr,
                     &accounts,
                     note_tags.clone(),
@@ -157,6 +213,11 @@ impl StateSync {
             {
                 break;
             }
+
+            steps += 1;
+            if self.max_steps.is_some_and(|max_steps| steps >= max_steps.get()) {
+                break;
+            }
         }
 
         self.sync_nullifiers(&mut state_sync_update, block_num).await?;
@@ -177,12 +238,17 @@ impl StateSync {
     /// This function returns whether the state sync process must continue, depending on whether
     /// the chain tip was reached already.
     async fn sync_state_step(
-        &self,
+        &mut self,
         state_sync_update: &mut StateSyncUpdate,
+        sync_from: BlockNumber,
         current_partial_mmr: &mut PartialMmr,
         accounts: &[AccountHeader],
         note_tags: Arc<BTreeSet<NoteTag>>,
     ) -> Result<bool, ClientError> {
+        if self.target_block.is_some_and(|target| state_sync_update.block_num >= target) {
+            return Ok(false);
+        }
+
         let account_ids: Vec<AccountId> = accounts.iter().map(AccountHeader::id).collect();
 
         let response = self
@@ -190,11 +256,30 @@ impl StateSync {
             .sync_state(state_sync_update.block_num, &account_ids, note_tags.as_ref())
             .await?;
 
+        // A node whose chain is shorter than the synced block has re-organized it away
+        if response.block_header.block_num() < state_sync_update.block_num {
+            return Err(ClientError::ChainDiverged(state_sync_update.block_num));
+        }
+
         // We don't need to continue if the chain has not advanced, there are no new changes
         if response.block_header.block_num() == state_sync_update.block_num {
             return Ok(false);
         }
 
+        if let Some(target_block) = self.target_block
+            && response.block_header.block_num() > target_block
+        {
+            self.sync_state_to_target(
+                state_sync_update,
+                current_partial_mmr,
+                accounts,
+                &response,
+                target_block,
+            )
+            .await?;
+            return Ok(false);
+        }
+
         let new_block_num = response.block_header.block_num();
         state_sync_update.block_num = new_block_num;
 
@@ -202,6 +287,8 @@ impl StateSync {
             &mut state_sync_update.account_updates,
             accounts,
             &response.account_commitment_updates,
+            sync_from,
+            new_block_num,
         )
         .await?;
 
@@ -227,9 +314,18 @@ impl StateSync {
             response.mmr_delta,
         )?;
 
+        // The node's delta only leads to the new block's chain commitment if the blocks tracked
+        // by the client are part of the node's chain
+        if new_mmr_peaks.hash_peaks() != response.block_header.chain_commitment() {
+            return Err(ClientError::ChainDiverged(sync_from));
+        }
+
         let mut new_blocks = vec![];
-        if found_relevant_note || response.chain_tip == new_block_num {
-            // Only track relevant blocks or the chain tip
+        if found_relevant_note
+            || response.chain_tip == new_block_num
+            || self.target_block == Some(new_block_num)
+        {
+            // Only track relevant blocks or the block the sync ends at
             new_blocks.push((response.block_header, found_relevant_note, new_mmr_peaks));
         }
 
@@ -237,6 +333,16 @@ impl StateSync {
             .block_updates
             .extend(BlockUpdates::new(new_blocks, new_authentication_nodes));
 
+        if let Some(on_progress) = self.on_progress.as_mut() {
+            let note_updates = &state_sync_update.note_updates;
+            on_progress(SyncProgress {
+                current_block: new_block_num,
+                target_block: self.target_block.unwrap_or(response.chain_tip),
+                notes_applied: note_updates.updated_input_notes().count()
+                    + note_updates.updated_output_notes().count(),
+            });
+        }
+
         if response.chain_tip == new_block_num {
             Ok(false)
         } else {
@@ -244,6 +350,82 @@ impl StateSync {
         }
     }
 
+    /// Advances the sync update to `target_block` after the node responded with a later block.
+    ///
+    /// The node responds with the first block after the requested one that has notes matching
+    /// the requested tags, so no relevant notes were created up to `target_block` and only the
+    /// transactions committed up to it are applied. The MMR is extended with the headers of the
+    /// blocks in between, which are requested from the node one by one.
+    ///
+    /// The response's account updates aren't tied to a block, so if any tracked account was
+    /// updated, the sync stops at its current block instead, leaving the account update for a
+    /// later sync.
+    async fn sync_state_to_target(
+        &mut self,
+        state_sync_update: &mut StateSyncUpdate,
+        current_partial_mmr: &mut PartialMmr,
+        accounts: &[AccountHeader],
+        response: &StateSyncInfo,
+        target_block: BlockNumber,
+    ) -> Result<(), ClientError> {
+        if response
+            .account_commitment_updates
+            .iter()
+            .any(|(account_id, _)| accounts.iter().any(|account| account.id() == *account_id))
+        {
+            info!(
+                "Tracked accounts were updated before block {}, stopping the sync at block {}.",
+                response.block_header.block_num(),
+                state_sync_update.block_num
+            );
+            return Ok(());
+        }
+
+        let transactions: Vec<TransactionInclusion> = response
+            .transactions
+            .iter()
+            .filter(|transaction| transaction.block_num <= target_block.as_u32())
+            .cloned()
+            .collect();
+        self.transaction_state_sync(
+            &mut state_sync_update.transaction_updates,
+            target_block,
+            &transactions,
+        );
+
+        let mut new_authentication_nodes = vec![];
+        for block_num in (state_sync_update.block_num.as_u32() + 1)..target_block.as_u32() {
+            let (block_header, _) =
+                self.rpc_api.get_block_header_by_number(Some(block_num.into()), false).await?;
+            new_authentication_nodes
+                .append(&mut current_partial_mmr.add(block_header.commitment(), false));
+        }
+
+        let new_peaks = current_partial_mmr.peaks();
+        let (target_header, _) =
+            self.rpc_api.get_block_header_by_number(Some(target_block), false).await?;
+        new_authentication_nodes
+            .append(&mut current_partial_mmr.add(target_header.commitment(), false));
+
+        state_sync_update.block_num = target_block;
+        state_sync_update.block_updates.extend(BlockUpdates::new(
+            vec![(target_header, false, new_peaks)],
+            new_authentication_nodes,
+        ));
+
+        if let Some(on_progress) = self.on_progress.as_mut() {
+            let note_updates = &state_sync_update.note_updates;
+            on_progress(SyncProgress {
+                current_block: target_block,
+                target_block,
+                notes_applied: note_updates.updated_input_notes().count()
+                    + note_updates.updated_output_notes().count(),
+            });
+        }
+
+        Ok(())
+    }
+
     // HELPERS
     // --------------------------------------------------------------------------------------------
 
@@ -251,6 +433,9 @@ impl StateSync {
     /// updates the `state_sync_update` field with the details of the accounts that need to be
     /// updated.
     ///
+    /// The `accounts` headers reflect the state of the tracked accounts as of `sync_from`, and
+    /// `account_commitment_updates` the state of the updated accounts as of `block_num`.
+    ///
     /// The account updates might include:
     /// * Public accounts that have been updated in the node.
     /// * Network accounts that have been updated in the node and are being tracked by the client.
@@ -262,12 +447,19 @@ impl StateSync {
         account_updates: &mut AccountUpdates,
         accounts: &[AccountHeader],
         account_commitment_updates: &[(AccountId, Digest)],
+        sync_from: BlockNumber,
+        block_num: BlockNumber,
     ) -> Result<(), ClientError> {
         let (public_accounts, private_accounts): (Vec<_>, Vec<_>) =
             accounts.iter().partition(|account_header| !account_header.id().is_private());
 
         let updated_public_accounts = self
-            .get_updated_public_accounts(account_commitment_updates, &public_accounts)
+            .get_updated_public_accounts(
+                account_commitment_updates,
+                &public_accounts,
+                sync_from,
+                block_num,
+            )
             .await?;
 
         let mismatched_private_accounts = account_commitment_updates
@@ -288,27 +480,55 @@ impl StateSync {
 
     /// Queries the node for the latest state of the public accounts that don't match the current
     /// state of the client.
+    ///
+    /// If a store was provided, the changes made to each account since `sync_from` are applied to
+    /// its locally stored state. Otherwise, the full account details are fetched.
     async fn get_updated_public_accounts(
         &self,
         account_updates: &[(AccountId, Digest)],
         current_public_accounts: &[&AccountHeader],
+        sync_from: BlockNumber,
+        block_num: BlockNumber,
     ) -> Result<Vec<Account>, ClientError> {
+        let mut updated_public_accounts = vec![];
         let mut mismatched_public_accounts = vec![];
 
         for (id, commitment) in account_updates {
             // check if this updated account state is tracked by the client
-            if let Some(account) = current_public_accounts
+            let Some(account) = current_public_accounts
                 .iter()
                 .find(|acc| *id == acc.id() && *commitment != acc.commitment())
-            {
-                mismatched_public_accounts.push(*account);
+            else {
+                continue;
+            };
+
+            let local_account = match &self.store {
+                Some(store) => store.get_account(*id).await?,
+                None => None,
+            };
+
+            match local_account {
+                Some(local_account) => {
+                    let updated_account = self
+                        .rpc_api
+                        .get_updated_public_account(
+                            local_account.into(),
+                            sync_from,
+                            block_num,
+                            *commitment,
+                        )
+                        .await?;
+
+                    updated_public_accounts.extend(updated_account);
+                },
+                None => mismatched_public_accounts.push(*account),
             }
         }
 
-        self.rpc_api
-            .get_updated_public_accounts(&mismatched_public_accounts)
-            .await
-            .map_err(ClientError::RpcError)
+        updated_public_accounts
+            .extend(self.rpc_api.get_updated_public_accounts(&mismatched_public_accounts).await?);
+
+        Ok(updated_public_accounts)
     }
 
     /// Applies the changes received from the sync response to the notes and transactions tracked
@@ -515,3 +735,19 @@ pub async fn on_note_received(
         Ok(false)
     }
 }
+
+/// Strict variant of [`on_note_received`].
+///
+/// The node matches note tags fuzzily (for example, the tag derived from an account ID is shared
+/// by every account with the same ID prefix), so a tracked tag doesn't mean that the note is
+/// addressed to the client. With this callback, notes that aren't tracked by the client are only
+/// kept if the [`NoteScreener`] finds them relevant to a tracked account, regardless of their tag.
+pub async fn on_note_received_strict(
+    store: Arc<dyn Store>,
+    committed_note: CommittedNote,
+    public_note: Option<InputNoteRecord>,
+    note_screener: Arc<NoteScreener>,
+) -> Result<bool, ClientError> {
+    on_note_received(store, committed_note, public_note, note_screener, Arc::new(BTreeSet::new()))
+        .await
+}
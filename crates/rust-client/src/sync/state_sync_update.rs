@@ -1,7 +1,9 @@
 use alloc::{
     collections::{BTreeMap, BTreeSet},
+    string::ToString,
     vec::Vec,
 };
+use core::num::NonZeroUsize;
 
 use miden_objects::{
     Digest,
@@ -11,6 +13,7 @@ use miden_objects::{
     note::{NoteId, Nullifier},
     transaction::TransactionId,
 };
+use miden_tx::utils::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable};
 
 use super::SyncSummary;
 use crate::{
@@ -139,6 +142,11 @@ impl BlockUpdates {
         self.block_headers.extend(other.block_headers);
         self.new_authentication_nodes.extend(other.new_authentication_nodes);
     }
+
+    /// Discards the new authentication nodes, so that only the block headers are stored.
+    pub(crate) fn clear_authentication_nodes(&mut self) {
+        self.new_authentication_nodes.clear();
+    }
 }
 
 /// Contains transaction changes to apply to the store.
@@ -236,7 +244,7 @@ impl TransactionUpdateTracker {
     /// Discards transactions that have the same initial account state as the provided one.
     pub fn apply_invalid_initial_account_state(&mut self, invalid_account_state: Digest) {
         self.discard_transaction_with_predicate(
-            |transaction| transaction.details.init_account_state == invalid_account_state,
+            |transaction| transaction.details.init_account_state == Some(invalid_account_state),
             DiscardCause::DiscardedInitialState,
         );
     }
@@ -306,3 +314,114 @@ impl AccountUpdates {
         self.mismatched_private_accounts.extend(other.mismatched_private_accounts);
     }
 }
+
+// SERIALIZATION
+// ================================================================================================
+
+impl Serializable for StateSyncUpdate {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.block_num.write_into(target);
+        self.block_updates.write_into(target);
+        self.note_updates.write_into(target);
+        self.transaction_updates.write_into(target);
+        self.account_updates.write_into(target);
+    }
+}
+
+impl Deserializable for StateSyncUpdate {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let block_num = BlockNumber::read_from(source)?;
+        let block_updates = BlockUpdates::read_from(source)?;
+        let note_updates = NoteUpdateTracker::read_from(source)?;
+        let transaction_updates = TransactionUpdateTracker::read_from(source)?;
+        let account_updates = AccountUpdates::read_from(source)?;
+
+        Ok(Self {
+            block_num,
+            block_updates,
+            note_updates,
+            transaction_updates,
+            account_updates,
+        })
+    }
+}
+
+impl Serializable for BlockUpdates {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_usize(self.block_headers.len());
+        for (block_header, has_client_notes, peaks) in &self.block_headers {
+            block_header.write_into(target);
+            target.write_bool(*has_client_notes);
+            target.write_usize(peaks.num_leaves());
+            peaks.peaks().write_into(target);
+        }
+
+        target.write_usize(self.new_authentication_nodes.len());
+        for (index, node) in &self.new_authentication_nodes {
+            target.write_u64(index.inner());
+            node.write_into(target);
+        }
+    }
+}
+
+impl Deserializable for BlockUpdates {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let num_block_headers = source.read_usize()?;
+        let mut block_headers = Vec::new();
+        for _ in 0..num_block_headers {
+            let block_header = BlockHeader::read_from(source)?;
+            let has_client_notes = source.read_bool()?;
+            let num_leaves = source.read_usize()?;
+            let peaks = MmrPeaks::new(num_leaves, Vec::<Digest>::read_from(source)?)
+                .map_err(|err| DeserializationError::InvalidValue(err.to_string()))?;
+
+            block_headers.push((block_header, has_client_notes, peaks));
+        }
+
+        let num_nodes = source.read_usize()?;
+        let mut new_authentication_nodes = Vec::new();
+        for _ in 0..num_nodes {
+            let index = usize::try_from(source.read_u64()?)
+                .ok()
+                .and_then(NonZeroUsize::new)
+                .map(InOrderIndex::new)
+                .ok_or(DeserializationError::InvalidValue("invalid in-order index".to_string()))?;
+            let node = Digest::read_from(source)?;
+
+            new_authentication_nodes.push((index, node));
+        }
+
+        Ok(Self { block_headers, new_authentication_nodes })
+    }
+}
+
+impl Serializable for TransactionUpdateTracker {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_usize(self.transactions.len());
+        for transaction in self.transactions.values() {
+            transaction.write_into(target);
+        }
+    }
+}
+
+impl Deserializable for TransactionUpdateTracker {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        Ok(Self::new(Vec::<TransactionRecord>::read_from(source)?))
+    }
+}
+
+impl Serializable for AccountUpdates {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.updated_public_accounts.write_into(target);
+        self.mismatched_private_accounts.write_into(target);
+    }
+}
+
+impl Deserializable for AccountUpdates {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let updated_public_accounts = Vec::<Account>::read_from(source)?;
+        let mismatched_private_accounts = Vec::<(AccountId, Digest)>::read_from(source)?;
+
+        Ok(Self::new(updated_public_accounts, mismatched_private_accounts))
+    }
+}
@@ -1,4 +1,4 @@
-use alloc::{string::ToString, vec::Vec};
+use alloc::{collections::BTreeSet, string::ToString, vec::Vec};
 
 use miden_objects::{
     account::{Account, AccountId},
@@ -31,11 +31,40 @@ impl Client {
         self.store.get_note_tags().await.map_err(Into::into)
     }
 
-    /// Adds a note tag for the client to track. This tag's source will be marked as `User`.
+    /// Returns each unique note tag tracked by the client along with the number of input notes
+    /// in the store that have that tag, sorted by tag.
+    ///
+    /// A note's tag is taken from its metadata or, for expected notes without metadata, from the
+    /// tag they were imported with. Every note with the tag is counted, including the ones kept
+    /// because of a [`TagSubscription::Prefix`] subscription that aren't relevant to any tracked
+    /// account. Tags that don't match any note are returned with a count of 0, which helps
+    /// identify subscriptions that can be removed.
+    pub async fn get_note_tag_summary(&self) -> Result<Vec<(NoteTag, usize)>, ClientError> {
+        Ok(self.store.get_note_tag_counts().await?.into_iter().collect())
+    }
+
+    /// Adds a note tag for the client to track. This tag's source will be marked as `User` and
+    /// every note received with it will be kept, as with a [`TagSubscription::Prefix`].
     pub async fn add_note_tag(&mut self, tag: NoteTag) -> Result<(), ClientError> {
+        self.add_note_tag_with_subscription(tag, TagSubscription::Prefix).await
+    }
+
+    /// Adds a note tag for the client to track with the provided [`TagSubscription`]. This tag's
+    /// source will be marked as `User`.
+    ///
+    /// If the tag is already being tracked, its subscription isn't changed.
+    pub async fn add_note_tag_with_subscription(
+        &mut self,
+        tag: NoteTag,
+        subscription: TagSubscription,
+    ) -> Result<(), ClientError> {
         match self
             .store
-            .add_note_tag(NoteTagRecord { tag, source: NoteTagSource::User })
+            .add_note_tag(NoteTagRecord {
+                tag,
+                source: NoteTagSource::User,
+                subscription,
+            })
             .await
             .map_err(Into::into)
         {
@@ -52,7 +81,11 @@ impl Client {
     pub async fn remove_note_tag(&mut self, tag: NoteTag) -> Result<(), ClientError> {
         if self
             .store
-            .remove_note_tag(NoteTagRecord { tag, source: NoteTagSource::User })
+            .remove_note_tag(NoteTagRecord {
+                tag,
+                source: NoteTagSource::User,
+                subscription: TagSubscription::default(),
+            })
             .await?
             == 0
         {
@@ -61,6 +94,26 @@ impl Client {
 
         Ok(())
     }
+
+    /// Removes the note tags whose source is an account that is no longer tracked by the client.
+    ///
+    /// Tags for accounts are registered automatically when the account is added to the client. If
+    /// the account stops being tracked, these tags would otherwise keep being included in every
+    /// sync request and fetching notes that are no longer relevant.
+    pub(crate) async fn remove_orphaned_account_tags(&mut self) -> Result<(), ClientError> {
+        let tracked_accounts: BTreeSet<AccountId> =
+            self.store.get_account_ids().await?.into_iter().collect();
+
+        for tag_record in self.store.get_note_tags().await? {
+            if let NoteTagSource::Account(account_id) = tag_record.source
+                && !tracked_accounts.contains(&account_id)
+            {
+                self.store.remove_note_tag(tag_record).await?;
+            }
+        }
+
+        Ok(())
+    }
 }
 
 /// Represents a note tag of which the Store can keep track and retrieve.
@@ -68,6 +121,7 @@ impl Client {
 pub struct NoteTagRecord {
     pub tag: NoteTag,
     pub source: NoteTagSource,
+    pub subscription: TagSubscription,
 }
 
 /// Represents the source of the tag. This is used to differentiate between tags that are added by
@@ -82,11 +136,27 @@ pub enum NoteTagSource {
     User,
 }
 
+/// Represents how the notes received for a tracked tag are filtered during sync.
+///
+/// The node returns every note whose tag matches a tracked tag. Tags derived from an account ID
+/// only contain a prefix of the ID, so they also match notes addressed to any other account that
+/// shares the prefix.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub enum TagSubscription {
+    /// Every note received with the tag is kept.
+    #[default]
+    Prefix,
+    /// Notes received with the tag are only kept if they are tracked by the client or relevant to
+    /// one of its accounts.
+    Exact,
+}
+
 impl NoteTagRecord {
     pub fn with_note_source(tag: NoteTag, note_id: NoteId) -> Self {
         Self {
             tag,
             source: NoteTagSource::Note(note_id),
+            subscription: TagSubscription::default(),
         }
     }
 
@@ -94,10 +164,29 @@ impl NoteTagRecord {
         Self {
             tag,
             source: NoteTagSource::Account(account_id),
+            subscription: TagSubscription::default(),
         }
     }
 }
 
+impl Serializable for NoteTagRecord {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.tag.write_into(target);
+        self.source.write_into(target);
+        self.subscription.write_into(target);
+    }
+}
+
+impl Deserializable for NoteTagRecord {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let tag = NoteTag::read_from(source)?;
+        let tag_source = NoteTagSource::read_from(source)?;
+        let subscription = TagSubscription::read_from(source)?;
+
+        Ok(Self { tag, source: tag_source, subscription })
+    }
+}
+
 impl Serializable for NoteTagSource {
     fn write_into<W: ByteWriter>(&self, target: &mut W) {
         match self {
@@ -125,6 +214,27 @@ impl Deserializable for NoteTagSource {
     }
 }
 
+impl Serializable for TagSubscription {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        match self {
+            TagSubscription::Prefix => target.write_u8(0),
+            TagSubscription::Exact => target.write_u8(1),
+        }
+    }
+}
+
+impl Deserializable for TagSubscription {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        match source.read_u8()? {
+            0 => Ok(TagSubscription::Prefix),
+            1 => Ok(TagSubscription::Exact),
+            val => {
+                Err(DeserializationError::InvalidValue(format!("Invalid tag subscription: {val}")))
+            },
+        }
+    }
+}
+
 impl PartialEq<NoteTag> for NoteTagRecord {
     fn eq(&self, other: &NoteTag) -> bool {
         self.tag == *other
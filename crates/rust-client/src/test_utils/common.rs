@@ -16,7 +16,7 @@ use miden_objects::{
     account::{Account, AccountId, AccountStorageMode},
     asset::{Asset, FungibleAsset, TokenSymbol},
     crypto::{dsa::rpo_falcon512::SecretKey, rand::RpoRandomCoin},
-    note::{NoteId, NoteType},
+    note::{NoteId, NoteRecipient, NoteType},
     transaction::{InputNote, OutputNote, TransactionId},
 };
 use rand::{Rng, RngCore, rngs::StdRng};
@@ -253,11 +253,39 @@ pub async fn execute_tx_and_sync(
 
 /// Syncs the client and waits for the transaction to be committed.
 pub async fn wait_for_tx(client: &mut TestClient, transaction_id: TransactionId) {
-    // wait until tx is committed
+    wait_for_tx_confirmations(client, transaction_id, 0).await;
+}
+
+/// Syncs the client and waits for the transaction to be committed in a block that is at least
+/// `min_confirmations` blocks behind the client's sync height.
+pub async fn wait_for_tx_confirmations(
+    client: &mut TestClient,
+    transaction_id: TransactionId,
+    min_confirmations: u32,
+) {
+    wait_for_tx_confirmations_with_sleep(
+        client,
+        transaction_id,
+        min_confirmations,
+        std::thread::sleep,
+    )
+    .await;
+}
+
+/// Syncs the client and waits for the transaction to be committed in a block that is at least
+/// `min_confirmations` blocks behind the client's sync height, calling `sleep` with the interval to
+/// wait between syncs.
+pub async fn wait_for_tx_confirmations_with_sleep(
+    client: &mut TestClient,
+    transaction_id: TransactionId,
+    min_confirmations: u32,
+    mut sleep: impl FnMut(Duration),
+) {
+    // wait until tx is committed and buried under enough blocks
     let now = Instant::now();
     println!("Syncing State...");
     loop {
-        client.sync_state().await.unwrap();
+        let sync_height = client.sync_state().await.unwrap().block_num;
 
         // Check if executed transaction got committed by the node
         let tracked_transaction = client
@@ -268,12 +296,12 @@ pub async fn wait_for_tx(client: &mut TestClient, transaction_id: TransactionId)
             .unwrap();
 
         match tracked_transaction.status {
-            TransactionStatus::Committed(n) => {
+            TransactionStatus::Committed(n) if sync_height >= n + min_confirmations => {
                 println!("tx committed in {n}");
                 break;
             },
-            TransactionStatus::Pending => {
-                std::thread::sleep(Duration::from_secs(1));
+            TransactionStatus::Committed(_) | TransactionStatus::Pending => {
+                sleep(Duration::from_secs(1));
             },
             TransactionStatus::Discarded(cause) => {
                 panic!("Transaction was discarded with cause: {:?}", cause);
@@ -300,11 +328,30 @@ pub async fn wait_for_tx(client: &mut TestClient, transaction_id: TransactionId)
     }
 }
 
+/// Time waited by [`wait_for_blocks`] before its first retry. The interval doubles on each retry
+/// until it reaches [`WAIT_FOR_BLOCKS_MAX_INTERVAL`].
+pub const WAIT_FOR_BLOCKS_INITIAL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Maximum time waited by [`wait_for_blocks`] between syncs.
+pub const WAIT_FOR_BLOCKS_MAX_INTERVAL: Duration = Duration::from_secs(3);
+
 /// Syncs until `amount_of_blocks` have been created onchain compared to client's sync height
 pub async fn wait_for_blocks(client: &mut TestClient, amount_of_blocks: u32) -> SyncSummary {
+    wait_for_blocks_with_sleep(client, amount_of_blocks, std::thread::sleep).await
+}
+
+/// Syncs until `amount_of_blocks` have been created onchain compared to client's sync height,
+/// calling `sleep` with the interval to wait between syncs.
+pub async fn wait_for_blocks_with_sleep(
+    client: &mut TestClient,
+    amount_of_blocks: u32,
+    mut sleep: impl FnMut(Duration),
+) -> SyncSummary {
     let current_block = client.get_sync_height().await.unwrap();
     let final_block = current_block + amount_of_blocks;
     println!("Syncing until block {final_block}...",);
+
+    let mut interval = WAIT_FOR_BLOCKS_INITIAL_INTERVAL;
     loop {
         let summary = client.sync_state().await.unwrap();
         println!("Synced to block {} (syncing until {})...", summary.block_num, final_block);
@@ -313,7 +360,8 @@ pub async fn wait_for_blocks(client: &mut TestClient, amount_of_blocks: u32) ->
             return summary;
         }
 
-        std::thread::sleep(Duration::from_secs(3));
+        sleep(interval);
+        interval = (interval * 2).min(WAIT_FOR_BLOCKS_MAX_INTERVAL);
     }
 }
 
@@ -433,6 +481,44 @@ pub async fn consume_notes(
     execute_tx_and_sync(client, account_id, tx_request).await;
 }
 
+/// Funds `target_account_id` with `amount` units of the `faucet_account_id` token.
+///
+/// Mints a private note for the target account and consumes it (see [`mint_amount_and_consume`]),
+/// returning once the target's vault reflects the funded amount.
+pub async fn fund_account(
+    client: &mut TestClient,
+    faucet_account_id: AccountId,
+    target_account_id: AccountId,
+    amount: u64,
+) {
+    let initial_balance = get_fungible_balance(client, target_account_id, faucet_account_id).await;
+
+    println!("Funding account {target_account_id} with {amount} units...");
+    mint_amount_and_consume(
+        client,
+        target_account_id,
+        faucet_account_id,
+        NoteType::Private,
+        amount,
+    )
+    .await;
+
+    assert_eq!(
+        get_fungible_balance(client, target_account_id, faucet_account_id).await,
+        initial_balance + amount
+    );
+}
+
+/// Returns the balance of the `faucet_account_id` token in the vault of the account.
+async fn get_fungible_balance(
+    client: &TestClient,
+    account_id: AccountId,
+    faucet_account_id: AccountId,
+) -> u64 {
+    let account: Account = client.get_account(account_id).await.unwrap().unwrap().into();
+    account.vault().get_balance(faucet_account_id).unwrap()
+}
+
 /// Asserts that the account has a single asset with the expected amount.
 pub async fn assert_account_has_single_asset(
     client: &TestClient,
@@ -503,6 +589,20 @@ pub fn mint_multiple_fungible_asset(
     TransactionRequestBuilder::new().own_output_notes(notes).build().unwrap()
 }
 
+/// Returns a copy of `note` with its serial number replaced by `serial_num`.
+///
+/// Notes built by [`NoteBuilder`](miden_objects::testing::note::NoteBuilder) draw a random serial
+/// number, so this can be used to get a note whose ID is the same across runs.
+pub fn with_serial_number(note: &Note, serial_num: Word) -> Note {
+    let recipient = NoteRecipient::new(
+        serial_num,
+        note.recipient().script().clone(),
+        note.recipient().inputs().clone(),
+    );
+
+    Note::new(note.assets().clone(), *note.metadata(), recipient)
+}
+
 /// Executes a transaction and consumes the resulting unauthenticated notes immediately without
 /// waiting for the first transaction to be committed.
 pub async fn execute_tx_and_consume_output_notes(
@@ -534,10 +634,23 @@ pub async fn mint_and_consume(
     basic_account_id: AccountId,
     faucet_account_id: AccountId,
     note_type: NoteType,
+) {
+    mint_amount_and_consume(client, basic_account_id, faucet_account_id, note_type, MINT_AMOUNT)
+        .await;
+}
+
+/// Mint `amount` units of the faucet's token for the target account and consume them immediately
+/// without waiting for the first transaction to be committed.
+pub async fn mint_amount_and_consume(
+    client: &mut TestClient,
+    basic_account_id: AccountId,
+    faucet_account_id: AccountId,
+    note_type: NoteType,
+    amount: u64,
 ) {
     let tx_request = TransactionRequestBuilder::new()
         .build_mint_fungible_asset(
-            FungibleAsset::new(faucet_account_id, MINT_AMOUNT).unwrap(),
+            FungibleAsset::new(faucet_account_id, amount).unwrap(),
             basic_account_id,
             note_type,
             client.rng(),
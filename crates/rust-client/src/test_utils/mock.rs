@@ -1,9 +1,15 @@
-use alloc::{collections::BTreeSet, sync::Arc, vec::Vec};
+use alloc::{
+    collections::{BTreeMap, BTreeSet},
+    string::ToString,
+    sync::Arc,
+    vec::Vec,
+};
+use core::sync::atomic::{AtomicUsize, Ordering};
 
 use miden_lib::transaction::TransactionKernel;
 use miden_objects::{
     Digest, Felt,
-    account::{AccountCode, AccountDelta, AccountId},
+    account::{AccountCode, AccountDelta, AccountId, delta::AccountUpdateDetails},
     block::{BlockHeader, BlockNumber, ProvenBlock},
     crypto::{
         merkle::{MerklePath, Mmr, MmrProof, SmtProof},
@@ -11,7 +17,10 @@ use miden_objects::{
     },
     note::{NoteExecutionMode, NoteId, NoteTag, NoteType, Nullifier},
     testing::{account_id::ACCOUNT_ID_PRIVATE_SENDER, note::NoteBuilder},
-    transaction::{InputNoteCommitment, OutputNote, ProvenTransaction},
+    transaction::{
+        InputNoteCommitment, OrderedTransactionHeaders, OutputNote, ProvenTransaction,
+        TransactionHeader,
+    },
 };
 use miden_testing::{MockChain, MockChainNote};
 use miden_tx::utils::sync::RwLock;
@@ -19,15 +28,17 @@ use miden_tx::utils::sync::RwLock;
 use crate::{
     Client,
     rpc::{
-        NodeRpcClient, RpcError,
+        NodeRpcClient, NodeRpcClientEndpoint, RpcError, SubmissionError,
         domain::{
-            account::{AccountProofs, FetchedAccount},
+            account::{AccountProofs, AccountUpdateSummary, FetchedAccount},
             note::{CommittedNote, FetchedNote, NoteSyncInfo},
             nullifier::NullifierUpdate,
+            status::NodeStatus,
             sync::StateSyncInfo,
         },
         generated::{
-            note::NoteSyncRecord, responses::SyncStateResponse, transaction::TransactionSummary,
+            account::AccountSummary, note::NoteSyncRecord, responses::SyncStateResponse,
+            transaction::TransactionSummary,
         },
     },
     transaction::ForeignAccount,
@@ -42,6 +53,20 @@ pub type MockClient = Client;
 #[derive(Clone)]
 pub struct MockRpcApi {
     committed_transactions: Arc<RwLock<Vec<TransactionSummary>>>, /* TODO: Should this be tracked by the mock_chain? */
+    /// Deltas of the public accounts updated by the submitted transactions, along with the block
+    /// in which they were committed.
+    account_deltas: Arc<RwLock<Vec<(BlockNumber, AccountId, AccountDelta)>>>,
+    /// Latest state commitment of the accounts updated by the submitted transactions, along with
+    /// the block in which it was committed.
+    account_commitments: Arc<RwLock<BTreeMap<AccountId, (Digest, BlockNumber)>>>,
+    /// Headers of the submitted transactions, along with the block in which they were committed.
+    /// The blocks proven by the mock chain don't contain them, so they're added to the blocks
+    /// returned by [`NodeRpcClient::get_block_by_number`].
+    transaction_headers: Arc<RwLock<Vec<(BlockNumber, TransactionHeader)>>>,
+    /// Number of requests received by [`NodeRpcClient::get_account_state_delta`].
+    account_state_delta_requests: Arc<AtomicUsize>,
+    /// Error returned by the next call to [`NodeRpcClient::submit_proven_transaction`], if any.
+    next_submission_error: Arc<RwLock<Option<SubmissionError>>>,
     pub mock_chain: Arc<RwLock<MockChain>>,
 }
 impl Default for MockRpcApi {
@@ -55,6 +80,11 @@ impl MockRpcApi {
         let mock_chain = MockChain::new();
         let api = Self {
             committed_transactions: Arc::new(RwLock::new(vec![])),
+            account_deltas: Arc::new(RwLock::new(vec![])),
+            account_commitments: Arc::new(RwLock::new(BTreeMap::new())),
+            transaction_headers: Arc::new(RwLock::new(vec![])),
+            account_state_delta_requests: Arc::new(AtomicUsize::new(0)),
+            next_submission_error: Arc::new(RwLock::new(None)),
             mock_chain: Arc::new(RwLock::new(mock_chain)),
         };
 
@@ -102,6 +132,17 @@ impl MockRpcApi {
         self.mock_chain.read().blockchain().as_mmr().clone()
     }
 
+    /// Returns the number of account state deltas requested to the mock so far.
+    pub fn account_state_delta_request_count(&self) -> usize {
+        self.account_state_delta_requests.load(Ordering::Relaxed)
+    }
+
+    /// Makes the next transaction submission fail with the provided error, without including the
+    /// transaction in a block.
+    pub fn fail_next_submission(&self, error: SubmissionError) {
+        *self.next_submission_error.write() = Some(error);
+    }
+
     /// Returns the chain tip block number.
     pub fn get_chain_tip_block_num(&self) -> BlockNumber {
         self.mock_chain.read().latest_block_header().block_num()
@@ -116,6 +157,7 @@ impl MockRpcApi {
     fn get_sync_state_request(
         &self,
         request_block_num: BlockNumber,
+        account_ids: &[AccountId],
         note_tags: &BTreeSet<NoteTag>,
     ) -> SyncStateResponse {
         // Determine the next block number to sync
@@ -155,15 +197,34 @@ impl MockRpcApi {
             .committed_transactions
             .read()
             .iter()
-            .filter(|tx| tx.block_num == next_block_num.as_u32())
+            .filter(|tx| {
+                tx.block_num > request_block_num.as_u32() && tx.block_num <= next_block_num.as_u32()
+            })
             .cloned()
             .collect::<Vec<_>>();
 
+        // Report the requested accounts that were updated by a transaction in the synced range
+        let accounts = self
+            .account_commitments
+            .read()
+            .iter()
+            .filter(|(account_id, (_, block_num))| {
+                account_ids.contains(account_id)
+                    && *block_num > request_block_num
+                    && *block_num <= next_block_num
+            })
+            .map(|(account_id, (commitment, block_num))| AccountSummary {
+                account_id: Some((*account_id).into()),
+                account_commitment: Some((*commitment).into()),
+                block_num: block_num.as_u32(),
+            })
+            .collect();
+
         SyncStateResponse {
             chain_tip: self.get_chain_tip_block_num().as_u32(),
             block_header: Some(next_block.into()),
             mmr_delta: Some(mmr_delta.into()),
-            accounts: vec![],
+            accounts,
             transactions,
             notes,
         }
@@ -217,7 +278,7 @@ impl NodeRpcClient for MockRpcApi {
         block_num: BlockNumber,
         note_tags: &BTreeSet<NoteTag>,
     ) -> Result<NoteSyncInfo, RpcError> {
-        let response = self.get_sync_state_request(block_num, note_tags);
+        let response = self.get_sync_state_request(block_num, &[], note_tags);
 
         let response = NoteSyncInfo {
             chain_tip: response.chain_tip,
@@ -245,10 +306,10 @@ impl NodeRpcClient for MockRpcApi {
     async fn sync_state(
         &self,
         block_num: BlockNumber,
-        _account_ids: &[AccountId],
+        account_ids: &[AccountId],
         note_tags: &BTreeSet<NoteTag>,
     ) -> Result<StateSyncInfo, RpcError> {
-        let response = self.get_sync_state_request(block_num, note_tags);
+        let response = self.get_sync_state_request(block_num, account_ids, note_tags);
 
         Ok(response.try_into().unwrap())
     }
@@ -276,6 +337,14 @@ impl NodeRpcClient for MockRpcApi {
         Ok((block, mmr_proof))
     }
 
+    async fn status(&self) -> Result<NodeStatus, RpcError> {
+        Ok(NodeStatus {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            chain_tip: self.get_chain_tip_block_num(),
+            genesis_commitment: self.get_block_by_num(BlockNumber::GENESIS).commitment(),
+        })
+    }
+
     async fn get_notes_by_id(&self, note_ids: &[NoteId]) -> Result<Vec<FetchedNote>, RpcError> {
         // assume all public notes for now
         let notes = self.mock_chain.read().committed_notes().clone();
@@ -300,6 +369,10 @@ impl NodeRpcClient for MockRpcApi {
         &self,
         proven_transaction: ProvenTransaction,
     ) -> Result<BlockNumber, RpcError> {
+        if let Some(error) = self.next_submission_error.write().take() {
+            return Err(RpcError::TransactionSubmissionError(error));
+        }
+
         // TODO: add some basic validations to test error cases
         let notes: Vec<OutputNote> = proven_transaction.output_notes().iter().cloned().collect();
 
@@ -309,6 +382,20 @@ impl NodeRpcClient for MockRpcApi {
             .map(InputNoteCommitment::nullifier)
             .collect();
 
+        // Reject transactions that consume notes that were already consumed
+        if let Some(spent_nullifier) = self
+            .mock_chain
+            .read()
+            .nullifier_tree()
+            .entries()
+            .map(|(nullifier, _)| nullifier)
+            .find(|nullifier| nullifiers.contains(nullifier))
+        {
+            return Err(RpcError::TransactionSubmissionError(SubmissionError::AlreadySpent(
+                format!("nullifier {} was already spent", spent_nullifier.to_hex()),
+            )));
+        }
+
         self.seal_block(notes, nullifiers);
         self.committed_transactions.write().push(TransactionSummary {
             transaction_id: Some(proven_transaction.id().into()),
@@ -316,14 +403,46 @@ impl NodeRpcClient for MockRpcApi {
             account_id: Some(proven_transaction.account_id().into()),
         });
 
+        self.transaction_headers
+            .write()
+            .push((self.get_chain_tip_block_num(), TransactionHeader::from(&proven_transaction)));
+
+        self.account_commitments.write().insert(
+            proven_transaction.account_id(),
+            (
+                proven_transaction.account_update().final_state_commitment(),
+                self.get_chain_tip_block_num(),
+            ),
+        );
+
+        if let AccountUpdateDetails::Delta(account_delta) =
+            proven_transaction.account_update().details()
+        {
+            self.account_deltas.write().push((
+                self.get_chain_tip_block_num(),
+                proven_transaction.account_id(),
+                account_delta.clone(),
+            ));
+        }
+
         Ok(self.get_chain_tip_block_num())
     }
 
-    async fn get_account_details(
-        &self,
-        _account_id: AccountId,
-    ) -> Result<FetchedAccount, RpcError> {
-        unimplemented!("shouldn't be used for now")
+    /// Returns the latest commitment of an account updated by a submitted transaction. As the
+    /// mock doesn't keep the full state of accounts, they are always returned as private.
+    async fn get_account_details(&self, account_id: AccountId) -> Result<FetchedAccount, RpcError> {
+        let (commitment, block_num) =
+            self.account_commitments.read().get(&account_id).copied().ok_or_else(|| {
+                RpcError::RequestError(
+                    NodeRpcClientEndpoint::GetAccountDetails.to_string(),
+                    format!("account {account_id} not found"),
+                )
+            })?;
+
+        Ok(FetchedAccount::Private(
+            account_id,
+            AccountUpdateSummary::new(commitment, block_num.as_u32()),
+        ))
     }
 
     async fn get_account_proofs(
@@ -363,11 +482,24 @@ impl NodeRpcClient for MockRpcApi {
 
     async fn get_account_state_delta(
         &self,
-        _account_id: AccountId,
-        _from_block: BlockNumber,
-        _to_block: BlockNumber,
+        account_id: AccountId,
+        from_block: BlockNumber,
+        to_block: BlockNumber,
     ) -> Result<AccountDelta, RpcError> {
-        unimplemented!("shouldn't be used for now")
+        self.account_state_delta_requests.fetch_add(1, Ordering::Relaxed);
+
+        self.account_deltas
+            .read()
+            .iter()
+            .filter(|(block_num, delta_account_id, _)| {
+                *delta_account_id == account_id && *block_num > from_block && *block_num <= to_block
+            })
+            .map(|(.., account_delta)| account_delta.clone())
+            .reduce(|mut merged_delta, account_delta| {
+                merged_delta.merge(account_delta).expect("account deltas should be mergeable");
+                merged_delta
+            })
+            .ok_or(RpcError::ExpectedDataMissing("AccountDelta".to_string()))
     }
 
     async fn get_block_by_number(&self, block_num: BlockNumber) -> Result<ProvenBlock, RpcError> {
@@ -380,6 +512,20 @@ impl NodeRpcClient for MockRpcApi {
             .unwrap()
             .clone();
 
-        Ok(block)
+        let transactions = self
+            .transaction_headers
+            .read()
+            .iter()
+            .filter(|(tx_block_num, _)| *tx_block_num == block_num)
+            .map(|(_, header)| header.clone())
+            .collect();
+
+        Ok(ProvenBlock::new_unchecked(
+            block.header().clone(),
+            block.updated_accounts().to_vec(),
+            block.output_note_batches().to_vec(),
+            block.created_nullifiers().to_vec(),
+            OrderedTransactionHeaders::new_unchecked(transactions),
+        ))
     }
 }
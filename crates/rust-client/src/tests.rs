@@ -1,70 +1,123 @@
 use alloc::vec::Vec;
-use std::{boxed::Box, collections::BTreeSet, env::temp_dir, println, sync::Arc};
+use std::{
+    boxed::Box,
+    cell::RefCell,
+    collections::{BTreeMap, BTreeSet},
+    env::temp_dir,
+    pin::Pin,
+    println,
+    rc::Rc,
+    string::{String, ToString},
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering},
+    },
+    task::{Poll, Waker},
+};
 
+use futures_core::Stream;
 // TESTS
 // ================================================================================================
 use miden_lib::{
     account::{
-        auth::RpoFalcon512, faucets::BasicFungibleFaucet, interface::AccountInterfaceError,
+        auth::RpoFalcon512,
+        faucets::BasicFungibleFaucet,
+        interface::{AccountInterface, AccountInterfaceError},
         wallets::BasicWallet,
     },
-    note::{utils, well_known_note::WellKnownNote},
+    note::{create_p2ide_note, utils, well_known_note::WellKnownNote},
     transaction::TransactionKernel,
+    utils::ScriptBuilder,
 };
 use miden_objects::{
-    EMPTY_WORD, Felt, FieldElement, ONE, Word, ZERO,
+    Digest, EMPTY_WORD, Felt, FieldElement, ONE, Word, ZERO,
     account::{
         Account, AccountBuilder, AccountCode, AccountHeader, AccountId, AccountStorageMode,
         AccountType, AuthSecretKey,
     },
-    asset::{Asset, FungibleAsset, TokenSymbol},
+    asset::{
+        Asset, AssetVault, FungibleAsset, NonFungibleAsset, NonFungibleAssetDetails, TokenSymbol,
+    },
+    block::BlockNumber,
     crypto::{
         dsa::rpo_falcon512::{PublicKey, SecretKey},
         rand::{FeltRng, RpoRandomCoin},
     },
     note::{
-        Note, NoteAssets, NoteExecutionHint, NoteFile, NoteInputs, NoteMetadata, NoteRecipient,
-        NoteTag, NoteType,
+        Note, NoteAssets, NoteDetails, NoteExecutionHint, NoteFile, NoteId, NoteInclusionProof,
+        NoteInputs, NoteMetadata, NoteRecipient, NoteTag, NoteType,
     },
-    testing::account_id::{
-        ACCOUNT_ID_PRIVATE_SENDER, ACCOUNT_ID_PUBLIC_FUNGIBLE_FAUCET_1,
-        ACCOUNT_ID_PUBLIC_FUNGIBLE_FAUCET_2, ACCOUNT_ID_REGULAR_PRIVATE_ACCOUNT_UPDATABLE_CODE,
-        ACCOUNT_ID_REGULAR_PUBLIC_ACCOUNT_IMMUTABLE_CODE,
-        ACCOUNT_ID_REGULAR_PUBLIC_ACCOUNT_UPDATABLE_CODE,
+    testing::{
+        account_id::{
+            ACCOUNT_ID_PRIVATE_SENDER, ACCOUNT_ID_PUBLIC_FUNGIBLE_FAUCET_1,
+            ACCOUNT_ID_PUBLIC_FUNGIBLE_FAUCET_2, ACCOUNT_ID_PUBLIC_NON_FUNGIBLE_FAUCET,
+            ACCOUNT_ID_REGULAR_PRIVATE_ACCOUNT_UPDATABLE_CODE,
+            ACCOUNT_ID_REGULAR_PUBLIC_ACCOUNT_IMMUTABLE_CODE,
+            ACCOUNT_ID_REGULAR_PUBLIC_ACCOUNT_UPDATABLE_CODE,
+        },
+        note::NoteBuilder,
+    },
+    transaction::{
+        ExecutedTransaction, InputNote, OutputNote, ProvenTransactionBuilder, TransactionId,
+        TransactionScript,
     },
-    transaction::{InputNote, OutputNote},
     vm::AdviceInputs,
 };
+use miden_testing::MockChain;
 use miden_tx::{
     TransactionExecutorError,
     utils::{Deserializable, Serializable},
 };
-use rand::{Rng, RngCore, rngs::StdRng};
+use rand::{Rng, RngCore, SeedableRng, rngs::StdRng};
+use tracing::{
+    Subscriber,
+    field::{Field, Visit},
+    span::{Attributes, Id, Record},
+};
+use tracing_subscriber::{
+    Layer,
+    layer::{Context, SubscriberExt},
+};
 use uuid::Uuid;
 
 use crate::{
     Client, ClientError,
+    account::TokenMetadata,
     builder::ClientBuilder,
-    keystore::FilesystemKeyStore,
-    note::NoteRelevance,
-    rpc::NodeRpcClient,
+    clock::Clock,
+    keystore::{FilesystemKeyStore, KeyStore, KeyStoreError, MemoryKeyStore},
+    note::{
+        ConsumableNoteStream, NoteConsumptionStatus, NoteRelevance, NoteScreener,
+        NoteTransferProof, NoteTransferProofError, create_p2id_note,
+    },
+    rpc::{
+        NodeRpcClient, RpcError, SubmissionError,
+        domain::{account::AccountStorageRequirements, note::FetchedNote},
+    },
     store::{
-        InputNoteRecord, InputNoteState, NoteFilter, TransactionFilter,
-        input_note_states::ConsumedAuthenticatedLocalNoteState, sqlite_store::SqliteStore,
+        InputNoteCounts, InputNoteRecord, InputNoteState, NoteExportType, NoteFilter,
+        NoteRecordError, OutputNoteRecord, PartialBlockchainFilter, STORE_ARCHIVE_VERSION,
+        StoreCounts, StoreError, TransactionFilter,
+        input_note_states::{CommittedNoteState, ConsumedAuthenticatedLocalNoteState},
+        sqlite_store::{SqliteStore, SyncFailpoint},
     },
-    sync::NoteTagSource,
+    sync::{AuditDiscrepancy, NoteTagRecord, NoteTagSource, TagSubscription},
     testing::{
         common::{
             ACCOUNT_ID_REGULAR, MINT_AMOUNT, RECALL_HEIGHT_DELTA, TRANSFER_AMOUNT,
+            WAIT_FOR_BLOCKS_INITIAL_INTERVAL, WAIT_FOR_BLOCKS_MAX_INTERVAL,
             assert_account_has_single_asset, assert_note_cannot_be_consumed_twice, consume_notes,
-            execute_failing_tx, execute_tx, execute_tx_and_sync, mint_and_consume, mint_note,
-            setup_two_wallets_and_faucet, setup_wallet_and_faucet, wait_for_tx,
+            execute_failing_tx, execute_tx, execute_tx_and_sync, fund_account, mint_and_consume,
+            mint_note, setup_two_wallets_and_faucet, setup_wallet_and_faucet,
+            wait_for_blocks_with_sleep, wait_for_tx, wait_for_tx_confirmations_with_sleep,
+            with_serial_number,
         },
         mock::{MockClient, MockRpcApi},
     },
     transaction::{
-        DiscardCause, PaymentNoteDescription, SwapTransactionData, TransactionRequestBuilder,
-        TransactionRequestError, TransactionStatus,
+        DiscardCause, ForeignAccount, PaymentNoteDescription, ResubmissionSummary,
+        SwapTransactionData, TransactionRequestBuilder, TransactionRequestError, TransactionStatus,
+        UnsignedTransaction,
     },
 };
 
@@ -172,9 +225,125 @@ async fn insert_new_fungible_faucet(
     Ok((account, seed))
 }
 
+/// Sends a public note without assets to each of the `targets`, all of them with the provided tag
+/// and each one in a separate transaction. The notes are sent from a wallet of a separate client,
+/// so the clients tracking the targets only find out about them through the sync. Returns the IDs
+/// of the notes, in the order of `targets`.
+async fn send_public_notes_with_tag(
+    rpc_api: &MockRpcApi,
+    targets: &[AccountId],
+    tag: NoteTag,
+) -> Vec<NoteId> {
+    let (builder, _, keystore) = create_test_client_builder().await;
+    let mut sender_client = builder.rpc(Arc::new(rpc_api.clone())).build().await.unwrap();
+    let (sender, _) = insert_new_wallet(&mut sender_client, AccountStorageMode::Private, &keystore)
+        .await
+        .unwrap();
+    sender_client.sync_state().await.unwrap();
+
+    let metadata =
+        NoteMetadata::new(sender.id(), NoteType::Public, tag, NoteExecutionHint::always(), ZERO)
+            .unwrap();
+
+    let mut note_ids = vec![];
+    for target in targets {
+        let serial_num = sender_client.rng().draw_word();
+        let recipient = utils::build_p2id_recipient(*target, serial_num).unwrap();
+        let note = Note::new(NoteAssets::new(vec![]).unwrap(), metadata, recipient);
+        note_ids.push(note.id());
+
+        let tx_request = TransactionRequestBuilder::new()
+            .own_output_notes(vec![OutputNote::Full(note)])
+            .build()
+            .unwrap();
+        execute_tx_and_sync(&mut sender_client, sender.id(), tx_request).await;
+    }
+
+    note_ids
+}
+
+/// Name and fields of a span recorded by [`SpanRecorder`].
+type RecordedSpan = (&'static str, BTreeMap<&'static str, String>);
+
+/// Tracing layer that records the name and fields of every span created while it's active.
+#[derive(Clone, Default)]
+struct SpanRecorder {
+    spans: Arc<Mutex<Vec<RecordedSpan>>>,
+    /// Maps the IDs of the spans to their position in `spans`, as the IDs of closed spans can be
+    /// reused.
+    span_indices: Arc<Mutex<BTreeMap<u64, usize>>>,
+}
+
+impl SpanRecorder {
+    /// Returns the fields of the last recorded span with the provided name.
+    fn span_fields(&self, name: &str) -> Option<BTreeMap<&'static str, String>> {
+        let spans = self.spans.lock().unwrap();
+        spans
+            .iter()
+            .rev()
+            .find(|(span_name, _)| *span_name == name)
+            .map(|(_, f)| f.clone())
+    }
+}
+
+struct FieldRecorder<'a>(&'a mut BTreeMap<&'static str, String>);
+
+impl Visit for FieldRecorder<'_> {
+    fn record_debug(&mut self, field: &Field, value: &dyn core::fmt::Debug) {
+        self.0.insert(field.name(), format!("{value:?}"));
+    }
+}
+
+impl<S: Subscriber> Layer<S> for SpanRecorder {
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, _ctx: Context<'_, S>) {
+        let mut fields = BTreeMap::new();
+        attrs.record(&mut FieldRecorder(&mut fields));
+
+        let mut spans = self.spans.lock().unwrap();
+        self.span_indices.lock().unwrap().insert(id.into_u64(), spans.len());
+        spans.push((attrs.metadata().name(), fields));
+    }
+
+    fn on_record(&self, id: &Id, values: &Record<'_>, _ctx: Context<'_, S>) {
+        let Some(index) = self.span_indices.lock().unwrap().get(&id.into_u64()).copied() else {
+            return;
+        };
+        values.record(&mut FieldRecorder(&mut self.spans.lock().unwrap()[index].1));
+    }
+}
+
 // TESTS
 // ================================================================================================
 
+#[tokio::test]
+async fn refresh_stale_inclusion_proof() {
+    let (mut client, rpc_api, _) = create_test_client().await;
+
+    // The note is imported without a tag, so the sync doesn't find its inclusion proof
+    let note = rpc_api.get_available_notes()[0].note().unwrap().clone();
+    client
+        .import_note(NoteFile::NoteDetails {
+            details: note.clone().into(),
+            after_block_num: 0.into(),
+            tag: None,
+        })
+        .await
+        .unwrap();
+
+    client.sync_state().await.unwrap();
+    assert!(!client.is_inclusion_proof_current(note.id()).await.unwrap());
+
+    assert!(client.refresh_inclusion_proof(note.id()).await.unwrap());
+    assert!(client.is_inclusion_proof_current(note.id()).await.unwrap());
+
+    let note_record = client.get_input_note(note.id()).await.unwrap().unwrap();
+    assert!(note_record.is_committed());
+
+    // Refreshing a current proof doesn't change the note
+    assert!(client.refresh_inclusion_proof(note.id()).await.unwrap());
+    assert_eq!(client.get_input_note(note.id()).await.unwrap().unwrap(), note_record);
+}
+
 #[tokio::test]
 async fn input_notes_round_trip() {
     // generate test client with a random store name
@@ -417,6 +586,304 @@ async fn sync_state() {
     assert_eq!(client.get_sync_height().await.unwrap(), rpc_api.get_chain_tip_block_num());
 }
 
+#[tokio::test]
+async fn sync_state_with_progress() {
+    let (mut client, rpc_api, _) = create_test_client().await;
+
+    // Track the tags of the mock chain notes so that the sync stops at each block with notes
+    for note in rpc_api.get_available_notes() {
+        let note = note.note().unwrap().clone();
+        client
+            .import_note(NoteFile::NoteDetails {
+                details: note.clone().into(),
+                after_block_num: 0.into(),
+                tag: Some(note.metadata().tag()),
+            })
+            .await
+            .unwrap();
+    }
+
+    let progress_reports = Rc::new(RefCell::new(Vec::new()));
+    let sync_summary = client
+        .sync_state_with_progress({
+            let progress_reports = progress_reports.clone();
+            move |progress| progress_reports.borrow_mut().push(progress)
+        })
+        .await
+        .unwrap();
+
+    let progress_reports = progress_reports.borrow();
+    assert!(progress_reports.len() > 1);
+    assert!(
+        progress_reports
+            .windows(2)
+            .all(|reports| reports[0].current_block < reports[1].current_block)
+    );
+
+    let chain_tip = rpc_api.get_chain_tip_block_num();
+    assert!(progress_reports.iter().all(|progress| progress.target_block == chain_tip));
+    assert_eq!(progress_reports.last().unwrap().current_block, chain_tip);
+    assert_eq!(sync_summary.block_num, chain_tip);
+}
+
+#[tokio::test]
+async fn sync_state_in_batches() {
+    let (builder, rpc_api, _) = create_test_client_builder().await;
+    let mut client = builder.build().await.unwrap();
+
+    let (builder, ..) = create_test_client_builder().await;
+    let mut batched_client = builder
+        .rpc(Arc::new(rpc_api.clone()))
+        .with_sync_batch_size(1)
+        .build()
+        .await
+        .unwrap();
+
+    for client in [&mut client, &mut batched_client] {
+        for note in rpc_api.get_available_notes() {
+            let note = note.note().unwrap().clone();
+            client
+                .import_note(NoteFile::NoteDetails {
+                    details: note.clone().into(),
+                    after_block_num: 0.into(),
+                    tag: Some(note.metadata().tag()),
+                })
+                .await
+                .unwrap();
+        }
+    }
+
+    let sync_summary = client.sync_state().await.unwrap();
+    let batched_sync_summary = batched_client.sync_state().await.unwrap();
+
+    let chain_tip = rpc_api.get_chain_tip_block_num();
+    assert_eq!(batched_sync_summary.block_num, chain_tip);
+    assert_eq!(batched_client.get_sync_height().await.unwrap(), chain_tip);
+
+    let sorted = |mut note_ids: Vec<_>| {
+        note_ids.sort();
+        note_ids
+    };
+    assert_eq!(
+        sorted(batched_sync_summary.committed_notes),
+        sorted(sync_summary.committed_notes)
+    );
+    assert_eq!(sorted(batched_sync_summary.consumed_notes), sorted(sync_summary.consumed_notes));
+
+    // Both clients should end up with the same state
+    let note_states = |notes: Vec<InputNoteRecord>| {
+        let mut note_states: Vec<_> =
+            notes.into_iter().map(|note| (note.id(), note.state().clone())).collect();
+        note_states.sort_by_key(|(note_id, _)| *note_id);
+        note_states
+    };
+    assert_eq!(
+        note_states(batched_client.get_input_notes(NoteFilter::All).await.unwrap()),
+        note_states(client.get_input_notes(NoteFilter::All).await.unwrap())
+    );
+    assert_eq!(
+        batched_client.test_store().get_tracked_block_headers().await.unwrap(),
+        client.test_store().get_tracked_block_headers().await.unwrap()
+    );
+
+    // Batch sizes must be at least 1
+    let (builder, ..) = create_test_client_builder().await;
+    assert!(matches!(
+        builder.with_sync_batch_size(0).build().await,
+        Err(ClientError::ClientInitializationError(_))
+    ));
+}
+
+#[tokio::test]
+async fn sync_to_block() {
+    let (mut client, rpc_api, _) = create_test_client().await;
+    let (mut reference_client, ..) = create_test_client().await;
+
+    for client in [&mut client, &mut reference_client] {
+        for note in rpc_api.get_available_notes() {
+            let note = note.note().unwrap().clone();
+            client
+                .import_note(NoteFile::NoteDetails {
+                    details: note.clone().into(),
+                    after_block_num: 0.into(),
+                    tag: Some(note.metadata().tag()),
+                })
+                .await
+                .unwrap();
+        }
+    }
+
+    // The mock chain has notes in blocks 1 and 4, so block 3 is between two sync steps
+    let chain_tip = rpc_api.get_chain_tip_block_num();
+    let target_block = BlockNumber::from(3);
+    let sync_summary = client.sync_to_block(target_block).await.unwrap();
+
+    assert_eq!(sync_summary.block_num, target_block);
+    assert_eq!(client.get_sync_height().await.unwrap(), target_block);
+
+    // Only the notes created up to the target block were committed
+    let expected_notes = client.get_input_notes(NoteFilter::Expected).await.unwrap();
+    for note in rpc_api.get_available_notes() {
+        assert_eq!(
+            expected_notes.iter().any(|record| record.id() == note.id()),
+            note.inclusion_proof().location().block_num() > target_block
+        );
+    }
+
+    let partial_mmr = client.build_current_partial_mmr().await.unwrap();
+    assert_eq!(partial_mmr.forest(), target_block.as_usize() + 1);
+    let (block_1, _) = rpc_api.get_block_header_by_number(Some(1.into()), false).await.unwrap();
+    partial_mmr
+        .peaks()
+        .verify(block_1.commitment(), partial_mmr.open(1).unwrap().unwrap())
+        .unwrap();
+
+    // Targets that aren't after the sync height or are after the chain tip are rejected
+    assert!(matches!(
+        client.sync_to_block(target_block).await,
+        Err(ClientError::InvalidSyncTarget(..))
+    ));
+    assert!(matches!(
+        client.sync_to_block((chain_tip.as_u32() + 1).into()).await,
+        Err(ClientError::InvalidSyncTarget(..))
+    ));
+
+    // Syncing the rest of the chain leaves the client in the same state as a single sync
+    client.sync_state().await.unwrap();
+    reference_client.sync_state().await.unwrap();
+
+    assert_eq!(client.get_sync_height().await.unwrap(), chain_tip);
+    assert_eq!(
+        client.build_current_partial_mmr().await.unwrap().peaks().hash_peaks(),
+        reference_client.build_current_partial_mmr().await.unwrap().peaks().hash_peaks()
+    );
+    let note_states = |notes: Vec<InputNoteRecord>| {
+        let mut note_states: Vec<_> =
+            notes.into_iter().map(|note| (note.id(), note.state().clone())).collect();
+        note_states.sort_by_key(|(note_id, _)| *note_id);
+        note_states
+    };
+    assert_eq!(
+        note_states(client.get_input_notes(NoteFilter::All).await.unwrap()),
+        note_states(reference_client.get_input_notes(NoteFilter::All).await.unwrap())
+    );
+}
+
+#[tokio::test]
+async fn sync_to_block_stops_at_account_update() {
+    let (mut client, rpc_api, keystore) = create_test_client().await;
+    let (faucet, ..) =
+        insert_new_fungible_faucet(&mut client, AccountStorageMode::Private, &keystore)
+            .await
+            .unwrap();
+    client.sync_state().await.unwrap();
+    let sync_height = client.get_sync_height().await.unwrap();
+
+    // The faucet is updated in the next block by a mint whose note isn't tracked by the client
+    let target_account_id =
+        AccountId::try_from(ACCOUNT_ID_REGULAR_PRIVATE_ACCOUNT_UPDATABLE_CODE).unwrap();
+    let tx_request = TransactionRequestBuilder::new()
+        .build_mint_fungible_asset(
+            FungibleAsset::new(faucet.id(), MINT_AMOUNT).unwrap(),
+            target_account_id,
+            NoteType::Private,
+            client.rng(),
+        )
+        .unwrap();
+    execute_tx(&mut client, faucet.id(), tx_request).await;
+    rpc_api.advance_blocks(3);
+
+    // The account update isn't tied to a block, so the sync can't reach the target block and stops
+    // at the sync height
+    let target_block = BlockNumber::from(sync_height.as_u32() + 3);
+    assert!(matches!(
+        client.sync_to_block(target_block).await,
+        Err(ClientError::SyncTargetNotReached(reached, target))
+            if reached == sync_height && target == target_block
+    ));
+    assert_eq!(client.get_sync_height().await.unwrap(), sync_height);
+
+    // A regular sync applies the account update and reaches the chain tip
+    client.sync_state().await.unwrap();
+    assert_eq!(client.get_sync_height().await.unwrap(), rpc_api.get_chain_tip_block_num());
+}
+
+#[tokio::test]
+async fn apply_serialized_sync() {
+    let (mut client, rpc_api, _) = create_test_client().await;
+    let (mut replay_client, ..) = create_test_client().await;
+
+    for client in [&mut client, &mut replay_client] {
+        client.ensure_genesis_in_place().await.unwrap();
+        for note in rpc_api.get_available_notes() {
+            let note = note.note().unwrap().clone();
+            client
+                .import_note(NoteFile::NoteDetails {
+                    details: note.clone().into(),
+                    after_block_num: 0.into(),
+                    tag: Some(note.metadata().tag()),
+                })
+                .await
+                .unwrap();
+        }
+    }
+
+    // Capture the update of a real sync without applying it
+    let bytes = client.capture_sync_update().await.unwrap();
+    assert_eq!(client.get_sync_height().await.unwrap(), BlockNumber::GENESIS);
+
+    // Replaying the update on a fresh store reaches the same state as syncing with the node
+    let replay_summary = replay_client.apply_serialized_sync(&bytes).await.unwrap();
+    let sync_summary = client.sync_state().await.unwrap();
+
+    assert_eq!(replay_summary, sync_summary);
+    assert_eq!(
+        replay_client.get_sync_height().await.unwrap(),
+        rpc_api.get_chain_tip_block_num()
+    );
+    assert_eq!(
+        replay_client.get_sync_height().await.unwrap(),
+        client.get_sync_height().await.unwrap()
+    );
+    assert_eq!(
+        replay_client.build_current_partial_mmr().await.unwrap().peaks().hash_peaks(),
+        client.build_current_partial_mmr().await.unwrap().peaks().hash_peaks()
+    );
+    let note_states = |notes: Vec<InputNoteRecord>| {
+        let mut note_states: Vec<_> =
+            notes.into_iter().map(|note| (note.id(), note.state().clone())).collect();
+        note_states.sort_by_key(|(note_id, _)| *note_id);
+        note_states
+    };
+    assert_eq!(
+        note_states(replay_client.get_input_notes(NoteFilter::All).await.unwrap()),
+        note_states(client.get_input_notes(NoteFilter::All).await.unwrap())
+    );
+
+    // Invalid bytes are rejected
+    assert!(matches!(
+        replay_client.apply_serialized_sync(&bytes[..bytes.len() / 2]).await,
+        Err(ClientError::DataDeserializationError(_))
+    ));
+
+    // The update can't be applied again, as it doesn't start at the new sync height
+    assert!(matches!(
+        replay_client.apply_serialized_sync(&bytes).await,
+        Err(ClientError::SyncUpdateMismatch(_))
+    ));
+
+    // Updates captured from a different chain are rejected
+    let mut other_chain_bytes = bytes.clone();
+    let other_genesis_commitment = Digest::from([Felt::new(1); 4]);
+    other_chain_bytes[..32].copy_from_slice(&other_genesis_commitment.to_bytes());
+    let (mut fresh_client, ..) = create_test_client().await;
+    assert!(matches!(
+        fresh_client.apply_serialized_sync(&other_chain_bytes).await,
+        Err(ClientError::SyncUpdateMismatch(_))
+    ));
+    assert_eq!(fresh_client.get_sync_height().await.unwrap(), BlockNumber::GENESIS);
+}
+
 #[tokio::test]
 async fn sync_state_mmr() {
     // generate test client with a random store name
@@ -491,61 +958,571 @@ async fn sync_state_mmr() {
 }
 
 #[tokio::test]
-async fn sync_state_tags() {
-    // generate test client with a random store name
-    let (mut client, rpc_api, _) = create_test_client().await;
+async fn export_import_mmr_state() {
+    let (mut client, rpc_api, keystore) = create_test_client().await;
+    insert_new_wallet(&mut client, AccountStorageMode::Private, &keystore)
+        .await
+        .unwrap();
 
-    // Import first mockchain note as expected
-    let expected_notes = rpc_api.get_available_notes();
-    for tag in expected_notes.iter().map(|n| n.metadata().tag()) {
-        client.add_note_tag(tag).await.unwrap();
+    // Import notes so that the blocks that contain them are tracked by the client
+    for note in rpc_api.get_available_notes() {
+        let note = note.note().unwrap().clone();
+        client
+            .import_note(NoteFile::NoteDetails {
+                details: note.clone().into(),
+                after_block_num: 0.into(),
+                tag: Some(note.metadata().tag()),
+            })
+            .await
+            .unwrap();
     }
+    let sync_height = client.sync_state().await.unwrap().block_num;
 
-    // assert that we have no expected notes prior to syncing state
-    assert!(client.get_input_notes(NoteFilter::Expected).await.unwrap().is_empty());
+    let mmr_state = client.export_mmr_state().await.unwrap();
+    let (trusted_block_header, _) =
+        rpc_api.get_block_header_by_number(Some(sync_height), false).await.unwrap();
 
-    // sync state
-    // The mockchain API has one public note and one private note, so in the end we will have
-    // the public one in the client
-    let sync_details = client.sync_state().await.unwrap();
+    // Importing the state with a block header that doesn't match the exported tip fails
+    let (mut new_client, ..) = create_test_client().await;
+    let (wrong_block_header, _) =
+        rpc_api.get_block_header_by_number(Some(1.into()), false).await.unwrap();
+    assert!(matches!(
+        new_client.import_mmr_state(&mmr_state, &wrong_block_header).await,
+        Err(ClientError::MmrStateImportError(_))
+    ));
 
-    // verify that the client is synced to the latest block
+    new_client.import_mmr_state(&mmr_state, &trusted_block_header).await.unwrap();
+    assert_eq!(new_client.get_sync_height().await.unwrap(), sync_height);
+
+    // The imported partial MMR matches the exported one
+    let partial_mmr = client.build_current_partial_mmr().await.unwrap();
+    let imported_partial_mmr = new_client.build_current_partial_mmr().await.unwrap();
+    assert_eq!(imported_partial_mmr.forest(), partial_mmr.forest());
+    assert_eq!(imported_partial_mmr.peaks().hash_peaks(), partial_mmr.peaks().hash_peaks());
+
+    for block_num in [1, 4] {
+        let mmr_proof = imported_partial_mmr.open(block_num).unwrap().unwrap();
+        let (block_header, _) = rpc_api
+            .get_block_header_by_number(Some(u32::try_from(block_num).unwrap().into()), false)
+            .await
+            .unwrap();
+        imported_partial_mmr
+            .peaks()
+            .verify(block_header.commitment(), mmr_proof)
+            .unwrap();
+    }
     assert_eq!(
-        sync_details.block_num,
-        rpc_api.get_block_header_by_number(None, false).await.unwrap().0.block_num()
+        new_client.test_store().get_tracked_block_headers().await.unwrap(),
+        client.test_store().get_tracked_block_headers().await.unwrap()
     );
 
-    assert_eq!(client.get_input_notes(NoteFilter::All).await.unwrap().len(), 1);
-    // as we are syncing with tags, the response should contain blocks for both notes
-    assert_eq!(client.test_store().get_tracked_block_headers().await.unwrap().len(), 1);
+    // The state can't be imported twice
+    assert!(matches!(
+        new_client.import_mmr_state(&mmr_state, &trusted_block_header).await,
+        Err(ClientError::MmrStateImportError(_))
+    ));
 }
 
 #[tokio::test]
-async fn tags() {
-    // generate test client with a random store name
-    let (mut client, _rpc_api, _) = create_test_client().await;
-
-    // Assert that the store gets created with the tag 0 (used for notes consumable by any account)
-    assert!(client.get_note_tags().await.unwrap().is_empty());
-
-    // add a tag
-    let tag_1: NoteTag = 1.into();
-    let tag_2: NoteTag = 2.into();
-    client.add_note_tag(tag_1).await.unwrap();
-    client.add_note_tag(tag_2).await.unwrap();
+async fn store_counts() {
+    let (mut client, rpc_api, keystore) = create_test_client().await;
+    assert_eq!(client.test_store().counts().await.unwrap(), StoreCounts::default());
 
-    // verify that the tag is being tracked
-    assert_eq!(client.get_note_tags().await.unwrap(), vec![tag_1, tag_2]);
+    let (wallet, faucet) =
+        setup_wallet_and_faucet(&mut client, AccountStorageMode::Private, &keystore).await;
 
-    // attempt to add the same tag again
-    client.add_note_tag(tag_1).await.unwrap();
+    let note = mint_note(&mut client, wallet.id(), faucet.id(), NoteType::Private).await;
+    consume_notes(&mut client, wallet.id(), &[note]).await;
 
-    // verify that the tag is still being tracked only once
-    assert_eq!(client.get_note_tags().await.unwrap(), vec![tag_1, tag_2]);
+    // Only public notes have their details available to be imported. They are already committed on
+    // the node, so the client imports them as committed notes
+    let public_notes: Vec<Note> = rpc_api
+        .get_available_notes()
+        .iter()
+        .filter_map(|note| note.note().cloned())
+        .collect();
+    for note in &public_notes {
+        client
+            .import_note(NoteFile::NoteDetails {
+                details: note.clone().into(),
+                after_block_num: 0.into(),
+                tag: Some(note.metadata().tag()),
+            })
+            .await
+            .unwrap();
+    }
 
-    // Try removing non-existent tag
-    let tag_4: NoteTag = 4.into();
-    client.remove_note_tag(tag_4).await.unwrap();
+    let counts = client.test_store().counts().await.unwrap();
+    assert_eq!(
+        counts,
+        StoreCounts {
+            accounts: 2,
+            input_notes: InputNoteCounts {
+                committed: public_notes.len(),
+                consumed: 1,
+                ..Default::default()
+            },
+            output_notes: 1,
+            transactions: 2,
+        }
+    );
+
+    // The counts match the number of records returned by the store
+    assert_eq!(counts.accounts, client.get_account_headers().await.unwrap().len());
+    assert_eq!(
+        counts.input_notes.total(),
+        client.get_input_notes(NoteFilter::All).await.unwrap().len()
+    );
+    assert_eq!(
+        counts.output_notes,
+        client.get_output_notes(NoteFilter::All).await.unwrap().len()
+    );
+    assert_eq!(
+        counts.transactions,
+        client.get_transactions(TransactionFilter::All).await.unwrap().len()
+    );
+}
+
+#[tokio::test]
+async fn export_import_store_archive() {
+    fn sorted_bytes<T: Serializable>(items: &[T]) -> Vec<Vec<u8>> {
+        let mut bytes: Vec<Vec<u8>> = items.iter().map(Serializable::to_bytes).collect();
+        bytes.sort();
+        bytes
+    }
+
+    let (mut client, _rpc_api, keystore) = create_test_client().await;
+
+    let (wallet, faucet) =
+        setup_wallet_and_faucet(&mut client, AccountStorageMode::Private, &keystore).await;
+
+    let note = mint_note(&mut client, wallet.id(), faucet.id(), NoteType::Private).await;
+    consume_notes(&mut client, wallet.id(), &[note]).await;
+
+    // Leave a transaction pending
+    let tx_request = TransactionRequestBuilder::new()
+        .build_mint_fungible_asset(
+            FungibleAsset::new(faucet.id(), MINT_AMOUNT).unwrap(),
+            wallet.id(),
+            NoteType::Private,
+            client.rng(),
+        )
+        .unwrap();
+    execute_tx(&mut client, faucet.id(), tx_request).await;
+
+    let user_tag: NoteTag = 1.into();
+    client.add_note_tag(user_tag).await.unwrap();
+
+    let archive = client.export_store_archive().await.unwrap();
+
+    // Archives with a different version can't be imported
+    let (mut new_client, ..) = create_test_client().await;
+    let mut wrong_version_archive = archive.clone();
+    wrong_version_archive[4..6].copy_from_slice(&(STORE_ARCHIVE_VERSION + 1).to_le_bytes());
+    assert!(matches!(
+        new_client.import_store_archive(&wrong_version_archive).await,
+        Err(ClientError::StoreArchiveImportError(_))
+    ));
+
+    // Trailing bytes after the archive contents are rejected
+    let mut trailing_bytes_archive = archive.clone();
+    trailing_bytes_archive.push(0);
+    assert!(matches!(
+        new_client.import_store_archive(&trailing_bytes_archive).await,
+        Err(ClientError::DataDeserializationError(_))
+    ));
+
+    // A store that only tracks a note tag isn't empty, and is left unchanged
+    new_client.add_note_tag(user_tag).await.unwrap();
+    assert!(matches!(
+        new_client.import_store_archive(&archive).await,
+        Err(ClientError::StoreArchiveImportError(_))
+    ));
+    assert!(new_client.test_store().get_account_ids().await.unwrap().is_empty());
+    new_client.remove_note_tag(user_tag).await.unwrap();
+
+    // Failed imports don't leave partial data behind, so the import can be retried
+    new_client.import_store_archive(&archive).await.unwrap();
+
+    assert_eq!(
+        new_client.get_sync_height().await.unwrap(),
+        client.get_sync_height().await.unwrap()
+    );
+
+    let account_ids = client.test_store().get_account_ids().await.unwrap();
+    assert_eq!(account_ids.len(), 2);
+    for account_id in account_ids {
+        let account = client.get_account(account_id).await.unwrap().unwrap();
+        let imported_account = new_client.get_account(account_id).await.unwrap().unwrap();
+        assert_eq!(imported_account.account(), account.account());
+        assert_eq!(imported_account.seed(), account.seed());
+    }
+
+    assert_eq!(
+        sorted_bytes(&new_client.get_input_notes(NoteFilter::All).await.unwrap()),
+        sorted_bytes(&client.get_input_notes(NoteFilter::All).await.unwrap())
+    );
+    assert_eq!(
+        sorted_bytes(&new_client.get_output_notes(NoteFilter::All).await.unwrap()),
+        sorted_bytes(&client.get_output_notes(NoteFilter::All).await.unwrap())
+    );
+
+    let transactions = client.get_transactions(TransactionFilter::All).await.unwrap();
+    assert!(transactions.iter().any(|tx| tx.status == TransactionStatus::Pending));
+    assert_eq!(
+        sorted_bytes(&new_client.get_transactions(TransactionFilter::All).await.unwrap()),
+        sorted_bytes(&transactions)
+    );
+
+    assert_eq!(
+        sorted_bytes(&new_client.get_note_tags().await.unwrap()),
+        sorted_bytes(&client.get_note_tags().await.unwrap())
+    );
+    assert_eq!(
+        new_client.test_store().get_tracked_block_headers().await.unwrap(),
+        client.test_store().get_tracked_block_headers().await.unwrap()
+    );
+    assert_eq!(
+        new_client
+            .test_store()
+            .get_partial_blockchain_nodes(PartialBlockchainFilter::All)
+            .await
+            .unwrap(),
+        client
+            .test_store()
+            .get_partial_blockchain_nodes(PartialBlockchainFilter::All)
+            .await
+            .unwrap()
+    );
+
+    // The archive can only be imported into an empty store
+    assert!(matches!(
+        new_client.import_store_archive(&archive).await,
+        Err(ClientError::StoreArchiveImportError(_))
+    ));
+}
+
+#[tokio::test]
+async fn sync_state_tags() {
+    // generate test client with a random store name
+    let (mut client, rpc_api, _) = create_test_client().await;
+
+    // Import first mockchain note as expected
+    let expected_notes = rpc_api.get_available_notes();
+    for tag in expected_notes.iter().map(|n| n.metadata().tag()) {
+        client.add_note_tag(tag).await.unwrap();
+    }
+
+    // assert that we have no expected notes prior to syncing state
+    assert!(client.get_input_notes(NoteFilter::Expected).await.unwrap().is_empty());
+
+    // sync state
+    // The mockchain API has one public note and one private note, so in the end we will have
+    // the public one in the client
+    let sync_details = client.sync_state().await.unwrap();
+
+    // verify that the client is synced to the latest block
+    assert_eq!(
+        sync_details.block_num,
+        rpc_api.get_block_header_by_number(None, false).await.unwrap().0.block_num()
+    );
+
+    assert_eq!(client.get_input_notes(NoteFilter::All).await.unwrap().len(), 1);
+    // as we are syncing with tags, the response should contain blocks for both notes
+    assert_eq!(client.test_store().get_tracked_block_headers().await.unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn sync_state_strict_tag_matching() {
+    let (builder, rpc_api, keystore) = create_test_client_builder().await;
+    let mut client = builder.with_strict_note_tag_matching(true).build().await.unwrap();
+    let (wallet, _) = insert_new_wallet(&mut client, AccountStorageMode::Private, &keystore)
+        .await
+        .unwrap();
+
+    // Both notes match a tracked tag, but only the second one is addressed to a tracked account
+    let untracked_account_id = AccountId::try_from(ACCOUNT_ID_REGULAR).unwrap();
+    let tag = NoteTag::from_account_id(untracked_account_id);
+    client.add_note_tag(tag).await.unwrap();
+
+    let note_ids =
+        send_public_notes_with_tag(&rpc_api, &[untracked_account_id, wallet.id()], tag).await;
+
+    client.sync_state().await.unwrap();
+
+    let input_notes = client.get_input_notes(NoteFilter::All).await.unwrap();
+    assert_eq!(
+        input_notes.iter().map(InputNoteRecord::id).collect::<Vec<_>>(),
+        vec![note_ids[1]]
+    );
+
+    // Only the block of the relevant note is marked as having client notes
+    let relevant_block = input_notes[0].inclusion_proof().unwrap().location().block_num();
+    let tracked_blocks: Vec<BlockNumber> = client
+        .test_store()
+        .get_tracked_block_headers()
+        .await
+        .unwrap()
+        .iter()
+        .map(|header| header.block_num())
+        .collect();
+    assert_eq!(tracked_blocks, vec![relevant_block]);
+}
+
+#[tokio::test]
+async fn sync_state_light_mode() {
+    let (builder, rpc_api, _) = create_test_client_builder().await;
+    let mut light_client = builder.light_mode(true).build().await.unwrap();
+    let (mut client, ..) = create_test_client().await;
+
+    let expected_notes = rpc_api.get_available_notes();
+    for tag in expected_notes.iter().map(|n| n.metadata().tag()) {
+        light_client.add_note_tag(tag).await.unwrap();
+        client.add_note_tag(tag).await.unwrap();
+    }
+
+    let light_summary = light_client.sync_state().await.unwrap();
+    let summary = client.sync_state().await.unwrap();
+    assert_eq!(light_summary, summary);
+
+    // Notes and the blocks that contain them are still tracked
+    assert_eq!(light_client.get_input_notes(NoteFilter::All).await.unwrap().len(), 1);
+    assert_eq!(
+        light_client.test_store().get_tracked_block_headers().await.unwrap(),
+        client.test_store().get_tracked_block_headers().await.unwrap()
+    );
+
+    // But no MMR nodes are stored, so the notes can't be authenticated
+    assert!(
+        !client
+            .test_store()
+            .get_partial_blockchain_nodes(PartialBlockchainFilter::All)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+    assert!(
+        light_client
+            .test_store()
+            .get_partial_blockchain_nodes(PartialBlockchainFilter::All)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+
+    // The partial MMR is built from the peaks alone, which still match the chain
+    assert_eq!(
+        light_client.build_current_partial_mmr().await.unwrap().peaks().hash_peaks(),
+        client.build_current_partial_mmr().await.unwrap().peaks().hash_peaks()
+    );
+}
+
+#[tokio::test]
+async fn backfill_mmr() {
+    let (builder, rpc_api, keystore) = create_test_client_builder().await;
+    let mut client = builder.light_mode(true).build().await.unwrap();
+    client.sync_state().await.unwrap();
+
+    let (wallet, faucet) =
+        setup_wallet_and_faucet(&mut client, AccountStorageMode::Private, &keystore).await;
+    let note = mint_note(&mut client, wallet.id(), faucet.id(), NoteType::Private).await;
+
+    // Move past the note's block so that it needs to be authenticated
+    rpc_api.advance_blocks(2);
+    client.sync_state().await.unwrap();
+    assert!(
+        client
+            .test_store()
+            .get_partial_blockchain_nodes(PartialBlockchainFilter::All)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+
+    let tx_request = TransactionRequestBuilder::new().build_consume_notes(vec![note.id()]).unwrap();
+    assert!(client.new_transaction(wallet.id(), tx_request).await.is_err());
+
+    client.backfill_mmr().await.unwrap();
+    assert!(
+        !client
+            .test_store()
+            .get_partial_blockchain_nodes(PartialBlockchainFilter::All)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+
+    let input_note = client.get_input_note(note.id()).await.unwrap().unwrap();
+    assert!(input_note.is_authenticated());
+    consume_notes(&mut client, wallet.id(), &[note]).await;
+    assert_account_has_single_asset(&client, wallet.id(), faucet.id(), MINT_AMOUNT).await;
+}
+
+#[tokio::test]
+async fn sync_state_tag_subscriptions() {
+    let (mut exact_client, rpc_api, keystore) = create_test_client().await;
+    let (builder, ..) = create_test_client_builder().await;
+    let mut prefix_client = builder.rpc(Arc::new(rpc_api.clone())).build().await.unwrap();
+    let (wallet, _) = insert_new_wallet(&mut exact_client, AccountStorageMode::Private, &keystore)
+        .await
+        .unwrap();
+
+    // Both notes have the subscribed tag, but only the second one is addressed to an account
+    // tracked by the exact client
+    let untracked_account_id = AccountId::try_from(ACCOUNT_ID_REGULAR).unwrap();
+    let tag = NoteTag::from_account_id(untracked_account_id);
+
+    exact_client
+        .add_note_tag_with_subscription(tag, TagSubscription::Exact)
+        .await
+        .unwrap();
+    prefix_client.add_note_tag(tag).await.unwrap();
+
+    assert!(exact_client.get_note_tags().await.unwrap().contains(&NoteTagRecord {
+        tag,
+        source: NoteTagSource::User,
+        subscription: TagSubscription::Exact,
+    }));
+    assert_eq!(
+        prefix_client.get_note_tags().await.unwrap(),
+        vec![NoteTagRecord {
+            tag,
+            source: NoteTagSource::User,
+            subscription: TagSubscription::Prefix,
+        }]
+    );
+
+    let note_ids =
+        send_public_notes_with_tag(&rpc_api, &[untracked_account_id, wallet.id()], tag).await;
+
+    exact_client.sync_state().await.unwrap();
+    prefix_client.sync_state().await.unwrap();
+
+    // An exact subscription only keeps relevant notes, while a prefix one keeps every note
+    assert!(exact_client.get_input_note(note_ids[0]).await.unwrap().is_none());
+    assert!(exact_client.get_input_note(note_ids[1]).await.unwrap().is_some());
+    assert!(prefix_client.get_input_note(note_ids[0]).await.unwrap().is_some());
+    assert!(prefix_client.get_input_note(note_ids[1]).await.unwrap().is_some());
+}
+
+#[tokio::test]
+async fn sync_state_rolls_back_chain_reorg() {
+    let (mut client, rpc_api, _) = create_test_client().await;
+
+    let note_tags: Vec<NoteTag> =
+        rpc_api.get_available_notes().iter().map(|note| note.metadata().tag()).collect();
+    for tag in &note_tags {
+        client.add_note_tag(*tag).await.unwrap();
+    }
+    client.sync_state().await.unwrap();
+    assert_eq!(client.get_input_notes(NoteFilter::Committed).await.unwrap().len(), 1);
+
+    // Replace the chain with a longer one that only shares the genesis block
+    let mut canonical_chain = MockChain::new();
+    canonical_chain
+        .prove_until_block(rpc_api.get_chain_tip_block_num() + 3)
+        .unwrap();
+    *rpc_api.mock_chain.write() = canonical_chain;
+
+    assert!(matches!(
+        client.sync_state().await.unwrap_err(),
+        ClientError::ChainReorg(block_num) if block_num == BlockNumber::GENESIS
+    ));
+    assert_eq!(client.get_sync_height().await.unwrap(), BlockNumber::GENESIS);
+    client.sync_state().await.unwrap();
+
+    let (builder, ..) = create_test_client_builder().await;
+    let mut fresh_client = builder.rpc(Arc::new(rpc_api.clone())).build().await.unwrap();
+    for tag in &note_tags {
+        fresh_client.add_note_tag(*tag).await.unwrap();
+    }
+    fresh_client.sync_state().await.unwrap();
+
+    let sync_height = fresh_client.get_sync_height().await.unwrap();
+    assert_eq!(client.get_sync_height().await.unwrap(), sync_height);
+    assert_eq!(
+        client.test_store().get_tracked_block_headers().await.unwrap(),
+        fresh_client.test_store().get_tracked_block_headers().await.unwrap()
+    );
+    assert_eq!(
+        client
+            .test_store()
+            .get_partial_blockchain_peaks_by_block_num(sync_height)
+            .await
+            .unwrap(),
+        fresh_client
+            .test_store()
+            .get_partial_blockchain_peaks_by_block_num(sync_height)
+            .await
+            .unwrap()
+    );
+    assert_eq!(
+        client.build_current_partial_mmr().await.unwrap().peaks(),
+        fresh_client.build_current_partial_mmr().await.unwrap().peaks()
+    );
+
+    // The note that was committed in the orphaned chain is expected again
+    assert!(client.get_input_notes(NoteFilter::Committed).await.unwrap().is_empty());
+    assert_eq!(client.get_input_notes(NoteFilter::Expected).await.unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn chain_reorg_fails_if_accounts_changed_after_common_block() {
+    let (mut client, rpc_api, keystore) = create_test_client().await;
+    let (wallet, faucet) =
+        setup_wallet_and_faucet(&mut client, AccountStorageMode::Private, &keystore).await;
+    mint_note(&mut client, wallet.id(), faucet.id(), NoteType::Private).await;
+
+    let sync_height = client.get_sync_height().await.unwrap();
+    let faucet_commitment =
+        client.get_account(faucet.id()).await.unwrap().unwrap().account().commitment();
+
+    // Replace the chain with a longer one that doesn't include the mint transaction
+    let mut canonical_chain = MockChain::new();
+    canonical_chain
+        .prove_until_block(rpc_api.get_chain_tip_block_num() + 3)
+        .unwrap();
+    *rpc_api.mock_chain.write() = canonical_chain;
+
+    // The faucet's state can't be rolled back, so the store is left as it was
+    assert!(matches!(
+        client.sync_state().await.unwrap_err(),
+        ClientError::StoreError(StoreError::AccountChangedAfterBlock(account_id, _))
+            if account_id == faucet.id()
+    ));
+    assert_eq!(client.get_sync_height().await.unwrap(), sync_height);
+    assert_eq!(
+        client.get_account(faucet.id()).await.unwrap().unwrap().account().commitment(),
+        faucet_commitment
+    );
+}
+
+#[tokio::test]
+async fn tags() {
+    // generate test client with a random store name
+    let (mut client, _rpc_api, _) = create_test_client().await;
+
+    // Assert that the store gets created with the tag 0 (used for notes consumable by any account)
+    assert!(client.get_note_tags().await.unwrap().is_empty());
+
+    // add a tag
+    let tag_1: NoteTag = 1.into();
+    let tag_2: NoteTag = 2.into();
+    client.add_note_tag(tag_1).await.unwrap();
+    client.add_note_tag(tag_2).await.unwrap();
+
+    // verify that the tag is being tracked
+    assert_eq!(client.get_note_tags().await.unwrap(), vec![tag_1, tag_2]);
+
+    // attempt to add the same tag again
+    client.add_note_tag(tag_1).await.unwrap();
+
+    // verify that the tag is still being tracked only once
+    assert_eq!(client.get_note_tags().await.unwrap(), vec![tag_1, tag_2]);
+
+    // Try removing non-existent tag
+    let tag_4: NoteTag = 4.into();
+    client.remove_note_tag(tag_4).await.unwrap();
 
     // verify that the tracked tags are unchanged
     assert_eq!(client.get_note_tags().await.unwrap(), vec![tag_1, tag_2]);
@@ -558,101 +1535,1636 @@ async fn tags() {
 }
 
 #[tokio::test]
-async fn mint_transaction() {
-    // generate test client with a random store name
-    let (mut client, _rpc_api, keystore) = create_test_client().await;
+async fn note_tag_summary() {
+    let (mut client, ..) = create_test_client().await;
+
+    let tag_1: NoteTag = 1.into();
+    let tag_2: NoteTag = 2.into();
+    let unused_tag: NoteTag = 3.into();
+    client.add_note_tag(tag_1).await.unwrap();
+    client.add_note_tag(tag_2).await.unwrap();
+    client.add_note_tag(unused_tag).await.unwrap();
+
+    // Import expected notes with two of the tags
+    let sender = AccountId::try_from(ACCOUNT_ID_PRIVATE_SENDER).unwrap();
+    for (seed, tag) in [(0, tag_1), (1, tag_1), (2, tag_2)] {
+        let note =
+            NoteBuilder::new(sender, RpoRandomCoin::new([Felt::new(seed), ZERO, ZERO, ZERO]))
+                .build(&TransactionKernel::assembler())
+                .unwrap();
+        client
+            .import_note(NoteFile::NoteDetails {
+                details: note.into(),
+                after_block_num: 1.into(),
+                tag: Some(tag),
+            })
+            .await
+            .unwrap();
+    }
+
+    // Tags added both by the user and by the imported notes are only reported once
+    assert_eq!(
+        client.get_note_tag_summary().await.unwrap(),
+        vec![(tag_1, 2), (tag_2, 1), (unused_tag, 0)]
+    );
+}
+
+#[tokio::test]
+async fn sync_state_removes_orphaned_account_tags() {
+    // generate test client with a random store name
+    let (mut client, _rpc_api, keystore) = create_test_client().await;
+
+    let (account, _) = insert_new_wallet(&mut client, AccountStorageMode::Private, &keystore)
+        .await
+        .unwrap();
+    let account_tag = NoteTagRecord::from(&account);
+
+    // Simulate a tag that was left behind by an account that is no longer tracked
+    let removed_account_id =
+        AccountId::try_from(ACCOUNT_ID_REGULAR_PUBLIC_ACCOUNT_IMMUTABLE_CODE).unwrap();
+    let orphaned_tag = NoteTagRecord::with_account_source(
+        NoteTag::from_account_id(removed_account_id),
+        removed_account_id,
+    );
+    client.test_store().add_note_tag(orphaned_tag).await.unwrap();
+    assert_eq!(client.get_note_tags().await.unwrap(), vec![account_tag, orphaned_tag]);
+
+    client.sync_state().await.unwrap();
+
+    // Only the tag for the tracked account should remain
+    assert_eq!(client.get_note_tags().await.unwrap(), vec![account_tag]);
+    assert!(
+        !client
+            .test_store()
+            .get_unique_note_tags()
+            .await
+            .unwrap()
+            .contains(&orphaned_tag.tag)
+    );
+}
+
+#[tokio::test]
+async fn mint_transaction() {
+    // generate test client with a random store name
+    let (mut client, _rpc_api, keystore) = create_test_client().await;
+
+    // Faucet account generation
+    let (faucet, _seed) =
+        insert_new_fungible_faucet(&mut client, AccountStorageMode::Private, &keystore)
+            .await
+            .unwrap();
+
+    client.sync_state().await.unwrap();
+
+    // Test submitting a mint transaction
+    let transaction_request = TransactionRequestBuilder::new()
+        .build_mint_fungible_asset(
+            FungibleAsset::new(faucet.id(), 5u64).unwrap(),
+            AccountId::try_from(ACCOUNT_ID_PUBLIC_FUNGIBLE_FAUCET_1).unwrap(),
+            miden_objects::note::NoteType::Private,
+            client.rng(),
+        )
+        .unwrap();
+
+    let transaction = client.new_transaction(faucet.id(), transaction_request).await.unwrap();
+
+    assert_eq!(transaction.executed_transaction().account_delta().nonce_delta(), ONE);
+}
+
+#[tokio::test]
+async fn wait_for_transaction_confirmations() {
+    let (mut client, rpc_api, keystore) = create_test_client().await;
+
+    let (faucet, _seed) =
+        insert_new_fungible_faucet(&mut client, AccountStorageMode::Private, &keystore)
+            .await
+            .unwrap();
+
+    client.sync_state().await.unwrap();
+
+    let transaction_request = TransactionRequestBuilder::new()
+        .build_mint_fungible_asset(
+            FungibleAsset::new(faucet.id(), 5u64).unwrap(),
+            AccountId::try_from(ACCOUNT_ID_REGULAR_PUBLIC_ACCOUNT_IMMUTABLE_CODE).unwrap(),
+            miden_objects::note::NoteType::Private,
+            client.rng(),
+        )
+        .unwrap();
+
+    // The mock node commits the transaction in a new block on submission
+    let transaction_id = execute_tx(&mut client, faucet.id(), transaction_request).await;
+    let commit_block = rpc_api.get_chain_tip_block_num();
+
+    // A block is produced each time the helper waits, so it only returns once the transaction is
+    // buried under the two blocks needed for the confirmations
+    let mut produced_blocks = 0;
+    wait_for_tx_confirmations_with_sleep(&mut client, transaction_id, 2, |_| {
+        rpc_api.advance_blocks(1);
+        produced_blocks += 1;
+    })
+    .await;
+
+    assert_eq!(produced_blocks, 2);
+    assert_eq!(client.get_sync_height().await.unwrap(), commit_block + 2);
+}
+
+#[tokio::test]
+async fn verify_output_note_committed() {
+    let (mut client, rpc_api, keystore) = create_test_client().await;
+
+    let (faucet, _seed) =
+        insert_new_fungible_faucet(&mut client, AccountStorageMode::Private, &keystore)
+            .await
+            .unwrap();
+
+    client.sync_state().await.unwrap();
+
+    let transaction_request = TransactionRequestBuilder::new()
+        .build_mint_fungible_asset(
+            FungibleAsset::new(faucet.id(), 5u64).unwrap(),
+            AccountId::try_from(ACCOUNT_ID_REGULAR_PUBLIC_ACCOUNT_IMMUTABLE_CODE).unwrap(),
+            NoteType::Private,
+            client.rng(),
+        )
+        .unwrap();
+    let note_id = transaction_request.expected_output_own_notes().pop().unwrap().id();
+
+    execute_tx(&mut client, faucet.id(), transaction_request).await;
+
+    let mut inclusion_proof = None;
+    for _ in 0..5 {
+        inclusion_proof = client.verify_output_note_committed(note_id).await.unwrap();
+        if inclusion_proof.is_some() {
+            break;
+        }
+        std::thread::sleep(std::time::Duration::from_millis(500));
+    }
+    let inclusion_proof = inclusion_proof.expect("output note should be committed");
+
+    // The inclusion proof should be stored without syncing the client
+    let output_note = client.get_output_note(note_id).await.unwrap().unwrap();
+    assert!(output_note.is_committed());
+    assert_eq!(output_note.inclusion_proof(), Some(&inclusion_proof));
+
+    // Notes that aren't tracked as output notes can't be verified
+    let untracked_note_id = rpc_api.get_available_notes()[0].note().unwrap().id();
+    assert!(matches!(
+        client.verify_output_note_committed(untracked_note_id).await,
+        Err(ClientError::NoteNotTracked(note_id)) if note_id == untracked_note_id
+    ));
+}
+
+#[tokio::test]
+async fn resubmitting_transaction_is_noop() {
+    let (mut client, rpc_api, keystore) = create_test_client().await;
+
+    let (faucet, _seed) =
+        insert_new_fungible_faucet(&mut client, AccountStorageMode::Private, &keystore)
+            .await
+            .unwrap();
+
+    client.sync_state().await.unwrap();
+
+    let transaction_request = TransactionRequestBuilder::new()
+        .build_mint_fungible_asset(
+            FungibleAsset::new(faucet.id(), 5u64).unwrap(),
+            AccountId::try_from(ACCOUNT_ID_REGULAR_PUBLIC_ACCOUNT_IMMUTABLE_CODE).unwrap(),
+            NoteType::Private,
+            client.rng(),
+        )
+        .unwrap();
+
+    let tx_result = client.new_transaction(faucet.id(), transaction_request).await.unwrap();
+    let transaction_id = tx_result.executed_transaction().id();
+    assert!(!client.is_transaction_submitted(transaction_id).await.unwrap());
+
+    client.submit_transaction(tx_result.clone()).await.unwrap();
+    assert!(client.is_transaction_submitted(transaction_id).await.unwrap());
+
+    let chain_tip = rpc_api.get_chain_tip_block_num();
+    let (faucet_header, _) = client.get_account_header_by_id(faucet.id()).await.unwrap().unwrap();
+
+    // The second submission shouldn't reach the node nor change the local state
+    client.submit_transaction(tx_result).await.unwrap();
+
+    assert_eq!(rpc_api.get_chain_tip_block_num(), chain_tip);
+    assert_eq!(client.get_transactions(TransactionFilter::All).await.unwrap().len(), 1);
+    assert_eq!(
+        client.get_account_header_by_id(faucet.id()).await.unwrap().unwrap().0,
+        faucet_header
+    );
+}
+
+#[tokio::test]
+async fn verify_before_submit_rejects_invalid_proof() {
+    let (builder, rpc_api, keystore) = create_test_client_builder().await;
+    let mut client = builder.with_verify_before_submit(true).build().await.unwrap();
+    client.ensure_genesis_in_place().await.unwrap();
+
+    let (faucet, _seed) =
+        insert_new_fungible_faucet(&mut client, AccountStorageMode::Private, &keystore)
+            .await
+            .unwrap();
+
+    client.sync_state().await.unwrap();
+
+    let transaction_request = TransactionRequestBuilder::new()
+        .expiration_delta(10)
+        .build_mint_fungible_asset(
+            FungibleAsset::new(faucet.id(), 5u64).unwrap(),
+            AccountId::try_from(ACCOUNT_ID_REGULAR_PUBLIC_ACCOUNT_IMMUTABLE_CODE).unwrap(),
+            NoteType::Private,
+            client.rng(),
+        )
+        .unwrap();
+
+    let tx_result = client.new_transaction(faucet.id(), transaction_request).await.unwrap();
+    let proven_transaction = client.testing_prove_transaction(&tx_result).await.unwrap();
+
+    // The expiration block number is one of the proof's public inputs, so rebuilding the
+    // transaction with a different one invalidates the proof
+    let account_update = proven_transaction.account_update();
+    let corrupted_expiration = proven_transaction.expiration_block_num() + 1;
+    let corrupted_transaction = ProvenTransactionBuilder::new(
+        proven_transaction.account_id(),
+        account_update.initial_state_commitment(),
+        account_update.final_state_commitment(),
+        account_update.account_delta_commitment(),
+        proven_transaction.ref_block_num(),
+        proven_transaction.ref_block_commitment(),
+        corrupted_expiration,
+        proven_transaction.proof().clone(),
+    )
+    .account_update_details(account_update.details().clone())
+    .add_input_notes(proven_transaction.input_notes().iter().cloned())
+    .add_output_notes(proven_transaction.output_notes().iter().cloned())
+    .build()
+    .unwrap();
+    assert_eq!(corrupted_transaction.expiration_block_num(), corrupted_expiration);
+
+    let chain_tip = rpc_api.get_chain_tip_block_num();
+    let result = client.testing_submit_proven_transaction(corrupted_transaction).await;
+    assert!(matches!(result, Err(ClientError::TransactionVerificationError(_))));
+    assert_eq!(rpc_api.get_chain_tip_block_num(), chain_tip);
+
+    // The valid proof passes the local verification and gets submitted
+    client.testing_submit_proven_transaction(proven_transaction).await.unwrap();
+    assert_eq!(rpc_api.get_chain_tip_block_num().as_u32(), chain_tip.as_u32() + 1);
+}
+
+#[tokio::test]
+async fn await_transaction_commit() {
+    let (mut client, _rpc_api, keystore) = create_test_client().await;
+
+    let (faucet, _seed) =
+        insert_new_fungible_faucet(&mut client, AccountStorageMode::Private, &keystore)
+            .await
+            .unwrap();
+
+    client.sync_state().await.unwrap();
+
+    let transaction_request = TransactionRequestBuilder::new()
+        .build_mint_fungible_asset(
+            FungibleAsset::new(faucet.id(), 5u64).unwrap(),
+            AccountId::try_from(ACCOUNT_ID_REGULAR_PUBLIC_ACCOUNT_IMMUTABLE_CODE).unwrap(),
+            NoteType::Private,
+            client.rng(),
+        )
+        .unwrap();
+
+    let tx_result = client.new_transaction(faucet.id(), transaction_request).await.unwrap();
+    let transaction_id = tx_result.executed_transaction().id();
+    client.submit_transaction(tx_result).await.unwrap();
+
+    let transaction = client
+        .await_transaction_commit(transaction_id, 50, tokio::task::yield_now)
+        .await
+        .unwrap();
+
+    assert_eq!(transaction.id, transaction_id);
+    assert!(matches!(transaction.status, TransactionStatus::Committed(_)));
+
+    // A transaction that is never committed times out
+    let untracked_transaction_id = TransactionId::from(Digest::default());
+    let result = client
+        .await_transaction_commit(untracked_transaction_id, 3, tokio::task::yield_now)
+        .await;
+
+    assert!(matches!(
+        result,
+        Err(ClientError::TransactionNotCommitted(id)) if id == untracked_transaction_id
+    ));
+}
+
+#[tokio::test]
+async fn offline_transaction_signing() {
+    let (mut client, _rpc_api, keystore) = create_test_client().await;
+
+    let (faucet, _seed) =
+        insert_new_fungible_faucet(&mut client, AccountStorageMode::Private, &keystore)
+            .await
+            .unwrap();
+
+    // The wallet's key is only held by the offline signer, not by the client's keystore
+    let offline_key = SecretKey::with_rng(&mut client.rng);
+    let mut init_seed = [0u8; 32];
+    client.rng.fill_bytes(&mut init_seed);
+    let (wallet, seed) = AccountBuilder::new(init_seed)
+        .account_type(AccountType::RegularAccountImmutableCode)
+        .storage_mode(AccountStorageMode::Private)
+        .with_auth_component(RpoFalcon512::new(offline_key.public_key()))
+        .with_component(BasicWallet)
+        .build()
+        .unwrap();
+    client.add_account(&wallet, Some(seed), false).await.unwrap();
+
+    client.sync_state().await.unwrap();
+    let note = mint_note(&mut client, wallet.id(), faucet.id(), NoteType::Private).await;
+
+    let tx_request = TransactionRequestBuilder::new().build_consume_notes(vec![note.id()]).unwrap();
+
+    // The online client can't sign the transaction by itself
+    assert!(client.new_transaction(wallet.id(), tx_request.clone()).await.is_err());
+
+    let unsigned_transaction =
+        client.export_unsigned_transaction(wallet.id(), tx_request).await.unwrap();
+    assert_eq!(unsigned_transaction.pub_key(), Word::from(offline_key.public_key()));
+
+    // Move the payload to the offline signer and sign it there
+    let unsigned_transaction =
+        UnsignedTransaction::read_from_bytes(&unsigned_transaction.to_bytes()).unwrap();
+    let signature = miden_tx::auth::signatures::get_falcon_signature(
+        &offline_key,
+        unsigned_transaction.message(),
+        &mut StdRng::seed_from_u64(0),
+    )
+    .unwrap();
+
+    // A signature over a different message is rejected
+    let wrong_signature = miden_tx::auth::signatures::get_falcon_signature(
+        &offline_key,
+        EMPTY_WORD,
+        &mut StdRng::seed_from_u64(0),
+    )
+    .unwrap();
+    assert!(
+        client
+            .import_signature(unsigned_transaction.clone(), wrong_signature)
+            .await
+            .is_err()
+    );
+
+    let tx_result = client.import_signature(unsigned_transaction, signature).await.unwrap();
+    client.submit_transaction(tx_result).await.unwrap();
+
+    assert_account_has_single_asset(&client, wallet.id(), faucet.id(), MINT_AMOUNT).await;
+}
+
+/// A [`KeyStore`] that counts how many times its keys are retrieved.
+struct CountingKeyStore {
+    inner: MemoryKeyStore,
+    get_key_calls: Arc<AtomicUsize>,
+}
+
+impl KeyStore for CountingKeyStore {
+    fn add_key(&self, key: &AuthSecretKey) -> Result<(), KeyStoreError> {
+        self.inner.add_key(key)
+    }
+
+    fn get_key(&self, pub_key: Word) -> Result<Option<AuthSecretKey>, KeyStoreError> {
+        self.get_key_calls.fetch_add(1, Ordering::Relaxed);
+        self.inner.get_key(pub_key)
+    }
+}
+
+#[tokio::test]
+async fn custom_keystore_is_used_for_signing() {
+    let (builder, _rpc_api, _keystore) = create_test_client_builder().await;
+
+    let key_pair = SecretKey::new();
+    let keystore = CountingKeyStore {
+        inner: MemoryKeyStore::new(),
+        get_key_calls: Arc::new(AtomicUsize::new(0)),
+    };
+    keystore.add_key(&AuthSecretKey::RpoFalcon512(key_pair.clone())).unwrap();
+    let get_key_calls = keystore.get_key_calls.clone();
+
+    let mut client = builder.keystore(keystore).build().await.unwrap();
+    client.ensure_genesis_in_place().await.unwrap();
+
+    let mut init_seed = [0u8; 32];
+    client.rng.fill_bytes(&mut init_seed);
+    let (faucet, seed) = AccountBuilder::new(init_seed)
+        .account_type(AccountType::FungibleFaucet)
+        .storage_mode(AccountStorageMode::Private)
+        .with_auth_component(RpoFalcon512::new(key_pair.public_key()))
+        .with_component(
+            BasicFungibleFaucet::new(TokenSymbol::new("TEST").unwrap(), 10, Felt::new(1_000_000))
+                .unwrap(),
+        )
+        .build()
+        .unwrap();
+    client.add_account(&faucet, Some(seed), false).await.unwrap();
+    client.sync_state().await.unwrap();
+
+    let transaction_request = TransactionRequestBuilder::new()
+        .build_mint_fungible_asset(
+            FungibleAsset::new(faucet.id(), 5u64).unwrap(),
+            AccountId::try_from(ACCOUNT_ID_REGULAR_PUBLIC_ACCOUNT_IMMUTABLE_CODE).unwrap(),
+            NoteType::Private,
+            client.rng(),
+        )
+        .unwrap();
+
+    assert_eq!(get_key_calls.load(Ordering::Relaxed), 0);
+    client.new_transaction(faucet.id(), transaction_request).await.unwrap();
+    assert_eq!(get_key_calls.load(Ordering::Relaxed), 1);
+}
+
+#[test]
+fn filesystem_keystore_errors() {
+    let keys_directory = temp_dir().join(Uuid::new_v4().to_string());
+    let keystore = FilesystemKeyStore::new(keys_directory.clone()).unwrap();
+
+    let secret_key = SecretKey::new();
+    let pub_key = Digest::from(Word::from(secret_key.public_key()));
+    let key = AuthSecretKey::RpoFalcon512(secret_key);
+    keystore.add_key(&key).unwrap();
+
+    // Adding the same key again is a no-op
+    keystore.add_key(&key).unwrap();
+    assert_eq!(keystore.get_key(pub_key.into()).unwrap().unwrap().to_bytes(), key.to_bytes());
+
+    // A different key stored for the same public key isn't overwritten
+    let key_file = std::fs::read_dir(&keys_directory).unwrap().next().unwrap().unwrap().path();
+    let other_key = AuthSecretKey::RpoFalcon512(SecretKey::new());
+    std::fs::write(&key_file, hex::encode(other_key.to_bytes())).unwrap();
+    assert!(matches!(
+        keystore.add_key(&key),
+        Err(KeyStoreError::DuplicateKey(duplicate)) if duplicate == pub_key
+    ));
+    assert_eq!(
+        keystore.get_key(pub_key.into()).unwrap().unwrap().to_bytes(),
+        other_key.to_bytes()
+    );
+
+    // The keys directory can't be created under a file
+    let file_path = keys_directory.join("file");
+    std::fs::write(&file_path, "").unwrap();
+    assert!(matches!(
+        FilesystemKeyStore::new(file_path.join("keys")),
+        Err(KeyStoreError::DirectoryNotWritable(..))
+    ));
+}
+
+#[tokio::test]
+async fn get_accounts_with_balances() {
+    let (mut client, _rpc_api, keystore) = create_test_client().await;
+
+    let (first_wallet, second_wallet, faucet) =
+        setup_two_wallets_and_faucet(&mut client, AccountStorageMode::Private, &keystore).await;
+
+    mint_and_consume(&mut client, first_wallet.id(), faucet.id(), NoteType::Private).await;
+    mint_and_consume(&mut client, first_wallet.id(), faucet.id(), NoteType::Private).await;
+    mint_and_consume(&mut client, second_wallet.id(), faucet.id(), NoteType::Private).await;
+
+    let balances = client
+        .get_accounts_with_balances()
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|(header, balances)| (header.id(), balances))
+        .collect::<BTreeMap<_, _>>();
+
+    assert_eq!(balances.len(), 3);
+    assert_eq!(balances[&first_wallet.id()], BTreeMap::from([(faucet.id(), 2 * MINT_AMOUNT)]));
+    assert_eq!(balances[&second_wallet.id()], BTreeMap::from([(faucet.id(), MINT_AMOUNT)]));
+    assert!(balances[&faucet.id()].is_empty());
+}
+
+#[tokio::test]
+async fn get_account_assets() {
+    let (mut client, _rpc_api, keystore) = create_test_client().await;
+
+    let (wallet, faucet) =
+        setup_wallet_and_faucet(&mut client, AccountStorageMode::Private, &keystore).await;
+
+    assert!(client.test_store().get_account_assets(wallet.id()).await.unwrap().is_empty());
+
+    mint_and_consume(&mut client, wallet.id(), faucet.id(), NoteType::Private).await;
+
+    let assets = client.test_store().get_account_assets(wallet.id()).await.unwrap();
+    let account = client.get_account(wallet.id()).await.unwrap().unwrap();
+
+    assert_eq!(assets, account.account().vault().assets().collect::<Vec<_>>());
+    assert_eq!(assets, vec![FungibleAsset::new(faucet.id(), MINT_AMOUNT).unwrap().into()]);
+
+    let unknown_account_id = AccountId::try_from(ACCOUNT_ID_PRIVATE_SENDER).unwrap();
+    assert!(matches!(
+        client.test_store().get_account_assets(unknown_account_id).await,
+        Err(StoreError::AccountDataNotFound(account_id)) if account_id == unknown_account_id
+    ));
+}
+
+#[tokio::test]
+async fn execute_serialized_request() {
+    let (mut client, _rpc_api, keystore) = create_test_client().await;
+
+    let (wallet, faucet) =
+        setup_wallet_and_faucet(&mut client, AccountStorageMode::Private, &keystore).await;
+
+    let transaction_request = TransactionRequestBuilder::new()
+        .build_mint_fungible_asset(
+            FungibleAsset::new(faucet.id(), MINT_AMOUNT).unwrap(),
+            wallet.id(),
+            NoteType::Private,
+            client.rng(),
+        )
+        .unwrap();
+    let bytes = transaction_request.to_bytes();
+
+    let tx_result = client.execute_serialized_request(faucet.id(), &bytes).await.unwrap();
+    assert_eq!(tx_result.created_notes().num_notes(), 1);
+    assert_eq!(
+        tx_result
+            .created_notes()
+            .get_note(0)
+            .assets()
+            .unwrap()
+            .iter()
+            .collect::<Vec<_>>(),
+        vec![&Asset::from(FungibleAsset::new(faucet.id(), MINT_AMOUNT).unwrap())]
+    );
+
+    client.testing_apply_transaction(tx_result).await.unwrap();
+    assert_eq!(client.get_transactions(TransactionFilter::All).await.unwrap().len(), 1);
+
+    // Requests with foreign accounts that can't be resolved are rejected
+    let private_account_id =
+        AccountId::try_from(ACCOUNT_ID_REGULAR_PRIVATE_ACCOUNT_UPDATABLE_CODE).unwrap();
+    let transaction_request = TransactionRequestBuilder::new()
+        .foreign_accounts([ForeignAccount::Public(
+            private_account_id,
+            AccountStorageRequirements::default(),
+        )])
+        .build_mint_fungible_asset(
+            FungibleAsset::new(faucet.id(), MINT_AMOUNT).unwrap(),
+            wallet.id(),
+            NoteType::Private,
+            client.rng(),
+        )
+        .unwrap();
+
+    assert!(matches!(
+        client.execute_serialized_request(faucet.id(), &transaction_request.to_bytes()).await,
+        Err(ClientError::TransactionRequestError(
+            TransactionRequestError::InvalidForeignAccountId(account_id)
+        )) if account_id == private_account_id
+    ));
+
+    assert!(matches!(
+        client.execute_serialized_request(faucet.id(), &bytes[..bytes.len() / 2]).await,
+        Err(ClientError::DataDeserializationError(_))
+    ));
+}
+
+#[tokio::test]
+async fn known_faucets() {
+    let (mut client, _rpc_api, keystore) = create_test_client().await;
+
+    let (wallet, first_faucet) =
+        setup_wallet_and_faucet(&mut client, AccountStorageMode::Private, &keystore).await;
+    let (second_faucet, _) =
+        insert_new_fungible_faucet(&mut client, AccountStorageMode::Private, &keystore)
+            .await
+            .unwrap();
+
+    assert!(client.known_faucets().await.unwrap().is_empty());
+
+    // The first faucet's asset ends up in the wallet's vault and the second one's stays in a note
+    mint_and_consume(&mut client, wallet.id(), first_faucet.id(), NoteType::Private).await;
+    mint_note(&mut client, wallet.id(), second_faucet.id(), NoteType::Private).await;
+
+    let mut expected_faucets = vec![first_faucet.id(), second_faucet.id()];
+    expected_faucets.sort();
+
+    assert_eq!(client.known_faucets().await.unwrap(), expected_faucets);
+}
+
+#[tokio::test]
+async fn audit_against_node() {
+    let (mut client, _rpc_api, keystore) = create_test_client().await;
+    let (wallet, faucet) =
+        setup_wallet_and_faucet(&mut client, AccountStorageMode::Private, &keystore).await;
+    mint_and_consume(&mut client, wallet.id(), faucet.id(), NoteType::Private).await;
+    client.sync_state().await.unwrap();
+
+    let report = client.audit_against_node().await.unwrap();
+    assert!(report.is_consistent());
+    assert_eq!(report.audited_accounts(), 2);
+
+    // Empty the wallet's vault in the store without going through a transaction. The nonce is
+    // bumped so that the tampered state replaces the current one, as states are stored by
+    // commitment and a state with the same nonce would be stored alongside it
+    let account: Account = client.get_account(wallet.id()).await.unwrap().unwrap().into();
+    let corrupted_account = Account::from_parts(
+        account.id(),
+        AssetVault::new(&[]).unwrap(),
+        account.storage().clone(),
+        account.code().clone(),
+        account.nonce() + ONE,
+    );
+    client.test_store().update_account(&corrupted_account).await.unwrap();
+
+    let report = client.audit_against_node().await.unwrap();
+    assert_eq!(
+        report.discrepancies(),
+        [AuditDiscrepancy::AccountCommitmentMismatch {
+            account_id: wallet.id(),
+            local_commitment: corrupted_account.commitment(),
+            node_commitment: account.commitment(),
+        }]
+    );
+
+    // Auditing doesn't fix the store
+    let account: Account = client.get_account(wallet.id()).await.unwrap().unwrap().into();
+    assert_eq!(account.commitment(), corrupted_account.commitment());
+
+    // An account the node can't return is reported instead of failing the audit
+    let unknown_account = Account::mock(
+        ACCOUNT_ID_REGULAR_PUBLIC_ACCOUNT_UPDATABLE_CODE,
+        Felt::new(10),
+        RpoFalcon512::new(PublicKey::new(EMPTY_WORD)),
+        TransactionKernel::assembler(),
+    );
+    client.add_account(&unknown_account, None, false).await.unwrap();
+
+    let report = client.audit_against_node().await.unwrap();
+    assert_eq!(report.audited_accounts(), 3);
+    assert_eq!(report.discrepancies().len(), 2);
+    assert!(report.discrepancies().iter().any(|discrepancy| matches!(
+        discrepancy,
+        AuditDiscrepancy::AccountNotRetrieved { account_id, .. } if *account_id == unknown_account.id()
+    )));
+}
+
+#[tokio::test]
+async fn audit_against_node_notes() {
+    let (mut client, _rpc_api, keystore) = create_test_client().await;
+    let (wallet, faucet) =
+        setup_wallet_and_faucet(&mut client, AccountStorageMode::Private, &keystore).await;
+    let note = mint_note(&mut client, wallet.id(), faucet.id(), NoteType::Private).await;
+
+    let report = client.audit_against_node().await.unwrap();
+    assert!(report.is_consistent());
+    // The note is both an input note of the wallet and an output note of the faucet
+    assert_eq!(report.audited_notes(), 2);
+
+    // Point the input note's inclusion proof to another position of the block's note tree
+    let record = client.get_input_note(note.id()).await.unwrap().unwrap();
+    let InputNoteState::Committed(state) = record.state() else {
+        panic!("minted note should be committed");
+    };
+    let location = state.inclusion_proof.location();
+    let corrupted_proof = NoteInclusionProof::new(
+        location.block_num(),
+        location.node_index_in_block() + 1,
+        state.inclusion_proof.note_path().clone(),
+    )
+    .unwrap();
+    let corrupted_record = InputNoteRecord::new(
+        record.details().clone(),
+        record.created_at(),
+        CommittedNoteState {
+            metadata: state.metadata,
+            inclusion_proof: corrupted_proof,
+            block_note_root: state.block_note_root,
+        }
+        .into(),
+    );
+    client.test_store().upsert_input_notes(&[corrupted_record]).await.unwrap();
+
+    let report = client.audit_against_node().await.unwrap();
+    assert_eq!(
+        report.discrepancies(),
+        [AuditDiscrepancy::InvalidNoteInclusionProof {
+            note_id: note.id(),
+            block_num: location.block_num(),
+        }]
+    );
+}
+
+#[tokio::test]
+async fn wait_for_blocks_returns_after_target_block() {
+    let (mut client, rpc_api, _) = create_test_client().await;
+    client.sync_state().await.unwrap();
+    let target_block = client.get_sync_height().await.unwrap() + 2;
+
+    // The blocks are produced once the interval between syncs reaches its maximum
+    let mut intervals = Vec::new();
+    let summary = wait_for_blocks_with_sleep(&mut client, 2, |interval| {
+        intervals.push(interval);
+        if interval == WAIT_FOR_BLOCKS_MAX_INTERVAL {
+            rpc_api.advance_blocks(2);
+        }
+    })
+    .await;
+
+    assert_eq!(summary.block_num, target_block);
+    assert_eq!(
+        intervals,
+        [
+            WAIT_FOR_BLOCKS_INITIAL_INTERVAL,
+            WAIT_FOR_BLOCKS_INITIAL_INTERVAL * 2,
+            WAIT_FOR_BLOCKS_INITIAL_INTERVAL * 4,
+            WAIT_FOR_BLOCKS_INITIAL_INTERVAL * 8,
+            WAIT_FOR_BLOCKS_MAX_INTERVAL,
+        ]
+    );
+}
+
+#[tokio::test]
+async fn fund_account_adds_amount_to_vault() {
+    let (mut client, _rpc_api, keystore) = create_test_client().await;
+    let (wallet, faucet) =
+        setup_wallet_and_faucet(&mut client, AccountStorageMode::Private, &keystore).await;
+
+    fund_account(&mut client, faucet.id(), wallet.id(), 150).await;
+    assert_account_has_single_asset(&client, wallet.id(), faucet.id(), 150).await;
+
+    fund_account(&mut client, faucet.id(), wallet.id(), 50).await;
+    assert_account_has_single_asset(&client, wallet.id(), faucet.id(), 200).await;
+}
+
+#[test]
+fn note_with_fixed_serial_number_is_deterministic() {
+    let sender = AccountId::try_from(ACCOUNT_ID_PRIVATE_SENDER).unwrap();
+    let serial_number = [Felt::new(1), Felt::new(2), Felt::new(3), Felt::new(4)];
+
+    // Builders with different RNG seeds only share the fixed serial number
+    let build_note = |seed: u64| {
+        let note =
+            NoteBuilder::new(sender, RpoRandomCoin::new([Felt::new(seed), ZERO, ZERO, ZERO]))
+                .build(&TransactionKernel::assembler())
+                .unwrap();
+        with_serial_number(&note, serial_number)
+    };
+
+    let note = build_note(0);
+    assert_eq!(note.id(), build_note(1).id());
+    assert_eq!(note.serial_num(), serial_number);
+}
+
+#[tokio::test]
+async fn get_pending_output_notes() {
+    let (mut client, _rpc_api, keystore) = create_test_client().await;
+    let (wallet, faucet) =
+        setup_wallet_and_faucet(&mut client, AccountStorageMode::Private, &keystore).await;
+    assert!(client.get_pending_output_notes().await.unwrap().is_empty());
+
+    let tx_request = TransactionRequestBuilder::new()
+        .build_mint_fungible_asset(
+            FungibleAsset::new(faucet.id(), MINT_AMOUNT).unwrap(),
+            wallet.id(),
+            NoteType::Private,
+            client.rng(),
+        )
+        .unwrap();
+    let note_id = tx_request.expected_output_own_notes().pop().unwrap().id();
+    execute_tx(&mut client, faucet.id(), tx_request).await;
+
+    let pending_notes = client.get_pending_output_notes().await.unwrap();
+    assert_eq!(
+        pending_notes.iter().map(OutputNoteRecord::id).collect::<Vec<_>>(),
+        vec![note_id]
+    );
+
+    // The mock node includes the note on submission, which the client learns about on sync
+    client.sync_state().await.unwrap();
+    assert!(client.get_pending_output_notes().await.unwrap().is_empty());
+    assert!(client.get_output_note(note_id).await.unwrap().unwrap().is_committed());
+}
+
+#[tokio::test]
+async fn submitting_already_spent_note_is_rejected() {
+    let (mut client, rpc_api, keystore) = create_test_client().await;
+    let (wallet, faucet) =
+        setup_wallet_and_faucet(&mut client, AccountStorageMode::Private, &keystore).await;
+    let note = mint_note(&mut client, wallet.id(), faucet.id(), NoteType::Private).await;
+
+    // Consume the note in two different transactions (as authenticated and unauthenticated note).
+    // Reservations are kept in memory, so the second one is built by another client sharing the
+    // same store.
+    let (builder, ..) = create_test_client_builder().await;
+    let mut other_client = builder
+        .rpc(Arc::new(rpc_api.clone()))
+        .store(client.test_store().clone())
+        .authenticator(Arc::new(keystore.clone()))
+        .build()
+        .await
+        .unwrap();
+
+    let tx_request = TransactionRequestBuilder::new().build_consume_notes(vec![note.id()]).unwrap();
+    let first_result = client.new_transaction(wallet.id(), tx_request).await.unwrap();
+    let tx_request = TransactionRequestBuilder::new()
+        .unauthenticated_input_notes([(note.note().clone(), None)])
+        .build()
+        .unwrap();
+    let second_result = other_client.new_transaction(wallet.id(), tx_request).await.unwrap();
+    let second_transaction_id = second_result.executed_transaction().id();
+
+    client.submit_transaction(first_result).await.unwrap();
+    let error = other_client.submit_transaction(second_result).await.unwrap_err();
+
+    let ClientError::RpcError(RpcError::TransactionSubmissionError(error)) = error else {
+        panic!("unexpected error: {error:?}");
+    };
+    assert!(matches!(error, SubmissionError::AlreadySpent(_)));
+    assert!(!error.is_retryable());
+
+    // The rejection is permanent, so the note is no longer reserved by the second transaction
+    assert!(!other_client.is_note_reserved(note.id()));
+
+    // The rejected transaction isn't tracked, so it isn't left pending
+    assert!(
+        client
+            .get_transactions(TransactionFilter::Ids(vec![second_transaction_id]))
+            .await
+            .unwrap()
+            .is_empty()
+    );
+    client.sync_state().await.unwrap();
+    assert!(
+        client
+            .get_transactions(TransactionFilter::Uncommitted)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+    assert_account_has_single_asset(&client, wallet.id(), faucet.id(), MINT_AMOUNT).await;
+}
+
+#[tokio::test]
+async fn get_token_metadata() {
+    let (mut client, _rpc_api, keystore) = create_test_client().await;
+
+    // Build a faucet like the genesis `TST` faucet
+    let key_pair = SecretKey::with_rng(&mut client.rng);
+    let pub_key = key_pair.public_key();
+    keystore.add_key(&AuthSecretKey::RpoFalcon512(key_pair)).unwrap();
+
+    let mut init_seed = [0u8; 32];
+    client.rng.fill_bytes(&mut init_seed);
+    let symbol = TokenSymbol::new("TST").unwrap();
+    let (faucet, seed) = AccountBuilder::new(init_seed)
+        .account_type(AccountType::FungibleFaucet)
+        .storage_mode(AccountStorageMode::Private)
+        .with_auth_component(RpoFalcon512::new(pub_key))
+        .with_component(BasicFungibleFaucet::new(symbol, 12, Felt::new(1_000_000_000)).unwrap())
+        .build()
+        .unwrap();
+    client.add_account(&faucet, Some(seed), false).await.unwrap();
+
+    let (wallet, _) = insert_new_wallet(&mut client, AccountStorageMode::Private, &keystore)
+        .await
+        .unwrap();
+
+    assert!(client.test_store().get_token_metadata(faucet.id()).await.unwrap().is_none());
+
+    let metadata = client.get_token_metadata(faucet.id()).await.unwrap();
+    assert_eq!(metadata, TokenMetadata::new(symbol, 12));
+
+    // The metadata is cached after the first lookup
+    assert_eq!(
+        client.test_store().get_token_metadata(faucet.id()).await.unwrap(),
+        Some(metadata)
+    );
+
+    assert!(matches!(
+        client.get_token_metadata(wallet.id()).await,
+        Err(ClientError::AccountNotFungibleFaucet(account_id)) if account_id == wallet.id()
+    ));
+}
+
+#[tokio::test]
+async fn note_transfer_proof() {
+    let (mut client, _rpc_api, keystore) = create_test_client().await;
+
+    let key_pair = AuthSecretKey::RpoFalcon512(SecretKey::with_rng(&mut client.rng));
+    let AuthSecretKey::RpoFalcon512(secret_key) = &key_pair;
+    let pub_key = secret_key.public_key();
+    keystore.add_key(&key_pair).unwrap();
+
+    let mut init_seed = [0u8; 32];
+    client.rng.fill_bytes(&mut init_seed);
+    let (faucet, seed) = AccountBuilder::new(init_seed)
+        .account_type(AccountType::FungibleFaucet)
+        .storage_mode(AccountStorageMode::Private)
+        .with_auth_component(RpoFalcon512::new(pub_key))
+        .with_component(
+            BasicFungibleFaucet::new(TokenSymbol::new("TST").unwrap(), 10, Felt::new(1_000_000))
+                .unwrap(),
+        )
+        .build()
+        .unwrap();
+    client.add_account(&faucet, Some(seed), false).await.unwrap();
+
+    let (wallet, _) = insert_new_wallet(&mut client, AccountStorageMode::Private, &keystore)
+        .await
+        .unwrap();
+
+    let note_id = mint_note(&mut client, wallet.id(), faucet.id(), NoteType::Private).await.id();
+
+    let proof = client.create_note_transfer_proof(note_id, &keystore).await.unwrap();
+    assert_eq!(proof.note().id(), note_id);
+    assert_eq!(proof.public_key(), pub_key);
+
+    let proof = NoteTransferProof::read_from_bytes(&proof.to_bytes()).unwrap();
+    client.verify_note_transfer_proof(&proof).await.unwrap();
+
+    // A proof for a note with different assets doesn't match the signature
+    let note = proof.note();
+    let tampered_assets =
+        NoteAssets::new(vec![FungibleAsset::new(faucet.id(), 2 * MINT_AMOUNT).unwrap().into()])
+            .unwrap();
+    let tampered_note = Note::new(tampered_assets, *note.metadata(), note.recipient().clone());
+    let tampered_proof =
+        NoteTransferProof::new(tampered_note, proof.public_key(), proof.signature().clone());
+
+    assert!(matches!(
+        client.verify_note_transfer_proof(&tampered_proof).await,
+        Err(ClientError::NoteTransferProofError(NoteTransferProofError::InvalidSignature(_)))
+    ));
+
+    // A proof signed by a key other than the sender's is rejected
+    let other_key = SecretKey::with_rng(&mut client.rng);
+    let forged_proof = NoteTransferProof::new(
+        note.clone(),
+        other_key.public_key(),
+        other_key.sign(NoteTransferProof::message(note)),
+    );
+    assert!(matches!(
+        client.verify_note_transfer_proof(&forged_proof).await,
+        Err(ClientError::NoteTransferProofError(NoteTransferProofError::SignerNotSender(
+            id, sender
+        ))) if id == note_id && sender == faucet.id()
+    ));
+
+    // The transfer can only be signed if the keystore holds the sender's key
+    assert!(matches!(
+        client.create_note_transfer_proof(note_id, &MemoryKeyStore::new()).await,
+        Err(ClientError::NoteTransferProofError(NoteTransferProofError::SigningKeyNotFound(
+            id, sender
+        ))) if id == note_id && sender == faucet.id()
+    ));
+
+    // A client that can't resolve the key of the private sender can't verify the proof
+    let (other_client, ..) = create_test_client().await;
+    assert!(matches!(
+        other_client.verify_note_transfer_proof(&proof).await,
+        Err(ClientError::NoteTransferProofError(NoteTransferProofError::SenderKeyUnknown(
+            id, sender
+        ))) if id == note_id && sender == faucet.id()
+    ));
+}
+
+#[tokio::test]
+async fn import_note_validation() {
+    // generate test client
+    let (mut client, rpc_api, _) = create_test_client().await;
+
+    // generate test data
+    let expected_note = rpc_api.get_available_notes()[0].clone();
+    let consumed_note = rpc_api.get_available_notes()[1].clone();
+
+    client
+        .import_note(NoteFile::NoteWithProof(
+            consumed_note.note().unwrap().clone(),
+            consumed_note.inclusion_proof().clone(),
+        ))
+        .await
+        .unwrap();
+
+    client
+        .import_note(NoteFile::NoteDetails {
+            details: expected_note.note().unwrap().into(),
+            after_block_num: 0.into(),
+            tag: None,
+        })
+        .await
+        .unwrap();
+
+    let expected_note = client
+        .get_input_note(expected_note.note().unwrap().id())
+        .await
+        .unwrap()
+        .unwrap();
+
+    let consumed_note = client
+        .get_input_note(consumed_note.note().unwrap().id())
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert!(expected_note.inclusion_proof().is_none());
+    assert!(consumed_note.is_consumed());
+}
+
+#[tokio::test]
+async fn import_note_size_limit() {
+    let (builder, rpc_api, _) = create_test_client_builder().await;
+
+    let note = rpc_api.get_available_notes()[0].note().unwrap().clone();
+    let note_details = NoteDetails::from(note.clone());
+    let max_note_bytes = note_details.to_bytes().len();
+    let mut client = builder.with_max_note_bytes(max_note_bytes).build().await.unwrap();
+
+    // A note with the same assets but many more inputs exceeds the limit
+    let large_recipient = NoteRecipient::new(
+        note.serial_num(),
+        note.script().clone(),
+        NoteInputs::new(vec![ONE; 100]).unwrap(),
+    );
+    let large_note_details = NoteDetails::new(note.assets().clone(), large_recipient);
+
+    let result = client
+        .import_note(NoteFile::NoteDetails {
+            details: large_note_details.clone(),
+            after_block_num: 0.into(),
+            tag: None,
+        })
+        .await;
+    assert!(matches!(
+        result,
+        Err(ClientError::NoteRecordConversionError(NoteRecordError::NoteTooLarge(_, max)))
+            if max == max_note_bytes
+    ));
+    assert!(client.get_input_note(large_note_details.id()).await.unwrap().is_none());
+
+    // A note within the limit is imported normally
+    client
+        .import_note(NoteFile::NoteDetails {
+            details: note_details,
+            after_block_num: 0.into(),
+            tag: None,
+        })
+        .await
+        .unwrap();
+    assert!(client.get_input_note(note.id()).await.unwrap().is_some());
+}
+
+#[tokio::test]
+async fn import_notes_from_dir() {
+    let (mut client, rpc_api, _) = create_test_client().await;
+
+    let notes_dir = temp_dir().join(Uuid::new_v4().to_string());
+    std::fs::create_dir(&notes_dir).unwrap();
+
+    let notes: Vec<Note> = rpc_api
+        .get_available_notes()
+        .into_iter()
+        .take(2)
+        .map(|note| note.note().unwrap().clone())
+        .collect();
+    for (index, note) in notes.iter().enumerate() {
+        let note_file = NoteFile::NoteDetails {
+            details: note.clone().into(),
+            after_block_num: 0.into(),
+            tag: Some(note.metadata().tag()),
+        };
+        std::fs::write(notes_dir.join(format!("note_{index}.mno")), note_file.to_bytes()).unwrap();
+    }
+    let malformed_path = notes_dir.join("malformed.mno");
+    std::fs::write(&malformed_path, [1, 2, 3]).unwrap();
+
+    // Files that aren't note files are skipped
+    std::fs::write(notes_dir.join("README.txt"), "not a note").unwrap();
+    std::fs::create_dir(notes_dir.join("nested.mno")).unwrap();
+
+    let results = client.import_notes_from_dir(&notes_dir).await.unwrap();
+    assert_eq!(results.len(), 3);
+
+    let (imported, failed): (Vec<_>, Vec<_>) =
+        results.into_iter().partition(|(_, result)| result.is_ok());
+    assert_eq!(imported.len(), 2);
+    for note in &notes {
+        assert!(imported.iter().any(|(_, result)| result.as_ref().unwrap() == &note.id()));
+        assert!(client.get_input_note(note.id()).await.unwrap().is_some());
+    }
+
+    assert_eq!(failed.len(), 1);
+    assert_eq!(failed[0].0, malformed_path);
+    assert!(matches!(failed[0].1, Err(ClientError::DataDeserializationError(_))));
+
+    // Directories that can't be read are reported as errors
+    assert!(matches!(
+        client.import_notes_from_dir(notes_dir.join("missing")).await,
+        Err(ClientError::NoteImportError(_))
+    ));
+}
+
+/// A [`Clock`] whose time is set manually.
+struct FakeClock(Arc<AtomicU64>);
+
+impl Clock for FakeClock {
+    fn current_timestamp(&self) -> Option<u64> {
+        Some(self.0.load(Ordering::Relaxed))
+    }
+}
+
+#[tokio::test]
+async fn custom_clock_is_used_for_timestamps() {
+    let (builder, ..) = create_test_client_builder().await;
+
+    let time = Arc::new(AtomicU64::new(1_700_000_000));
+    let client = builder.with_clock(Arc::new(FakeClock(time.clone()))).build().await.unwrap();
+    assert_eq!(client.get_current_timestamp(), Some(1_700_000_000));
+
+    time.store(1_700_000_060, Ordering::Relaxed);
+    assert_eq!(client.get_current_timestamp(), Some(1_700_000_060));
+}
+
+#[tokio::test]
+async fn reimported_note_keeps_inclusion_proof() {
+    let (mut client, rpc_api, _) = create_test_client().await;
+
+    let committed_note = rpc_api.get_available_notes()[0].clone();
+    let note = committed_note.note().unwrap().clone();
+
+    client
+        .import_note(NoteFile::NoteWithProof(
+            note.clone(),
+            committed_note.inclusion_proof().clone(),
+        ))
+        .await
+        .unwrap();
+    let original_record = client.get_input_note(note.id()).await.unwrap().unwrap();
+    assert!(original_record.inclusion_proof().is_some());
+
+    // Importing the same note again without a proof updates the stored record instead of
+    // tracking a second one
+    client
+        .import_note(NoteFile::NoteDetails {
+            details: note.clone().into(),
+            after_block_num: 0.into(),
+            tag: None,
+        })
+        .await
+        .unwrap();
+
+    let records = client.get_input_notes(NoteFilter::All).await.unwrap();
+    assert_eq!(records.iter().filter(|record| record.id() == note.id()).count(), 1);
+
+    let record = client.get_input_note(note.id()).await.unwrap().unwrap();
+    assert_eq!(record.inclusion_proof(), Some(committed_note.inclusion_proof()));
+    assert_eq!(record.created_at(), original_record.created_at());
+}
+
+#[tokio::test]
+async fn transaction_request_expiration() {
+    let (mut client, _, keystore) = create_test_client().await;
+    client.sync_state().await.unwrap();
+
+    let current_height = client.get_sync_height().await.unwrap();
+    let (faucet, _seed) =
+        insert_new_fungible_faucet(&mut client, AccountStorageMode::Private, &keystore)
+            .await
+            .unwrap();
+
+    let transaction_request = TransactionRequestBuilder::new()
+        .expiration_delta(5)
+        .build_mint_fungible_asset(
+            FungibleAsset::new(faucet.id(), 5u64).unwrap(),
+            AccountId::try_from(ACCOUNT_ID_REGULAR_PUBLIC_ACCOUNT_IMMUTABLE_CODE).unwrap(),
+            miden_objects::note::NoteType::Private,
+            client.rng(),
+        )
+        .unwrap();
+
+    let transaction = client.new_transaction(faucet.id(), transaction_request).await.unwrap();
+
+    let (_, tx_outputs, ..) = transaction.executed_transaction().clone().into_parts();
+
+    assert_eq!(tx_outputs.expiration_block_num, current_height + 5);
+}
+
+#[tokio::test]
+async fn note_nullifier_matches_consumed_nullifier() {
+    let (mut client, _, keystore) = create_test_client().await;
+
+    let (regular_account, faucet_account_header) =
+        setup_wallet_and_faucet(&mut client, AccountStorageMode::Private, &keystore).await;
+    let account_id = regular_account.id();
+
+    let note =
+        mint_note(&mut client, account_id, faucet_account_header.id(), NoteType::Private).await;
+    let nullifier = client.note_nullifier(note.id()).await.unwrap();
+
+    consume_notes(&mut client, account_id, core::slice::from_ref(&note)).await;
+
+    let consume_tx = client
+        .get_transactions(TransactionFilter::All)
+        .await
+        .unwrap()
+        .into_iter()
+        .find(|tx| tx.details.account_id == account_id)
+        .unwrap();
+    assert_eq!(consume_tx.details.input_note_nullifiers, vec![nullifier.inner()]);
+
+    // Untracked notes have no nullifier to compute
+    let untracked_note_id = NoteId::new(Digest::default(), Digest::default());
+    assert!(matches!(
+        client.note_nullifier(untracked_note_id).await,
+        Err(ClientError::NoteNotTracked(id)) if id == untracked_note_id
+    ));
+}
+
+#[tokio::test]
+async fn get_expired_transactions() {
+    let (mut client, rpc_api, keystore) = create_test_client().await;
+
+    let (regular_account, faucet_account_header) =
+        setup_wallet_and_faucet(&mut client, AccountStorageMode::Private, &keystore).await;
+
+    let account_id = regular_account.id();
+    let faucet_account_id = faucet_account_header.id();
+
+    let note = mint_note(&mut client, account_id, faucet_account_id, NoteType::Private).await;
+    consume_notes(&mut client, account_id, &[note]).await;
+
+    // Create a mint transaction that will expire in 2 blocks
+    let tx_request = TransactionRequestBuilder::new()
+        .expiration_delta(2)
+        .build_mint_fungible_asset(
+            FungibleAsset::new(faucet_account_id, MINT_AMOUNT).unwrap(),
+            account_id,
+            NoteType::Private,
+            client.rng(),
+        )
+        .unwrap();
+
+    // Execute the transaction but don't submit it to the node
+    let tx_result = client.new_transaction(faucet_account_id, tx_request).await.unwrap();
+    let expired_tx_id = tx_result.executed_transaction().id();
+    client.testing_prove_transaction(&tx_result).await.unwrap();
+    client.testing_apply_transaction(tx_result).await.unwrap();
+
+    // Create a transaction from another account that will not expire
+    let asset = FungibleAsset::new(faucet_account_id, TRANSFER_AMOUNT).unwrap();
+    let tx_request = TransactionRequestBuilder::new()
+        .build_pay_to_id(
+            PaymentNoteDescription::new(vec![Asset::Fungible(asset)], account_id, account_id),
+            NoteType::Private,
+            client.rng(),
+        )
+        .unwrap();
+
+    let tx_result = client.new_transaction(account_id, tx_request).await.unwrap();
+    let valid_tx_id = tx_result.executed_transaction().id();
+    client.testing_prove_transaction(&tx_result).await.unwrap();
+    client.testing_apply_transaction(tx_result).await.unwrap();
+
+    assert!(client.get_expired_transactions().await.unwrap().is_empty());
+
+    rpc_api.advance_blocks(3);
+    client.sync_state().await.unwrap();
+
+    let expired_transactions = client.get_expired_transactions().await.unwrap();
+    assert_eq!(expired_transactions.len(), 1);
+    assert_eq!(expired_transactions[0].id, expired_tx_id);
+
+    let valid_tx_record = client
+        .get_transactions(TransactionFilter::Ids(vec![valid_tx_id]))
+        .await
+        .unwrap()
+        .pop()
+        .unwrap();
+    assert_eq!(valid_tx_record.status, TransactionStatus::Pending);
+}
+
+#[tokio::test]
+async fn get_transaction_detail() {
+    let (mut client, _rpc_api, keystore) = create_test_client().await;
+
+    let (regular_account, faucet_account_header) =
+        setup_wallet_and_faucet(&mut client, AccountStorageMode::Private, &keystore).await;
+
+    let account_id = regular_account.id();
+    let faucet_account_id = faucet_account_header.id();
+
+    let note = mint_note(&mut client, account_id, faucet_account_id, NoteType::Private).await;
+
+    // Consume the minted note and send part of its assets in the same transaction
+    let output_note = create_p2id_note(
+        account_id,
+        AccountId::try_from(ACCOUNT_ID_REGULAR).unwrap(),
+        vec![FungibleAsset::new(faucet_account_id, TRANSFER_AMOUNT).unwrap().into()],
+        NoteType::Private,
+        ZERO,
+        client.rng(),
+    )
+    .unwrap();
+
+    let tx_request = TransactionRequestBuilder::new()
+        .authenticated_input_notes([(note.id(), None)])
+        .own_output_notes(vec![OutputNote::Full(output_note.clone())])
+        .build()
+        .unwrap();
+    let tx_id = execute_tx(&mut client, account_id, tx_request).await;
+
+    let detail = client.get_transaction_detail(tx_id).await.unwrap().unwrap();
+    assert_eq!(detail.record.id, tx_id);
+    assert_eq!(
+        detail.input_notes.iter().map(InputNoteRecord::id).collect::<Vec<_>>(),
+        vec![note.id()]
+    );
+    assert_eq!(
+        detail.output_notes.iter().map(OutputNoteRecord::id).collect::<Vec<_>>(),
+        vec![output_note.id()]
+    );
+
+    // The mint transaction created the consumed note
+    let mint_tx = client
+        .get_transactions(TransactionFilter::All)
+        .await
+        .unwrap()
+        .into_iter()
+        .find(|tx| tx.details.account_id == faucet_account_id)
+        .unwrap();
+    let mint_detail = client.get_transaction_detail(mint_tx.id).await.unwrap().unwrap();
+    assert!(mint_detail.input_notes.is_empty());
+    assert_eq!(
+        mint_detail.output_notes.iter().map(OutputNoteRecord::id).collect::<Vec<_>>(),
+        vec![note.id()]
+    );
+
+    // Untracked transactions have no detail
+    assert!(
+        client
+            .get_transaction_detail(TransactionId::from(Digest::default()))
+            .await
+            .unwrap()
+            .is_none()
+    );
+}
+
+#[tokio::test]
+async fn resubmit_expired_transaction() {
+    let (mut client, rpc_api, keystore) = create_test_client().await;
 
-    // Faucet account generation
-    let (faucet, _seed) =
-        insert_new_fungible_faucet(&mut client, AccountStorageMode::Private, &keystore)
-            .await
-            .unwrap();
+    let (regular_account, faucet_account_header) =
+        setup_wallet_and_faucet(&mut client, AccountStorageMode::Private, &keystore).await;
+
+    let account_id = regular_account.id();
+    let faucet_account_id = faucet_account_header.id();
+
+    let note = mint_note(&mut client, account_id, faucet_account_id, NoteType::Private).await;
+
+    // Consume the minted note and send part of its assets in a transaction that will expire in 2
+    // blocks
+    let output_note = create_p2id_note(
+        account_id,
+        AccountId::try_from(ACCOUNT_ID_REGULAR).unwrap(),
+        vec![FungibleAsset::new(faucet_account_id, TRANSFER_AMOUNT).unwrap().into()],
+        NoteType::Private,
+        ZERO,
+        client.rng(),
+    )
+    .unwrap();
+
+    let tx_request = TransactionRequestBuilder::new()
+        .authenticated_input_notes([(note.id(), None)])
+        .own_output_notes(vec![OutputNote::Full(output_note)])
+        .expiration_delta(2)
+        .build()
+        .unwrap();
 
+    // Execute the transaction but don't submit it to the node
+    let tx_result = client.new_transaction(account_id, tx_request).await.unwrap();
+    let expired_tx_id = tx_result.executed_transaction().id();
+    client.testing_prove_transaction(&tx_result).await.unwrap();
+    client.testing_apply_transaction(tx_result).await.unwrap();
+
+    rpc_api.advance_blocks(3);
     client.sync_state().await.unwrap();
 
-    // Test submitting a mint transaction
-    let transaction_request = TransactionRequestBuilder::new()
-        .build_mint_fungible_asset(
-            FungibleAsset::new(faucet.id(), 5u64).unwrap(),
-            AccountId::try_from(ACCOUNT_ID_PUBLIC_FUNGIBLE_FAUCET_1).unwrap(),
-            miden_objects::note::NoteType::Private,
-            client.rng(),
-        )
+    let summary = client.resubmit_expired().await.unwrap();
+    assert!(summary.skipped.is_empty());
+    assert_eq!(summary.resubmitted, vec![expired_tx_id]);
+
+    // The new transaction makes the same changes, so it has the same ID and replaces the record of
+    // the expired transaction
+    let new_tx_id = expired_tx_id;
+    let new_tx_record = client
+        .get_transactions(TransactionFilter::Ids(vec![new_tx_id]))
+        .await
+        .unwrap()
+        .pop()
         .unwrap();
+    assert_eq!(new_tx_record.status, TransactionStatus::Pending);
 
-    let transaction = client.new_transaction(faucet.id(), transaction_request).await.unwrap();
+    // The transaction isn't expired anymore, so it isn't resubmitted again
+    assert_eq!(client.resubmit_expired().await.unwrap(), ResubmissionSummary::default());
 
-    assert_eq!(transaction.executed_transaction().account_delta().nonce_delta(), ONE);
+    client.sync_state().await.unwrap();
+
+    let new_tx_record = client
+        .get_transactions(TransactionFilter::Ids(vec![new_tx_id]))
+        .await
+        .unwrap()
+        .pop()
+        .unwrap();
+    assert!(matches!(new_tx_record.status, TransactionStatus::Committed(_)));
+
+    let note_record = client.get_input_note(note.id()).await.unwrap().unwrap();
+    assert!(note_record.is_consumed());
+    assert_eq!(note_record.consumer_transaction_id(), Some(&new_tx_id));
+
+    assert_account_has_single_asset(
+        &client,
+        account_id,
+        faucet_account_id,
+        MINT_AMOUNT - TRANSFER_AMOUNT,
+    )
+    .await;
 }
 
 #[tokio::test]
-async fn import_note_validation() {
-    // generate test client
-    let (mut client, rpc_api, _) = create_test_client().await;
+async fn resubmit_expired_skips_transactions_of_changed_accounts() {
+    let (mut client, rpc_api, keystore) = create_test_client().await;
 
-    // generate test data
-    let expected_note = rpc_api.get_available_notes()[0].clone();
-    let consumed_note = rpc_api.get_available_notes()[1].clone();
+    let (regular_account, faucet_account_header) =
+        setup_wallet_and_faucet(&mut client, AccountStorageMode::Private, &keystore).await;
 
-    client
-        .import_note(NoteFile::NoteWithProof(
-            consumed_note.note().unwrap().clone(),
-            consumed_note.inclusion_proof().clone(),
-        ))
-        .await
-        .unwrap();
+    let account_id = regular_account.id();
+    let faucet_account_id = faucet_account_header.id();
 
-    client
-        .import_note(NoteFile::NoteDetails {
-            details: expected_note.note().unwrap().into(),
-            after_block_num: 0.into(),
-            tag: None,
-        })
-        .await
+    let expired_note =
+        mint_note(&mut client, account_id, faucet_account_id, NoteType::Private).await;
+
+    // Execute a transaction that expires in 2 blocks but don't submit it to the node
+    let tx_request = TransactionRequestBuilder::new()
+        .authenticated_input_notes([(expired_note.id(), None)])
+        .expiration_delta(2)
+        .build()
         .unwrap();
+    let tx_result = client.new_transaction(account_id, tx_request).await.unwrap();
+    let expired_tx_id = tx_result.executed_transaction().id();
+    client.testing_prove_transaction(&tx_result).await.unwrap();
+    client.testing_apply_transaction(tx_result).await.unwrap();
 
-    let expected_note = client
-        .get_input_note(expected_note.note().unwrap().id())
-        .await
-        .unwrap()
+    rpc_api.advance_blocks(3);
+    client.sync_state().await.unwrap();
+
+    // Once the account state changes, the expired transaction can't be executed again
+    fund_account(&mut client, faucet_account_id, account_id, TRANSFER_AMOUNT).await;
+
+    let summary = client.resubmit_expired().await.unwrap();
+    assert!(summary.resubmitted.is_empty());
+    assert_eq!(summary.skipped, vec![expired_tx_id]);
+
+    let expired_note_record = client.get_input_note(expired_note.id()).await.unwrap().unwrap();
+    assert!(!expired_note_record.is_consumed());
+}
+
+#[tokio::test]
+async fn clean_discarded_transactions() {
+    let (mut client, rpc_api, keystore) = create_test_client().await;
+
+    let (regular_account, faucet_account_header) =
+        setup_wallet_and_faucet(&mut client, AccountStorageMode::Private, &keystore).await;
+
+    let account_id = regular_account.id();
+    let faucet_account_id = faucet_account_header.id();
+
+    let note = mint_note(&mut client, account_id, faucet_account_id, NoteType::Private).await;
+
+    // Consume the minted note in a transaction that will expire in 2 blocks
+    let tx_request = TransactionRequestBuilder::new()
+        .authenticated_input_notes([(note.id(), None)])
+        .expiration_delta(2)
+        .build()
         .unwrap();
 
-    let consumed_note = client
-        .get_input_note(consumed_note.note().unwrap().id())
+    // Execute the transaction but don't submit it to the node. The expiration delta applies even
+    // though the transaction doesn't create any notes
+    let tx_result = client.new_transaction(account_id, tx_request).await.unwrap();
+    let discarded_tx_id = tx_result.executed_transaction().id();
+    assert_eq!(
+        tx_result.executed_transaction().expiration_block_num(),
+        tx_result.executed_transaction().block_header().block_num() + 2
+    );
+    client.testing_prove_transaction(&tx_result).await.unwrap();
+    client.testing_apply_transaction(tx_result).await.unwrap();
+
+    // Nothing is cleaned while the transaction is pending
+    assert!(client.clean_discarded_transactions().await.unwrap().is_empty());
+    let note_record = client.get_input_note(note.id()).await.unwrap().unwrap();
+    assert!(note_record.is_processing());
+
+    rpc_api.advance_blocks(3);
+    client.sync_state().await.unwrap();
+
+    let discarded_tx_record = client
+        .get_transactions(TransactionFilter::Ids(vec![discarded_tx_id]))
         .await
         .unwrap()
+        .pop()
         .unwrap();
+    assert!(matches!(
+        discarded_tx_record.status,
+        TransactionStatus::Discarded(DiscardCause::Expired)
+    ));
 
-    assert!(expected_note.inclusion_proof().is_none());
-    assert!(consumed_note.is_consumed());
+    let cleaned = client.clean_discarded_transactions().await.unwrap();
+    assert_eq!(cleaned, vec![discarded_tx_id]);
+    assert!(
+        client
+            .get_transactions(TransactionFilter::Ids(vec![discarded_tx_id]))
+            .await
+            .unwrap()
+            .is_empty()
+    );
+
+    // The note was released, so it can be consumed again
+    let note_record = client.get_input_note(note.id()).await.unwrap().unwrap();
+    assert!(note_record.is_committed());
+    assert!(note_record.consumer_transaction_id().is_none());
+
+    consume_notes(&mut client, account_id, &[note]).await;
+    assert_account_has_single_asset(&client, account_id, faucet_account_id, MINT_AMOUNT).await;
+
+    // Cleaning again doesn't remove anything else
+    assert!(client.clean_discarded_transactions().await.unwrap().is_empty());
 }
 
 #[tokio::test]
-async fn transaction_request_expiration() {
-    let (mut client, _, keystore) = create_test_client().await;
-    client.sync_state().await.unwrap();
+async fn building_transaction_over_reserved_note_fails() {
+    let (mut client, _rpc_api, keystore) = create_test_client().await;
 
-    let current_height = client.get_sync_height().await.unwrap();
-    let (faucet, _seed) =
-        insert_new_fungible_faucet(&mut client, AccountStorageMode::Private, &keystore)
-            .await
-            .unwrap();
+    let (wallet, faucet) =
+        setup_wallet_and_faucet(&mut client, AccountStorageMode::Private, &keystore).await;
 
-    let transaction_request = TransactionRequestBuilder::new()
-        .expiration_delta(5)
-        .build_mint_fungible_asset(
-            FungibleAsset::new(faucet.id(), 5u64).unwrap(),
-            AccountId::try_from(ACCOUNT_ID_REGULAR_PUBLIC_ACCOUNT_IMMUTABLE_CODE).unwrap(),
-            miden_objects::note::NoteType::Private,
-            client.rng(),
-        )
-        .unwrap();
+    let note = mint_note(&mut client, wallet.id(), faucet.id(), NoteType::Private).await;
 
-    let transaction = client.new_transaction(faucet.id(), transaction_request).await.unwrap();
+    let tx_request = TransactionRequestBuilder::new().build_consume_notes(vec![note.id()]).unwrap();
+    let first_tx_result = client.new_transaction(wallet.id(), tx_request.clone()).await.unwrap();
+    let first_tx_id = first_tx_result.executed_transaction().id();
 
-    let (_, tx_outputs, ..) = transaction.executed_transaction().clone().into_parts();
+    assert!(client.is_note_reserved(note.id()));
+    assert!(client.get_consumable_notes(Some(wallet.id())).await.unwrap().is_empty());
 
-    assert_eq!(tx_outputs.expiration_block_num, current_height + 5);
+    // The note is reserved by the first transaction, which wasn't submitted yet
+    let error = client.new_transaction(wallet.id(), tx_request.clone()).await.unwrap_err();
+    assert!(matches!(
+        error,
+        ClientError::TransactionRequestError(TransactionRequestError::InputNoteReserved(
+            note_id,
+            transaction_id
+        )) if note_id == note.id() && transaction_id == first_tx_id
+    ));
+
+    // Once released, the note can be consumed by a new transaction
+    client.release_reserved_notes(first_tx_id);
+    assert!(!client.is_note_reserved(note.id()));
+    assert_eq!(client.get_consumable_notes(Some(wallet.id())).await.unwrap().len(), 1);
+
+    let second_tx_result = client.new_transaction(wallet.id(), tx_request).await.unwrap();
+    assert!(client.is_note_reserved(note.id()));
+
+    // Submitting the transaction releases the reservation, the note is now being processed
+    client.submit_transaction(second_tx_result).await.unwrap();
+    assert!(!client.is_note_reserved(note.id()));
+
+    let note_record = client.get_input_note(note.id()).await.unwrap().unwrap();
+    assert!(note_record.is_processing());
+}
+
+#[tokio::test]
+async fn temporary_submission_failure_keeps_notes_reserved() {
+    let (mut client, rpc_api, keystore) = create_test_client().await;
+
+    let (wallet, faucet) =
+        setup_wallet_and_faucet(&mut client, AccountStorageMode::Private, &keystore).await;
+
+    let note = mint_note(&mut client, wallet.id(), faucet.id(), NoteType::Private).await;
+
+    let tx_request = TransactionRequestBuilder::new().build_consume_notes(vec![note.id()]).unwrap();
+    let tx_result = client.new_transaction(wallet.id(), tx_request).await.unwrap();
+
+    rpc_api.fail_next_submission(SubmissionError::Temporary("mempool is full".to_string()));
+    let error = client.submit_transaction(tx_result.clone()).await.unwrap_err();
+    assert!(matches!(
+        error,
+        ClientError::RpcError(RpcError::TransactionSubmissionError(SubmissionError::Temporary(_)))
+    ));
+
+    // The note stays reserved, so that the same result can be submitted again
+    assert!(client.is_note_reserved(note.id()));
+
+    client.submit_transaction(tx_result).await.unwrap();
+    assert!(!client.is_note_reserved(note.id()));
+    assert!(client.get_input_note(note.id()).await.unwrap().unwrap().is_processing());
 }
 
 #[tokio::test]
@@ -766,29 +3278,203 @@ async fn note_without_asset() {
         ))
     ));
 
-    let error = TransactionRequestBuilder::new()
-        .build_pay_to_id(
-            PaymentNoteDescription::new(vec![], faucet.id(), wallet.id()),
-            NoteType::Public,
-            client.rng(),
-        )
-        .unwrap_err();
+    let error = TransactionRequestBuilder::new()
+        .build_pay_to_id(
+            PaymentNoteDescription::new(vec![], faucet.id(), wallet.id()),
+            NoteType::Public,
+            client.rng(),
+        )
+        .unwrap_err();
+
+    assert!(matches!(error, TransactionRequestError::P2IDNoteWithoutAsset));
+
+    let error = TransactionRequestBuilder::new()
+        .build_pay_to_id(
+            PaymentNoteDescription::new(
+                vec![Asset::Fungible(FungibleAsset::new(faucet.id(), 0).unwrap())],
+                faucet.id(),
+                wallet.id(),
+            ),
+            NoteType::Public,
+            client.rng(),
+        )
+        .unwrap_err();
+
+    assert!(matches!(error, TransactionRequestError::P2IDNoteWithoutAsset));
+}
+
+#[tokio::test]
+async fn note_value() {
+    let (mut client, _rpc_api, _keystore) = create_test_client().await;
+
+    let faucet_1 = AccountId::try_from(ACCOUNT_ID_PUBLIC_FUNGIBLE_FAUCET_1).unwrap();
+    let faucet_2 = AccountId::try_from(ACCOUNT_ID_PUBLIC_FUNGIBLE_FAUCET_2).unwrap();
+    let nft_faucet = AccountId::try_from(ACCOUNT_ID_PUBLIC_NON_FUNGIBLE_FAUCET).unwrap();
+    let target = AccountId::try_from(ACCOUNT_ID_REGULAR_PRIVATE_ACCOUNT_UPDATABLE_CODE).unwrap();
+
+    let nft = NonFungibleAsset::new(
+        &NonFungibleAssetDetails::new(nft_faucet.prefix(), vec![1, 2, 3]).unwrap(),
+    )
+    .unwrap();
+    let assets = NoteAssets::new(vec![
+        FungibleAsset::new(faucet_1, 100).unwrap().into(),
+        FungibleAsset::new(faucet_2, 30).unwrap().into(),
+        nft.into(),
+    ])
+    .unwrap();
+
+    let serial_num = client.rng().draw_word();
+    let recipient = utils::build_p2id_recipient(target, serial_num).unwrap();
+    let metadata = NoteMetadata::new(
+        faucet_1,
+        NoteType::Private,
+        NoteTag::from_account_id(target),
+        NoteExecutionHint::always(),
+        ZERO,
+    )
+    .unwrap();
+    let note = Note::new(assets, metadata, recipient);
+
+    client.test_store().upsert_input_notes(&[note.clone().into()]).await.unwrap();
+
+    // Assets from `faucet_2` are not part of the valuation table so they are worth nothing
+    let valuation = BTreeMap::from([(faucet_1, 3)]);
+    assert_eq!(client.note_value(note.id(), &valuation, 50).await.unwrap(), 100 * 3 + 50);
+
+    let valuation = BTreeMap::from([(faucet_1, 3), (faucet_2, 10)]);
+    assert_eq!(client.note_value(note.id(), &valuation, 0).await.unwrap(), 100 * 3 + 30 * 10);
+
+    // Values saturate instead of overflowing
+    let valuation = BTreeMap::from([(faucet_1, u64::MAX)]);
+    assert_eq!(client.note_value(note.id(), &valuation, 0).await.unwrap(), u64::MAX);
+
+    let untracked_recipient =
+        utils::build_p2id_recipient(target, client.rng().draw_word()).unwrap();
+    let untracked_note_id =
+        Note::new(note.assets().clone(), *note.metadata(), untracked_recipient).id();
+    assert!(matches!(
+        client.note_value(untracked_note_id, &valuation, 0).await,
+        Err(ClientError::NoteNotTracked(note_id)) if note_id == untracked_note_id
+    ));
+}
+
+#[tokio::test]
+async fn note_consumption_status_reports_failure_reason() {
+    let (mut client, _rpc_api, keystore) = create_test_client().await;
+
+    let (wallet, _seed) = insert_new_wallet(&mut client, AccountStorageMode::Private, &keystore)
+        .await
+        .unwrap();
+    let (faucet, _seed) =
+        insert_new_fungible_faucet(&mut client, AccountStorageMode::Private, &keystore)
+            .await
+            .unwrap();
+
+    client.sync_state().await.unwrap();
+
+    // P2ID note targeting an account other than the wallet
+    let other_account_id =
+        AccountId::try_from(ACCOUNT_ID_REGULAR_PUBLIC_ACCOUNT_IMMUTABLE_CODE).unwrap();
+    let note = create_p2id_note(
+        faucet.id(),
+        other_account_id,
+        vec![FungibleAsset::new(faucet.id(), 10).unwrap().into()],
+        NoteType::Private,
+        ZERO,
+        client.rng(),
+    )
+    .unwrap();
+
+    let note_screener = NoteScreener::new(client.test_store().clone(), None, Arc::default());
+    let NoteConsumptionStatus::Unconsumable(reason) =
+        note_screener.check_consumption_status(&wallet, &note).await.unwrap()
+    else {
+        panic!("P2ID note with a different target shouldn't be consumable");
+    };
+
+    assert_eq!(
+        reason,
+        format!(
+            "note {} targets account {other_account_id}, not account {}",
+            note.id(),
+            wallet.id()
+        )
+    );
+
+    // P2IDE note targeting the wallet that is still timelocked
+    let timelock_height = client.get_sync_height().await.unwrap() + 10;
+    let note = create_p2ide_note(
+        faucet.id(),
+        wallet.id(),
+        vec![FungibleAsset::new(faucet.id(), 10).unwrap().into()],
+        None,
+        Some(timelock_height),
+        NoteType::Private,
+        ZERO,
+        client.rng(),
+    )
+    .unwrap();
+
+    let NoteConsumptionStatus::Unconsumable(reason) =
+        note_screener.check_consumption_status(&wallet, &note).await.unwrap()
+    else {
+        panic!("timelocked P2IDE note shouldn't be consumable");
+    };
+
+    assert!(
+        reason.contains(&format!("is timelocked until block {timelock_height}")),
+        "unexpected failure reason: {reason}"
+    );
+}
+
+#[tokio::test]
+async fn note_screener_uses_registered_known_script_handler() {
+    // The script always fails, so the note can only be relevant through the handler
+    let note_script = ScriptBuilder::new(true)
+        .compile_note_script("begin push.0 assert.err=\"custom note\" end")
+        .unwrap();
+
+    let (builder, _rpc_api, keystore) = create_test_client_builder().await;
+    let mut client = builder
+        .register_known_script(note_script.root(), |note, account| {
+            let target = [account.id().suffix(), account.id().prefix().as_felt()];
+            (note.inputs().values() == target).then_some(NoteRelevance::After(42))
+        })
+        .build()
+        .await
+        .unwrap();
+    client.ensure_genesis_in_place().await.unwrap();
+
+    let (wallet, _seed) = insert_new_wallet(&mut client, AccountStorageMode::Private, &keystore)
+        .await
+        .unwrap();
+    let (other_wallet, _seed) =
+        insert_new_wallet(&mut client, AccountStorageMode::Private, &keystore)
+            .await
+            .unwrap();
 
-    assert!(matches!(error, TransactionRequestError::P2IDNoteWithoutAsset));
+    let inputs =
+        NoteInputs::new(vec![wallet.id().suffix(), wallet.id().prefix().as_felt()]).unwrap();
+    let recipient = NoteRecipient::new(client.rng().draw_word(), note_script, inputs);
+    let metadata = NoteMetadata::new(
+        other_wallet.id(),
+        NoteType::Private,
+        NoteTag::from_account_id(wallet.id()),
+        NoteExecutionHint::always(),
+        ZERO,
+    )
+    .unwrap();
+    let note = Note::new(NoteAssets::new(vec![]).unwrap(), metadata, recipient);
 
-    let error = TransactionRequestBuilder::new()
-        .build_pay_to_id(
-            PaymentNoteDescription::new(
-                vec![Asset::Fungible(FungibleAsset::new(faucet.id(), 0).unwrap())],
-                faucet.id(),
-                wallet.id(),
-            ),
-            NoteType::Public,
-            client.rng(),
-        )
-        .unwrap_err();
+    // A screener without the handler can't determine the note's relevance
+    let note_screener = NoteScreener::new(client.test_store().clone(), None, Arc::default());
+    assert!(note_screener.check_relevance(&note).await.unwrap().is_empty());
 
-    assert!(matches!(error, TransactionRequestError::P2IDNoteWithoutAsset));
+    // The client screens notes with the handlers registered through its builder
+    assert_eq!(
+        client.get_note_consumability(note.into()).await.unwrap(),
+        vec![(wallet.id(), NoteRelevance::After(42))]
+    );
 }
 
 #[tokio::test]
@@ -827,6 +3513,308 @@ async fn execute_program() {
     assert_eq!(output_stack, expected_stack);
 }
 
+#[tokio::test]
+async fn execute_script() {
+    let (mut client, _, keystore) = create_test_client().await;
+    let _ = client.sync_state().await.unwrap();
+
+    let (wallet, _seed) = insert_new_wallet(&mut client, AccountStorageMode::Private, &keystore)
+        .await
+        .unwrap();
+
+    let output_stack = client
+        .execute_script(
+            wallet.id(),
+            "begin push.1 push.2 add swap drop end",
+            AdviceInputs::default(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(output_stack[0], Felt::new(3));
+
+    // Compilation errors are returned before executing anything
+    let error = client
+        .execute_script(
+            wallet.id(),
+            "begin push.1 unknown_instruction end",
+            AdviceInputs::default(),
+        )
+        .await
+        .unwrap_err();
+    assert!(matches!(error, ClientError::ScriptBuilderError(_)));
+}
+
+#[tokio::test]
+async fn transaction_cycle_budget() {
+    let (mut client, _, keystore) = create_test_client().await;
+    let _ = client.sync_state().await.unwrap();
+
+    let (wallet, _seed) = insert_new_wallet(&mut client, AccountStorageMode::Private, &keystore)
+        .await
+        .unwrap();
+
+    let code = "
+        begin
+            repeat.1000
+                push.1 drop
+            end
+        end
+        ";
+    let tx_script = client.script_builder().compile_tx_script(code).unwrap();
+
+    // The script alone takes more cycles than the budget
+    let tx_request = TransactionRequestBuilder::new()
+        .custom_script(tx_script.clone())
+        .with_max_cycles(1000)
+        .build()
+        .unwrap();
+    let error = client.new_transaction(wallet.id(), tx_request).await.unwrap_err();
+    let ClientError::TransactionRequestError(TransactionRequestError::CycleBudgetExceeded(
+        cycles,
+        max_cycles,
+    )) = error
+    else {
+        panic!("expected the cycle budget to be exceeded, got: {error}");
+    };
+    assert_eq!(max_cycles, 1000);
+    assert!(cycles > max_cycles);
+
+    // Executing the same transaction takes the same number of cycles, so a budget that covers
+    // them is enough
+    let tx_request = TransactionRequestBuilder::new()
+        .custom_script(tx_script)
+        .with_max_cycles(cycles)
+        .build()
+        .unwrap();
+    let tx_result = client.new_transaction(wallet.id(), tx_request).await.unwrap();
+    assert_eq!(tx_result.executed_transaction().measurements().total_cycles(), cycles as usize);
+    client.submit_transaction(tx_result).await.unwrap();
+}
+
+#[tokio::test]
+async fn backfill_account_history() {
+    let (mut client, rpc_api, keystore) = create_test_client().await;
+
+    let (wallet, faucet) =
+        setup_wallet_and_faucet(&mut client, AccountStorageMode::Private, &keystore).await;
+    mint_note(&mut client, wallet.id(), faucet.id(), NoteType::Private).await;
+    mint_note(&mut client, wallet.id(), faucet.id(), NoteType::Private).await;
+
+    let faucet_transactions: Vec<_> = client
+        .get_transactions(TransactionFilter::All)
+        .await
+        .unwrap()
+        .into_iter()
+        .filter(|tx| tx.details.account_id == faucet.id())
+        .collect();
+    assert_eq!(faucet_transactions.len(), 2);
+
+    // Add the faucet's latest state to a new client, which doesn't know its history
+    let initial_faucet_commitment = faucet.commitment();
+    let (builder, ..) = create_test_client_builder().await;
+    let mut new_client = builder.rpc(Arc::new(rpc_api.clone())).build().await.unwrap();
+    let faucet: Account = client.get_account(faucet.id()).await.unwrap().unwrap().into();
+    new_client.add_account(&faucet, None, false).await.unwrap();
+    new_client.sync_state().await.unwrap();
+    assert!(new_client.get_transactions(TransactionFilter::All).await.unwrap().is_empty());
+
+    let progress = Rc::new(RefCell::new(vec![]));
+    let progress_clone = progress.clone();
+    let backfilled = new_client
+        .backfill_account_history_with_progress(faucet.id(), BlockNumber::GENESIS, move |p| {
+            progress_clone.borrow_mut().push(p);
+        })
+        .await
+        .unwrap();
+    assert_eq!(backfilled.len(), 2);
+
+    let sync_height = new_client.get_sync_height().await.unwrap();
+    let last_progress = *progress.borrow().last().unwrap();
+    assert_eq!(last_progress.current_block, sync_height);
+    assert_eq!(last_progress.transactions_found, 2);
+
+    let history = new_client.get_transactions(TransactionFilter::All).await.unwrap();
+    assert_eq!(history.len(), 2);
+    for transaction in &faucet_transactions {
+        let backfilled = history.iter().find(|tx| tx.id == transaction.id).unwrap();
+        assert_eq!(backfilled.status, transaction.status);
+        // The new client doesn't know the state the faucet was created from
+        let expected_init_state = transaction
+            .details
+            .init_account_state
+            .filter(|state| *state != initial_faucet_commitment);
+        assert_eq!(backfilled.details.init_account_state, expected_init_state);
+        assert_eq!(backfilled.details.final_account_state, transaction.details.final_account_state);
+        assert_eq!(
+            backfilled.details.output_notes.commitment(),
+            transaction.details.output_notes.commitment()
+        );
+    }
+
+    // Transactions that are already stored aren't backfilled again
+    assert!(
+        new_client
+            .backfill_account_history(faucet.id(), BlockNumber::GENESIS)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+
+    // The state the wallet was created from is known while the client tracks it
+    let wallet_record = client.get_account(wallet.id()).await.unwrap().unwrap();
+    new_client
+        .add_account(wallet_record.account(), wallet_record.seed().copied(), false)
+        .await
+        .unwrap();
+
+    let note_id = client.get_input_notes(NoteFilter::Committed).await.unwrap()[0].id();
+    let tx_request = TransactionRequestBuilder::new().build_consume_notes(vec![note_id]).unwrap();
+    execute_tx_and_sync(&mut client, wallet.id(), tx_request).await;
+    new_client.sync_state().await.unwrap();
+
+    let backfilled = new_client
+        .backfill_account_history(wallet.id(), BlockNumber::GENESIS)
+        .await
+        .unwrap();
+    assert_eq!(backfilled.len(), 1);
+    let history = new_client.get_transactions(TransactionFilter::Ids(backfilled)).await.unwrap();
+    assert_eq!(
+        history[0].details.init_account_state,
+        Some(wallet_record.account().commitment())
+    );
+}
+
+#[tokio::test]
+async fn new_transaction_at_reference_block() {
+    let (mut client, rpc_api, keystore) = create_test_client().await;
+
+    let (wallet, faucet) =
+        setup_wallet_and_faucet(&mut client, AccountStorageMode::Private, &keystore).await;
+    let note = mint_note(&mut client, wallet.id(), faucet.id(), NoteType::Private).await;
+    let note_block = client
+        .get_input_note(note.id())
+        .await
+        .unwrap()
+        .unwrap()
+        .inclusion_proof()
+        .unwrap()
+        .location()
+        .block_num();
+
+    rpc_api.advance_blocks(2);
+    client.sync_state().await.unwrap();
+    let sync_height = client.get_sync_height().await.unwrap();
+    assert!(note_block < sync_height);
+
+    let tx_request = TransactionRequestBuilder::new().build_consume_notes(vec![note.id()]).unwrap();
+
+    // The reference block can't be after the sync height or before the note was committed
+    assert!(matches!(
+        client
+            .new_transaction_at(wallet.id(), tx_request.clone(), sync_height + 1)
+            .await,
+        Err(ClientError::InvalidReferenceBlock(..))
+    ));
+    assert!(matches!(
+        client
+            .new_transaction_at(wallet.id(), tx_request.clone(), (note_block.as_u32() - 1).into())
+            .await,
+        Err(ClientError::InvalidReferenceBlock(..))
+    ));
+
+    let tx_result = client.new_transaction_at(wallet.id(), tx_request, note_block).await.unwrap();
+    assert_eq!(tx_result.block_num(), note_block);
+    assert_eq!(tx_result.executed_transaction().block_header().block_num(), note_block);
+}
+
+#[tokio::test]
+async fn validate_request_advice() {
+    let (mut client, _rpc_api, keystore) = create_test_client().await;
+
+    let (wallet, faucet) =
+        setup_wallet_and_faucet(&mut client, AccountStorageMode::Private, &keystore).await;
+    let note = mint_note(&mut client, wallet.id(), faucet.id(), NoteType::Private).await;
+
+    let wallet_commitment =
+        client.get_account(wallet.id()).await.unwrap().unwrap().account().commitment();
+    let account_request = TransactionRequestBuilder::new()
+        .extend_advice_map([(wallet_commitment, vec![Felt::new(1)])])
+        .build()
+        .unwrap();
+    let note_request = TransactionRequestBuilder::new()
+        .extend_advice_map([(note.id().inner(), vec![Felt::new(2)])])
+        .build()
+        .unwrap();
+
+    client.validate_request_advice(&account_request).await.unwrap();
+    client.validate_request_advice(&note_request).await.unwrap();
+
+    // Consuming the note changes the state of the wallet once the transaction is synced
+    consume_notes(&mut client, wallet.id(), &[note]).await;
+
+    assert!(matches!(
+        client.validate_request_advice(&account_request).await,
+        Err(ClientError::TransactionRequestError(
+            TransactionRequestError::StaleAdviceMapEntry(key, _)
+        )) if key == wallet_commitment
+    ));
+    assert!(matches!(
+        client.validate_request_advice(&note_request).await,
+        Err(ClientError::TransactionRequestError(
+            TransactionRequestError::StaleAdviceMapEntry(..)
+        ))
+    ));
+
+    // Keys that don't reference any known state are ignored
+    let unrelated_request = TransactionRequestBuilder::new()
+        .extend_advice_map([(Digest::from([Felt::new(7); 4]), vec![Felt::new(3)])])
+        .build()
+        .unwrap();
+    client.validate_request_advice(&unrelated_request).await.unwrap();
+}
+
+#[tokio::test]
+async fn consumption_script_for() {
+    let (mut client, rpc_api, keystore) = create_test_client().await;
+    let (wallet, faucet) =
+        setup_wallet_and_faucet(&mut client, AccountStorageMode::Private, &keystore).await;
+    let note = mint_note(&mut client, wallet.id(), faucet.id(), NoteType::Private).await;
+
+    let script = client.consumption_script_for(note.id(), wallet.id()).await.unwrap();
+
+    // The P2ID note script calls the basic wallet procedures itself, so the transaction script is
+    // the one used for any request that only consumes notes
+    let wallet: Account = client.get_account(wallet.id()).await.unwrap().unwrap().into();
+    let expected_script = TransactionRequestBuilder::new()
+        .build_consume_notes(vec![note.id()])
+        .unwrap()
+        .build_transaction_script(&AccountInterface::from(&wallet), client.in_debug_mode())
+        .unwrap();
+    assert_eq!(script.root(), expected_script.root());
+    assert_eq!(
+        script.root(),
+        TransactionScript::compile("begin nop end", TransactionKernel::assembler())
+            .unwrap()
+            .root()
+    );
+
+    // Nothing was executed, so the note is still unconsumed
+    assert!(!client.get_input_note(note.id()).await.unwrap().unwrap().is_consumed());
+
+    let untracked_note_id = rpc_api.get_available_notes()[0].id();
+    assert!(matches!(
+        client.consumption_script_for(untracked_note_id, wallet.id()).await,
+        Err(ClientError::NoteNotTracked(note_id)) if note_id == untracked_note_id
+    ));
+
+    let untracked_account_id =
+        AccountId::try_from(ACCOUNT_ID_REGULAR_PUBLIC_ACCOUNT_IMMUTABLE_CODE).unwrap();
+    assert!(matches!(
+        client.consumption_script_for(note.id(), untracked_account_id).await,
+        Err(ClientError::AccountDataNotFound(account_id)) if account_id == untracked_account_id
+    ));
+}
+
 #[tokio::test]
 async fn real_note_roundtrip() {
     let (mut client, _, keystore) = create_test_client().await;
@@ -905,6 +3893,64 @@ async fn added_notes() {
     assert!(notes.is_empty());
 }
 
+#[tokio::test]
+async fn get_account_state_delta() {
+    let (mut client, rpc_api, keystore) = create_test_client().await;
+
+    let (wallet, faucet) =
+        setup_wallet_and_faucet(&mut client, AccountStorageMode::Public, &keystore).await;
+
+    // The first transaction deploys the wallet, so the node only keeps deltas from then on
+    mint_and_consume(&mut client, wallet.id(), faucet.id(), NoteType::Public).await;
+    let from_block = client.get_sync_height().await.unwrap();
+    let mut local_account: Account = client.get_account(wallet.id()).await.unwrap().unwrap().into();
+
+    mint_and_consume(&mut client, wallet.id(), faucet.id(), NoteType::Public).await;
+
+    let account_delta = rpc_api
+        .get_account_state_delta(wallet.id(), from_block, rpc_api.get_chain_tip_block_num())
+        .await
+        .unwrap();
+    assert_eq!(account_delta.nonce_delta(), ONE);
+
+    local_account.apply_delta(&account_delta).unwrap();
+    let updated_account: Account = client.get_account(wallet.id()).await.unwrap().unwrap().into();
+    assert_eq!(local_account.commitment(), updated_account.commitment());
+    assert_account_has_single_asset(&client, wallet.id(), faucet.id(), MINT_AMOUNT * 2).await;
+}
+
+#[tokio::test]
+async fn get_updated_public_account_applies_delta() {
+    let (mut client, rpc_api, keystore) = create_test_client().await;
+
+    let (wallet, faucet) =
+        setup_wallet_and_faucet(&mut client, AccountStorageMode::Public, &keystore).await;
+
+    mint_and_consume(&mut client, wallet.id(), faucet.id(), NoteType::Public).await;
+    let from_block = client.get_sync_height().await.unwrap();
+    let local_account: Account = client.get_account(wallet.id()).await.unwrap().unwrap().into();
+
+    mint_and_consume(&mut client, wallet.id(), faucet.id(), NoteType::Public).await;
+    let full_account: Account = client.get_account(wallet.id()).await.unwrap().unwrap().into();
+
+    let delta_requests = rpc_api.account_state_delta_request_count();
+    let updated_account = rpc_api
+        .get_updated_public_account(
+            local_account,
+            from_block,
+            rpc_api.get_chain_tip_block_num(),
+            full_account.commitment(),
+        )
+        .await
+        .unwrap()
+        .unwrap();
+
+    // The mock only returns private account details, so the account can only be rebuilt from the
+    // delta
+    assert_eq!(rpc_api.account_state_delta_request_count(), delta_requests + 1);
+    assert_eq!(updated_account, full_account);
+}
+
 #[tokio::test]
 async fn p2id_transfer() {
     let (mut client, _, authenticator) = create_test_client().await;
@@ -1248,11 +4294,118 @@ async fn p2ide_transfer_consumed_by_sender() {
         panic!("Error: Account should have a fungible asset");
     }
 
-    let regular_account: Account = client.get_account(to_account_id).await.unwrap().unwrap().into();
-    assert_eq!(regular_account.vault().assets().count(), 0);
+    let regular_account: Account = client.get_account(to_account_id).await.unwrap().unwrap().into();
+    assert_eq!(regular_account.vault().assets().count(), 0);
+
+    // Check that the target can't consume the note anymore
+    assert_note_cannot_be_consumed_twice(&mut client, to_account_id, notes[0].id()).await;
+}
+
+#[tokio::test]
+async fn p2ide_recall_height() {
+    let (mut client, rpc_api, keystore) = create_test_client().await;
+
+    let (sender, faucet) =
+        setup_wallet_and_faucet(&mut client, AccountStorageMode::Private, &keystore).await;
+    mint_and_consume(&mut client, sender.id(), faucet.id(), NoteType::Private).await;
+
+    let target_account_id = AccountId::try_from(ACCOUNT_ID_REGULAR).unwrap();
+    let asset = FungibleAsset::new(faucet.id(), TRANSFER_AMOUNT).unwrap();
+    let recall_height = client.get_sync_height().await.unwrap() + RECALL_HEIGHT_DELTA;
+
+    let tx_request = TransactionRequestBuilder::new()
+        .build_pay_to_id(
+            PaymentNoteDescription::new(vec![asset.into()], sender.id(), target_account_id)
+                .with_reclaim_height(recall_height),
+            NoteType::Private,
+            client.rng(),
+        )
+        .unwrap();
+    let p2ide_note = tx_request.expected_output_own_notes().pop().unwrap();
+    execute_tx_and_sync(&mut client, sender.id(), tx_request).await;
+
+    assert_eq!(client.p2ide_recall_height(p2ide_note.id()).await.unwrap(), Some(recall_height));
+
+    // P2ID notes can't be recalled
+    let tx_request = TransactionRequestBuilder::new()
+        .build_pay_to_id(
+            PaymentNoteDescription::new(vec![asset.into()], sender.id(), target_account_id),
+            NoteType::Private,
+            client.rng(),
+        )
+        .unwrap();
+    let p2id_note_id = tx_request.expected_output_own_notes().pop().unwrap().id();
+    execute_tx_and_sync(&mut client, sender.id(), tx_request).await;
+
+    assert_eq!(client.p2ide_recall_height(p2id_note_id).await.unwrap(), None);
+
+    // A client that doesn't track the sender can't recall the note
+    let (builder, ..) = create_test_client_builder().await;
+    let mut other_client = builder.rpc(Arc::new(rpc_api.clone())).build().await.unwrap();
+    other_client.ensure_genesis_in_place().await.unwrap();
+
+    let note_file = client
+        .get_output_note(p2ide_note.id())
+        .await
+        .unwrap()
+        .unwrap()
+        .into_note_file(&NoteExportType::NoteWithProof)
+        .unwrap();
+    other_client.import_note(note_file).await.unwrap();
+
+    assert_eq!(other_client.p2ide_recall_height(p2ide_note.id()).await.unwrap(), None);
+}
+
+#[tokio::test]
+async fn recall_p2ide_note() {
+    let (mut client, rpc_api, keystore) = create_test_client().await;
+
+    let (sender, faucet) =
+        setup_wallet_and_faucet(&mut client, AccountStorageMode::Private, &keystore).await;
+    mint_and_consume(&mut client, sender.id(), faucet.id(), NoteType::Private).await;
+
+    let target_account_id = AccountId::try_from(ACCOUNT_ID_REGULAR).unwrap();
+    let asset = FungibleAsset::new(faucet.id(), TRANSFER_AMOUNT).unwrap();
+    let recall_height = client.get_sync_height().await.unwrap() + RECALL_HEIGHT_DELTA;
+
+    let tx_request = TransactionRequestBuilder::new()
+        .build_pay_to_id(
+            PaymentNoteDescription::new(vec![asset.into()], sender.id(), target_account_id)
+                .with_reclaim_height(recall_height),
+            NoteType::Private,
+            client.rng(),
+        )
+        .unwrap();
+    let note_id = tx_request.expected_output_own_notes().pop().unwrap().id();
+    execute_tx_and_sync(&mut client, sender.id(), tx_request).await;
+    assert_account_has_single_asset(
+        &client,
+        sender.id(),
+        faucet.id(),
+        MINT_AMOUNT - TRANSFER_AMOUNT,
+    )
+    .await;
+
+    // The note can't be recalled before its recall height
+    let note = client.get_input_note(note_id).await.unwrap().unwrap();
+    let sync_height = client.get_sync_height().await.unwrap();
+    assert!(matches!(
+        TransactionRequestBuilder::new().build_recall_p2ide(&note, sync_height).unwrap_err(),
+        TransactionRequestError::P2IDENoteNotRecallable(id, height, _)
+            if id == note_id && height == recall_height
+    ));
+
+    rpc_api.advance_blocks(RECALL_HEIGHT_DELTA);
+    client.sync_state().await.unwrap();
+
+    let note = client.get_input_note(note_id).await.unwrap().unwrap();
+    let sync_height = client.get_sync_height().await.unwrap();
+    let tx_request =
+        TransactionRequestBuilder::new().build_recall_p2ide(&note, sync_height).unwrap();
+    assert!(tx_request.expected_output_own_notes().is_empty());
 
-    // Check that the target can't consume the note anymore
-    assert_note_cannot_be_consumed_twice(&mut client, to_account_id, notes[0].id()).await;
+    execute_tx_and_sync(&mut client, sender.id(), tx_request).await;
+    assert_account_has_single_asset(&client, sender.id(), faucet.id(), MINT_AMOUNT).await;
 }
 
 #[tokio::test]
@@ -1389,6 +4542,111 @@ async fn get_consumable_notes() {
     assert_eq!(to_account_relevance, NoteRelevance::Now);
 }
 
+#[tokio::test]
+async fn watch_consumable_notes() {
+    #[derive(Default)]
+    struct FlagWaker(AtomicBool);
+
+    impl std::task::Wake for FlagWaker {
+        fn wake(self: Arc<Self>) {
+            self.0.store(true, Ordering::SeqCst);
+        }
+    }
+
+    fn poll_notes(stream: &mut ConsumableNoteStream) -> Poll<Option<InputNoteRecord>> {
+        let mut cx = core::task::Context::from_waker(Waker::noop());
+        Pin::new(stream).poll_next(&mut cx)
+    }
+
+    let (mut client, rpc_api, authenticator) = create_test_client().await;
+
+    let (first_regular_account, second_regular_account, faucet_account_header) =
+        setup_two_wallets_and_faucet(&mut client, AccountStorageMode::Private, &authenticator)
+            .await;
+
+    let mut first_account_notes = client.watch_consumable_notes(first_regular_account.id());
+    let mut second_account_notes = client.watch_consumable_notes(second_regular_account.id());
+    assert!(poll_notes(&mut first_account_notes).is_pending());
+
+    // The sync that commits the minted note delivers it to the stream of its target account
+    let note = mint_note(
+        &mut client,
+        first_regular_account.id(),
+        faucet_account_header.id(),
+        NoteType::Private,
+    )
+    .await;
+
+    match poll_notes(&mut first_account_notes) {
+        Poll::Ready(Some(note_record)) => {
+            assert_eq!(note_record.id(), note.id());
+            assert!(matches!(note_record.state(), InputNoteState::Committed(_)));
+        },
+        _ => panic!("the minted note should be yielded by the stream"),
+    }
+    assert!(poll_notes(&mut first_account_notes).is_pending());
+    assert!(poll_notes(&mut second_account_notes).is_pending());
+
+    // Later syncs don't yield the note again
+    client.sync_state().await.unwrap();
+    assert!(poll_notes(&mut first_account_notes).is_pending());
+
+    // Private notes minted by another client aren't received in the sync, but are yielded once
+    // they're imported
+    let (builder, _, other_keystore) = create_test_client_builder().await;
+    let mut other_client = builder.rpc(Arc::new(rpc_api.clone())).build().await.unwrap();
+    other_client.sync_state().await.unwrap();
+    let (other_faucet, ..) =
+        insert_new_fungible_faucet(&mut other_client, AccountStorageMode::Private, &other_keystore)
+            .await
+            .unwrap();
+    let tx_request = TransactionRequestBuilder::new()
+        .build_mint_fungible_asset(
+            FungibleAsset::new(other_faucet.id(), MINT_AMOUNT).unwrap(),
+            first_regular_account.id(),
+            NoteType::Private,
+            other_client.rng(),
+        )
+        .unwrap();
+    let imported_note_id = tx_request.expected_output_own_notes().pop().unwrap().id();
+    execute_tx_and_sync(&mut other_client, other_faucet.id(), tx_request).await;
+
+    rpc_api.advance_blocks(1);
+    client.sync_state().await.unwrap();
+    assert!(poll_notes(&mut first_account_notes).is_pending());
+
+    let imported_note: Note = other_client
+        .get_output_note(imported_note_id)
+        .await
+        .unwrap()
+        .unwrap()
+        .try_into()
+        .unwrap();
+    let inclusion_proof = match rpc_api.get_notes_by_id(&[imported_note_id]).await.unwrap().pop() {
+        Some(FetchedNote::Private(_, _, inclusion_proof)) => inclusion_proof,
+        _ => panic!("the minted note should be committed as a private note"),
+    };
+    client
+        .import_note(NoteFile::NoteWithProof(imported_note, inclusion_proof))
+        .await
+        .unwrap();
+    match poll_notes(&mut first_account_notes) {
+        Poll::Ready(Some(note_record)) => assert_eq!(note_record.id(), imported_note_id),
+        _ => panic!("the imported note should be yielded by the stream"),
+    }
+    assert!(poll_notes(&mut second_account_notes).is_pending());
+
+    // Once the client is dropped, the task waiting on the stream is woken up and the stream ends
+    let flag_waker = Arc::new(FlagWaker::default());
+    let waker = Waker::from(flag_waker.clone());
+    let mut cx = core::task::Context::from_waker(&waker);
+    assert!(Pin::new(&mut first_account_notes).poll_next(&mut cx).is_pending());
+
+    drop(client);
+    assert!(flag_waker.0.load(Ordering::SeqCst));
+    assert!(matches!(poll_notes(&mut first_account_notes), Poll::Ready(None)));
+}
+
 #[tokio::test]
 async fn get_output_notes() {
     let (mut client, _, authenticator) = create_test_client().await;
@@ -1442,6 +4700,67 @@ async fn get_output_notes() {
     assert!(client.get_input_note(output_note_id).await.unwrap().is_none());
 }
 
+#[tokio::test]
+async fn export_output_note_for_recipient() {
+    let (mut sender_client, rpc_api, sender_keystore) = create_test_client().await;
+
+    let (builder, _, recipient_keystore) = create_test_client_builder().await;
+    let mut recipient_client = builder.rpc(Arc::new(rpc_api.clone())).build().await.unwrap();
+    recipient_client.ensure_genesis_in_place().await.unwrap();
+
+    let (sender, faucet) =
+        setup_wallet_and_faucet(&mut sender_client, AccountStorageMode::Private, &sender_keystore)
+            .await;
+    mint_and_consume(&mut sender_client, sender.id(), faucet.id(), NoteType::Private).await;
+
+    let (recipient, _) =
+        insert_new_wallet(&mut recipient_client, AccountStorageMode::Private, &recipient_keystore)
+            .await
+            .unwrap();
+
+    // Send a private P2ID note to the account tracked by the recipient client
+    let asset = FungibleAsset::new(faucet.id(), TRANSFER_AMOUNT).unwrap();
+    let tx_request = TransactionRequestBuilder::new()
+        .build_pay_to_id(
+            PaymentNoteDescription::new(vec![asset.into()], sender.id(), recipient.id()),
+            NoteType::Private,
+            sender_client.rng(),
+        )
+        .unwrap();
+    let note_id = tx_request.expected_output_own_notes().pop().unwrap().id();
+    execute_tx_and_sync(&mut sender_client, sender.id(), tx_request).await;
+
+    let note_bytes = sender_client.export_output_note_for_recipient(note_id).await.unwrap();
+
+    // The recipient imports the note details and consumes the note once it syncs
+    recipient_client
+        .import_note(NoteFile::read_from_bytes(&note_bytes).unwrap())
+        .await
+        .unwrap();
+    recipient_client.sync_state().await.unwrap();
+
+    let tx_request = TransactionRequestBuilder::new().build_consume_notes(vec![note_id]).unwrap();
+    execute_tx_and_sync(&mut recipient_client, recipient.id(), tx_request).await;
+    assert_account_has_single_asset(
+        &recipient_client,
+        recipient.id(),
+        faucet.id(),
+        TRANSFER_AMOUNT,
+    )
+    .await;
+
+    // Public notes can't be exported, as their details are already available on-chain
+    let public_note =
+        mint_note(&mut sender_client, sender.id(), faucet.id(), NoteType::Public).await;
+    assert!(matches!(
+        sender_client
+            .export_output_note_for_recipient(public_note.id())
+            .await
+            .unwrap_err(),
+        ClientError::NoteIsPublic(_)
+    ));
+}
+
 #[tokio::test]
 async fn account_rollback() {
     let (builder, rpc_api, authenticator) = create_test_client_builder().await;
@@ -1655,6 +4974,51 @@ async fn missing_recipient_digest() {
     }
 }
 
+#[tokio::test]
+async fn missing_recipient_digest_detected_before_execution() {
+    let (mut client, _, keystore) = create_test_client().await;
+
+    let (faucet, _seed) =
+        insert_new_fungible_faucet(&mut client, AccountStorageMode::Private, &keystore)
+            .await
+            .unwrap();
+
+    let dummy_recipient = NoteRecipient::new(
+        Word::default(),
+        WellKnownNote::P2ID.script(),
+        NoteInputs::new(vec![]).unwrap(),
+    );
+    let dummy_recipient_digest = dummy_recipient.digest();
+
+    let tx_request = TransactionRequestBuilder::new()
+        .expected_output_recipients(vec![dummy_recipient])
+        .build_mint_fungible_asset(
+            FungibleAsset::new(faucet.id(), 5u64).unwrap(),
+            AccountId::try_from(ACCOUNT_ID_PRIVATE_SENDER).unwrap(),
+            NoteType::Public,
+            client.rng(),
+        )
+        .unwrap();
+
+    // The mismatch is detected while validating the request, before the transaction is executed
+    let error = client.validate_request(faucet.id(), &tx_request).await.unwrap_err();
+    assert!(matches!(
+        error,
+        ClientError::MissingOutputRecipients(digests) if digests == vec![dummy_recipient_digest]
+    ));
+
+    // Requests whose expected notes are all created by the script are valid
+    let tx_request = TransactionRequestBuilder::new()
+        .build_mint_fungible_asset(
+            FungibleAsset::new(faucet.id(), 5u64).unwrap(),
+            AccountId::try_from(ACCOUNT_ID_PRIVATE_SENDER).unwrap(),
+            NoteType::Public,
+            client.rng(),
+        )
+        .unwrap();
+    client.validate_request(faucet.id(), &tx_request).await.unwrap();
+}
+
 #[tokio::test]
 async fn input_note_checks() {
     let (mut client, _, authenticator) = create_test_client().await;
@@ -1721,6 +5085,74 @@ async fn input_note_checks() {
     ));
 }
 
+#[tokio::test]
+async fn input_note_missing_from_store_fails_early() {
+    let (mut client, rpc_api, keystore) = create_test_client().await;
+    let wallet = insert_new_wallet(&mut client, AccountStorageMode::Private, &keystore)
+        .await
+        .unwrap()
+        .0;
+
+    // The request provides one note and references another one by ID, which the client doesn't
+    // track
+    let provided_note = rpc_api.get_available_notes()[0].note().unwrap().clone();
+    let phantom_note_id = NoteId::from(Digest::from([Felt::new(7); 4]));
+    let tx_request = TransactionRequestBuilder::new()
+        .unauthenticated_input_notes([(provided_note.clone(), None)])
+        .authenticated_input_notes([(phantom_note_id, None)])
+        .build()
+        .unwrap();
+
+    let error = client.new_transaction(wallet.id(), tx_request).await.unwrap_err();
+    assert!(matches!(
+        error,
+        ClientError::TransactionRequestError(
+            TransactionRequestError::MissingAuthenticatedInputNote(note_id)
+        ) if note_id == phantom_note_id
+    ));
+
+    // The request was rejected before the provided note was added to the store
+    assert!(client.get_input_note(provided_note.id()).await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn build_swap_offer() {
+    let (mut client, _rpc_api, keystore) = create_test_client().await;
+
+    let (wallet, faucet) =
+        setup_wallet_and_faucet(&mut client, AccountStorageMode::Private, &keystore).await;
+
+    let offered: Asset = FungibleAsset::new(faucet.id(), 100).unwrap().into();
+    let requested: Asset =
+        FungibleAsset::new(AccountId::try_from(ACCOUNT_ID_PUBLIC_FUNGIBLE_FAUCET_2).unwrap(), 50)
+            .unwrap()
+            .into();
+
+    let tx_request = client
+        .build_swap_offer(wallet.id(), offered, requested, NoteType::Public)
+        .await
+        .unwrap();
+
+    let [swap_note] = tx_request.expected_output_own_notes().try_into().unwrap();
+    assert_eq!(swap_note.script().root(), WellKnownNote::SWAP.script_root());
+    assert_eq!(swap_note.metadata().sender(), wallet.id());
+    assert_eq!(swap_note.metadata().note_type(), NoteType::Public);
+    assert_eq!(swap_note.assets().iter().collect::<Vec<_>>(), vec![&offered]);
+
+    let [(payback_note, payback_tag)] =
+        tx_request.expected_future_notes().collect::<Vec<_>>().try_into().unwrap();
+    assert_eq!(*payback_tag, NoteTag::from_account_id(wallet.id()));
+    assert_eq!(payback_note.assets().iter().collect::<Vec<_>>(), vec![&requested]);
+
+    // Offers can only be built for tracked accounts
+    let untracked_account_id =
+        AccountId::try_from(ACCOUNT_ID_REGULAR_PUBLIC_ACCOUNT_IMMUTABLE_CODE).unwrap();
+    assert!(matches!(
+        client.build_swap_offer(untracked_account_id, offered, requested, NoteType::Public).await,
+        Err(ClientError::AccountDataNotFound(account_id)) if account_id == untracked_account_id
+    ));
+}
+
 #[tokio::test]
 async fn swap_chain_test() {
     // This test simulates a "swap chain" scenario with multiple wallets and fungible assets.
@@ -1803,3 +5235,234 @@ async fn swap_chain_test() {
         1
     );
 }
+
+#[tokio::test]
+async fn build_private_foreign_account() {
+    let (mut client, _, keystore) = create_test_client().await;
+
+    let (private_account, _) =
+        insert_new_wallet(&mut client, AccountStorageMode::Private, &keystore)
+            .await
+            .unwrap();
+    let (public_account, _) = insert_new_wallet(&mut client, AccountStorageMode::Public, &keystore)
+        .await
+        .unwrap();
+
+    // The foreign account is built from the locally tracked state
+    let foreign_account = client.build_private_foreign_account(private_account.id()).await.unwrap();
+    let ForeignAccount::Private(partial_account) = &foreign_account else {
+        panic!("expected a private foreign account");
+    };
+    assert_eq!(partial_account.id(), private_account.id());
+    assert_eq!(partial_account.nonce(), private_account.nonce());
+    assert_eq!(partial_account.code().commitment(), private_account.code().commitment());
+
+    let tx_request = TransactionRequestBuilder::new()
+        .foreign_accounts([foreign_account.clone()])
+        .build()
+        .unwrap();
+    assert!(tx_request.foreign_accounts().contains(&foreign_account));
+
+    // Public accounts can't be used as private foreign accounts
+    assert!(matches!(
+        client.build_private_foreign_account(public_account.id()).await.unwrap_err(),
+        ClientError::TransactionRequestError(TransactionRequestError::InvalidForeignAccountId(_))
+    ));
+
+    // Untracked accounts can't be used either
+    let untracked_account_id =
+        AccountId::try_from(ACCOUNT_ID_REGULAR_PRIVATE_ACCOUNT_UPDATABLE_CODE).unwrap();
+    assert!(matches!(
+        client.build_private_foreign_account(untracked_account_id).await.unwrap_err(),
+        ClientError::AccountDataNotFound(_)
+    ));
+}
+
+#[tokio::test]
+async fn rebase_transaction() {
+    let (mut client, _rpc_api, keystore) = create_test_client().await;
+
+    let (wallet, faucet) =
+        setup_wallet_and_faucet(&mut client, AccountStorageMode::Private, &keystore).await;
+    let first_note = mint_note(&mut client, wallet.id(), faucet.id(), NoteType::Private).await;
+    let second_note = mint_note(&mut client, wallet.id(), faucet.id(), NoteType::Private).await;
+
+    // Both requests are built before the account consumes the first note
+    let first_request = TransactionRequestBuilder::new()
+        .build_consume_notes(vec![first_note.id()])
+        .unwrap();
+    let second_request = TransactionRequestBuilder::new()
+        .unauthenticated_input_notes(vec![(second_note.note().clone(), None)])
+        .build()
+        .unwrap();
+
+    consume_notes(&mut client, wallet.id(), core::slice::from_ref(&first_note)).await;
+
+    let err = client.rebase_transaction(first_request, wallet.id()).await.unwrap_err();
+    assert!(matches!(
+        err,
+        ClientError::TransactionRequestError(TransactionRequestError::InputNoteAlreadyConsumed(
+            note_id
+        )) if note_id == first_note.id()
+    ));
+
+    // The committed note is now consumed with its inclusion proof
+    let rebased_request = client.rebase_transaction(second_request, wallet.id()).await.unwrap();
+    assert!(rebased_request.unauthenticated_input_notes().is_empty());
+    assert_eq!(
+        rebased_request.authenticated_input_note_ids().collect::<Vec<_>>(),
+        vec![second_note.id()]
+    );
+
+    execute_tx_and_sync(&mut client, wallet.id(), rebased_request).await;
+    assert_account_has_single_asset(&client, wallet.id(), faucet.id(), 2 * MINT_AMOUNT).await;
+}
+
+#[tokio::test]
+async fn failed_state_sync_leaves_store_unchanged() {
+    let (builder, _rpc_api, keystore) = create_test_client_builder().await;
+    let store = Arc::new(SqliteStore::new(create_test_store_path()).await.unwrap());
+    let mut client = builder.store(store.clone()).build().await.unwrap();
+    client.ensure_genesis_in_place().await.unwrap();
+
+    let (wallet, faucet) =
+        setup_wallet_and_faucet(&mut client, AccountStorageMode::Private, &keystore).await;
+
+    // Mint a note, which is committed in the block the next sync applies
+    let tx_request = TransactionRequestBuilder::new()
+        .build_mint_fungible_asset(
+            FungibleAsset::new(faucet.id(), MINT_AMOUNT).unwrap(),
+            wallet.id(),
+            NoteType::Private,
+            client.rng(),
+        )
+        .unwrap();
+    let note_id = tx_request.expected_output_own_notes().pop().unwrap().id();
+    execute_tx(&mut client, faucet.id(), tx_request).await;
+
+    let sync_height = client.get_sync_height().await.unwrap();
+    for failpoint in [SyncFailpoint::AfterBlockHeaders, SyncFailpoint::AfterNoteUpdates] {
+        store.set_sync_failpoint(Some(failpoint));
+        client.sync_state().await.unwrap_err();
+
+        assert_eq!(client.get_sync_height().await.unwrap(), sync_height);
+        assert!(
+            client
+                .get_input_note(note_id)
+                .await
+                .unwrap()
+                .is_none_or(|note| !note.is_committed())
+        );
+    }
+
+    store.set_sync_failpoint(None);
+    client.sync_state().await.unwrap();
+
+    assert!(client.get_sync_height().await.unwrap() > sync_height);
+    assert!(client.get_input_note(note_id).await.unwrap().unwrap().is_committed());
+}
+
+#[tokio::test]
+async fn prove_transactions() {
+    let (mut client, _rpc_api, keystore) = create_test_client().await;
+
+    let (wallet, faucet) =
+        setup_wallet_and_faucet(&mut client, AccountStorageMode::Private, &keystore).await;
+
+    let mut transactions = Vec::new();
+    for amount in [1, 2, 3] {
+        let tx_request = TransactionRequestBuilder::new()
+            .build_mint_fungible_asset(
+                FungibleAsset::new(faucet.id(), amount).unwrap(),
+                wallet.id(),
+                NoteType::Private,
+                client.rng(),
+            )
+            .unwrap();
+        let tx_result = client.new_transaction(faucet.id(), tx_request).await.unwrap();
+        transactions.push(tx_result.executed_transaction().clone());
+    }
+    let transaction_ids: Vec<TransactionId> =
+        transactions.iter().map(ExecutedTransaction::id).collect();
+
+    let proven_transaction_ids: Vec<TransactionId> = client
+        .prove_transactions(transactions)
+        .await
+        .into_iter()
+        .map(|result| result.unwrap().id())
+        .collect();
+
+    assert_eq!(proven_transaction_ids, transaction_ids);
+}
+
+#[tokio::test]
+async fn node_status() {
+    let (client, rpc_api, _) = create_test_client().await;
+
+    let status = client.node_status().await.unwrap();
+    let (genesis_header, _) = rpc_api
+        .get_block_header_by_number(Some(BlockNumber::GENESIS), false)
+        .await
+        .unwrap();
+    assert_eq!(status.chain_tip, rpc_api.get_chain_tip_block_num());
+    assert_eq!(status.genesis_commitment, genesis_header.commitment());
+
+    rpc_api.advance_blocks(2);
+
+    let new_status = client.node_status().await.unwrap();
+    assert_eq!(new_status.chain_tip, status.chain_tip + 2);
+    assert_eq!(new_status.genesis_commitment, status.genesis_commitment);
+}
+
+#[tokio::test]
+async fn sync_state_emits_span() {
+    let span_recorder = SpanRecorder::default();
+    let _guard = tracing::subscriber::set_default(
+        tracing_subscriber::registry().with(span_recorder.clone()),
+    );
+
+    let (mut client, rpc_api, _) = create_test_client().await;
+    rpc_api.advance_blocks(2);
+
+    let sync_from = client.get_sync_height().await.unwrap();
+    let sync_summary = client.sync_state().await.unwrap();
+
+    let fields = span_recorder.span_fields("sync_state").unwrap();
+    assert_eq!(fields["block_from"], sync_from.as_u32().to_string());
+    assert_eq!(fields["block_to"], sync_summary.block_num.as_u32().to_string());
+    assert_eq!(fields.len(), 2);
+}
+
+#[tokio::test]
+async fn sync_fails_on_genesis_mismatch() {
+    let (builder, _rpc_api, _) = create_test_client_builder().await;
+    let store = Arc::new(SqliteStore::new(create_test_store_path()).await.unwrap());
+    let mut client = builder.store(store.clone()).build().await.unwrap();
+    client.sync_state().await.unwrap();
+    let genesis = client.ensure_genesis_in_place().await.unwrap();
+
+    // A node on a different chain, whose genesis block includes an account
+    let account = Account::mock(
+        ACCOUNT_ID_REGULAR_PUBLIC_ACCOUNT_IMMUTABLE_CODE,
+        Felt::new(1),
+        RpoFalcon512::new(PublicKey::new(EMPTY_WORD)),
+        TransactionKernel::testing_assembler(),
+    );
+    let other_rpc_api = MockRpcApi::new();
+    *other_rpc_api.mock_chain.write() = MockChain::with_accounts(&[account]).unwrap();
+    let (other_genesis, _) = other_rpc_api
+        .get_block_header_by_number(Some(BlockNumber::GENESIS), false)
+        .await
+        .unwrap();
+
+    let (builder, ..) = create_test_client_builder().await;
+    let mut other_client = builder.rpc(Arc::new(other_rpc_api)).store(store).build().await.unwrap();
+    let sync_height = other_client.get_sync_height().await.unwrap();
+
+    assert!(matches!(
+        other_client.sync_state().await.unwrap_err(),
+        ClientError::GenesisMismatch(expected, found)
+            if expected == genesis.commitment() && found == other_genesis.commitment()
+    ));
+    assert_eq!(other_client.get_sync_height().await.unwrap(), sync_height);
+}
@@ -67,36 +67,40 @@ use alloc::{
     sync::Arc,
     vec::Vec,
 };
-use core::fmt::{self};
+use core::{
+    fmt::{self},
+    task::Poll,
+};
 
 use miden_objects::{
-    AssetError, Digest, Felt,
+    AssetError, Digest, Felt, MIN_PROOF_SECURITY_LEVEL,
     account::{Account, AccountCode, AccountDelta, AccountId},
     assembly::DefaultSourceManager,
-    asset::{Asset, NonFungibleAsset},
+    asset::{Asset, FungibleAsset, NonFungibleAsset},
     block::BlockNumber,
-    note::{Note, NoteDetails, NoteId, NoteRecipient, NoteTag},
+    note::{Note, NoteDetails, NoteId, NoteRecipient, NoteTag, NoteType, Nullifier, PartialNote},
     transaction::{AccountInputs, TransactionArgs},
 };
 use miden_tx::{
-    NoteAccountExecution, NoteConsumptionChecker, TransactionExecutor,
+    NoteAccountExecution, NoteConsumptionChecker, TransactionExecutor, TransactionVerifier,
     utils::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable},
 };
-use tracing::info;
+use tracing::{Span, field::Empty, info, instrument};
 
 use super::Client;
 use crate::{
     ClientError,
     note::{NoteScreener, NoteUpdateTracker},
-    rpc::domain::account::AccountProof,
+    rpc::{RpcError, domain::account::AccountProof},
     store::{
-        InputNoteRecord, InputNoteState, NoteFilter, OutputNoteRecord, StoreError,
+        InputNoteRecord, InputNoteState, NoteFilter, NoteRecordError, OutputNoteRecord, StoreError,
         TransactionFilter, data_store::ClientDataStore, input_note_states::ExpectedNoteState,
     },
     sync::NoteTagRecord,
 };
 
 mod request;
+mod signing;
 
 // RE-EXPORTS
 // ================================================================================================
@@ -120,6 +124,11 @@ pub use request::{
     ForeignAccount, NoteArgs, PaymentNoteDescription, SwapTransactionData, TransactionRequest,
     TransactionRequestBuilder, TransactionRequestError, TransactionScriptTemplate,
 };
+pub use signing::UnsignedTransaction;
+use signing::{ExternalSignature, SignatureRequestRecorder};
+
+/// Maximum number of proofs that [`Client::prove_transactions`] waits on at the same time.
+const MAX_CONCURRENT_PROOFS: usize = 4;
 
 // TRANSACTION RESULT
 // ================================================================================================
@@ -256,13 +265,35 @@ impl TransactionRecord {
     }
 }
 
+impl Serializable for TransactionRecord {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.id.write_into(target);
+        self.details.write_into(target);
+        self.script.write_into(target);
+        self.status.write_into(target);
+    }
+}
+
+impl Deserializable for TransactionRecord {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let id = TransactionId::read_from(source)?;
+        let details = TransactionDetails::read_from(source)?;
+        let script = Option::<TransactionScript>::read_from(source)?;
+        let status = TransactionStatus::read_from(source)?;
+
+        Ok(Self { id, details, script, status })
+    }
+}
+
 /// Describes the details associated with a transaction.
 #[derive(Debug, Clone)]
 pub struct TransactionDetails {
     /// ID of the account that executed the transaction.
     pub account_id: AccountId,
-    /// Initial state of the account before the transaction was executed.
-    pub init_account_state: Digest,
+    /// Initial state of the account before the transaction was executed, or `None` if it isn't
+    /// known. This is only the case for transactions that created their account and were
+    /// retrieved from the network.
+    pub init_account_state: Option<Digest>,
     /// Final state of the account after the transaction was executed.
     pub final_account_state: Digest,
     /// Nullifiers of the input notes consumed in the transaction.
@@ -293,7 +324,7 @@ impl Serializable for TransactionDetails {
 impl Deserializable for TransactionDetails {
     fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
         let account_id = AccountId::read_from(source)?;
-        let init_account_state = Digest::read_from(source)?;
+        let init_account_state = Option::<Digest>::read_from(source)?;
         let final_account_state = Digest::read_from(source)?;
         let input_note_nullifiers = Vec::<Digest>::read_from(source)?;
         let output_notes = OutputNotes::read_from(source)?;
@@ -392,6 +423,33 @@ impl fmt::Display for TransactionStatus {
     }
 }
 
+impl Serializable for TransactionStatus {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        match self {
+            TransactionStatus::Pending => target.write_u8(0),
+            TransactionStatus::Committed(block_num) => {
+                target.write_u8(1);
+                block_num.write_into(target);
+            },
+            TransactionStatus::Discarded(cause) => {
+                target.write_u8(2);
+                cause.write_into(target);
+            },
+        }
+    }
+}
+
+impl Deserializable for TransactionStatus {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        match source.read_u8()? {
+            0 => Ok(TransactionStatus::Pending),
+            1 => Ok(TransactionStatus::Committed(BlockNumber::read_from(source)?)),
+            2 => Ok(TransactionStatus::Discarded(DiscardCause::read_from(source)?)),
+            _ => Err(DeserializationError::InvalidValue("Invalid transaction status".to_string())),
+        }
+    }
+}
+
 // TRANSACTION STORE UPDATE
 // ================================================================================================
 
@@ -462,6 +520,36 @@ impl TransactionStoreUpdate {
     }
 }
 
+// TRANSACTION DETAIL
+// ================================================================================================
+
+/// A tracked transaction along with the notes it consumed and created, as returned by
+/// [`Client::get_transaction_detail`].
+#[derive(Debug, Clone)]
+pub struct TransactionDetail {
+    /// The record of the transaction.
+    pub record: TransactionRecord,
+    /// Tracked input notes consumed by the transaction.
+    pub input_notes: Vec<InputNoteRecord>,
+    /// Tracked output notes created by the transaction.
+    pub output_notes: Vec<OutputNoteRecord>,
+}
+
+// RESUBMISSION SUMMARY
+// ================================================================================================
+
+/// Contains the outcome of resubmitting the expired transactions with
+/// [`Client::resubmit_expired`].
+#[derive(Debug, Default, PartialEq)]
+pub struct ResubmissionSummary {
+    /// IDs of the expired transactions that were resubmitted. Transaction IDs don't commit to the
+    /// reference or expiration blocks, so the re-executed transaction keeps the ID of the expired
+    /// one and its record replaces the expired record.
+    pub resubmitted: Vec<TransactionId>,
+    /// IDs of the expired transactions that were skipped because they can no longer be executed.
+    pub skipped: Vec<TransactionId>,
+}
+
 /// Transaction management methods
 impl Client {
     // TRANSACTION DATA RETRIEVAL
@@ -475,6 +563,108 @@ impl Client {
         self.store.get_transactions(filter).await.map_err(Into::into)
     }
 
+    /// Retrieves the tracked transaction with the specified ID along with the input notes it
+    /// consumed and the output notes it created.
+    ///
+    /// Only notes tracked by the client are included. Returns `None` if the transaction isn't
+    /// tracked.
+    pub async fn get_transaction_detail(
+        &self,
+        transaction_id: TransactionId,
+    ) -> Result<Option<TransactionDetail>, ClientError> {
+        let Some(record) = self
+            .store
+            .get_transactions(TransactionFilter::Ids(vec![transaction_id]))
+            .await?
+            .pop()
+        else {
+            return Ok(None);
+        };
+
+        let (input_notes, output_notes) = self.store.get_transaction_notes(transaction_id).await?;
+
+        Ok(Some(TransactionDetail { record, input_notes, output_notes }))
+    }
+
+    /// Retrieves the tracked transactions that expired without being committed, that is, whose
+    /// expiration block is at or before the current sync height. These transactions either need
+    /// to be re-executed and re-submitted or can be cleaned up.
+    pub async fn get_expired_transactions(&self) -> Result<Vec<TransactionRecord>, ClientError> {
+        let sync_height = self.store.get_sync_height().await?;
+        self.get_transactions(TransactionFilter::Expired(sync_height)).await
+    }
+
+    /// Returns whether the transaction with the specified ID was already submitted to the
+    /// network by this client, that is, whether it's tracked with a pending or committed status.
+    pub async fn is_transaction_submitted(
+        &self,
+        transaction_id: TransactionId,
+    ) -> Result<bool, ClientError> {
+        let transaction = self
+            .store
+            .get_transactions(TransactionFilter::Ids(vec![transaction_id]))
+            .await?
+            .pop();
+
+        Ok(transaction.is_some_and(|transaction| {
+            matches!(
+                transaction.status,
+                TransactionStatus::Pending | TransactionStatus::Committed(_)
+            )
+        }))
+    }
+
+    /// Syncs the client state until the transaction with the specified ID is committed and returns
+    /// its updated record.
+    ///
+    /// The client is synced at most `max_syncs` times until the transaction's status becomes
+    /// [`TransactionStatus::Committed`], awaiting the future returned by `delay` between syncs.
+    /// This allows waiting with the timer of the async runtime in use (for example,
+    /// `|| tokio::time::sleep(interval)`) without blocking the thread.
+    ///
+    /// The wait is bounded by the number of syncs rather than by wall-clock time, as the client
+    /// doesn't have access to a timer in `no_std` environments. A time-based timeout can be
+    /// approximated by picking `max_syncs` as the timeout divided by the delay's interval.
+    ///
+    /// # Errors
+    ///
+    /// - Returns [`ClientError::TransactionNotCommitted`] if the transaction isn't committed after
+    ///   `max_syncs` syncs.
+    /// - Returns [`ClientError::TransactionDiscarded`] if the transaction gets discarded while
+    ///   waiting.
+    pub async fn await_transaction_commit<D: Future<Output = ()>>(
+        &mut self,
+        transaction_id: TransactionId,
+        max_syncs: u32,
+        mut delay: impl FnMut() -> D,
+    ) -> Result<TransactionRecord, ClientError> {
+        for sync_index in 0..max_syncs {
+            if sync_index > 0 {
+                delay().await;
+            }
+
+            self.sync_state().await?;
+
+            let transaction = self
+                .store
+                .get_transactions(TransactionFilter::Ids(vec![transaction_id]))
+                .await?
+                .pop();
+
+            if let Some(transaction) = transaction {
+                match transaction.status {
+                    TransactionStatus::Committed(_) => return Ok(transaction),
+                    TransactionStatus::Discarded(_) => {
+                        return Err(ClientError::TransactionDiscarded(transaction_id));
+                    },
+                    TransactionStatus::Pending => {},
+                }
+            }
+        }
+
+        Err(ClientError::TransactionNotCommitted(transaction_id))
+    }
+
     // TRANSACTION
     // --------------------------------------------------------------------------------------------
 
@@ -491,24 +681,267 @@ impl Client {
     ///   notes are not a subset of executor's output notes.
     /// - Returns a [`ClientError::TransactionExecutorError`] if the execution fails.
     /// - Returns a [`ClientError::TransactionRequestError`] if the request is invalid.
+    /// - Returns [`TransactionRequestError::InputNoteReserved`] if an input note is reserved by
+    ///   another transaction built by the client.
+    /// - Returns [`TransactionRequestError::MissingAuthenticatedInputNote`] if an input note that
+    ///   isn't provided in the request isn't tracked by the client either. This is checked before
+    ///   validating or executing the request.
+    ///
+    /// The input notes of the executed transaction are reserved for it until it's submitted (see
+    /// [`Client::release_reserved_notes`]).
+    #[instrument(
+        skip_all,
+        fields(
+            account_id = %account_id,
+            input_notes = transaction_request.input_notes().len(),
+            output_notes = Empty,
+        )
+    )]
     pub async fn new_transaction(
         &mut self,
         account_id: AccountId,
         transaction_request: TransactionRequest,
     ) -> Result<TransactionResult, ClientError> {
-        // Validates the transaction request before executing
-        self.validate_request(account_id, &transaction_request).await?;
+        self.check_reserved_notes(&transaction_request)?;
+
+        let authenticator = self.authenticator.clone();
+        let tx_result = self
+            .execute_transaction_request(
+                account_id,
+                transaction_request,
+                authenticator.as_deref(),
+                None,
+            )
+            .await?;
+        self.reserve_notes(&tx_result);
+
+        Span::current().record("output_notes", tx_result.created_notes().num_notes());
+        Ok(tx_result)
+    }
+
+    /// Creates and executes a transaction specified by the request against the specified account,
+    /// as done by [`Client::new_transaction`], but using `reference_block` as the transaction's
+    /// reference block instead of the current sync height.
+    ///
+    /// This allows transactions to be built against a fixed anchor, for example so that several
+    /// parties can prove transactions that reference a common block.
+    ///
+    /// # Errors
+    ///
+    /// - Returns [`ClientError::InvalidReferenceBlock`] if `reference_block` is after the sync
+    ///   height, is more than the client's maximum block number delta behind it, isn't stored by
+    ///   the client (only the sync height, the genesis block and blocks with relevant notes are
+    ///   kept), is before the block in which an authenticated input note was committed, or differs
+    ///   from the block at which foreign account data is retrieved.
+    /// - Returns any of the errors returned by [`Client::new_transaction`].
+    pub async fn new_transaction_at(
+        &mut self,
+        account_id: AccountId,
+        transaction_request: TransactionRequest,
+        reference_block: BlockNumber,
+    ) -> Result<TransactionResult, ClientError> {
+        self.check_reserved_notes(&transaction_request)?;
+
+        let authenticator = self.authenticator.clone();
+        let tx_result = self
+            .execute_transaction_request(
+                account_id,
+                transaction_request,
+                authenticator.as_deref(),
+                Some(reference_block),
+            )
+            .await?;
+        self.reserve_notes(&tx_result);
+
+        Ok(tx_result)
+    }
+
+    /// Releases the input notes reserved for the transaction with the specified ID, so that they
+    /// can be consumed by other transactions.
+    ///
+    /// Notes are reserved when a transaction consuming them is built, and released once it's
+    /// submitted or permanently rejected by the node. This only needs to be called for
+    /// transactions that are discarded without being submitted, including those whose submission
+    /// failed before reaching the node or with a retryable error (see
+    /// [`SubmissionError::is_retryable`](crate::rpc::SubmissionError::is_retryable)).
+    ///
+    /// Reservations are only kept in memory and aren't persisted to the store. They are lost when
+    /// the client is dropped, so a transaction built by another client instance (or before a
+    /// restart) doesn't prevent its input notes from being consumed.
+    pub fn release_reserved_notes(&mut self, transaction_id: TransactionId) {
+        self.reserved_notes.retain(|_, reserving_tx| *reserving_tx != transaction_id);
+    }
+
+    /// Returns whether the note with the specified ID is reserved by a transaction that was built
+    /// but not submitted yet.
+    pub fn is_note_reserved(&self, note_id: NoteId) -> bool {
+        self.reserved_notes.contains_key(&note_id)
+    }
 
-        // Ensure authenticated notes have their inclusion proofs (a.k.a they're in a committed
-        // state)
+    /// Checks that none of the input notes of the request are reserved by another transaction.
+    fn check_reserved_notes(
+        &self,
+        transaction_request: &TransactionRequest,
+    ) -> Result<(), TransactionRequestError> {
+        for note_id in transaction_request.get_input_note_ids() {
+            if let Some(transaction_id) = self.reserved_notes.get(&note_id) {
+                return Err(TransactionRequestError::InputNoteReserved(note_id, *transaction_id));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Reserves the input notes of the executed transaction for it.
+    fn reserve_notes(&mut self, tx_result: &TransactionResult) {
+        let executed_transaction = tx_result.executed_transaction();
+        for note in executed_transaction.input_notes().iter() {
+            self.reserved_notes.insert(note.id(), executed_transaction.id());
+        }
+    }
+
+    /// Executes the transaction specified by the request against the specified account up to the
+    /// point where it needs to be signed, and returns the data that needs to be signed.
+    ///
+    /// This allows the signature to be produced by a signer that isn't available to the client,
+    /// such as an offline machine holding the account's secret key. Once the
+    /// [`UnsignedTransaction::message`] is signed, the transaction can be completed with
+    /// [`Client::import_signature`]. The transaction isn't applied to the local state.
+    ///
+    /// # Errors
+    ///
+    /// - Returns [`ClientError::TransactionSignatureNotRequired`] if the transaction executes
+    ///   without requesting a signature.
+    /// - Returns a [`ClientError::TransactionExecutorError`] if the transaction fails to execute
+    ///   before reaching the signature.
+    pub async fn export_unsigned_transaction(
+        &mut self,
+        account_id: AccountId,
+        transaction_request: TransactionRequest,
+    ) -> Result<UnsignedTransaction, ClientError> {
+        let recorder = SignatureRequestRecorder::default();
+        let result = self
+            .execute_transaction_request(
+                account_id,
+                transaction_request.clone(),
+                Some(&recorder),
+                None,
+            )
+            .await;
+
+        match (recorder.signature_request(), result) {
+            (Some((pub_key, message)), _) => {
+                Ok(UnsignedTransaction::new(account_id, transaction_request, pub_key, message))
+            },
+            (None, Ok(_)) => Err(ClientError::TransactionSignatureNotRequired),
+            (None, Err(err)) => Err(err),
+        }
+    }
+
+    /// Executes the transaction described by the [`UnsignedTransaction`] again, using the provided
+    /// `signature` over its [`UnsignedTransaction::message`].
+    ///
+    /// The returned [`TransactionResult`] can be proven and submitted with
+    /// [`Client::submit_transaction`]. If the account or its notes changed since the transaction
+    /// was exported, the message to be signed may differ and the execution fails, in which case
+    /// the transaction needs to be exported and signed again.
+    pub async fn import_signature(
+        &mut self,
+        unsigned_transaction: UnsignedTransaction,
+        signature: Vec<Felt>,
+    ) -> Result<TransactionResult, ClientError> {
+        self.check_reserved_notes(unsigned_transaction.transaction_request())?;
+
+        let authenticator = ExternalSignature::new(&unsigned_transaction, signature);
+
+        let tx_result = self
+            .execute_transaction_request(
+                unsigned_transaction.account_id(),
+                unsigned_transaction.transaction_request().clone(),
+                Some(&authenticator),
+                None,
+            )
+            .await?;
+        self.reserve_notes(&tx_result);
+
+        Ok(tx_result)
+    }
+
+    /// Deserializes a [`TransactionRequest`] from `bytes` and executes it against the specified
+    /// account, as done by [`Client::new_transaction`].
+    ///
+    /// This allows requests to be built on a different machine (or shipped pre-built by a service)
+    /// and executed by the client that tracks the account.
+    ///
+    /// # Errors
+    ///
+    /// - Returns [`ClientError::DataDeserializationError`] if `bytes` isn't a serialized
+    ///   [`TransactionRequest`].
+    /// - Returns [`TransactionRequestError::InvalidForeignAccountId`] if a public foreign account
+    ///   has a private account ID, or a private foreign account has a public account ID.
+    /// - Returns any of the errors returned by [`Client::new_transaction`].
+    pub async fn execute_serialized_request(
+        &mut self,
+        account_id: AccountId,
+        bytes: &[u8],
+    ) -> Result<TransactionResult, ClientError> {
+        let transaction_request = TransactionRequest::read_from_bytes(bytes)?;
+
+        // Deserialization bypasses the checks done when building the foreign accounts, so make
+        // sure that they can be resolved at execution time
+        for foreign_account in transaction_request.foreign_accounts() {
+            let foreign_account_id = foreign_account.account_id();
+            let is_resolvable = match foreign_account {
+                ForeignAccount::Public(..) => foreign_account_id.is_public(),
+                ForeignAccount::Private(_) => !foreign_account_id.is_public(),
+            };
+
+            if !is_resolvable {
+                return Err(
+                    TransactionRequestError::InvalidForeignAccountId(foreign_account_id).into()
+                );
+            }
+        }
+
+        self.new_transaction(account_id, transaction_request).await
+    }
+
+    /// Executes the transaction specified by the request, using the provided `authenticator` to
+    /// sign it. If `reference_block` isn't provided, the transaction references the current sync
+    /// height (or the block at which foreign account data is retrieved).
+    async fn execute_transaction_request(
+        &mut self,
+        account_id: AccountId,
+        transaction_request: TransactionRequest,
+        authenticator: Option<&dyn TransactionAuthenticator>,
+        reference_block: Option<BlockNumber>,
+    ) -> Result<TransactionResult, ClientError> {
+        // Input notes that aren't provided in the request are only referenced by their ID, so
+        // they must be tracked by the client. This is checked first so that a missing note isn't
+        // reported as an unrelated validation or execution error.
         let authenticated_input_note_ids: Vec<NoteId> =
             transaction_request.authenticated_input_note_ids().collect::<Vec<_>>();
 
         let authenticated_note_records = self
             .store
-            .get_input_notes(NoteFilter::List(authenticated_input_note_ids))
+            .get_input_notes(NoteFilter::List(authenticated_input_note_ids.clone()))
             .await?;
 
+        if let Some(note_id) = authenticated_input_note_ids
+            .into_iter()
+            .find(|note_id| !authenticated_note_records.iter().any(|note| note.id() == *note_id))
+        {
+            return Err(TransactionRequestError::MissingAuthenticatedInputNote(note_id).into());
+        }
+
+        if let Some(reference_block) = reference_block {
+            self.validate_reference_block(reference_block, &authenticated_note_records)
+                .await?;
+        }
+
+        // Validates the transaction request before executing
+        self.validate_request(account_id, &transaction_request).await?;
+
         // If tx request contains unauthenticated_input_notes we should insert them
         let unauthenticated_input_notes = transaction_request
             .unauthenticated_input_notes()
@@ -539,6 +972,7 @@ impl Client {
             self.retrieve_foreign_account_inputs(foreign_accounts).await?;
 
         let ignore_invalid_notes = transaction_request.ignore_invalid_input_notes();
+        let max_cycles = transaction_request.max_cycles();
 
         let data_store = ClientDataStore::new(self.store.clone());
         for fpi_account in &foreign_account_inputs {
@@ -547,10 +981,15 @@ impl Client {
 
         let tx_args = transaction_request.into_transaction_args(tx_script, foreign_account_inputs);
 
-        let block_num = if let Some(block_num) = fpi_block_num {
-            block_num
-        } else {
-            self.store.get_sync_height().await?
+        let block_num = match (reference_block, fpi_block_num) {
+            (Some(reference_block), Some(fpi_block_num)) if reference_block != fpi_block_num => {
+                return Err(ClientError::InvalidReferenceBlock(
+                    reference_block,
+                    format!("foreign account data was retrieved at block {fpi_block_num}"),
+                ));
+            },
+            (Some(block_num), _) | (None, Some(block_num)) => block_num,
+            (None, None) => self.store.get_sync_height().await?,
         };
 
         // TODO: Refactor this to get account code only?
@@ -568,24 +1007,46 @@ impl Client {
         }
 
         // Execute the transaction and get the witness
-        let executed_transaction = self
-            .build_executor(&data_store)?
-            .execute_transaction(
-                account_id,
-                block_num,
-                notes,
-                tx_args,
-                Arc::new(DefaultSourceManager::default()), // TODO: Use the correct source manager
-            )
-            .await?;
+        let executed_transaction =
+            TransactionExecutor::with_options(&data_store, authenticator, self.exec_options)?
+                .execute_transaction(
+                    account_id,
+                    block_num,
+                    notes,
+                    tx_args,
+                    Arc::new(DefaultSourceManager::default()), /* TODO: Use the correct source
+                                                                * manager */
+                )
+                .await?;
 
         validate_executed_transaction(&executed_transaction, &output_recipients)?;
 
+        if let Some(max_cycles) = max_cycles {
+            let cycles = u32::try_from(executed_transaction.measurements().total_cycles())
+                .unwrap_or(u32::MAX);
+            if cycles > max_cycles {
+                return Err(TransactionRequestError::CycleBudgetExceeded(cycles, max_cycles).into());
+            }
+        }
+
         TransactionResult::new(executed_transaction, future_notes)
     }
 
     /// Proves the specified transaction using a local prover, submits it to the network, and saves
     /// the transaction into the local database for tracking.
+    ///
+    /// # Errors
+    ///
+    /// If the node doesn't accept the transaction, a [`ClientError::RpcError`] is returned with the
+    /// [`SubmissionError`](crate::rpc::SubmissionError) describing why. The transaction is only
+    /// saved once the node accepts it, so a rejected transaction never stays pending in the local
+    /// database: retryable errors can be handled by submitting the same result again, while
+    /// permanent ones require executing a new transaction.
+    ///
+    /// If the client was built with
+    /// [`ClientBuilder::with_verify_before_submit`](crate::builder::ClientBuilder::with_verify_before_submit),
+    /// the proof is verified locally first, and a [`ClientError::TransactionVerificationError`] is
+    /// returned without contacting the node if it's invalid.
     pub async fn submit_transaction(
         &mut self,
         tx_result: TransactionResult,
@@ -595,14 +1056,172 @@ impl Client {
 
     /// Proves the specified transaction using the provided prover, submits it to the network, and
     /// saves the transaction into the local database for tracking.
+    ///
+    /// If the transaction was already submitted (see [`Client::is_transaction_submitted`]), this
+    /// is a no-op so that the transaction doesn't get applied to the local state twice.
+    #[instrument(
+        name = "submit_transaction",
+        skip_all,
+        fields(transaction_id = %tx_result.executed_transaction().id())
+    )]
     pub async fn submit_transaction_with_prover(
         &mut self,
         tx_result: TransactionResult,
         tx_prover: Arc<dyn TransactionProver>,
     ) -> Result<(), ClientError> {
-        let proven_transaction = self.prove_transaction(&tx_result, tx_prover).await?;
-        let block_num = self.submit_proven_transaction(proven_transaction).await?;
-        self.apply_transaction(block_num, tx_result).await
+        let transaction_id = tx_result.executed_transaction().id();
+        if self.is_transaction_submitted(transaction_id).await? {
+            info!("Transaction {transaction_id} was already submitted, skipping submission.");
+            return Ok(());
+        }
+
+        let submission = match self.prove_transaction(&tx_result, tx_prover).await {
+            Ok(proven_transaction) => self.submit_proven_transaction(proven_transaction).await,
+            Err(err) => Err(err),
+        };
+
+        // Once submitted, the notes are tracked as being processed by the transaction. If the node
+        // rejected the transaction permanently, they can be consumed by other transactions again.
+        // Otherwise, they stay reserved so that the same result can be submitted again.
+        match submission {
+            Ok(submission_height) => self.apply_transaction(submission_height, tx_result).await,
+            Err(err) => {
+                if let ClientError::RpcError(RpcError::TransactionSubmissionError(submission_err)) =
+                    &err
+                    && !submission_err.is_retryable()
+                {
+                    self.release_reserved_notes(transaction_id);
+                }
+                Err(err)
+            },
+        }
+    }
+
+    /// Re-executes the expired transactions (see [`Client::get_expired_transactions`]) against the
+    /// current sync height and submits them to the network.
+    ///
+    /// The request of each expired transaction is rebuilt from its tracked record, that is, from
+    /// its input notes and its transaction script. Note arguments, advice inputs and foreign
+    /// accounts aren't tracked, so transactions that relied on them can't be resubmitted this
+    /// way. An expired transaction is skipped if:
+    /// - Any of its input notes was consumed, or is being processed by another transaction.
+    /// - The state of its account changed since it was executed, which is also the case once the
+    ///   transaction was resubmitted.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any of the rebuilt transactions fails to execute or to be submitted.
+    /// The transactions resubmitted up to that point remain submitted.
+    pub async fn resubmit_expired(&mut self) -> Result<ResubmissionSummary, ClientError> {
+        let mut summary = ResubmissionSummary::default();
+
+        for transaction in self.get_expired_transactions().await? {
+            let details = &transaction.details;
+            let account_record = self.try_get_account(details.account_id).await?;
+
+            let nullifiers: Vec<Nullifier> =
+                details.input_note_nullifiers.iter().copied().map(Nullifier::from).collect();
+            let mut input_notes = self.store.get_input_notes_by_nullifier(&nullifiers).await?;
+
+            let is_executable = !account_record.is_locked()
+                && details.init_account_state == Some(account_record.account().commitment())
+                && input_notes.len() == details.input_note_nullifiers.len()
+                && input_notes.iter().all(|note| {
+                    !note.is_consumed()
+                        && note.consumer_transaction_id().is_none_or(|id| *id == transaction.id)
+                });
+
+            if !is_executable {
+                summary.skipped.push(transaction.id);
+                continue;
+            }
+
+            // Release the input notes so that they can be consumed by the new transaction
+            for note in &mut input_notes {
+                note.consumption_discarded(transaction.id);
+            }
+            self.store.upsert_input_notes(&input_notes).await?;
+
+            let (authenticated_notes, unauthenticated_notes): (Vec<_>, Vec<_>) =
+                input_notes.into_iter().partition(InputNoteRecord::is_authenticated);
+
+            let unauthenticated_notes = unauthenticated_notes
+                .into_iter()
+                .map(|note| Ok((note.try_into()?, None)))
+                .collect::<Result<Vec<(Note, Option<NoteArgs>)>, NoteRecordError>>()?;
+
+            let expected_recipients = details
+                .output_notes
+                .iter()
+                .filter_map(|note| match note {
+                    OutputNote::Full(note) => Some(note.recipient().clone()),
+                    _ => None,
+                })
+                .collect();
+
+            let mut request_builder = TransactionRequestBuilder::new()
+                .authenticated_input_notes(authenticated_notes.iter().map(|note| (note.id(), None)))
+                .unauthenticated_input_notes(unauthenticated_notes)
+                .expected_output_recipients(expected_recipients);
+
+            // The script of the transaction already encodes its expiration delta, if it had one
+            if let Some(script) = transaction.script.clone() {
+                request_builder = request_builder.custom_script(script);
+            }
+
+            let tx_result =
+                self.new_transaction(details.account_id, request_builder.build()?).await?;
+            self.submit_transaction(tx_result).await?;
+
+            summary.resubmitted.push(transaction.id);
+        }
+
+        Ok(summary)
+    }
+
+    /// Removes the discarded transactions from the store and releases their input notes, so that
+    /// they can be consumed by other transactions. Returns the IDs of the removed transactions.
+    ///
+    /// Transactions are discarded during [`Client::sync_state`], for instance once they expire
+    /// without being committed. Expired transactions that weren't discarded yet are kept, as the
+    /// changes they made to their account are only reverted when they get discarded.
+    ///
+    /// Input notes that are being processed by a discarded transaction go back to being committed
+    /// (or expected, if they were consumed as unauthenticated notes). Notes that were consumed or
+    /// that are being processed by another transaction aren't modified.
+    pub async fn clean_discarded_transactions(
+        &mut self,
+    ) -> Result<Vec<TransactionId>, ClientError> {
+        let discarded_transactions: Vec<TransactionRecord> = self
+            .store
+            .get_transactions(TransactionFilter::Uncommitted)
+            .await?
+            .into_iter()
+            .filter(|tx| matches!(tx.status, TransactionStatus::Discarded(_)))
+            .collect();
+
+        let mut released_notes = Vec::new();
+        for transaction in &discarded_transactions {
+            let nullifiers: Vec<Nullifier> = transaction
+                .details
+                .input_note_nullifiers
+                .iter()
+                .copied()
+                .map(Nullifier::from)
+                .collect();
+
+            for mut note in self.store.get_input_notes_by_nullifier(&nullifiers).await? {
+                if note.consumption_discarded(transaction.id) {
+                    released_notes.push(note);
+                }
+            }
+        }
+
+        let transaction_ids: Vec<TransactionId> =
+            discarded_transactions.iter().map(|tx| tx.id).collect();
+        self.store.remove_transactions(&transaction_ids, &released_notes).await?;
+
+        Ok(transaction_ids)
     }
 
     /// Proves the specified transaction result using the provided prover.
@@ -621,10 +1240,81 @@ impl Client {
         Ok(proven_transaction)
     }
 
+    /// Proves the specified transactions using the client's local prover.
+    ///
+    /// See [`Client::prove_transactions_with_prover`] for details.
+    pub async fn prove_transactions(
+        &self,
+        transactions: Vec<ExecutedTransaction>,
+    ) -> Vec<Result<ProvenTransaction, ClientError>> {
+        self.prove_transactions_with_prover(transactions, self.tx_prover.clone()).await
+    }
+
+    /// Proves the specified transactions using the provided prover, and returns the result of
+    /// each proof in the same order as the transactions.
+    ///
+    /// Up to a fixed number of proofs are awaited concurrently, so provers that don't generate
+    /// proofs in the current task, such as [`crate::RemoteTransactionProver`], prove the
+    /// transactions in parallel. A failed proof doesn't stop the remaining ones.
+    pub async fn prove_transactions_with_prover(
+        &self,
+        transactions: Vec<ExecutedTransaction>,
+        tx_prover: Arc<dyn TransactionProver>,
+    ) -> Vec<Result<ProvenTransaction, ClientError>> {
+        info!("Proving {} transactions...", transactions.len());
+
+        let mut results: Vec<Option<Result<ProvenTransaction, ClientError>>> =
+            transactions.iter().map(|_| None).collect();
+        let mut pending = transactions.into_iter().enumerate();
+        let mut in_flight = Vec::with_capacity(MAX_CONCURRENT_PROOFS);
+
+        core::future::poll_fn(|cx| {
+            loop {
+                while in_flight.len() < MAX_CONCURRENT_PROOFS {
+                    let Some((index, transaction)) = pending.next() else {
+                        break;
+                    };
+                    in_flight.push((index, tx_prover.prove(transaction.into())));
+                }
+
+                let proofs_in_flight = in_flight.len();
+                in_flight.retain_mut(|(index, proof)| match proof.as_mut().poll(cx) {
+                    Poll::Ready(result) => {
+                        results[*index] = Some(result.map_err(Into::into));
+                        false
+                    },
+                    Poll::Pending => true,
+                });
+
+                if in_flight.is_empty() && pending.len() == 0 {
+                    return Poll::Ready(());
+                }
+
+                // Start new proofs only if some of the previous ones finished
+                if in_flight.len() == proofs_in_flight {
+                    return Poll::Pending;
+                }
+            }
+        })
+        .await;
+
+        info!("Transactions proven.");
+
+        results
+            .into_iter()
+            .map(|result| result.expect("every transaction should have been proven"))
+            .collect()
+    }
+
     async fn submit_proven_transaction(
         &mut self,
         proven_transaction: ProvenTransaction,
     ) -> Result<BlockNumber, ClientError> {
+        if self.verify_before_submit {
+            info!("Verifying transaction proof...");
+            TransactionVerifier::new(MIN_PROOF_SECURITY_LEVEL).verify(&proven_transaction)?;
+        }
+
         info!("Submitting transaction to the network...");
         let block_num = self.rpc_api.submit_proven_transaction(proven_transaction).await?;
         info!("Transaction submitted.");
@@ -633,7 +1323,7 @@ impl Client {
     }
 
     async fn apply_transaction(
-        &self,
+        &mut self,
         submission_height: BlockNumber,
         tx_result: TransactionResult,
     ) -> Result<(), ClientError> {
@@ -641,6 +1331,7 @@ impl Client {
         // update account
         info!("Applying transaction to the local store...");
 
+        let transaction_id = tx_result.executed_transaction().id();
         let account_id = tx_result.executed_transaction().account_id();
         let account_delta = tx_result.account_delta();
         let account_record = self.try_get_account(account_id).await?;
@@ -689,6 +1380,7 @@ impl Client {
         );
 
         self.store.apply_transaction(tx_update).await?;
+        self.release_reserved_notes(transaction_id);
         info!("Transaction stored.");
         Ok(())
     }
@@ -743,9 +1435,190 @@ impl Client {
             .await?)
     }
 
+    /// Compiles the provided MASM source code as a transaction script and executes it against the
+    /// specified account, returning the resulting stack. Nothing is submitted to the network or
+    /// stored, so this can be used to debug scripts and account procedures.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ClientError::ScriptBuilderError`] if the source code can't be compiled. The
+    /// assembler diagnostics, including the source spans of the errors, are kept as the error's
+    /// source.
+    pub async fn execute_script(
+        &mut self,
+        account_id: AccountId,
+        masm: &str,
+        advice_inputs: AdviceInputs,
+    ) -> Result<[Felt; 16], ClientError> {
+        let tx_script = self.script_builder().compile_tx_script(masm)?;
+
+        self.execute_program(account_id, tx_script, advice_inputs, BTreeSet::new())
+            .await
+    }
+
+    /// Returns the transaction script that would be used to consume the specified note with the
+    /// specified account, without executing any transaction.
+    ///
+    /// The script is built in the same way as for a request created with
+    /// [`TransactionRequestBuilder::build_consume_notes`], based on the interface of the account.
+    /// This doesn't check whether the account can actually consume the note.
+    ///
+    /// # Errors
+    ///
+    /// - Returns [`ClientError::NoteNotTracked`] if the note isn't tracked by the client.
+    /// - Returns [`ClientError::AccountDataNotFound`] if the account isn't tracked by the client.
+    pub async fn consumption_script_for(
+        &self,
+        note_id: NoteId,
+        account_id: AccountId,
+    ) -> Result<TransactionScript, ClientError> {
+        if self.store.get_input_notes(NoteFilter::List(vec![note_id])).await?.is_empty() {
+            return Err(ClientError::NoteNotTracked(note_id));
+        }
+
+        let transaction_request =
+            TransactionRequestBuilder::new().build_consume_notes(vec![note_id])?;
+
+        Ok(transaction_request.build_transaction_script(
+            &self.get_account_interface(account_id).await?,
+            self.in_debug_mode(),
+        )?)
+    }
+
+    /// Builds a [`ForeignAccount::Private`] out of the latest local state of a private account
+    /// tracked by the client. The account's code, storage and vault are taken from the store, so
+    /// no request to the network is needed to assemble the account's partial state.
+    ///
+    /// # Errors
+    ///
+    /// - Returns [`ClientError::AccountDataNotFound`] if the account isn't tracked by the client.
+    /// - Returns a [`ClientError::TransactionRequestError`] if the account isn't private.
+    pub async fn build_private_foreign_account(
+        &self,
+        account_id: AccountId,
+    ) -> Result<ForeignAccount, ClientError> {
+        let account: Account = self.try_get_account(account_id).await?.into();
+
+        Ok(ForeignAccount::private(account)?)
+    }
+
+    /// Builds a [`TransactionRequest`] for a SWAP offer from the tracked account with ID
+    /// `account_id`, offering the `offered` asset in exchange for the `requested` asset.
+    ///
+    /// The request's output note is the SWAP note with the provided `note_type`. The payback note
+    /// that the offerer receives once the SWAP note is consumed is registered as an expected
+    /// future note, tagged for the offerer's account.
+    ///
+    /// # Errors
+    ///
+    /// - Returns [`ClientError::AccountDataNotFound`] if the account isn't tracked by the client.
+    /// - Returns a [`ClientError::TransactionRequestError`] if the SWAP note can't be created.
+    pub async fn build_swap_offer(
+        &mut self,
+        account_id: AccountId,
+        offered: Asset,
+        requested: Asset,
+        note_type: NoteType,
+    ) -> Result<TransactionRequest, ClientError> {
+        self.try_get_account(account_id).await?;
+
+        let swap_data = SwapTransactionData::new(account_id, offered, requested);
+
+        Ok(TransactionRequestBuilder::new().build_swap(&swap_data, note_type, self.rng())?)
+    }
+
+    /// Builds a [`TransactionRequest`] that mints `amount` tokens of the tracked basic fungible
+    /// faucet with ID `faucet_id` and sends them to `target_id` in a P2ID note of the provided
+    /// `note_type`.
+    ///
+    /// The minted note is registered as an expected output note of the request, so it's tracked
+    /// by the client once the transaction is executed.
+    ///
+    /// # Errors
+    ///
+    /// - Returns [`ClientError::AccountDataNotFound`] if the faucet isn't tracked by the client.
+    /// - Returns [`ClientError::AccountLocked`] if the faucet is locked.
+    /// - Returns [`ClientError::AccountNotFungibleFaucet`] if the account isn't a basic fungible
+    ///   faucet.
+    /// - Returns a [`ClientError::AssetError`] if `amount` isn't a valid fungible asset amount.
+    pub async fn mint_fungible(
+        &mut self,
+        faucet_id: AccountId,
+        target_id: AccountId,
+        amount: u64,
+        note_type: NoteType,
+    ) -> Result<TransactionRequest, ClientError> {
+        self.validate_fungible_faucet(faucet_id).await?;
+
+        let asset = FungibleAsset::new(faucet_id, amount)?;
+
+        Ok(TransactionRequestBuilder::new().build_mint_fungible_asset(
+            asset,
+            target_id,
+            note_type,
+            self.rng(),
+        )?)
+    }
+
+    /// Builds a [`TransactionRequest`] that burns the assets of `note` by consuming it with the
+    /// tracked basic fungible faucet with ID `faucet_id`, reducing the faucet's total issuance.
+    ///
+    /// The note's script is expected to call the faucet's `burn` procedure for its assets. The
+    /// note is consumed as an authenticated note if its inclusion proof has been verified, and
+    /// as an unauthenticated note otherwise.
+    ///
+    /// # Errors
+    ///
+    /// - Returns [`ClientError::AccountDataNotFound`] if the faucet isn't tracked by the client.
+    /// - Returns [`ClientError::AccountLocked`] if the faucet is locked.
+    /// - Returns [`ClientError::AccountNotFungibleFaucet`] if the account isn't a basic fungible
+    ///   faucet.
+    /// - Returns [`ClientError::NoteAssetsNotIssuedByFaucet`] if the note has no assets or any of
+    ///   them wasn't issued by the faucet.
+    pub async fn burn_fungible(
+        &self,
+        faucet_id: AccountId,
+        note: InputNoteRecord,
+    ) -> Result<TransactionRequest, ClientError> {
+        self.validate_fungible_faucet(faucet_id).await?;
+
+        let issued_by_faucet = |asset: &Asset| matches!(asset, Asset::Fungible(asset) if asset.faucet_id() == faucet_id);
+        if note.assets().is_empty() || !note.assets().iter().all(issued_by_faucet) {
+            return Err(ClientError::NoteAssetsNotIssuedByFaucet(note.id(), faucet_id));
+        }
+
+        let builder = TransactionRequestBuilder::new();
+        if note.is_authenticated() {
+            Ok(builder.build_consume_notes(vec![note.id()])?)
+        } else {
+            let note: Note = note.try_into()?;
+            Ok(builder.unauthenticated_input_notes([(note, None)]).build()?)
+        }
+    }
+
     // HELPERS
     // --------------------------------------------------------------------------------------------
 
+    /// Checks that the account with ID `faucet_id` is a basic fungible faucet tracked by the
+    /// client and that it isn't locked, so that it can mint and burn its tokens.
+    async fn validate_fungible_faucet(&self, faucet_id: AccountId) -> Result<(), ClientError> {
+        let faucet_record = self.try_get_account(faucet_id).await?;
+        if faucet_record.is_locked() {
+            return Err(ClientError::AccountLocked(faucet_id));
+        }
+
+        let faucet_interface = AccountInterface::from(faucet_record.account());
+        if !faucet_interface
+            .components()
+            .iter()
+            .any(|component| matches!(component, AccountComponentInterface::BasicFungibleFaucet(_)))
+        {
+            return Err(ClientError::AccountNotFungibleFaucet(faucet_id));
+        }
+
+        Ok(())
+    }
+
     /// Compiles the note updates needed to be applied to the store after executing a
     /// transaction.
     ///
@@ -761,7 +1634,7 @@ impl Client {
         tx_result: &TransactionResult,
     ) -> Result<NoteUpdateTracker, ClientError> {
         let executed_tx = tx_result.executed_transaction();
-        let current_timestamp = self.store.get_current_timestamp();
+        let current_timestamp = self.get_current_timestamp();
         let current_block_num = self.store.get_sync_height().await?;
 
         // New output notes
@@ -776,7 +1649,11 @@ impl Client {
 
         // New relevant input notes
         let mut new_input_notes = vec![];
-        let note_screener = NoteScreener::new(self.store.clone(), self.authenticator.clone());
+        let note_screener = NoteScreener::new(
+            self.store.clone(),
+            self.authenticator.clone(),
+            self.known_scripts.clone(),
+        );
 
         for note in notes_from_output(executed_tx.output_notes()) {
             let account_relevance = note_screener.check_relevance(note).await?;
@@ -822,7 +1699,7 @@ impl Client {
             if input_note_record.consumed_locally(
                 executed_tx.account_id(),
                 executed_tx.id(),
-                self.store.get_current_timestamp(),
+                self.get_current_timestamp(),
             )? {
                 updated_input_notes.push(input_note_record);
             }
@@ -968,11 +1845,15 @@ impl Client {
     /// transactions that are guaranteed to fail. Some of the validations include:
     /// - That the account has enough balance to cover the outgoing assets.
     /// - That the client is not too far behind the chain tip.
+    /// - That the expected output notes are created by the transaction script, if the script is the
+    ///   only possible source of output notes.
     pub async fn validate_request(
         &mut self,
         account_id: AccountId,
         transaction_request: &TransactionRequest,
     ) -> Result<(), ClientError> {
+        validate_script_output_recipients(transaction_request)?;
+
         let current_chain_tip =
             self.rpc_api.get_block_header_by_number(None, false).await?.0.block_num();
 
@@ -995,6 +1876,175 @@ impl Client {
         }
     }
 
+    /// Checks that the entries of the transaction request's advice map don't reference state that
+    /// is no longer valid according to the store.
+    ///
+    /// An advice map key is considered stale if:
+    /// - It's the commitment of a past state of a tracked account, which changed since the request
+    ///   was built.
+    /// - It's the ID of a tracked input note that was consumed, or is being consumed by another
+    ///   transaction.
+    ///
+    /// Keys that don't match any account state or note known to the client aren't checked.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TransactionRequestError::StaleAdviceMapEntry`] for the first stale entry found.
+    pub async fn validate_request_advice(
+        &self,
+        transaction_request: &TransactionRequest,
+    ) -> Result<(), ClientError> {
+        let keys: Vec<Digest> = transaction_request
+            .advice_map()
+            .clone()
+            .into_iter()
+            .map(|(key, _)| key)
+            .collect();
+
+        for key in &keys {
+            let Some(header) = self.store.get_account_header_by_commitment(*key).await? else {
+                continue;
+            };
+
+            if let Some((current_header, _)) = self.store.get_account_header(header.id()).await?
+                && current_header.commitment() != *key
+            {
+                return Err(TransactionRequestError::StaleAdviceMapEntry(
+                    *key,
+                    format!(
+                        "account {} changed its state to {}",
+                        header.id(),
+                        current_header.commitment()
+                    ),
+                )
+                .into());
+            }
+        }
+
+        let consumed_note = self
+            .store
+            .get_input_notes(NoteFilter::List(keys.into_iter().map(NoteId::from).collect()))
+            .await?
+            .into_iter()
+            .find(|note| note.is_consumed() || note.is_processing());
+
+        if let Some(note) = consumed_note {
+            return Err(TransactionRequestError::StaleAdviceMapEntry(
+                note.id().inner(),
+                format!("input note {} has already been consumed", note.id()),
+            )
+            .into());
+        }
+
+        Ok(())
+    }
+
+    /// Checks that a transaction with the provided authenticated input notes can be executed
+    /// against `reference_block`.
+    async fn validate_reference_block(
+        &self,
+        reference_block: BlockNumber,
+        authenticated_note_records: &[InputNoteRecord],
+    ) -> Result<(), ClientError> {
+        let sync_height = self.store.get_sync_height().await?;
+        if reference_block > sync_height {
+            return Err(ClientError::InvalidReferenceBlock(
+                reference_block,
+                format!("it's after the sync height ({sync_height})"),
+            ));
+        }
+
+        if let Some(max_block_number_delta) = self.max_block_number_delta
+            && reference_block + max_block_number_delta < sync_height
+        {
+            return Err(ClientError::InvalidReferenceBlock(
+                reference_block,
+                format!(
+                    "it's more than {max_block_number_delta} blocks behind the sync height ({sync_height})"
+                ),
+            ));
+        }
+
+        for note in authenticated_note_records {
+            if let Some(inclusion_proof) = note.inclusion_proof()
+                && inclusion_proof.location().block_num() > reference_block
+            {
+                return Err(ClientError::InvalidReferenceBlock(
+                    reference_block,
+                    format!(
+                        "input note {} was committed in a later block ({})",
+                        note.id(),
+                        inclusion_proof.location().block_num()
+                    ),
+                ));
+            }
+        }
+
+        if self.store.get_block_header_by_num(reference_block).await?.is_none() {
+            return Err(ClientError::InvalidReferenceBlock(
+                reference_block,
+                "its header isn't stored by the client".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Refreshes a [`TransactionRequest`] that was built before the client's state advanced, so
+    /// that it can be executed against the latest synced state of the account.
+    ///
+    /// The reference block of the transaction and the inclusion proofs of its authenticated input
+    /// notes are resolved from the store when it's executed, so they always correspond to the
+    /// latest sync height. Rebasing the request additionally:
+    /// - Checks that none of its input notes were consumed since the request was built.
+    /// - Turns the unauthenticated input notes that were committed since into authenticated ones,
+    ///   so that their inclusion proofs are used.
+    ///
+    /// # Errors
+    ///
+    /// - Returns [`ClientError::AccountDataNotFound`] if the account isn't tracked by the client.
+    /// - Returns [`TransactionRequestError::InputNoteAlreadyConsumed`] if an input note was
+    ///   consumed, or is being consumed by another transaction.
+    /// - Returns [`TransactionRequestError::MissingAuthenticatedInputNote`] if an authenticated
+    ///   input note isn't tracked by the client.
+    pub async fn rebase_transaction(
+        &self,
+        mut transaction_request: TransactionRequest,
+        account_id: AccountId,
+    ) -> Result<TransactionRequest, ClientError> {
+        self.try_get_account(account_id).await?;
+
+        let note_records: BTreeMap<NoteId, InputNoteRecord> = self
+            .store
+            .get_input_notes(NoteFilter::List(transaction_request.get_input_note_ids()))
+            .await?
+            .into_iter()
+            .map(|record| (record.id(), record))
+            .collect();
+
+        if let Some(note_id) = transaction_request
+            .authenticated_input_note_ids()
+            .find(|note_id| !note_records.contains_key(note_id))
+        {
+            return Err(TransactionRequestError::MissingAuthenticatedInputNote(note_id).into());
+        }
+
+        let mut committed_note_ids = BTreeSet::new();
+        for record in note_records.values() {
+            if record.is_consumed() || record.is_processing() {
+                return Err(TransactionRequestError::InputNoteAlreadyConsumed(record.id()).into());
+            }
+
+            if record.is_committed() {
+                committed_note_ids.insert(record.id());
+            }
+        }
+
+        transaction_request.authenticate_input_notes(&committed_note_ids);
+
+        Ok(transaction_request)
+    }
+
     async fn get_valid_input_notes(
         &self,
         account_id: AccountId,
@@ -1154,7 +2204,7 @@ impl Client {
     }
 
     pub async fn testing_apply_transaction(
-        &self,
+        &mut self,
         tx_result: TransactionResult,
     ) -> Result<(), ClientError> {
         self.apply_transaction(self.get_sync_height().await.unwrap(), tx_result).await
@@ -1230,6 +2280,37 @@ fn validate_executed_transaction(
     Ok(())
 }
 
+/// Checks that the expected output recipients of a request that sends its own notes correspond to
+/// notes created by its script. Output notes can also be created by the input notes consumed in
+/// the transaction, so requests with input notes aren't checked.
+fn validate_script_output_recipients(
+    transaction_request: &TransactionRequest,
+) -> Result<(), ClientError> {
+    let Some(TransactionScriptTemplate::SendNotes(notes)) = transaction_request.script_template()
+    else {
+        return Ok(());
+    };
+
+    if !transaction_request.input_notes().is_empty() {
+        return Ok(());
+    }
+
+    let script_recipient_digests: BTreeSet<Digest> =
+        notes.iter().map(PartialNote::recipient_digest).collect();
+
+    let missing_recipient_digests: Vec<Digest> = transaction_request
+        .expected_output_recipients()
+        .map(NoteRecipient::digest)
+        .filter(|digest| !script_recipient_digests.contains(digest))
+        .collect();
+
+    if !missing_recipient_digests.is_empty() {
+        return Err(ClientError::MissingOutputRecipients(missing_recipient_digests));
+    }
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod test {
     use miden_lib::{account::auth::RpoFalcon512, transaction::TransactionKernel};
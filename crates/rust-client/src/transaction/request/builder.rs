@@ -5,23 +5,31 @@ use alloc::{
     vec::Vec,
 };
 
-use miden_lib::note::{create_p2id_note, create_p2ide_note, create_swap_note};
+use miden_lib::note::{
+    create_p2id_note, create_p2ide_note, create_swap_note, well_known_note::WellKnownNote,
+};
 use miden_objects::{
     Digest, Felt, FieldElement, NoteError, Word,
     account::AccountId,
     asset::{Asset, FungibleAsset},
     block::BlockNumber,
-    crypto::merkle::{InnerNodeInfo, MerkleStore},
-    note::{Note, NoteDetails, NoteId, NoteRecipient, NoteTag, NoteType, PartialNote},
+    crypto::{
+        hash::rpo::Rpo256,
+        merkle::{InnerNodeInfo, MerkleStore},
+    },
+    note::{
+        Note, NoteDetails, NoteId, NoteMetadata, NoteRecipient, NoteTag, NoteType, PartialNote,
+    },
     transaction::{OutputNote, TransactionScript},
     vm::AdviceMap,
 };
+use miden_tx::utils::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable};
 
 use super::{
     ForeignAccount, NoteArgs, TransactionRequest, TransactionRequestError,
     TransactionScriptTemplate,
 };
-use crate::ClientRng;
+use crate::{ClientRng, note::get_p2ide_recall_height, store::InputNoteRecord};
 
 // TRANSACTION REQUEST BUILDER
 // ================================================================================================
@@ -40,6 +48,9 @@ pub struct TransactionRequestBuilder {
     /// Notes to be created by the transaction. This includes both full and partial output notes.
     /// The transaction script will be generated based on these notes.
     own_output_notes: Vec<OutputNote>,
+    /// Tags that override the ones of the own output notes, keyed by the position of the note in
+    /// `own_output_notes`.
+    output_note_tags: BTreeMap<usize, NoteTag>,
     /// A map of recipients of the output notes expected to be generated by the transaction.
     expected_output_recipients: BTreeMap<Digest, NoteRecipient>,
     /// A map of details and tags of notes we expect to be created as part of future transactions
@@ -51,6 +62,9 @@ pub struct TransactionRequestBuilder {
     custom_script: Option<TransactionScript>,
     /// Initial state of the `AdviceMap` that provides data during runtime.
     advice_map: AdviceMap,
+    /// The first key that was inserted in the advice map with a value different from the one it
+    /// already had, if any.
+    advice_map_conflict: Option<Digest>,
     /// Initial state of the `MerkleStore` that provides data during runtime.
     merkle_store: MerkleStore,
     /// Foreign account data requirements. At execution time, account data will be retrieved from
@@ -68,6 +82,12 @@ pub struct TransactionRequestBuilder {
     /// execution. If the advice map is extended with some user defined entries, this script
     /// argument could be used as a key to access the corresponding value.
     script_arg: Option<Word>,
+    /// Optional list of arguments for the custom transaction script. At build time, they are
+    /// inserted in the advice map under their commitment, which is used as the script argument.
+    script_args: Option<Vec<Felt>>,
+    /// Maximum number of VM cycles the transaction can take to execute. If `None`, the number of
+    /// cycles isn't bounded by the request.
+    max_cycles: Option<u32>,
 }
 
 impl TransactionRequestBuilder {
@@ -80,15 +100,19 @@ impl TransactionRequestBuilder {
             unauthenticated_input_notes: vec![],
             input_notes: vec![],
             own_output_notes: Vec::new(),
+            output_note_tags: BTreeMap::new(),
             expected_output_recipients: BTreeMap::new(),
             expected_future_notes: BTreeMap::new(),
             custom_script: None,
             advice_map: AdviceMap::default(),
+            advice_map_conflict: None,
             merkle_store: MerkleStore::default(),
             expiration_delta: None,
             foreign_accounts: BTreeMap::default(),
             ignore_invalid_input_notes: false,
             script_arg: None,
+            script_args: None,
+            max_cycles: None,
         }
     }
 
@@ -105,6 +129,17 @@ impl TransactionRequestBuilder {
         self
     }
 
+    /// Adds the specified note as an unauthenticated input note to the transaction request, to be
+    /// consumed with the provided note arguments.
+    ///
+    /// This is equivalent to calling [`TransactionRequestBuilder::unauthenticated_input_notes`]
+    /// with a single note, and keeps the note and its arguments together so that they can't get
+    /// out of sync.
+    #[must_use]
+    pub fn consume_note_with_args(self, note: Note, args: NoteArgs) -> Self {
+        self.unauthenticated_input_notes([(note, Some(args))])
+    }
+
     /// Adds the specified notes as authenticated input notes to the transaction request.
     #[must_use]
     pub fn authenticated_input_notes(
@@ -137,6 +172,22 @@ impl TransactionRequestBuilder {
         self
     }
 
+    /// Overrides the tag of the own output note at position `note_index` with `tag`, so that the
+    /// note is created with it instead of the tag it was built with (which is usually derived
+    /// from its recipient). This allows routing notes with custom tags.
+    ///
+    /// The tag is replaced when the request is built, so `note_index` refers to the own output
+    /// notes set up to that point, including the ones created by the standardized requests (such
+    /// as [`TransactionRequestBuilder::build_pay_to_id`]).
+    ///
+    /// If `note_index` is out of range, the [`TransactionRequestBuilder::build`] method will
+    /// return an error.
+    #[must_use]
+    pub fn with_output_note_tag(mut self, note_index: usize, tag: NoteTag) -> Self {
+        self.output_note_tags.insert(note_index, tag);
+        self
+    }
+
     /// Specifies a custom transaction script to be used.
     ///
     /// If a script template is already set (e.g. by calling `with_own_output_notes`), the
@@ -198,12 +249,25 @@ impl TransactionRequestBuilder {
     }
 
     /// Extends the advice map with the specified `([Digest], Vec<[Felt]>)` pairs.
+    ///
+    /// Inserting a key that is already in the map is only allowed if its value is the same. If a
+    /// key is inserted with a different value, the existing value is kept and
+    /// [`TransactionRequestBuilder::build`] returns an error.
     #[must_use]
     pub fn extend_advice_map<T: IntoIterator<Item = (Digest, Vec<Felt>)>>(
         mut self,
         iter: T,
     ) -> Self {
-        self.advice_map.extend(iter);
+        for (key, value) in iter {
+            match self.advice_map.get(&key) {
+                Some(existing_value) => {
+                    if existing_value != value.as_slice() && self.advice_map_conflict.is_none() {
+                        self.advice_map_conflict = Some(key);
+                    }
+                },
+                None => self.advice_map.extend([(key, value)]),
+            }
+        }
         self
     }
 
@@ -217,6 +281,10 @@ impl TransactionRequestBuilder {
     /// The number of blocks in relation to the transaction's reference block after which the
     /// transaction will expire. By default, the transaction will not expire.
     ///
+    /// The expiration delta applies to every transaction built from the request, including those
+    /// that only consume notes. It can't be set along with a custom script, which has to set the
+    /// expiration delta itself.
+    ///
     /// Setting transaction expiration delta defines an upper bound for transaction expiration,
     /// but other code executed during the transaction may impose an even smaller transaction
     /// expiration delta.
@@ -226,6 +294,16 @@ impl TransactionRequestBuilder {
         self
     }
 
+    /// Sets the maximum number of VM cycles the transaction can take to execute. If the executed
+    /// transaction takes more cycles, it's rejected before it can be proven and submitted, which
+    /// allows bounding the cost of expensive custom scripts. By default, the number of cycles isn't
+    /// bounded.
+    #[must_use]
+    pub fn with_max_cycles(mut self, max_cycles: u32) -> Self {
+        self.max_cycles = Some(max_cycles);
+        self
+    }
+
     /// The resulting transaction will **silently** ignore invalid input notes when being executed.
     /// By default, this will not happen.
     #[must_use]
@@ -243,6 +321,24 @@ impl TransactionRequestBuilder {
         self
     }
 
+    /// Sets the arguments that are made available to the custom transaction script.
+    ///
+    /// The arguments are inserted in the advice map under their commitment (the RPO hash of
+    /// `script_args`), and the commitment is pushed to the operand stack as the script argument.
+    /// The script can then load them with `adv.push_mapval`, which leaves `script_args[0]` at the
+    /// top of the advice stack. This means that after `adv_push.N` the last argument is at the top
+    /// of the operand stack, and after `adv_loadw` the first four arguments form the top word.
+    ///
+    /// Script arguments can only be used along with a custom script (see
+    /// [`TransactionRequestBuilder::custom_script`]) and can't be combined with
+    /// [`TransactionRequestBuilder::script_arg`]. Otherwise, [`TransactionRequestBuilder::build`]
+    /// returns an error.
+    #[must_use]
+    pub fn with_script_args(mut self, script_args: Vec<Felt>) -> Self {
+        self.script_args = Some(script_args);
+        self
+    }
+
     // STANDARDIZED REQUESTS
     // --------------------------------------------------------------------------------------------
 
@@ -351,6 +447,43 @@ impl TransactionRequestBuilder {
             .build()
     }
 
+    /// Consumes the builder and returns a [`TransactionRequest`] for a transaction to recall a
+    /// P2IDE note. This request must be executed against the note's sender account, and the
+    /// recalled assets are added back to its vault.
+    ///
+    /// - `note` is the P2IDE note to be recalled.
+    /// - `sync_height` is the current sync height of the client, which must be at or past the
+    ///   note's recall height.
+    ///
+    /// # Errors
+    ///
+    /// - Returns a [`TransactionRequestError::InvalidP2IDENote`] if the note isn't a P2IDE note.
+    /// - Returns a [`TransactionRequestError::P2IDENoteNotRecallable`] if the note's recall height
+    ///   hasn't been reached yet.
+    pub fn build_recall_p2ide(
+        self,
+        note: &InputNoteRecord,
+        sync_height: BlockNumber,
+    ) -> Result<TransactionRequest, TransactionRequestError> {
+        let recipient = note.details().recipient();
+        if recipient.script().root() != WellKnownNote::P2IDE.script_root() {
+            return Err(TransactionRequestError::InvalidP2IDENote(note.id()));
+        }
+
+        let recall_height = get_p2ide_recall_height(note.id(), recipient.inputs().values())
+            .map_err(|_err| TransactionRequestError::InvalidP2IDENote(note.id()))?;
+
+        if sync_height < recall_height {
+            return Err(TransactionRequestError::P2IDENoteNotRecallable(
+                note.id(),
+                recall_height,
+                sync_height,
+            ));
+        }
+
+        self.build_consume_notes(vec![note.id()])
+    }
+
     // FINALIZE BUILDER
     // --------------------------------------------------------------------------------------------
 
@@ -360,7 +493,15 @@ impl TransactionRequestBuilder {
     /// - If both a custom script and own output notes are set.
     /// - If an expiration delta is set when a custom script is set.
     /// - If an invalid note variant is encountered in the own output notes.
-    pub fn build(self) -> Result<TransactionRequest, TransactionRequestError> {
+    /// - If the same advice map key was inserted with different values.
+    /// - If an output note tag was set for a note index that is out of range, or the tag isn't
+    ///   valid for the note.
+    /// - If script arguments are set without a custom script, or along with a script argument.
+    pub fn build(mut self) -> Result<TransactionRequest, TransactionRequestError> {
+        if let Some(key) = self.advice_map_conflict {
+            return Err(TransactionRequestError::AdviceMapConflict(key));
+        }
+
         let mut seen_input_notes = BTreeSet::new();
         for (note_id, _) in &self.input_notes {
             if !seen_input_notes.insert(note_id) {
@@ -368,6 +509,15 @@ impl TransactionRequestBuilder {
             }
         }
 
+        for (&note_index, &tag) in &self.output_note_tags {
+            let num_notes = self.own_output_notes.len();
+            let note = self
+                .own_output_notes
+                .get_mut(note_index)
+                .ok_or(TransactionRequestError::OutputNoteIndexOutOfRange(note_index, num_notes))?;
+            *note = with_note_tag(note, tag)?;
+        }
+
         let script_template = match (self.custom_script, self.own_output_notes.is_empty()) {
             (Some(_), false) => {
                 return Err(TransactionRequestError::ScriptTemplateError(
@@ -399,6 +549,26 @@ impl TransactionRequestBuilder {
             (None, true) => None,
         };
 
+        let mut script_arg = self.script_arg;
+        if let Some(script_args) = self.script_args {
+            if !matches!(script_template, Some(TransactionScriptTemplate::CustomScript(_))) {
+                return Err(TransactionRequestError::ScriptTemplateError(
+                    "Script arguments can only be set when a custom script is set".to_string(),
+                ));
+            }
+
+            if script_arg.is_some() {
+                return Err(TransactionRequestError::ScriptTemplateError(
+                    "Cannot set both a script argument and script arguments".to_string(),
+                ));
+            }
+
+            // The key is the commitment to the value, so it can't conflict with other entries
+            let commitment = Rpo256::hash_elements(&script_args);
+            self.advice_map.insert(commitment, script_args);
+            script_arg = Some(commitment.into());
+        }
+
         Ok(TransactionRequest {
             unauthenticated_input_notes: self.unauthenticated_input_notes,
             input_notes: self.input_notes,
@@ -410,16 +580,45 @@ impl TransactionRequestBuilder {
             foreign_accounts: self.foreign_accounts.into_values().collect(),
             expiration_delta: self.expiration_delta,
             ignore_invalid_input_notes: self.ignore_invalid_input_notes,
-            script_arg: self.script_arg,
+            script_arg,
+            max_cycles: self.max_cycles,
         })
     }
 }
 
+/// Returns a copy of the output note with its tag replaced by `tag`. The note's ID doesn't depend
+/// on its metadata, so it's the same for the returned note.
+fn with_note_tag(note: &OutputNote, tag: NoteTag) -> Result<OutputNote, TransactionRequestError> {
+    let retag = |metadata: &NoteMetadata| {
+        NoteMetadata::new(
+            metadata.sender(),
+            metadata.note_type(),
+            tag,
+            metadata.execution_hint(),
+            metadata.aux(),
+        )
+    };
+
+    match note {
+        OutputNote::Full(note) => Ok(OutputNote::Full(Note::new(
+            note.assets().clone(),
+            retag(note.metadata())?,
+            note.recipient().clone(),
+        ))),
+        OutputNote::Partial(note) => Ok(OutputNote::Partial(PartialNote::new(
+            retag(note.metadata())?,
+            note.recipient_digest(),
+            note.assets().clone(),
+        ))),
+        OutputNote::Header(_) => Err(TransactionRequestError::InvalidNoteVariant),
+    }
+}
+
 // PAYMENT NOTE DESCRIPTION
 // ================================================================================================
 
 /// Contains information needed to create a payment note.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct PaymentNoteDescription {
     /// Assets that are meant to be sent to the target account.
     assets: Vec<Asset>,
@@ -538,7 +737,7 @@ impl PaymentNoteDescription {
 /// A swap transaction involves creating a SWAP note, which will carry the offered asset and which,
 /// when consumed, will create a payback note that carries the requested asset taken from the
 /// consumer account's vault.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct SwapTransactionData {
     /// Account ID of the sender account.
     sender_account_id: AccountId,
@@ -580,3 +779,52 @@ impl SwapTransactionData {
         self.requested_asset
     }
 }
+
+// SERIALIZATION
+// ================================================================================================
+
+impl Serializable for PaymentNoteDescription {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.assets.write_into(target);
+        self.sender_account_id.write_into(target);
+        self.target_account_id.write_into(target);
+        self.reclaim_height.write_into(target);
+        self.timelock_height.write_into(target);
+    }
+}
+
+impl Deserializable for PaymentNoteDescription {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let assets = Vec::<Asset>::read_from(source)?;
+        let sender_account_id = AccountId::read_from(source)?;
+        let target_account_id = AccountId::read_from(source)?;
+        let reclaim_height = Option::<BlockNumber>::read_from(source)?;
+        let timelock_height = Option::<BlockNumber>::read_from(source)?;
+
+        Ok(PaymentNoteDescription {
+            assets,
+            sender_account_id,
+            target_account_id,
+            reclaim_height,
+            timelock_height,
+        })
+    }
+}
+
+impl Serializable for SwapTransactionData {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.sender_account_id.write_into(target);
+        self.offered_asset.write_into(target);
+        self.requested_asset.write_into(target);
+    }
+}
+
+impl Deserializable for SwapTransactionData {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let sender_account_id = AccountId::read_from(source)?;
+        let offered_asset = Asset::read_from(source)?;
+        let requested_asset = Asset::read_from(source)?;
+
+        Ok(SwapTransactionData::new(sender_account_id, offered_asset, requested_asset))
+    }
+}
@@ -3,6 +3,7 @@
 use alloc::{
     boxed::Box,
     collections::{BTreeMap, BTreeSet},
+    format,
     string::{String, ToString},
     vec::Vec,
 };
@@ -14,9 +15,15 @@ use miden_lib::{
 use miden_objects::{
     Digest, Felt, NoteError, TransactionInputError, TransactionScriptError, Word,
     account::AccountId,
-    crypto::merkle::MerkleStore,
+    block::BlockNumber,
+    crypto::{
+        hash::rpo::Rpo256,
+        merkle::{InnerNodeInfo, MerkleStore},
+    },
     note::{Note, NoteDetails, NoteId, NoteRecipient, NoteTag, PartialNote},
-    transaction::{AccountInputs, InputNote, InputNotes, TransactionArgs, TransactionScript},
+    transaction::{
+        AccountInputs, InputNote, InputNotes, TransactionArgs, TransactionId, TransactionScript,
+    },
     vm::AdviceMap,
 };
 use miden_tx::utils::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable};
@@ -90,6 +97,9 @@ pub struct TransactionRequest {
     /// Optional [`Word`] that will be pushed to the operand stack before the transaction script
     /// execution.
     script_arg: Option<Word>,
+    /// Maximum number of VM cycles the transaction can take to execute. If `None`, the number of
+    /// cycles isn't bounded by the request.
+    max_cycles: Option<u32>,
 }
 
 impl TransactionRequest {
@@ -178,6 +188,12 @@ impl TransactionRequest {
         &self.script_template
     }
 
+    /// Returns the argument that is pushed to the operand stack before the transaction script is
+    /// executed, if any.
+    pub fn script_arg(&self) -> Option<Word> {
+        self.script_arg
+    }
+
     /// Returns the [`AdviceMap`] for the transaction request.
     pub fn advice_map(&self) -> &AdviceMap {
         &self.advice_map
@@ -198,6 +214,17 @@ impl TransactionRequest {
         self.ignore_invalid_input_notes
     }
 
+    /// Returns the maximum number of VM cycles the transaction can take to execute, if any.
+    pub fn max_cycles(&self) -> Option<u32> {
+        self.max_cycles
+    }
+
+    /// Treats the specified unauthenticated input notes as authenticated, so that their inclusion
+    /// proofs are retrieved from the store when the transaction is executed.
+    pub(crate) fn authenticate_input_notes(&mut self, note_ids: &BTreeSet<NoteId>) {
+        self.unauthenticated_input_notes.retain(|note| !note_ids.contains(&note.id()));
+    }
+
     /// Builds the [`InputNotes`] needed for the transaction execution. Full valid notes for the
     /// specified authenticated notes need to be provided, otherwise an error will be returned.
     /// The transaction input notes will include both authenticated and unauthenticated notes in the
@@ -307,10 +334,15 @@ impl TransactionRequest {
                     return Err(TransactionRequestError::NoInputNotes);
                 }
 
-                let empty_script =
-                    TransactionScript::compile("begin nop end", TransactionKernel::assembler())?;
+                // Transactions that only consume notes still set their expiration delta, if any
+                let script_code = match self.expiration_delta {
+                    Some(expiration_delta) => format!(
+                        "begin push.{expiration_delta} exec.::miden::tx::update_expiration_block_delta end"
+                    ),
+                    None => "begin nop end".to_string(),
+                };
 
-                Ok(empty_script)
+                Ok(TransactionScript::compile(script_code, TransactionKernel::assembler())?)
             },
         }
     }
@@ -337,11 +369,12 @@ impl Serializable for TransactionRequest {
         self.expected_output_recipients.write_into(target);
         self.expected_future_notes.write_into(target);
         self.advice_map.clone().into_iter().collect::<Vec<_>>().write_into(target);
-        self.merkle_store.write_into(target);
+        write_merkle_store(&self.merkle_store, target);
         self.foreign_accounts.write_into(target);
         self.expiration_delta.write_into(target);
         target.write_u8(u8::from(self.ignore_invalid_input_notes));
         self.script_arg.write_into(target);
+        self.max_cycles.write_into(target);
     }
 }
 
@@ -373,11 +406,12 @@ impl Deserializable for TransactionRequest {
         let mut advice_map = AdviceMap::new();
         let advice_vec = Vec::<(Digest, Vec<Felt>)>::read_from(source)?;
         advice_map.extend(advice_vec);
-        let merkle_store = MerkleStore::read_from(source)?;
+        let merkle_store = read_merkle_store(source)?;
         let foreign_accounts = BTreeSet::<ForeignAccount>::read_from(source)?;
         let expiration_delta = Option::<u16>::read_from(source)?;
         let ignore_invalid_input_notes = source.read_u8()? == 1;
         let script_arg = Option::<Word>::read_from(source)?;
+        let max_cycles = Option::<u32>::read_from(source)?;
 
         Ok(TransactionRequest {
             unauthenticated_input_notes,
@@ -391,10 +425,41 @@ impl Deserializable for TransactionRequest {
             expiration_delta,
             ignore_invalid_input_notes,
             script_arg,
+            max_cycles,
         })
     }
 }
 
+/// Writes the nodes of the merkle store that aren't present in an empty [`MerkleStore`], as the
+/// roots of the empty subtrees are always part of it. Only the children of each node are written,
+/// since the node itself is their hash.
+fn write_merkle_store<W: ByteWriter>(merkle_store: &MerkleStore, target: &mut W) {
+    let empty_store: MerkleStore = MerkleStore::new();
+    let empty_store_nodes: BTreeSet<Digest> =
+        empty_store.inner_nodes().map(|node| node.value).collect();
+
+    merkle_store
+        .inner_nodes()
+        .filter(|node| !empty_store_nodes.contains(&node.value))
+        .map(|node| (node.left, node.right))
+        .collect::<Vec<_>>()
+        .write_into(target);
+}
+
+/// Reads a [`MerkleStore`] written by [`write_merkle_store`].
+fn read_merkle_store<R: ByteReader>(source: &mut R) -> Result<MerkleStore, DeserializationError> {
+    let nodes = Vec::<(Digest, Digest)>::read_from(source)?;
+
+    let mut merkle_store = MerkleStore::default();
+    merkle_store.extend(nodes.into_iter().map(|(left, right)| InnerNodeInfo {
+        value: Rpo256::merge(&[left, right]),
+        left,
+        right,
+    }));
+
+    Ok(merkle_store)
+}
+
 impl Default for TransactionRequestBuilder {
     fn default() -> Self {
         Self::new()
@@ -409,6 +474,8 @@ impl Default for TransactionRequestBuilder {
 pub enum TransactionRequestError {
     #[error("account interface error")]
     AccountInterfaceError(#[from] AccountInterfaceError),
+    #[error("advice map key {0} was inserted with different values")]
+    AdviceMapConflict(Digest),
     #[error("duplicate input note with IDs: {0}")]
     DuplicateInputNote(NoteId),
     #[error("foreign account data missing in the account proof")]
@@ -421,8 +488,12 @@ pub enum TransactionRequestError {
     InputNoteNotAuthenticated(NoteId),
     #[error("note {0} has already been consumed")]
     InputNoteAlreadyConsumed(NoteId),
+    #[error("note {0} is reserved by transaction {1}, which wasn't submitted yet")]
+    InputNoteReserved(NoteId, TransactionId),
     #[error("own notes shouldn't be of the header variant")]
     InvalidNoteVariant,
+    #[error("note {0} is not a valid P2IDE note")]
+    InvalidP2IDENote(NoteId),
     #[error("invalid sender account id: {0}")]
     InvalidSenderAccount(AccountId),
     #[error("invalid transaction script")]
@@ -435,10 +506,18 @@ pub enum TransactionRequestError {
     NoteNotFound(String),
     #[error("note creation error")]
     NoteCreationError(#[from] NoteError),
+    #[error("output note index {0} is out of range, the request has {1} own output notes")]
+    OutputNoteIndexOutOfRange(usize, usize),
     #[error("pay to id note doesn't contain at least one asset")]
     P2IDNoteWithoutAsset,
+    #[error("note {0} can't be recalled before block {1} (the current sync height is {2})")]
+    P2IDENoteNotRecallable(NoteId, BlockNumber, BlockNumber),
+    #[error("transaction took {0} cycles to execute, which exceeds its budget of {1} cycles")]
+    CycleBudgetExceeded(u32, u32),
     #[error("transaction script template error: {0}")]
     ScriptTemplateError(String),
+    #[error("advice map entry {0} is stale: {1}")]
+    StaleAdviceMapEntry(Digest, String),
     #[error("storage slot {0} not found in account ID {1}")]
     StorageSlotNotFound(u8, AccountId),
     #[error("error while building the input notes: {0}")]
@@ -456,14 +535,17 @@ mod tests {
         account::auth::RpoFalcon512, note::create_p2id_note, transaction::TransactionKernel,
     };
     use miden_objects::{
-        Digest, EMPTY_WORD, Felt, ZERO,
+        Digest, EMPTY_WORD, Felt, Word, ZERO,
         account::{AccountBuilder, AccountId, AccountType},
         asset::FungibleAsset,
+        block::BlockNumber,
         crypto::{
             dsa::rpo_falcon512::PublicKey,
+            hash::rpo::Rpo256,
+            merkle::MerkleTree,
             rand::{FeltRng, RpoRandomCoin},
         },
-        note::{NoteTag, NoteType},
+        note::{Note, NoteTag, NoteType},
         testing::{
             account_component::AccountMockComponent,
             account_id::{
@@ -471,13 +553,48 @@ mod tests {
                 ACCOUNT_ID_REGULAR_PUBLIC_ACCOUNT_IMMUTABLE_CODE, ACCOUNT_ID_SENDER,
             },
         },
-        transaction::OutputNote,
+        transaction::{OutputNote, TransactionScript},
     };
     use miden_tx::utils::{Deserializable, Serializable};
 
-    use super::{TransactionRequest, TransactionRequestBuilder};
+    use super::{
+        PaymentNoteDescription, SwapTransactionData, TransactionRequest, TransactionRequestBuilder,
+        TransactionRequestError,
+    };
     use crate::{rpc::domain::account::AccountStorageRequirements, transaction::ForeignAccount};
 
+    // HELPERS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the IDs of the sender, target and faucet accounts used by the tests.
+    fn test_account_ids() -> (AccountId, AccountId, AccountId) {
+        let sender_id = AccountId::try_from(ACCOUNT_ID_SENDER).unwrap();
+        let target_id =
+            AccountId::try_from(ACCOUNT_ID_REGULAR_PUBLIC_ACCOUNT_IMMUTABLE_CODE).unwrap();
+        let faucet_id = AccountId::try_from(ACCOUNT_ID_PRIVATE_FUNGIBLE_FAUCET).unwrap();
+
+        (sender_id, target_id, faucet_id)
+    }
+
+    /// Creates a private P2ID note with `amount` units of the faucet's asset, sent from the sender
+    /// to the target account of [`test_account_ids`].
+    fn create_test_p2id_note(amount: u64, rng: &mut RpoRandomCoin) -> Note {
+        let (sender_id, target_id, faucet_id) = test_account_ids();
+
+        create_p2id_note(
+            sender_id,
+            target_id,
+            vec![FungibleAsset::new(faucet_id, amount).unwrap().into()],
+            NoteType::Private,
+            ZERO,
+            rng,
+        )
+        .unwrap()
+    }
+
+    // TESTS
+    // --------------------------------------------------------------------------------------------
+
     #[test]
     fn transaction_request_serialization() {
         let sender_id = AccountId::try_from(ACCOUNT_ID_SENDER).unwrap();
@@ -537,6 +654,7 @@ mod tests {
                 OutputNote::Full(notes.pop().unwrap()),
                 OutputNote::Partial(notes.pop().unwrap().into()),
             ])
+            .with_max_cycles(100_000)
             .build()
             .unwrap();
 
@@ -546,4 +664,166 @@ mod tests {
         let deserialized_tx_request = TransactionRequest::read_from_bytes(&buffer).unwrap();
         assert_eq!(tx_request, deserialized_tx_request);
     }
+
+    #[test]
+    fn advice_map_conflicts() {
+        let key = Digest::new([Felt::new(1), Felt::new(2), Felt::new(3), Felt::new(4)]);
+        let other_key = Digest::new([Felt::new(5), Felt::new(6), Felt::new(7), Felt::new(8)]);
+
+        // Inserting the same value twice is allowed
+        let tx_request = TransactionRequestBuilder::new()
+            .extend_advice_map([(key, vec![Felt::new(1)]), (other_key, vec![Felt::new(2)])])
+            .extend_advice_map([(key, vec![Felt::new(1)])])
+            .build()
+            .unwrap();
+        assert_eq!(tx_request.advice_map().get(&key).unwrap(), &[Felt::new(1)]);
+
+        // Inserting a different value for the same key is an error
+        let error = TransactionRequestBuilder::new()
+            .extend_advice_map([(key, vec![Felt::new(1)]), (other_key, vec![Felt::new(2)])])
+            .extend_advice_map([(key, vec![Felt::new(2)])])
+            .build()
+            .unwrap_err();
+        assert!(
+            matches!(error, TransactionRequestError::AdviceMapConflict(conflict) if conflict == key)
+        );
+
+        // Conflicts within the same call are detected as well
+        let error = TransactionRequestBuilder::new()
+            .extend_advice_map([(key, vec![Felt::new(1)]), (key, vec![Felt::new(2)])])
+            .build()
+            .unwrap_err();
+        assert!(
+            matches!(error, TransactionRequestError::AdviceMapConflict(conflict) if conflict == key)
+        );
+    }
+
+    #[test]
+    fn script_args() {
+        let script_args: Vec<Felt> = (1..=6).map(Felt::new).collect();
+        let commitment = Rpo256::hash_elements(&script_args);
+        let script =
+            TransactionScript::compile("begin nop end", TransactionKernel::assembler()).unwrap();
+
+        let tx_request = TransactionRequestBuilder::new()
+            .custom_script(script.clone())
+            .with_script_args(script_args.clone())
+            .build()
+            .unwrap();
+        assert_eq!(tx_request.script_arg(), Some(commitment.into()));
+        assert_eq!(tx_request.advice_map().get(&commitment).unwrap(), script_args.as_slice());
+
+        // Script arguments can only be used with a custom script
+        let error = TransactionRequestBuilder::new()
+            .with_script_args(script_args.clone())
+            .build()
+            .unwrap_err();
+        assert!(matches!(error, TransactionRequestError::ScriptTemplateError(_)));
+
+        // Script arguments can't be combined with a script argument
+        let error = TransactionRequestBuilder::new()
+            .custom_script(script)
+            .script_arg(EMPTY_WORD)
+            .with_script_args(script_args)
+            .build()
+            .unwrap_err();
+        assert!(matches!(error, TransactionRequestError::ScriptTemplateError(_)));
+    }
+
+    #[test]
+    fn output_note_tag_override() {
+        let note = create_test_p2id_note(100, &mut RpoRandomCoin::new(Default::default()));
+
+        let default_tag = note.metadata().tag();
+        let custom_tag = NoteTag::from(0xc000_0001);
+        assert_ne!(default_tag, custom_tag);
+
+        let tx_request = TransactionRequestBuilder::new()
+            .own_output_notes(vec![OutputNote::Full(note.clone())])
+            .with_output_note_tag(0, custom_tag)
+            .build()
+            .unwrap();
+
+        let output_notes = tx_request.expected_output_own_notes();
+        assert_eq!(output_notes.len(), 1);
+        assert_eq!(output_notes[0].id(), note.id());
+        assert_eq!(output_notes[0].metadata().tag(), custom_tag);
+
+        // The overridden tag survives serialization
+        let deserialized_tx_request =
+            TransactionRequest::read_from_bytes(&tx_request.to_bytes()).unwrap();
+        assert_eq!(tx_request, deserialized_tx_request);
+
+        // Overriding the tag of a note that doesn't exist is an error
+        let error = TransactionRequestBuilder::new()
+            .own_output_notes(vec![OutputNote::Full(note)])
+            .with_output_note_tag(1, custom_tag)
+            .build()
+            .unwrap_err();
+        assert!(matches!(error, TransactionRequestError::OutputNoteIndexOutOfRange(1, 1)));
+    }
+
+    #[test]
+    fn consume_note_with_args() {
+        let note = create_test_p2id_note(100, &mut RpoRandomCoin::new(Default::default()));
+        let args = [Felt::new(1), Felt::new(2), Felt::new(3), Felt::new(4)];
+
+        let tx_request = TransactionRequestBuilder::new()
+            .consume_note_with_args(note.clone(), args)
+            .build()
+            .unwrap();
+
+        assert_eq!(tx_request.unauthenticated_input_notes(), core::slice::from_ref(&note));
+        assert_eq!(tx_request.get_input_note_ids(), vec![note.id()]);
+        assert_eq!(tx_request.get_note_args().get(&note.id()), Some(&args));
+
+        // The arguments survive the serialization of the request
+        let deserialized_request =
+            TransactionRequest::read_from_bytes(&tx_request.to_bytes()).unwrap();
+        assert_eq!(deserialized_request.get_note_args().get(&note.id()), Some(&args));
+    }
+
+    #[test]
+    fn merkle_store_serialization() {
+        let leaves: Vec<Word> = (1..=8)
+            .map(|i| [Felt::new(i), Felt::new(i), Felt::new(i), Felt::new(i)])
+            .collect();
+        let merkle_tree = MerkleTree::new(leaves).unwrap();
+
+        let tx_request = TransactionRequestBuilder::new()
+            .extend_merkle_store(merkle_tree.inner_nodes())
+            .build()
+            .unwrap();
+
+        let serialized_request = tx_request.to_bytes();
+        let deserialized_request =
+            TransactionRequest::read_from_bytes(&serialized_request).unwrap();
+        assert_eq!(tx_request, deserialized_request);
+
+        // The whole request is smaller than its merkle store serialized on its own, as the nodes
+        // every merkle store contains aren't written
+        assert!(serialized_request.len() < tx_request.merkle_store().to_bytes().len());
+    }
+
+    #[test]
+    fn payment_and_swap_data_serialization() {
+        let (sender_id, target_id, faucet_id) = test_account_ids();
+        let offered_asset = FungibleAsset::new(faucet_id, 100).unwrap().into();
+        let requested_asset = FungibleAsset::new(faucet_id, 50).unwrap().into();
+
+        let payment_data = PaymentNoteDescription::new(vec![offered_asset], sender_id, target_id)
+            .with_reclaim_height(BlockNumber::from(10))
+            .with_timelock_height(BlockNumber::from(5));
+        let deserialized_payment_data =
+            PaymentNoteDescription::read_from_bytes(&payment_data.to_bytes()).unwrap();
+        assert_eq!(payment_data, deserialized_payment_data);
+
+        let swap_data = SwapTransactionData::new(sender_id, offered_asset, requested_asset);
+        let deserialized_swap_data =
+            SwapTransactionData::read_from_bytes(&swap_data.to_bytes()).unwrap();
+        assert_eq!(swap_data, deserialized_swap_data);
+        assert_eq!(deserialized_swap_data.account_id(), sender_id);
+        assert_eq!(deserialized_swap_data.offered_asset(), offered_asset);
+        assert_eq!(deserialized_swap_data.requested_asset(), requested_asset);
+    }
 }
@@ -0,0 +1,165 @@
+//! Contains the types used to sign transactions outside of the client that executes them, such as
+//! on an offline machine holding the account's secret key.
+
+use alloc::{string::ToString, vec::Vec};
+
+use miden_objects::{
+    Felt, Word,
+    account::{AccountDelta, AccountId},
+};
+use miden_tx::{
+    AuthenticationError,
+    auth::TransactionAuthenticator,
+    utils::{
+        ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable, sync::RwLock,
+    },
+};
+
+use super::TransactionRequest;
+
+// UNSIGNED TRANSACTION
+// ================================================================================================
+
+/// A transaction that was executed up to the point where its signature is required.
+///
+/// It contains the public key of the signer and the message that needs to be signed, along with
+/// the data needed to execute the transaction again once the signature is available. It can be
+/// serialized and moved to the machine holding the secret key, where the message is signed.
+#[derive(Clone, Debug, PartialEq)]
+pub struct UnsignedTransaction {
+    /// The ID of the account executing the transaction.
+    account_id: AccountId,
+    /// The request that was used to execute the transaction.
+    transaction_request: TransactionRequest,
+    /// The public key of the key that needs to sign the transaction.
+    pub_key: Word,
+    /// The message that needs to be signed.
+    message: Word,
+}
+
+impl UnsignedTransaction {
+    /// Creates a new [`UnsignedTransaction`].
+    pub(crate) fn new(
+        account_id: AccountId,
+        transaction_request: TransactionRequest,
+        pub_key: Word,
+        message: Word,
+    ) -> Self {
+        Self {
+            account_id,
+            transaction_request,
+            pub_key,
+            message,
+        }
+    }
+
+    /// Returns the ID of the account executing the transaction.
+    pub fn account_id(&self) -> AccountId {
+        self.account_id
+    }
+
+    /// Returns the request used to execute the transaction.
+    pub fn transaction_request(&self) -> &TransactionRequest {
+        &self.transaction_request
+    }
+
+    /// Returns the public key of the key that needs to sign the transaction.
+    pub fn pub_key(&self) -> Word {
+        self.pub_key
+    }
+
+    /// Returns the message that needs to be signed.
+    pub fn message(&self) -> Word {
+        self.message
+    }
+}
+
+impl Serializable for UnsignedTransaction {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.account_id.write_into(target);
+        self.transaction_request.write_into(target);
+        self.pub_key.write_into(target);
+        self.message.write_into(target);
+    }
+}
+
+impl Deserializable for UnsignedTransaction {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let account_id = AccountId::read_from(source)?;
+        let transaction_request = TransactionRequest::read_from(source)?;
+        let pub_key = Word::read_from(source)?;
+        let message = Word::read_from(source)?;
+
+        Ok(Self::new(account_id, transaction_request, pub_key, message))
+    }
+}
+
+// AUTHENTICATORS
+// ================================================================================================
+
+/// A [`TransactionAuthenticator`] that records the signature request instead of signing it.
+///
+/// Signing always fails, which stops the transaction execution right after the message to be
+/// signed is known.
+#[derive(Debug, Default)]
+pub(crate) struct SignatureRequestRecorder {
+    request: RwLock<Option<(Word, Word)>>,
+}
+
+impl SignatureRequestRecorder {
+    /// Returns the public key and message of the recorded signature request, if any.
+    pub fn signature_request(&self) -> Option<(Word, Word)> {
+        *self.request.read()
+    }
+}
+
+impl TransactionAuthenticator for SignatureRequestRecorder {
+    fn get_signature(
+        &self,
+        pub_key: Word,
+        message: Word,
+        _account_delta: &AccountDelta,
+    ) -> Result<Vec<Felt>, AuthenticationError> {
+        *self.request.write() = Some((pub_key, message));
+
+        Err(AuthenticationError::other(
+            "signature deferred to an external signer".to_string(),
+        ))
+    }
+}
+
+/// A [`TransactionAuthenticator`] that provides a signature that was produced externally.
+///
+/// The signature is only provided for the public key and message it was created for.
+pub(crate) struct ExternalSignature {
+    pub_key: Word,
+    message: Word,
+    signature: Vec<Felt>,
+}
+
+impl ExternalSignature {
+    pub fn new(unsigned_transaction: &UnsignedTransaction, signature: Vec<Felt>) -> Self {
+        Self {
+            pub_key: unsigned_transaction.pub_key(),
+            message: unsigned_transaction.message(),
+            signature,
+        }
+    }
+}
+
+impl TransactionAuthenticator for ExternalSignature {
+    fn get_signature(
+        &self,
+        pub_key: Word,
+        message: Word,
+        _account_delta: &AccountDelta,
+    ) -> Result<Vec<Felt>, AuthenticationError> {
+        if pub_key != self.pub_key || message != self.message {
+            return Err(AuthenticationError::other(
+                "the transaction's signature request doesn't match the signed message".to_string(),
+            ));
+        }
+
+        Ok(self.signature.clone())
+    }
+}
@@ -143,16 +143,17 @@ export {
  * Additionally, the wrapper provides a static createClient function. This static method
  * instantiates the WebClient object and ensures that the necessary createClient calls are
  * performed both in the main thread and within the worker thread. This dual initialization
- * correctly passes user parameters (RPC URL and seed) to both the main-thread
+ * correctly passes user parameters (RPC URL, seed and store name) to both the main-thread
  * WASM WebClient and the worker-side instance.
  *
  * Because of this implementation, the only breaking change for end users is in the way the
  * web client is instantiated. Users should now use the WebClient.createClient static call.
  */
 export class WebClient {
-  constructor(rpcUrl, seed) {
+  constructor(rpcUrl, seed, storeName) {
     this.rpcUrl = rpcUrl;
     this.seed = seed;
+    this.storeName = storeName;
 
     // Check if Web Workers are available.
     if (typeof Worker !== "undefined") {
@@ -213,7 +214,7 @@ export class WebClient {
       this.loaded.then(() => {
         this.worker.postMessage({
           action: WorkerAction.INIT,
-          args: [this.rpcUrl, this.seed],
+          args: [this.rpcUrl, this.seed, this.storeName],
         });
       });
     } else {
@@ -235,14 +236,15 @@ export class WebClient {
    *
    * @param {string} rpcUrl - The RPC URL.
    * @param {string} seed - The seed for the account.
+   * @param {string} storeName - Optional name used to namespace the client's IndexedDB database.
    * @returns {Promise<WebClient>} The fully initialized WebClient.
    */
-  static async createClient(rpcUrl, seed) {
+  static async createClient(rpcUrl, seed, storeName) {
     // Construct the instance (synchronously).
-    const instance = new WebClient(rpcUrl, seed);
+    const instance = new WebClient(rpcUrl, seed, storeName);
 
     // Wait for the underlying wasmWebClient to be initialized.
-    await instance.wasmWebClient.createClient(rpcUrl, seed);
+    await instance.wasmWebClient.createClient(rpcUrl, seed, storeName);
 
     // Wait for the worker to be ready
     await instance.ready;
@@ -71,9 +71,14 @@ export declare class WebClient extends WasmWebClient {
    *
    * @param rpcUrl - The RPC URL (optional).
    * @param seed - The seed for the account (optional).
+   * @param storeName - Name used to namespace the client's IndexedDB database (optional).
    * @returns A promise that resolves to a fully initialized WebClient.
    */
-  static createClient(rpcUrl?: string, seed?: string): Promise<WebClient>;
+  static createClient(
+    rpcUrl?: string,
+    seed?: string,
+    storeName?: string
+  ): Promise<WebClient>;
 
   /**
    * Terminates the underlying worker.
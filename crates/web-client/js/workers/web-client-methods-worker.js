@@ -9,7 +9,7 @@ import { MethodName, WorkerAction } from "../constants.js";
  * WASM WebClient, then listens for messages from the main thread to perform one of two actions:
  *
  * 1. **Initialization (init):**
- *    - The worker receives an "init" message along with user parameters (RPC URL and seed).
+ *    - The worker receives an "init" message along with user parameters (RPC URL, seed and store name).
  *    - It instantiates the WASM WebClient and calls its createClient method.
  *    - Once initialization is complete, the worker sends a `{ ready: true }` message back to signal
  *      that it is fully initialized.
@@ -127,10 +127,10 @@ async function processMessage(event) {
   const { action, args, methodName, requestId } = event.data;
   try {
     if (action === WorkerAction.INIT) {
-      const [rpcUrl, seed] = args;
+      const [rpcUrl, seed, storeName] = args;
       // Initialize the WASM WebClient.
       wasmWebClient = new wasm.WebClient();
-      await wasmWebClient.createClient(rpcUrl, seed);
+      await wasmWebClient.createClient(rpcUrl, seed, storeName);
       ready = true;
       // Signal that the worker is fully initialized.
       self.postMessage({ ready: true });
@@ -56,6 +56,7 @@ impl WebClient {
         &mut self,
         node_url: Option<String>,
         seed: Option<Vec<u8>>,
+        store_name: Option<String>,
     ) -> Result<JsValue, JsValue> {
         let mut rng = match seed {
             Some(seed_bytes) => {
@@ -72,12 +73,12 @@ impl WebClient {
         let coin_seed: [u64; 4] = rng.random();
 
         let rng = RpoRandomCoin::new(coin_seed.map(Felt::new));
-        let web_store: WebStore = WebStore::new()
+        let web_store: WebStore = WebStore::new(store_name.clone())
             .await
             .map_err(|_| JsValue::from_str("Failed to initialize WebStore"))?;
         let web_store = Arc::new(web_store);
 
-        let keystore = WebKeyStore::new(rng);
+        let keystore = WebKeyStore::new(rng, store_name);
 
         let endpoint = node_url.map_or(Ok(Endpoint::testnet()), |url| {
             Endpoint::try_from(url.as_str()).map_err(|_| JsValue::from_str("Invalid node URL"))
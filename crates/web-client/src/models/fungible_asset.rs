@@ -1,3 +1,4 @@
+use miden_client::amount::{format_amount, parse_amount};
 use miden_objects::{
     Word as NativeWord,
     account::AccountId as NativeAccountId,
@@ -6,6 +7,7 @@ use miden_objects::{
 use wasm_bindgen::prelude::*;
 
 use super::{account_id::AccountId, word::Word};
+use crate::js_error_with_context;
 
 #[derive(Clone, Copy)]
 #[wasm_bindgen]
@@ -35,6 +37,20 @@ impl FungibleAsset {
         let native_word: NativeWord = self.0.into();
         native_word.into()
     }
+
+    /// Formats an amount in the faucet's base units as a decimal number with `decimals`
+    /// fractional digits.
+    #[wasm_bindgen(js_name = "formatAmount")]
+    pub fn format_amount(decimals: u8, amount: u64) -> String {
+        format_amount(decimals, amount)
+    }
+
+    /// Parses a decimal number (such as `"1.25"`) into an amount in the faucet's base units.
+    #[wasm_bindgen(js_name = "parseAmount")]
+    pub fn parse_amount(decimals: u8, amount: &str) -> Result<u64, JsValue> {
+        parse_amount(decimals, amount)
+            .map_err(|err| js_error_with_context(err, "failed to parse amount"))
+    }
 }
 
 // CONVERSIONS
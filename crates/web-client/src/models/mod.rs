@@ -81,6 +81,7 @@ pub mod rpo_digest;
 pub mod secret_key;
 pub mod storage_map;
 pub mod storage_slot;
+pub mod sync_progress;
 pub mod sync_summary;
 pub mod transaction_args;
 pub mod transaction_filter;
@@ -0,0 +1,33 @@
+use miden_client::sync::SyncProgress as NativeSyncProgress;
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen]
+#[derive(Clone, Copy)]
+pub struct SyncProgress(NativeSyncProgress);
+
+#[wasm_bindgen]
+impl SyncProgress {
+    #[wasm_bindgen(js_name = "currentBlock")]
+    pub fn current_block(&self) -> u32 {
+        self.0.current_block.as_u32()
+    }
+
+    #[wasm_bindgen(js_name = "targetBlock")]
+    pub fn target_block(&self) -> u32 {
+        self.0.target_block.as_u32()
+    }
+
+    #[wasm_bindgen(js_name = "notesApplied")]
+    pub fn notes_applied(&self) -> usize {
+        self.0.notes_applied
+    }
+}
+
+// CONVERSIONS
+// ================================================================================================
+
+impl From<NativeSyncProgress> for SyncProgress {
+    fn from(native_sync_progress: NativeSyncProgress) -> Self {
+        SyncProgress(native_sync_progress)
+    }
+}
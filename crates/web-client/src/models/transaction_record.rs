@@ -22,8 +22,8 @@ impl TransactionRecord {
     }
 
     #[wasm_bindgen(js_name = "initAccountState")]
-    pub fn init_account_state(&self) -> RpoDigest {
-        self.0.details.init_account_state.into()
+    pub fn init_account_state(&self) -> Option<RpoDigest> {
+        self.0.details.init_account_state.map(Into::into)
     }
 
     #[wasm_bindgen(js_name = "finalAccountState")]
@@ -32,6 +32,33 @@ impl WebClient {
         }
     }
 
+    /// Returns at most `page_size` input notes matching the filter, ordered by note ID and
+    /// starting after `after_note_id`. Passing the ID of the last note of a page returns the
+    /// next one, so large stores can be listed without loading every note at once.
+    #[wasm_bindgen(js_name = "getInputNotesPage")]
+    pub async fn get_input_notes_page(
+        &mut self,
+        filter: NoteFilter,
+        page_size: u32,
+        after_note_id: Option<String>,
+    ) -> Result<Vec<InputNoteRecord>, JsValue> {
+        let store = self.store.as_ref().ok_or(JsValue::from_str("Store not initialized"))?;
+        let after_note_id = after_note_id
+            .map(|note_id| {
+                Digest::try_from(note_id)
+                    .map(NoteId::from)
+                    .map_err(|err| js_error_with_context(err, "failed to parse input note id"))
+            })
+            .transpose()?;
+
+        let result = store
+            .get_input_notes_page(&filter.into(), after_note_id, page_size)
+            .await
+            .map_err(|err| js_error_with_context(err, "failed to get input notes page"))?;
+
+        Ok(result.into_iter().map(Into::into).collect())
+    }
+
     #[wasm_bindgen(js_name = "getInputNote")]
     pub async fn get_input_note(
         &mut self,
@@ -1,11 +1,13 @@
 use miden_client::note::build_swap_tag as native_build_swap_tag;
 use miden_objects::asset::{Asset as NativeAsset, FungibleAsset as NativeFungibleAsset};
 use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::js_sys::Function;
 
 use crate::{
     WebClient, js_error_with_context,
     models::{
-        account_id::AccountId, note_tag::NoteTag, note_type::NoteType, sync_summary::SyncSummary,
+        account_id::AccountId, note_tag::NoteTag, note_type::NoteType, sync_progress::SyncProgress,
+        sync_summary::SyncSummary,
     },
 };
 
@@ -25,6 +27,28 @@ impl WebClient {
         }
     }
 
+    /// Syncs the client's state, calling `on_progress` with a `SyncProgress` after each block
+    /// received from the node is applied.
+    #[wasm_bindgen(js_name = "syncStateWithProgress")]
+    pub async fn sync_state_with_progress(
+        &mut self,
+        on_progress: Function,
+    ) -> Result<SyncSummary, JsValue> {
+        if let Some(client) = self.get_mut_inner() {
+            let sync_summary = client
+                .sync_state_with_progress(move |progress| {
+                    // Errors thrown by the callback shouldn't interrupt the sync
+                    let _ = on_progress.call1(&JsValue::NULL, &SyncProgress::from(progress).into());
+                })
+                .await
+                .map_err(|err| js_error_with_context(err, "failed to sync state"))?;
+
+            Ok(sync_summary.into())
+        } else {
+            Err(JsValue::from_str("Client not initialized"))
+        }
+    }
+
     #[wasm_bindgen(js_name = "getSyncHeight")]
     pub async fn get_sync_height(&mut self) -> Result<u32, JsValue> {
         if let Some(client) = self.get_mut_inner() {
@@ -149,3 +149,68 @@ describe("getAccounts tests", () => {
     expect(result.resultTypes.length).to.equal(0);
   });
 });
+
+// STORE ISOLATION TESTS
+// =======================================================================================================
+
+interface NamespacedStoresResult {
+  firstStoreAccountIds: string[];
+  secondStoreAccountIds: string[];
+  firstCreatedAccountId: string;
+  secondCreatedAccountId: string;
+}
+
+export const createAccountsInNamespacedStores =
+  async (): Promise<NamespacedStoresResult> => {
+    return await testingPage.evaluate(async () => {
+      const firstClient = await window.WebClient.createClient(
+        window.rpcUrl,
+        undefined,
+        "first_store"
+      );
+      const secondClient = await window.WebClient.createClient(
+        window.rpcUrl,
+        undefined,
+        "second_store"
+      );
+
+      const firstAccount = await firstClient.newWallet(
+        window.AccountStorageMode.private(),
+        true
+      );
+      const secondAccount = await secondClient.newWallet(
+        window.AccountStorageMode.private(),
+        true
+      );
+
+      const firstStoreAccounts = await firstClient.getAccounts();
+      const secondStoreAccounts = await secondClient.getAccounts();
+
+      firstClient.terminate();
+      secondClient.terminate();
+
+      return {
+        firstStoreAccountIds: firstStoreAccounts.map((account) =>
+          account.id().toString()
+        ),
+        secondStoreAccountIds: secondStoreAccounts.map((account) =>
+          account.id().toString()
+        ),
+        firstCreatedAccountId: firstAccount.id().toString(),
+        secondCreatedAccountId: secondAccount.id().toString(),
+      };
+    });
+  };
+
+describe("store name tests", () => {
+  it("keeps the accounts of clients with different store names isolated", async () => {
+    const result = await createAccountsInNamespacedStores();
+
+    expect(result.firstStoreAccountIds).to.deep.equal([
+      result.firstCreatedAccountId,
+    ]);
+    expect(result.secondStoreAccountIds).to.deep.equal([
+      result.secondCreatedAccountId,
+    ]);
+  });
+});
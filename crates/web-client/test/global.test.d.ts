@@ -121,6 +121,7 @@ declare global {
     WebClient: typeof WebClient;
     Word: typeof Word;
     createClient: () => Promise<void>;
+    rpcUrl: string;
 
     // Add the helpers namespace
     helpers: {
@@ -126,6 +126,7 @@ before(async () => {
       const client = await WebClient.createClient(rpcUrl);
 
       window.client = client;
+      window.rpcUrl = rpcUrl;
       window.Account = Account;
       window.AccountBuilder = AccountBuilder;
       window.AccountComponent = AccountComponent;
@@ -31,6 +31,32 @@ const getInputNotes = async () => {
   });
 };
 
+const getInputNotesInPages = async (pageSize: number) => {
+  return await testingPage.evaluate(async (_pageSize) => {
+    const client = window.client;
+    const pages: string[][] = [];
+    let afterNoteId: string | undefined = undefined;
+
+    while (true) {
+      const filter = new window.NoteFilter(window.NoteFilterTypes.All);
+      const page = await client.getInputNotesPage(
+        filter,
+        _pageSize,
+        afterNoteId
+      );
+      if (page.length === 0) {
+        break;
+      }
+
+      const noteIds = page.map((note) => note.id().toString());
+      pages.push(noteIds);
+      afterNoteId = noteIds[noteIds.length - 1];
+    }
+
+    return { pages };
+  }, pageSize);
+};
+
 const setupMintedNote = async () => {
   const { accountId, faucetId } = await setupWalletAndFaucet();
   const { createdNoteId } = await mintTransaction(accountId, faucetId);
@@ -95,6 +121,21 @@ describe("get_input_notes", () => {
   });
 });
 
+describe("get_input_notes_page", () => {
+  it("pages through all input notes", async () => {
+    await setupConsumedNote();
+    await setupMintedNote();
+    await setupMintedNote();
+
+    const { noteIds } = await getInputNotes();
+    const { pages } = await getInputNotesInPages(2);
+
+    pages.forEach((page) => expect(page).to.have.lengthOf.at.most(2));
+    expect(pages).to.have.lengthOf(Math.ceil(noteIds.length / 2));
+    expect(pages.flat()).to.have.members(noteIds);
+  });
+});
+
 describe("get_consumable_notes", () => {
   it("filter by account", async () => {
     const { createdNoteId: noteId1, accountId: accountId1 } =
@@ -1,5 +1,5 @@
 use miden_client::{
-    ZERO,
+    ClientError, ZERO,
     note::NoteExecutionHint,
     store::NoteFilter,
     testing::common::*,
@@ -8,7 +8,7 @@ use miden_client::{
 };
 use miden_objects::{
     Felt, Word,
-    account::{AccountId, AccountStorageMode},
+    account::{Account, AccountId, AccountStorageMode},
     asset::FungibleAsset,
     crypto::{
         hash::rpo::Rpo256,
@@ -232,6 +232,71 @@ async fn merkle_store() {
     client.sync_state().await.unwrap();
 }
 
+#[tokio::test]
+async fn script_args() {
+    let (mut client, authenticator) = create_test_client().await;
+    wait_for_node(&mut client).await;
+
+    client.sync_state().await.unwrap();
+    // Insert Account
+    let (regular_account, _seed, _) =
+        insert_new_wallet(&mut client, AccountStorageMode::Private, &authenticator)
+            .await
+            .unwrap();
+
+    let (fungible_faucet, _seed, _) =
+        insert_new_fungible_faucet(&mut client, AccountStorageMode::Private, &authenticator)
+            .await
+            .unwrap();
+
+    // Execute mint transaction in order to create custom note
+    let note = mint_custom_note(&mut client, fungible_faucet.id(), regular_account.id()).await;
+    client.sync_state().await.unwrap();
+
+    let note_args_commitment = Rpo256::hash_elements(&NOTE_ARGS);
+    let note_args_map = vec![(note, Some(note_args_commitment.into()))];
+    let mut advice_map = AdviceMap::default();
+    advice_map.insert(note_args_commitment, NOTE_ARGS.to_vec());
+
+    let code = "
+        use.miden::contracts::auth::basic->auth_tx
+
+        begin
+            # => [SCRIPT_ARGS_COMMITMENT]
+            adv.push_mapval adv_push.8
+            # => [8, 7, 6, 5, 4, 3, 2, 1, SCRIPT_ARGS_COMMITMENT]
+
+            push.5.6.7.8 assert_eqw.err=\"unexpected last script arguments\"
+            push.1.2.3.4 assert_eqw.err=\"unexpected first script arguments\"
+            dropw
+
+            call.auth_tx::auth__tx_rpo_falcon512
+        end
+        ";
+    let tx_script = client.script_builder().compile_tx_script(code).unwrap();
+
+    // The script fails if the arguments aren't the expected ones
+    let transaction_request = TransactionRequestBuilder::new()
+        .unauthenticated_input_notes(note_args_map.clone())
+        .custom_script(tx_script.clone())
+        .with_script_args((1..=8).rev().map(Felt::new).collect())
+        .extend_advice_map(advice_map.clone())
+        .build()
+        .unwrap();
+
+    assert!(client.new_transaction(regular_account.id(), transaction_request).await.is_err());
+
+    let transaction_request = TransactionRequestBuilder::new()
+        .unauthenticated_input_notes(note_args_map)
+        .custom_script(tx_script)
+        .with_script_args((1..=8).map(Felt::new).collect())
+        .extend_advice_map(advice_map)
+        .build()
+        .unwrap();
+
+    execute_tx_and_sync(&mut client, regular_account.id(), transaction_request).await;
+}
+
 #[tokio::test]
 async fn onchain_notes_sync_with_tag() {
     // Client 1 has an private faucet which will mint an onchain note for client 2
@@ -307,6 +372,68 @@ async fn onchain_notes_sync_with_tag() {
     assert!(client_3.get_input_notes(NoteFilter::All).await.unwrap().is_empty());
 }
 
+#[tokio::test]
+async fn burn_fungible_reduces_issuance() {
+    let (mut client, authenticator) = create_test_client().await;
+    wait_for_node(&mut client).await;
+
+    let (wallet, faucet) =
+        setup_wallet_and_faucet(&mut client, AccountStorageMode::Private, &authenticator).await;
+    fund_account(&mut client, faucet.id(), wallet.id(), MINT_AMOUNT).await;
+    let issuance_before = faucet_total_issuance(&client, faucet.id()).await;
+
+    // The wallet sends part of its tokens back to the faucet in a note that burns them
+    let note_script = client
+        .script_builder()
+        .compile_note_script(include_str!("asm/burn.masm"))
+        .unwrap();
+    let note_metadata = NoteMetadata::new(
+        wallet.id(),
+        NoteType::Private,
+        NoteTag::from_account_id(faucet.id()),
+        NoteExecutionHint::None,
+        Default::default(),
+    )
+    .unwrap();
+    let note_assets =
+        NoteAssets::new(vec![FungibleAsset::new(faucet.id(), TRANSFER_AMOUNT).unwrap().into()])
+            .unwrap();
+    let serial_num = client.rng().draw_word();
+    let note_recipient =
+        NoteRecipient::new(serial_num, note_script, NoteInputs::new(vec![]).unwrap());
+    let burn_note = Note::new(note_assets, note_metadata, note_recipient);
+
+    let tx_request = TransactionRequestBuilder::new()
+        .own_output_notes(vec![OutputNote::Full(burn_note.clone())])
+        .build()
+        .unwrap();
+    execute_tx_and_sync(&mut client, wallet.id(), tx_request).await;
+
+    // Only the faucet that issued the assets can burn them
+    let (other_faucet, ..) =
+        insert_new_fungible_faucet(&mut client, AccountStorageMode::Private, &authenticator)
+            .await
+            .unwrap();
+    assert!(matches!(
+        client.burn_fungible(other_faucet.id(), burn_note.clone().into()).await,
+        Err(ClientError::NoteAssetsNotIssuedByFaucet(note_id, faucet_id))
+            if note_id == burn_note.id() && faucet_id == other_faucet.id()
+    ));
+
+    let tx_request = client.burn_fungible(faucet.id(), burn_note.into()).await.unwrap();
+    execute_tx_and_sync(&mut client, faucet.id(), tx_request).await;
+
+    let issuance_after = faucet_total_issuance(&client, faucet.id()).await;
+    assert_eq!(issuance_after, issuance_before - TRANSFER_AMOUNT);
+    assert_account_has_single_asset(
+        &client,
+        wallet.id(),
+        faucet.id(),
+        MINT_AMOUNT - TRANSFER_AMOUNT,
+    )
+    .await;
+}
+
 async fn mint_custom_note(
     client: &mut TestClient,
     faucet_account_id: AccountId,
@@ -359,3 +486,10 @@ fn create_custom_note(
     let note_recipient = NoteRecipient::new(serial_num, note_script, inputs);
     Note::new(note_assets, note_metadata, note_recipient)
 }
+
+/// Returns the total issuance of the fungible faucet, which the transaction kernel keeps in the
+/// last element of the faucet's reserved storage slot.
+async fn faucet_total_issuance(client: &TestClient, faucet_id: AccountId) -> u64 {
+    let faucet: Account = client.get_account(faucet_id).await.unwrap().unwrap().into();
+    faucet.storage().get_item(0).unwrap()[3].as_int()
+}
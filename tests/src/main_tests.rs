@@ -3,7 +3,10 @@ use std::{sync::Arc, time::Duration};
 use miden_client::{
     ClientError, ONE,
     builder::ClientBuilder,
-    rpc::{Endpoint, NodeRpcClient, TonicRpcClient, domain::account::FetchedAccount},
+    rpc::{
+        CountingRpcMetrics, Endpoint, NodeRpcClient, NodeRpcClientEndpoint, TonicRpcClient,
+        domain::account::FetchedAccount,
+    },
     store::{InputNoteRecord, InputNoteState, NoteFilter, OutputNoteState, TransactionFilter},
     testing::common::*,
     transaction::{
@@ -795,10 +798,7 @@ async fn import_note_with_proof() {
     let to_account_id = second_regular_account.id();
     let faucet_account_id = faucet_account_header.id();
 
-    let note =
-        mint_note(&mut client_1, from_account_id, faucet_account_id, NoteType::Private).await;
-
-    consume_notes(&mut client_1, from_account_id, &[note]).await;
+    fund_account(&mut client_1, faucet_account_id, from_account_id, MINT_AMOUNT).await;
 
     let current_block_num = client_1.get_sync_height().await.unwrap();
     let asset = FungibleAsset::new(faucet_account_id, TRANSFER_AMOUNT).unwrap();
@@ -1198,3 +1198,67 @@ async fn ignore_invalid_notes() {
     assert!(consumed_notes.iter().any(|note| note.id() == note_1.id()));
     assert!(consumed_notes.iter().any(|note| note.id() == note_2.id()));
 }
+
+#[tokio::test]
+async fn rpc_metrics_record_calls() {
+    let (endpoint, timeout, ..) = get_client_config();
+    let metrics = Arc::new(CountingRpcMetrics::new());
+    let rpc_api = TonicRpcClient::new(&endpoint, timeout).with_metrics(metrics.clone());
+
+    rpc_api.get_block_header_by_number(None, false).await.unwrap();
+
+    let counts = metrics.counts(NodeRpcClientEndpoint::GetBlockHeaderByNumber);
+    assert_eq!(counts.successes, 1);
+    assert_eq!(counts.failures, 0);
+    assert!(counts.total_duration > Duration::ZERO);
+    assert_eq!(metrics.all_counts().len(), 1);
+}
+
+#[tokio::test]
+async fn node_status_chain_tip_advances() {
+    let (mut client, _) = create_test_client().await;
+    wait_for_node(&mut client).await;
+
+    let status = client.node_status().await.unwrap();
+    let (genesis_header, _) = client
+        .test_rpc_api()
+        .get_block_header_by_number(Some(0.into()), false)
+        .await
+        .unwrap();
+    assert_eq!(status.genesis_commitment, genesis_header.commitment());
+
+    wait_for_blocks(&mut client, 2).await;
+
+    let new_status = client.node_status().await.unwrap();
+    assert!(new_status.chain_tip > status.chain_tip);
+    assert_eq!(new_status.version, status.version);
+}
+
+#[tokio::test]
+async fn mint_fungible_and_consume() {
+    let (mut client, authenticator) = create_test_client().await;
+    wait_for_node(&mut client).await;
+
+    let (wallet, faucet) =
+        setup_wallet_and_faucet(&mut client, AccountStorageMode::Private, &authenticator).await;
+
+    // Only basic fungible faucets can mint tokens
+    assert!(matches!(
+        client.mint_fungible(wallet.id(), wallet.id(), MINT_AMOUNT, NoteType::Private).await,
+        Err(ClientError::AccountNotFungibleFaucet(account_id)) if account_id == wallet.id()
+    ));
+
+    let tx_request = client
+        .mint_fungible(faucet.id(), wallet.id(), MINT_AMOUNT, NoteType::Private)
+        .await
+        .unwrap();
+    let minted_note_id = tx_request.expected_output_own_notes().pop().unwrap().id();
+    execute_tx_and_sync(&mut client, faucet.id(), tx_request).await;
+
+    // The minted note is tracked by the client and can be consumed by the wallet
+    let minted_note = client.get_input_note(minted_note_id).await.unwrap().unwrap();
+    assert!(minted_note.is_committed());
+
+    consume_notes(&mut client, wallet.id(), &[minted_note.try_into().unwrap()]).await;
+    assert_account_has_single_asset(&client, wallet.id(), faucet.id(), MINT_AMOUNT).await;
+}